@@ -0,0 +1,55 @@
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::*;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_service::{
+    ScrollDirection, ScrollImageList,
+};
+
+/// 常见的滚动区域尺寸（宽，高）
+const RESOLUTION_LIST: [(u32, u32); 3] = [(1280, 720), (1920, 1080), (2560, 1440)];
+const FRAME_COUNT: u32 = 8;
+
+fn bench_handle_image(c: &mut Criterion) {
+    let mut group = c.benchmark_group("handle_image");
+    group.sample_size(10);
+
+    for (width, height) in RESOLUTION_LIST {
+        // 每次滚动四分之一屏
+        let step = height / 4;
+        let source = generate_source(
+            SyntheticContent::Text,
+            ScrollDirection::Vertical,
+            width,
+            height + step * FRAME_COUNT,
+            width as u64,
+        );
+        let frames = (0..=FRAME_COUNT)
+            .map(|i| slice_frame(&source, ScrollDirection::Vertical, height, i * step))
+            .collect::<Vec<_>>();
+
+        group.throughput(Throughput::Elements(frames.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}x{}", width, height)),
+            &frames,
+            |b, frames| {
+                b.iter(|| {
+                    let mut service = create_service(ScrollDirection::Vertical, height);
+                    stitch_frames(
+                        &mut service,
+                        frames
+                            .iter()
+                            .map(|frame| (frame.clone(), ScrollImageList::Bottom))
+                            .collect(),
+                    )
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_handle_image);
+criterion_main!(benches);
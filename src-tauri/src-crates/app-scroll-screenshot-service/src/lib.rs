@@ -1,3 +1,9 @@
 pub mod scroll_screenshot_capture_service;
+pub mod scroll_screenshot_diagnostics;
+pub mod scroll_screenshot_frame_store;
 pub mod scroll_screenshot_image_service;
+pub mod scroll_screenshot_mask;
+pub mod scroll_screenshot_offline_service;
+pub mod scroll_screenshot_pdf_service;
 pub mod scroll_screenshot_service;
+pub mod scroll_screenshot_session;
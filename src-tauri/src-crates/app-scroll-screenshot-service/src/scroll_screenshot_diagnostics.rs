@@ -0,0 +1,292 @@
+use serde::Serialize;
+
+use crate::scroll_screenshot_service::{
+    ScrollDirection, ScrollFrameStats, ScrollImageList, ScrollMatchStats, ScrollScreenshotService,
+};
+
+const RGBA_CHANNEL_COUNT: usize = 4;
+
+/// 重叠区域的平均像素差异超过该值时认为接缝错位（0 - 255）
+const SEAM_DIFFERENCE_THRESHOLD: f32 = 8.0;
+/// 内点数量低于该值时认为匹配不可靠
+const SEAM_MIN_INLIER_COUNT: usize = 16;
+/// 第二偏移的票数和主偏移的票数之比超过该值时认为偏移有歧义，拼接时要求小于 0.5
+const SEAM_SECOND_COUNT_RATIO: f32 = 0.45;
+
+#[derive(PartialEq, Serialize, Debug, Clone, Copy)]
+pub enum ScrollSeamIssue {
+    /// 重叠区域的像素不一致
+    PixelMismatch,
+    /// 内点数量过少
+    FewInliers,
+    /// 第二偏移的票数接近主偏移
+    AmbiguousOffset,
+}
+
+/// 图片与已有内容之间的接缝
+#[derive(Serialize, Debug, Clone)]
+pub struct ScrollSeam {
+    pub scroll_image_list: ScrollImageList,
+    /// 图片在列表中的序号
+    pub index: usize,
+    /// 接缝在导出图片中沿滚动方向的位置
+    pub position: i32,
+    /// 和已有内容重叠的尺寸
+    pub overlap_size: u32,
+    /// 重叠区域的平均像素差异，没有重叠时为空
+    pub mean_difference: Option<f32>,
+    /// 重叠区域中差异最大的一行（列）的平均像素差异
+    pub max_line_difference: Option<f32>,
+    pub match_stats: Option<ScrollMatchStats>,
+    pub issues: Vec<ScrollSeamIssue>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ScrollStitchReport {
+    pub direction: ScrollDirection,
+    pub width: u32,
+    pub height: u32,
+    pub frame_stats: Vec<ScrollFrameStats>,
+    pub seams: Vec<ScrollSeam>,
+    /// 可疑接缝的位置，按位置排序
+    pub suspicious_positions: Vec<i32>,
+}
+
+impl ScrollScreenshotService {
+    /**
+     * 读取导出布局中的图片在 [position_start, position_end) 范围内的像素
+     * 按滚动方向逐行（列）返回，每行（列）为垂直于滚动方向的 RGBA8 像素
+     */
+    fn read_layout_lines(
+        &mut self,
+        layout_image: (ScrollImageList, usize, i32),
+        position_start: usize,
+        position_end: usize,
+    ) -> Result<Vec<u8>, String> {
+        let (scroll_image_list, i, start) = layout_image;
+        let scroll_image = match scroll_image_list {
+            ScrollImageList::Top => &self.top_image_list[i],
+            ScrollImageList::Bottom => &self.bottom_image_list[i],
+        };
+        let image_row_size = scroll_image.width as usize * RGBA_CHANNEL_COUNT;
+        let line_start = (position_start as i32 - start) as usize;
+        let line_count = position_end - position_start;
+
+        if self.current_direction == ScrollDirection::Vertical {
+            let mut lines = vec![0u8; image_row_size * line_count];
+            scroll_image.read_rows(&mut self.frame_store, line_start as u32, &mut lines)?;
+
+            return Ok(lines);
+        }
+
+        // 水平方向读取整张图片后转置需要的列
+        let image_height = scroll_image.height as usize;
+        let mut image_pixels = vec![0u8; image_row_size * image_height];
+        scroll_image.read_rows(&mut self.frame_store, 0, &mut image_pixels)?;
+
+        let line_size = image_height * RGBA_CHANNEL_COUNT;
+        let mut lines = vec![0u8; line_size * line_count];
+        for (line_index, line) in lines.chunks_exact_mut(line_size).enumerate() {
+            let column_offset = (line_start + line_index) * RGBA_CHANNEL_COUNT;
+            for (row, pixel) in line.chunks_exact_mut(RGBA_CHANNEL_COUNT).enumerate() {
+                let offset = row * image_row_size + column_offset;
+                pixel.copy_from_slice(&image_pixels[offset..offset + RGBA_CHANNEL_COUNT]);
+            }
+        }
+
+        Ok(lines)
+    }
+
+    /**
+     * 检查每张图片和先绘制的内容之间的接缝
+     *
+     * 图片的重叠区域应该和先绘制的内容一致，差异较大说明拼接位置错误
+     */
+    pub fn check_seams(&mut self) -> Result<Vec<ScrollSeam>, String> {
+        let (layout_images, _) = self.get_export_layout();
+        let total_size = (self.top_image_size + self.bottom_image_size).max(0) as usize;
+        let is_vertical = self.current_direction == ScrollDirection::Vertical;
+
+        // 记录每个位置当前由哪张图片绘制
+        let mut coverage = vec![u32::MAX; total_size];
+        let mut seams = Vec::with_capacity(layout_images.len());
+
+        for (layout_index, layout_image) in layout_images.iter().enumerate() {
+            let (scroll_image_list, i, start) = *layout_image;
+            let scroll_image = match scroll_image_list {
+                ScrollImageList::Top => &self.top_image_list[i],
+                ScrollImageList::Bottom => &self.bottom_image_list[i],
+            };
+            let match_stats = scroll_image.match_stats;
+            let side_size = if is_vertical {
+                scroll_image.height
+            } else {
+                scroll_image.width
+            } as i32;
+
+            let span_start = start.clamp(0, total_size as i32) as usize;
+            let span_end = (start + side_size).clamp(0, total_size as i32) as usize;
+
+            // 首张图片没有接缝
+            if layout_index == 0 || span_start == span_end {
+                coverage[span_start..span_end].fill(layout_index as u32);
+                continue;
+            }
+
+            // 下方的图片和已有内容在头部重叠，上方的图片在尾部重叠
+            let (overlap_start, overlap_end, position) = match scroll_image_list {
+                ScrollImageList::Bottom => {
+                    let overlap_end = coverage[span_start..span_end]
+                        .iter()
+                        .position(|layout_index| *layout_index == u32::MAX)
+                        .map(|offset| span_start + offset)
+                        .unwrap_or(span_end);
+
+                    (span_start, overlap_end, overlap_end as i32)
+                }
+                ScrollImageList::Top => {
+                    let overlap_start = coverage[span_start..span_end]
+                        .iter()
+                        .rposition(|layout_index| *layout_index == u32::MAX)
+                        .map(|offset| span_start + offset + 1)
+                        .unwrap_or(span_start);
+
+                    (overlap_start, span_end, overlap_start as i32)
+                }
+            };
+
+            let mut mean_difference = None;
+            let mut max_line_difference = None;
+            if overlap_start < overlap_end {
+                let lines = self.read_layout_lines(*layout_image, overlap_start, overlap_end)?;
+                let line_size = lines.len() / (overlap_end - overlap_start);
+
+                let mut difference_sum = 0.0;
+                let mut max_difference = 0.0f32;
+
+                // 按绘制来源分段读取已有内容
+                let mut position = overlap_start;
+                while position < overlap_end {
+                    let source_index = coverage[position];
+                    let mut position_end = position + 1;
+                    while position_end < overlap_end && coverage[position_end] == source_index {
+                        position_end += 1;
+                    }
+
+                    let source_lines = self.read_layout_lines(
+                        layout_images[source_index as usize],
+                        position,
+                        position_end,
+                    )?;
+
+                    for (line, source_line) in lines[(position - overlap_start) * line_size
+                        ..(position_end - overlap_start) * line_size]
+                        .chunks_exact(line_size)
+                        .zip(source_lines.chunks_exact(line_size))
+                    {
+                        let line_difference = line
+                            .iter()
+                            .zip(source_line.iter())
+                            .map(|(a, b)| a.abs_diff(*b) as u64)
+                            .sum::<u64>() as f32
+                            / line_size as f32;
+
+                        difference_sum += line_difference;
+                        max_difference = max_difference.max(line_difference);
+                    }
+
+                    position = position_end;
+                }
+
+                mean_difference = Some(difference_sum / (overlap_end - overlap_start) as f32);
+                max_line_difference = Some(max_difference);
+            }
+
+            let mut issues = Vec::new();
+            if mean_difference.unwrap_or(0.0) > SEAM_DIFFERENCE_THRESHOLD {
+                issues.push(ScrollSeamIssue::PixelMismatch);
+            }
+            if let Some(match_stats) = match_stats {
+                if match_stats.inlier_count < SEAM_MIN_INLIER_COUNT {
+                    issues.push(ScrollSeamIssue::FewInliers);
+                }
+                if match_stats.second_count as f32
+                    > match_stats.inlier_count as f32 * SEAM_SECOND_COUNT_RATIO
+                {
+                    issues.push(ScrollSeamIssue::AmbiguousOffset);
+                }
+            }
+
+            seams.push(ScrollSeam {
+                scroll_image_list,
+                index: i,
+                position,
+                overlap_size: (overlap_end - overlap_start) as u32,
+                mean_difference,
+                max_line_difference,
+                match_stats,
+                issues,
+            });
+
+            coverage[span_start..span_end].fill(layout_index as u32);
+        }
+
+        Ok(seams)
+    }
+
+    /**
+     * 生成拼接质量报告，列出每张图片的匹配结果和可疑的接缝
+     */
+    pub fn get_stitch_report(&mut self) -> Result<ScrollStitchReport, String> {
+        let (width, height) = match self.get_export_size() {
+            Some(size) => size,
+            None => {
+                return Err(String::from(
+                    "[ScrollScreenshotService::get_stitch_report] No image to report",
+                ));
+            }
+        };
+
+        let seams = self.check_seams()?;
+
+        let mut suspicious_positions = seams
+            .iter()
+            .filter(|seam| !seam.issues.is_empty())
+            .map(|seam| seam.position)
+            .collect::<Vec<_>>();
+        suspicious_positions.sort();
+
+        Ok(ScrollStitchReport {
+            direction: self.current_direction,
+            width,
+            height,
+            frame_stats: self.frame_stats.clone(),
+            seams,
+            suspicious_positions,
+        })
+    }
+
+    /**
+     * 导出完成后检查接缝，存在可疑接缝时返回报告
+     */
+    pub fn check_exported_seams(&mut self) -> Option<ScrollStitchReport> {
+        let report = match self.get_stitch_report() {
+            Ok(report) => report,
+            Err(e) => {
+                log::warn!("[ScrollScreenshotService::check_exported_seams] {}", e);
+                return None;
+            }
+        };
+
+        if report.suspicious_positions.is_empty() {
+            return None;
+        }
+
+        log::warn!(
+            "[ScrollScreenshotService::check_exported_seams] Suspicious seams at {:?}",
+            report.suspicious_positions
+        );
+
+        Some(report)
+    }
+}
@@ -0,0 +1,130 @@
+use image::DynamicImage;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+const RGBA_CHANNEL_COUNT: u64 = 4;
+
+/// 图片在临时文件中的位置
+#[derive(Debug, Clone, Copy)]
+pub struct ScrollFrameHandle {
+    pub offset: u64,
+    pub width: u32,
+    pub height: u32,
+}
+
+/**
+ * 将已拼接的图片写入临时文件，降低长截图的内存占用
+ * 图片以 RGBA8 格式逐行写入，读取时可以只读取需要的行
+ */
+pub struct ScrollFrameStore {
+    file: Option<File>,
+    size: u64,
+}
+
+impl ScrollFrameStore {
+    pub fn new() -> Self {
+        Self {
+            file: None,
+            size: 0,
+        }
+    }
+
+    fn get_file(&mut self) -> Result<&mut File, String> {
+        if self.file.is_none() {
+            let file = match tempfile::tempfile() {
+                Ok(file) => file,
+                Err(e) => {
+                    return Err(format!(
+                        "[ScrollFrameStore::get_file] Failed to create temp file: {}",
+                        e
+                    ));
+                }
+            };
+
+            self.file = Some(file);
+        }
+
+        Ok(self.file.as_mut().unwrap())
+    }
+
+    /**
+     * 将图片追加到临时文件尾部
+     */
+    pub fn push(&mut self, image: &DynamicImage) -> Result<ScrollFrameHandle, String> {
+        let rgba_image;
+        let image_pixels = match image {
+            DynamicImage::ImageRgba8(image) => image.as_raw(),
+            _ => {
+                rgba_image = image.to_rgba8();
+                rgba_image.as_raw()
+            }
+        };
+
+        let offset = self.size;
+        let file = self.get_file()?;
+
+        if let Err(e) = file
+            .seek(SeekFrom::Start(offset))
+            .and_then(|_| file.write_all(image_pixels))
+        {
+            return Err(format!(
+                "[ScrollFrameStore::push] Failed to write image: {}",
+                e
+            ));
+        }
+
+        self.size += image_pixels.len() as u64;
+
+        Ok(ScrollFrameHandle {
+            offset,
+            width: image.width(),
+            height: image.height(),
+        })
+    }
+
+    /**
+     * 从 row_start 开始读取图片的行，读取的行数由 buf 的长度决定
+     */
+    pub fn read_rows(
+        &mut self,
+        handle: &ScrollFrameHandle,
+        row_start: u32,
+        buf: &mut [u8],
+    ) -> Result<(), String> {
+        let row_size = handle.width as u64 * RGBA_CHANNEL_COUNT;
+        let file = self.get_file()?;
+
+        if let Err(e) = file
+            .seek(SeekFrom::Start(handle.offset + row_start as u64 * row_size))
+            .and_then(|_| file.read_exact(buf))
+        {
+            return Err(format!(
+                "[ScrollFrameStore::read_rows] Failed to read image: {}",
+                e
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn read(&mut self, handle: &ScrollFrameHandle) -> Result<DynamicImage, String> {
+        let mut image_pixels =
+            vec![0u8; (handle.width as u64 * handle.height as u64 * RGBA_CHANNEL_COUNT) as usize];
+        self.read_rows(handle, 0, &mut image_pixels)?;
+
+        match image::RgbaImage::from_raw(handle.width, handle.height, image_pixels) {
+            Some(image) => Ok(DynamicImage::ImageRgba8(image)),
+            None => Err(String::from(
+                "[ScrollFrameStore::read] Failed to create image",
+            )),
+        }
+    }
+
+    /**
+     * 释放临时文件，临时文件会在关闭后被系统删除
+     */
+    pub fn clear(&mut self) {
+        self.file = None;
+        self.size = 0;
+    }
+}
@@ -0,0 +1,252 @@
+use image::GrayImage;
+use snow_shot_app_shared::ElementRect;
+use std::collections::HashSet;
+
+/// 检测变化区域的块大小（灰度图坐标）
+const MASK_BLOCK_SIZE: u32 = 16;
+/// 对齐后块内的平均灰度差异超过该值时认为块发生了变化
+const MASK_BLOCK_DIFFERENCE_THRESHOLD: u32 = 12;
+/// 块发生变化后保持屏蔽的比较次数
+const MASK_BLOCK_HOLD_COUNT: u8 = 3;
+
+/**
+ * 记录滚动区域中与滚动无关的变化区域，例如动画广告、闪烁的光标、视频和实时时间
+ *
+ * 按滚动偏移对齐相邻两张图片后比较每个块，仍然不一致的块即为变化区域
+ * 变化区域分别记录屏幕坐标和内容坐标：
+ * 屏幕坐标用于过滤新图片的特征点，内容坐标用于过滤建立索引的特征点
+ */
+pub struct ScrollDynamicMask {
+    is_vertical: bool,
+    /// 灰度图相对原图在垂直于滚动方向上的缩放
+    image_scale: f32,
+    /// 灰度图宽度
+    width: u32,
+    /// 灰度图高度
+    height: u32,
+    /// 屏幕坐标下每个块剩余的屏蔽次数
+    screen_blocks: Vec<u8>,
+    /// 内容坐标下发生过变化的块（滚动方向的块序号，垂直于滚动方向的块序号）
+    content_blocks: HashSet<(i32, u32)>,
+    /// 上一张图片的灰度图和在滚动方向上的位置
+    last_frame: Option<(GrayImage, i32)>,
+    /// 调用方指定的屏蔽区域（原图坐标）
+    mask_rects: Vec<ElementRect>,
+}
+
+/// 某张图片处理前检测到的变化区域，撤销时回退到该状态
+#[derive(Debug, Clone)]
+pub struct ScrollDynamicMaskSnapshot {
+    screen_blocks: Vec<u8>,
+    content_blocks: HashSet<(i32, u32)>,
+}
+
+impl ScrollDynamicMask {
+    pub fn new() -> Self {
+        Self {
+            is_vertical: true,
+            image_scale: 1.0,
+            width: 0,
+            height: 0,
+            screen_blocks: vec![],
+            content_blocks: HashSet::new(),
+            last_frame: None,
+            mask_rects: vec![],
+        }
+    }
+
+    /**
+     * 清除检测到的变化区域，保留灰度图尺寸和调用方指定的屏蔽区域
+     *
+     * 拼接服务清除图片后不会重新初始化尺寸，尺寸清零会让之后的检测失效
+     */
+    pub fn clear(&mut self) {
+        self.screen_blocks.fill(0);
+        self.content_blocks.clear();
+        self.last_frame = None;
+    }
+
+    pub fn snapshot(&self) -> ScrollDynamicMaskSnapshot {
+        ScrollDynamicMaskSnapshot {
+            screen_blocks: self.screen_blocks.clone(),
+            content_blocks: self.content_blocks.clone(),
+        }
+    }
+
+    /**
+     * 回退到快照时的变化区域
+     *
+     * 上一张图片可能已被撤销，下一张图片只作为比较的基准
+     */
+    pub fn restore(&mut self, snapshot: ScrollDynamicMaskSnapshot) {
+        if snapshot.screen_blocks.len() != self.screen_blocks.len() {
+            self.clear();
+            return;
+        }
+
+        self.screen_blocks = snapshot.screen_blocks;
+        self.content_blocks = snapshot.content_blocks;
+        self.last_frame = None;
+    }
+
+    pub fn set_mask_rects(&mut self, mask_rects: Vec<ElementRect>) {
+        self.mask_rects = mask_rects;
+    }
+
+    pub fn mask_rects(&self) -> &[ElementRect] {
+        &self.mask_rects
+    }
+
+    pub fn init_size(&mut self, width: u32, height: u32, image_scale: f32, is_vertical: bool) {
+        self.content_blocks.clear();
+        self.last_frame = None;
+        self.is_vertical = is_vertical;
+        self.image_scale = image_scale;
+        self.width = width;
+        self.height = height;
+        self.screen_blocks =
+            vec![0; (width.div_ceil(MASK_BLOCK_SIZE) * height.div_ceil(MASK_BLOCK_SIZE)) as usize];
+    }
+
+    /// 将灰度图坐标转换为（滚动方向，垂直于滚动方向）坐标
+    fn to_axis(&self, x: i32, y: i32) -> (i32, i32) {
+        if self.is_vertical { (y, x) } else { (x, y) }
+    }
+
+    /**
+     * 灰度图坐标是否在屏蔽区域或最近发生变化的区域中
+     */
+    pub fn is_screen_masked(&self, x: i32, y: i32) -> bool {
+        if !self.mask_rects.is_empty() {
+            // 灰度图只在垂直于滚动方向上缩放
+            let (image_x, image_y) = if self.is_vertical {
+                ((x as f32 / self.image_scale) as i32, y)
+            } else {
+                (x, (y as f32 / self.image_scale) as i32)
+            };
+
+            if self.mask_rects.iter().any(|rect| {
+                image_x >= rect.min_x
+                    && image_x < rect.max_x
+                    && image_y >= rect.min_y
+                    && image_y < rect.max_y
+            }) {
+                return true;
+            }
+        }
+
+        if x < 0 || y < 0 || x as u32 >= self.width || y as u32 >= self.height {
+            return false;
+        }
+
+        let block_columns = self.width.div_ceil(MASK_BLOCK_SIZE);
+        let block_index = (y as u32 / MASK_BLOCK_SIZE) * block_columns + x as u32 / MASK_BLOCK_SIZE;
+
+        self.screen_blocks
+            .get(block_index as usize)
+            .map(|count| *count > 0)
+            .unwrap_or(false)
+    }
+
+    /**
+     * 位于 frame_position 的图片中的灰度图坐标，在内容中是否发生过变化
+     */
+    pub fn is_content_masked(&self, x: i32, y: i32, frame_position: i32) -> bool {
+        if self.content_blocks.is_empty() {
+            return false;
+        }
+
+        let (along, across) = self.to_axis(x, y);
+
+        self.content_blocks.contains(&(
+            (frame_position + along).div_euclid(MASK_BLOCK_SIZE as i32),
+            across.max(0) as u32 / MASK_BLOCK_SIZE,
+        ))
+    }
+
+    /**
+     * 将图片按滚动位置和上一张图片对齐后比较，更新变化区域
+     */
+    pub fn update(&mut self, gray_image: &GrayImage, frame_position: i32) {
+        if gray_image.width() != self.width || gray_image.height() != self.height {
+            return;
+        }
+
+        let (last_image, last_position) = match self.last_frame.take() {
+            Some(last_frame) => last_frame,
+            None => {
+                self.last_frame = Some((gray_image.clone(), frame_position));
+                return;
+            }
+        };
+
+        let (along_size, across_size) = if self.is_vertical {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        };
+        let block_columns = self.width.div_ceil(MASK_BLOCK_SIZE);
+        let position_delta = frame_position - last_position;
+
+        for along_block in 0..along_size.div_ceil(MASK_BLOCK_SIZE) {
+            let along_start = along_block * MASK_BLOCK_SIZE;
+            let along_end = (along_start + MASK_BLOCK_SIZE).min(along_size);
+
+            // 只比较两张图片都包含的块
+            let last_along_start = along_start as i32 + position_delta;
+            let last_along_end = along_end as i32 + position_delta;
+            if last_along_start < 0 || last_along_end > along_size as i32 {
+                continue;
+            }
+
+            for across_block in 0..across_size.div_ceil(MASK_BLOCK_SIZE) {
+                let across_start = across_block * MASK_BLOCK_SIZE;
+                let across_end = (across_start + MASK_BLOCK_SIZE).min(across_size);
+
+                let mut difference_sum = 0;
+                for along in along_start..along_end {
+                    let last_along = (along as i32 + position_delta) as u32;
+                    for across in across_start..across_end {
+                        let (x, y, last_x, last_y) = if self.is_vertical {
+                            (across, along, across, last_along)
+                        } else {
+                            (along, across, last_along, across)
+                        };
+
+                        difference_sum += gray_image.get_pixel(x, y)[0]
+                            .abs_diff(last_image.get_pixel(last_x, last_y)[0])
+                            as u32;
+                    }
+                }
+
+                let pixel_count = (along_end - along_start) * (across_end - across_start);
+                let is_changed = difference_sum > MASK_BLOCK_DIFFERENCE_THRESHOLD * pixel_count;
+
+                let (block_x, block_y) = if self.is_vertical {
+                    (across_block, along_block)
+                } else {
+                    (along_block, across_block)
+                };
+                let screen_block =
+                    &mut self.screen_blocks[(block_y * block_columns + block_x) as usize];
+
+                if is_changed {
+                    *screen_block = MASK_BLOCK_HOLD_COUNT;
+
+                    // 块在内容中可能跨越两个块
+                    let content_start =
+                        (frame_position + along_start as i32).div_euclid(MASK_BLOCK_SIZE as i32);
+                    let content_end =
+                        (frame_position + along_end as i32 - 1).div_euclid(MASK_BLOCK_SIZE as i32);
+                    for content_block in content_start..=content_end {
+                        self.content_blocks.insert((content_block, across_block));
+                    }
+                } else {
+                    *screen_block = screen_block.saturating_sub(1);
+                }
+            }
+        }
+
+        self.last_frame = Some((gray_image.clone(), frame_position));
+    }
+}
@@ -0,0 +1,269 @@
+use ffmpeg_sidecar::{command::FfmpegCommand, event::FfmpegEvent};
+use image::DynamicImage;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::scroll_screenshot_service::{ScrollDirection, ScrollImageList, ScrollScreenshotService};
+
+/// 未指定最小变化量时，按图片滚动方向边长的比例计算，和截图工具保持一致
+const DEFAULT_MIN_SIZE_DELTA_RATIO: f32 = 0.8;
+
+const IMAGE_FILE_EXTENSIONS: [&str; 7] = ["png", "jpg", "jpeg", "webp", "bmp", "tif", "tiff"];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct ScrollScreenshotOfflineParams {
+    pub direction: ScrollDirection,
+    pub sample_rate: f32,
+    pub min_sample_size: u32,
+    pub max_sample_size: u32,
+    pub corner_threshold: u8,
+    pub descriptor_patch_size: usize,
+    /// 为空时根据首帧的尺寸计算
+    pub min_size_delta: Option<i32>,
+    pub try_rollback: bool,
+}
+
+/// 离线拼接的统计结果
+#[derive(Serialize, Debug, Clone, Copy, Default)]
+pub struct ScrollScreenshotOfflineResult {
+    /// 读取的帧数
+    pub frame_count: usize,
+    /// 拼接成功的帧数
+    pub stitched_count: usize,
+    /// 和已有内容相同、没有新增区域的帧数
+    pub unchanged_count: usize,
+    /// 未能匹配的帧数
+    pub unmatched_count: usize,
+    /// 导出图片的宽度
+    pub width: u32,
+    /// 导出图片的高度
+    pub height: u32,
+}
+
+/**
+ * 读取目录下的图片文件，按文件名排序
+ */
+pub fn list_image_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let read_dir = match fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(e) => {
+            return Err(format!(
+                "[list_image_files] Failed to read dir {}: {}",
+                dir.display(),
+                e
+            ));
+        }
+    };
+
+    let mut file_list = read_dir
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .map(|extension| {
+                        IMAGE_FILE_EXTENSIONS
+                            .iter()
+                            .any(|item| extension.eq_ignore_ascii_case(item))
+                    })
+                    .unwrap_or(false)
+        })
+        .collect::<Vec<_>>();
+
+    file_list.sort();
+
+    Ok(file_list)
+}
+
+/**
+ * 离线拼接，不依赖窗口和截图，将已有的图片或视频帧依次交给 handle_image 处理
+ */
+impl ScrollScreenshotService {
+    fn init_offline(&mut self, params: &ScrollScreenshotOfflineParams) {
+        self.init(
+            params.direction,
+            params.sample_rate,
+            params.min_sample_size,
+            params.max_sample_size,
+            params.corner_threshold,
+            params.descriptor_patch_size,
+            params.min_size_delta.unwrap_or(0),
+            params.try_rollback,
+        );
+    }
+
+    fn handle_offline_frame(
+        &mut self,
+        params: &ScrollScreenshotOfflineParams,
+        image: DynamicImage,
+        result: &mut ScrollScreenshotOfflineResult,
+    ) {
+        result.frame_count += 1;
+
+        if params.min_size_delta.is_none() && self.image_width == 0 {
+            let side_size = if params.direction == ScrollDirection::Vertical {
+                image.height()
+            } else {
+                image.width()
+            };
+
+            self.min_size_delta = (side_size as f32 * DEFAULT_MIN_SIZE_DELTA_RATIO).ceil() as i32;
+        }
+
+        // 录屏通常是向下滚动，向上滚动的部分依赖 try_rollback 处理
+        let (handle_result, is_origin, _) = self.handle_image(image, ScrollImageList::Bottom);
+
+        if is_origin {
+            result.unchanged_count += 1;
+            return;
+        }
+
+        match handle_result {
+            Some((_, Some(_))) => result.stitched_count += 1,
+            Some((_, None)) => result.unchanged_count += 1,
+            None => result.unmatched_count += 1,
+        }
+    }
+
+    fn finish_offline(
+        &self,
+        mut result: ScrollScreenshotOfflineResult,
+    ) -> Result<ScrollScreenshotOfflineResult, String> {
+        let (width, height) = match self.get_export_size() {
+            Some(size) => size,
+            None => {
+                return Err(format!(
+                    "[ScrollScreenshotService::finish_offline] No frame was stitched from {} frames",
+                    result.frame_count
+                ));
+            }
+        };
+
+        result.width = width;
+        result.height = height;
+
+        Ok(result)
+    }
+
+    /**
+     * 按顺序拼接图片文件
+     */
+    pub fn stitch_image_files(
+        &mut self,
+        params: &ScrollScreenshotOfflineParams,
+        file_list: &[PathBuf],
+    ) -> Result<ScrollScreenshotOfflineResult, String> {
+        self.init_offline(params);
+
+        let mut result = ScrollScreenshotOfflineResult::default();
+        for file_path in file_list {
+            let image = match image::open(file_path) {
+                Ok(image) => image,
+                Err(e) => {
+                    return Err(format!(
+                        "[ScrollScreenshotService::stitch_image_files] Failed to open image {}: {}",
+                        file_path.display(),
+                        e
+                    ));
+                }
+            };
+
+            self.handle_offline_frame(params, image, &mut result);
+        }
+
+        self.finish_offline(result)
+    }
+
+    /**
+     * 通过 ffmpeg 解码视频并拼接视频帧
+     *
+     * frame_rate 为空时使用视频原始帧率
+     */
+    pub fn stitch_video(
+        &mut self,
+        params: &ScrollScreenshotOfflineParams,
+        ffmpeg_path: &Path,
+        video_path: &Path,
+        frame_rate: Option<f32>,
+    ) -> Result<ScrollScreenshotOfflineResult, String> {
+        if !ffmpeg_path.exists() {
+            return Err(format!(
+                "[ScrollScreenshotService::stitch_video] ffmpeg not found: {}",
+                ffmpeg_path.display()
+            ));
+        }
+
+        self.init_offline(params);
+
+        let mut command = FfmpegCommand::new_with_path(ffmpeg_path);
+        command.hide_banner().input(video_path.to_string_lossy());
+        if let Some(frame_rate) = frame_rate {
+            let video_filter = format!("fps={}", frame_rate);
+            command.args(["-vf", video_filter.as_str()]);
+        }
+        command.rawvideo();
+
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                return Err(format!(
+                    "[ScrollScreenshotService::stitch_video] Failed to spawn ffmpeg: {}",
+                    e
+                ));
+            }
+        };
+
+        let iter = match child.iter() {
+            Ok(iter) => iter,
+            Err(e) => {
+                let _ = child.kill();
+                return Err(format!(
+                    "[ScrollScreenshotService::stitch_video] Failed to iter ffmpeg: {}",
+                    e
+                ));
+            }
+        };
+
+        let mut result = ScrollScreenshotOfflineResult::default();
+        let mut error_message = None;
+        for event in iter {
+            match event {
+                FfmpegEvent::OutputFrame(frame) => {
+                    let image =
+                        match image::RgbImage::from_raw(frame.width, frame.height, frame.data) {
+                            Some(image) => DynamicImage::ImageRgb8(image),
+                            None => {
+                                log::warn!(
+                                    "[ScrollScreenshotService::stitch_video] Invalid frame: {}",
+                                    frame.frame_num
+                                );
+                                continue;
+                            }
+                        };
+
+                    self.handle_offline_frame(params, image, &mut result);
+                }
+                FfmpegEvent::Error(e) => {
+                    log::error!("[ScrollScreenshotService::stitch_video] {}", e);
+                    error_message = Some(e);
+                }
+                _ => {}
+            }
+        }
+
+        let _ = child.wait();
+
+        if result.frame_count == 0 {
+            return Err(format!(
+                "[ScrollScreenshotService::stitch_video] No frame decoded from {}: {}",
+                video_path.display(),
+                error_message.unwrap_or_default()
+            ));
+        }
+
+        self.finish_offline(result)
+    }
+}
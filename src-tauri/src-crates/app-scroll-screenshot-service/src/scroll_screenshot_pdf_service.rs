@@ -0,0 +1,319 @@
+use flate2::{Compression, write::ZlibEncoder};
+use pdf_writer::{Content, Filter, Finish, Name, Pdf, Rect, Ref};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+use crate::scroll_screenshot_service::{CropRegion, ScrollDirection, ScrollScreenshotService};
+
+const RGBA_CHANNEL_COUNT: usize = 4;
+const RGB_CHANNEL_COUNT: usize = 3;
+
+/// 1 英寸 = 72 磅 = 25.4 毫米
+const POINTS_PER_INCH: f32 = 72.0;
+const MILLIMETERS_PER_INCH: f32 = 25.4;
+
+/// 在页面边界之前多大比例的范围内寻找空白行（列）
+const PDF_BLANK_SEARCH_RATIO: f32 = 0.2;
+/// 行（列）内所有像素和首个像素的通道差异都不超过该值时认为是空白行（列）
+const PDF_BLANK_LINE_TOLERANCE: u8 = 8;
+
+const PDF_IMAGE_NAME: Name = Name(b"Im1");
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct ScrollScreenshotPdfOptions {
+    /// 页面宽度（毫米）
+    pub page_width: f32,
+    /// 页面高度（毫米）
+    pub page_height: f32,
+    /// 页边距（毫米）
+    pub margin: f32,
+    /// 图片分辨率，决定图片在页面上的物理尺寸，图片超出页面宽度时会缩小显示
+    pub dpi: f32,
+}
+
+impl Default for ScrollScreenshotPdfOptions {
+    /// A4 纸张
+    fn default() -> Self {
+        Self {
+            page_width: 210.0,
+            page_height: 297.0,
+            margin: 10.0,
+            dpi: 96.0,
+        }
+    }
+}
+
+impl ScrollScreenshotPdfOptions {
+    fn to_points(millimeters: f32) -> f32 {
+        millimeters / MILLIMETERS_PER_INCH * POINTS_PER_INCH
+    }
+
+    /// 页面尺寸（磅）
+    fn page_size(&self) -> (f32, f32) {
+        (
+            Self::to_points(self.page_width.max(1.0)),
+            Self::to_points(self.page_height.max(1.0)),
+        )
+    }
+
+    /// 页面内容区域的尺寸（磅）
+    fn content_size(&self) -> (f32, f32) {
+        let (page_width, page_height) = self.page_size();
+        let margin = Self::to_points(self.margin.max(0.0));
+
+        (
+            (page_width - margin * 2.0).max(1.0),
+            (page_height - margin * 2.0).max(1.0),
+        )
+    }
+}
+
+/**
+ * 判断一行（列）像素是否为空白
+ *
+ * pixels 为 RGBA8 像素，stride 为相邻像素之间的字节数
+ */
+fn is_blank_line(pixels: &[u8], start: usize, count: usize, stride: usize) -> bool {
+    let first_pixel = &pixels[start..start + RGB_CHANNEL_COUNT];
+
+    (1..count).all(|i| {
+        let offset = start + i * stride;
+        pixels[offset..offset + RGB_CHANNEL_COUNT]
+            .iter()
+            .zip(first_pixel.iter())
+            .all(|(a, b)| a.abs_diff(*b) <= PDF_BLANK_LINE_TOLERANCE)
+    })
+}
+
+/**
+ * 导出为 PDF，每页嵌入一张无损压缩的图片
+ */
+impl ScrollScreenshotService {
+    /**
+     * 图片在页面上的缩放（磅 / 像素）
+     *
+     * 切分页面和写入页面都使用导出图片的尺寸计算，保证页面内容不超出页面
+     */
+    fn get_pdf_scale(&self, options: &ScrollScreenshotPdfOptions, export_size: (u32, u32)) -> f32 {
+        let (export_width, export_height) = export_size;
+        let (content_width, content_height) = options.content_size();
+        let (cross_size, content_cross_size) =
+            if self.current_direction == ScrollDirection::Vertical {
+                (export_width, content_width)
+            } else {
+                (export_height, content_height)
+            };
+
+        let scale = POINTS_PER_INCH / options.dpi.max(1.0);
+
+        // 垂直于滚动方向上超出页面时缩小显示
+        scale.min(content_cross_size / cross_size.max(1) as f32)
+    }
+
+    /**
+     * 在 [search_start, search_end) 中寻找最靠后的空白行（列）
+     */
+    fn find_blank_position(
+        &mut self,
+        search_start: u32,
+        search_end: u32,
+        total_width: u32,
+        total_height: u32,
+    ) -> Result<Option<u32>, String> {
+        if search_start >= search_end {
+            return Ok(None);
+        }
+
+        let search_size = search_end - search_start;
+        if self.current_direction == ScrollDirection::Vertical {
+            let pixels =
+                self.export_region(CropRegion::new(0, search_start, total_width, search_size))?;
+            let row_size = total_width as usize * RGBA_CHANNEL_COUNT;
+
+            Ok((0..search_size).rev().find_map(|row| {
+                is_blank_line(
+                    &pixels,
+                    row as usize * row_size,
+                    total_width as usize,
+                    RGBA_CHANNEL_COUNT,
+                )
+                .then_some(search_start + row)
+            }))
+        } else {
+            let pixels =
+                self.export_region(CropRegion::new(search_start, 0, search_size, total_height))?;
+            let row_size = search_size as usize * RGBA_CHANNEL_COUNT;
+
+            Ok((0..search_size).rev().find_map(|column| {
+                is_blank_line(
+                    &pixels,
+                    column as usize * RGBA_CHANNEL_COUNT,
+                    total_height as usize,
+                    row_size,
+                )
+                .then_some(search_start + column)
+            }))
+        }
+    }
+
+    /**
+     * 按页面尺寸切分导出图片，优先在页面边界附近的空白行（列）处切分，避免截断文字
+     */
+    pub fn get_pdf_pages(
+        &mut self,
+        options: &ScrollScreenshotPdfOptions,
+    ) -> Result<Vec<CropRegion>, String> {
+        let (total_width, total_height) = match self.get_export_size() {
+            Some(size) => size,
+            None => {
+                return Err(String::from(
+                    "[ScrollScreenshotService::get_pdf_pages] No image to export",
+                ));
+            }
+        };
+
+        let is_vertical = self.current_direction == ScrollDirection::Vertical;
+        let total_size = if is_vertical {
+            total_height
+        } else {
+            total_width
+        };
+
+        let scale = self.get_pdf_scale(options, (total_width, total_height));
+        let (content_width, content_height) = options.content_size();
+        let content_size = if is_vertical {
+            content_height
+        } else {
+            content_width
+        };
+        let page_size = ((content_size / scale) as u32).max(1);
+        let search_size = (page_size as f32 * PDF_BLANK_SEARCH_RATIO) as u32;
+
+        let mut page_list = Vec::new();
+        let mut page_start = 0;
+        while page_start < total_size {
+            let mut page_end = (page_start + page_size).min(total_size);
+
+            if page_end < total_size {
+                // 边界处已经是空白时直接切分
+                if let Some(blank_position) = self.find_blank_position(
+                    page_end - search_size.min(page_end - page_start - 1),
+                    page_end + 1,
+                    total_width,
+                    total_height,
+                )? {
+                    page_end = blank_position;
+                }
+            }
+
+            page_list.push(if is_vertical {
+                CropRegion::new(0, page_start, total_width, page_end - page_start)
+            } else {
+                CropRegion::new(page_start, 0, page_end - page_start, total_height)
+            });
+            page_start = page_end;
+        }
+
+        Ok(page_list)
+    }
+
+    /**
+     * 将导出图片写入 PDF，返回页数
+     */
+    pub fn export_pdf<W: Write>(
+        &mut self,
+        mut writer: W,
+        options: &ScrollScreenshotPdfOptions,
+    ) -> Result<usize, String> {
+        let page_list = self.get_pdf_pages(options)?;
+
+        let Some(export_size) = self.get_export_size() else {
+            return Err(String::from(
+                "[ScrollScreenshotService::export_pdf] No image to export",
+            ));
+        };
+        let scale = self.get_pdf_scale(options, export_size);
+        let (page_width, page_height) = options.page_size();
+        let margin = ScrollScreenshotPdfOptions::to_points(options.margin.max(0.0));
+
+        let mut pdf = Pdf::new();
+        let catalog_id = Ref::new(1);
+        let page_tree_id = Ref::new(2);
+        // 每页依次使用页面、内容、图片三个对象
+        let page_ids = (0..page_list.len())
+            .map(|i| Ref::new(3 + i as i32 * 3))
+            .collect::<Vec<_>>();
+
+        pdf.catalog(catalog_id).pages(page_tree_id);
+        pdf.pages(page_tree_id)
+            .kids(page_ids.iter().copied())
+            .count(page_list.len() as i32);
+
+        for (page_region, page_id) in page_list.iter().zip(page_ids.iter()) {
+            let content_id = Ref::new(page_id.get() + 1);
+            let image_id = Ref::new(page_id.get() + 2);
+
+            // 截图不包含透明区域，丢弃 alpha 通道后以 Flate 无损压缩
+            let page_pixels = self.export_region(*page_region)?;
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            for pixel in page_pixels.chunks_exact(RGBA_CHANNEL_COUNT) {
+                if let Err(e) = encoder.write_all(&pixel[..RGB_CHANNEL_COUNT]) {
+                    return Err(format!(
+                        "[ScrollScreenshotService::export_pdf] Failed to compress image: {}",
+                        e
+                    ));
+                }
+            }
+            let image_data = match encoder.finish() {
+                Ok(image_data) => image_data,
+                Err(e) => {
+                    return Err(format!(
+                        "[ScrollScreenshotService::export_pdf] Failed to compress image: {}",
+                        e
+                    ));
+                }
+            };
+
+            let mut image = pdf.image_xobject(image_id, &image_data);
+            image.filter(Filter::FlateDecode);
+            image.width(page_region.width as i32);
+            image.height(page_region.height as i32);
+            image.color_space().device_rgb();
+            image.bits_per_component(8);
+            image.finish();
+
+            // 图片贴在内容区域的左上角
+            let image_width = page_region.width as f32 * scale;
+            let image_height = page_region.height as f32 * scale;
+            let mut content = Content::new();
+            content.save_state();
+            content.transform([
+                image_width,
+                0.0,
+                0.0,
+                image_height,
+                margin,
+                page_height - margin - image_height,
+            ]);
+            content.x_object(PDF_IMAGE_NAME);
+            content.restore_state();
+            pdf.stream(content_id, &content.finish());
+
+            let mut page = pdf.page(*page_id);
+            page.media_box(Rect::new(0.0, 0.0, page_width, page_height));
+            page.parent(page_tree_id);
+            page.contents(content_id);
+            page.resources().x_objects().pair(PDF_IMAGE_NAME, image_id);
+            page.finish();
+        }
+
+        if let Err(e) = writer.write_all(&pdf.finish()) {
+            return Err(format!(
+                "[ScrollScreenshotService::export_pdf] Failed to write pdf: {}",
+                e
+            ));
+        }
+
+        Ok(page_list.len())
+    }
+}
@@ -7,8 +7,23 @@ use image::{DynamicImage, GenericImageView, GrayImage};
 use imageproc::corners;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::io::Write;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+use crate::scroll_screenshot_frame_store::{ScrollFrameHandle, ScrollFrameStore};
+use crate::scroll_screenshot_mask::{ScrollDynamicMask, ScrollDynamicMaskSnapshot};
+use snow_shot_app_shared::ElementRect;
+use snow_shot_app_utils::EncodeOptions;
+use snow_shot_app_utils::capture_metadata::CaptureMetadata;
+
+const RGBA_CHANNEL_COUNT: usize = 4;
+
+/// 每个方向保留在内存中的图片数量，更早的图片会写入临时文件
+const MEMORY_IMAGE_COUNT: usize = 2;
+
+/// 流式导出时每次合成的行数
+const EXPORT_BAND_SIZE: u32 = 256;
+
 #[derive(PartialEq, Serialize, Deserialize, Debug, Clone, Copy)]
 pub enum ScrollDirection {
     /// 垂直滚动
@@ -64,6 +79,14 @@ pub struct ScrollIndex {
     pub descriptors: Vec<Vec<f32>>,
 }
 
+/// 建立索引所需的特征数据，撤销图片时用于重建索引
+#[derive(Debug, Clone)]
+pub struct ScrollIndexFeature {
+    pub position: i32,
+    pub corners: Vec<ScrollOffset>,
+    pub descriptors: Vec<Vec<f32>>,
+}
+
 impl ScrollIndex {
     pub fn new(dimension: usize) -> Self {
         let mut index_params = HNSWParams::<f32>::default();
@@ -77,11 +100,119 @@ impl ScrollIndex {
             descriptors: vec![],
         }
     }
+
+    pub fn from_feature(dimension: usize, feature: &ScrollIndexFeature) -> Self {
+        let mut scroll_index = Self::new(dimension);
+        scroll_index.position = feature.position;
+        scroll_index.corners = feature.corners.clone();
+        scroll_index.descriptors = feature.descriptors.clone();
+
+        scroll_index
+            .descriptors
+            .iter()
+            .enumerate()
+            .for_each(|(i, descriptor)| {
+                scroll_index.ann_index.add(descriptor, i).unwrap();
+            });
+
+        scroll_index.ann_index.build(Metric::Euclidean).unwrap();
+
+        scroll_index
+    }
+}
+
+/// 单张图片的特征点匹配统计
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+pub struct ScrollMatchStats {
+    /// 图片的特征点数量
+    pub corner_count: usize,
+    /// 描述符距离满足阈值的匹配数量
+    pub match_count: usize,
+    /// 主偏移的票数，即内点数量
+    pub inlier_count: usize,
+    /// 主偏移
+    pub dominant_offset: i32,
+    /// 票数第二的偏移
+    pub second_offset: Option<i32>,
+    /// 票数第二的偏移的票数
+    pub second_count: usize,
+    /// 内点的平均描述符距离
+    pub mean_distance: f32,
+}
+
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum ScrollFrameStatus {
+    /// 已拼接
+    Stitched,
+    /// 没有新增区域
+    Unchanged,
+    /// 未匹配
+    Unmatched,
+}
+
+/// handle_image 处理的每张图片的结果
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct ScrollFrameStats {
+    /// 图片的处理序号
+    pub frame_index: usize,
+    pub status: ScrollFrameStatus,
+    /// 拼接或匹配的方向
+    pub scroll_image_list: ScrollImageList,
+    pub edge_position: Option<i32>,
+    pub match_stats: Option<ScrollMatchStats>,
+}
+
+pub enum ScrollImageData {
+    /// 图片在内存中
+    Memory(image::DynamicImage),
+    /// 图片已写入临时文件
+    Store(ScrollFrameHandle),
 }
 
 pub struct ScrollImage {
-    pub image: image::DynamicImage,
+    pub data: ScrollImageData,
+    pub width: u32,
+    pub height: u32,
     pub overlay_size: i32,
+    /// 推入时方向边的变化量
+    pub delta_size: i32,
+    /// 推入时索引尺寸的变化量
+    pub index_delta_size: i32,
+    /// 推入时建立的索引特征
+    pub index_feature: Option<ScrollIndexFeature>,
+    /// 推入时的匹配统计，首张图片没有匹配
+    pub match_stats: Option<ScrollMatchStats>,
+    /// 推入时图片的处理序号
+    pub frame_index: usize,
+    /// 推入前检测到的变化区域，撤销时回退到该状态
+    pub dynamic_mask_snapshot: Option<ScrollDynamicMaskSnapshot>,
+}
+
+impl ScrollImage {
+    /// 图片仍在内存中时返回图片
+    pub fn image(&self) -> Option<&image::DynamicImage> {
+        match &self.data {
+            ScrollImageData::Memory(image) => Some(image),
+            ScrollImageData::Store(_) => None,
+        }
+    }
+
+    /// 读取图片的部分行，读取的行数由 buf 的长度决定
+    pub(crate) fn read_rows(
+        &self,
+        frame_store: &mut ScrollFrameStore,
+        row_start: u32,
+        buf: &mut [u8],
+    ) -> Result<(), String> {
+        match &self.data {
+            ScrollImageData::Memory(image) => {
+                let start = row_start as usize * self.width as usize * RGBA_CHANNEL_COUNT;
+                buf.copy_from_slice(&image.as_bytes()[start..start + buf.len()]);
+                Ok(())
+            }
+            ScrollImageData::Store(handle) => frame_store.read_rows(handle, row_start, buf),
+        }
+    }
 }
 
 pub struct ScrollScreenshotService {
@@ -133,10 +264,16 @@ pub struct ScrollScreenshotService {
     pub min_sample_size: u32,
     /// 最大采样尺寸
     pub max_sample_size: u32,
+    /// 已拼接图片的临时文件存储
+    pub frame_store: ScrollFrameStore,
+    /// 每张处理过的图片的匹配结果
+    pub frame_stats: Vec<ScrollFrameStats>,
+    /// 与滚动无关的变化区域和指定的屏蔽区域
+    pub dynamic_mask: ScrollDynamicMask,
 }
 
 impl ScrollScreenshotService {
-    fn get_descriptor_size(&self) -> usize {
+    pub(crate) fn get_descriptor_size(&self) -> usize {
         self.descriptor_patch_size & !1
     }
 
@@ -233,12 +370,18 @@ impl ScrollScreenshotService {
             sample_rate: 0.0,
             min_sample_size: 0,
             max_sample_size: 0,
+            frame_store: ScrollFrameStore::new(),
+            frame_stats: vec![],
+            dynamic_mask: ScrollDynamicMask::new(),
         }
     }
 
     pub fn clear(&mut self) {
         self.top_image_list.clear();
         self.bottom_image_list.clear();
+        self.frame_store.clear();
+        self.frame_stats.clear();
+        self.dynamic_mask.clear();
         self.top_image_ann_index = ScrollIndex::new(0);
         self.bottom_image_ann_index = ScrollIndex::new(0);
     }
@@ -256,6 +399,9 @@ impl ScrollScreenshotService {
     ) {
         self.top_image_list.clear();
         self.bottom_image_list.clear();
+        self.frame_store.clear();
+        self.frame_stats.clear();
+        self.dynamic_mask = ScrollDynamicMask::new();
         self.current_direction = direction;
         self.image_width = 0;
         self.image_height = 0;
@@ -275,6 +421,13 @@ impl ScrollScreenshotService {
         self.max_sample_size = max_sample_size;
     }
 
+    /**
+     * 设置屏蔽区域（图片坐标），区域内的特征点不参与匹配
+     */
+    pub fn set_mask_rects(&mut self, mask_rects: Vec<ElementRect>) {
+        self.dynamic_mask.set_mask_rects(mask_rects);
+    }
+
     pub fn init_image_size(&mut self, image_width: u32, image_height: u32) {
         self.image_width = image_width;
         self.image_height = image_height;
@@ -305,9 +458,16 @@ impl ScrollScreenshotService {
         } else {
             self.image_width as i32
         };
+
+        self.dynamic_mask.init_size(
+            self.image_dst_width,
+            self.image_dst_height,
+            self.image_scale,
+            self.current_direction == ScrollDirection::Vertical,
+        );
     }
 
-    fn get_descriptors(
+    pub(crate) fn get_descriptors(
         &self,
         image: &image::ImageBuffer<image::Luma<u8>, Vec<u8>>,
         corners: &[ScrollOffset],
@@ -318,7 +478,7 @@ impl ScrollScreenshotService {
             .collect()
     }
 
-    fn get_gray_image(&mut self, image: &DynamicImage) -> GrayImage {
+    pub(crate) fn get_gray_image(&mut self, image: &DynamicImage) -> GrayImage {
         let image_width = image.width();
         let image_height = image.height();
 
@@ -374,7 +534,7 @@ impl ScrollScreenshotService {
             }
         } else {
             let start_position = image_width - delta_size.abs() as u32;
-            if start_position > 0 {
+            if delta_size > 0 {
                 region = CropRegion::new(
                     start_position,
                     0,
@@ -389,7 +549,7 @@ impl ScrollScreenshotService {
         region
     }
 
-    fn get_corners(&mut self, image: &image::GrayImage) -> Vec<ScrollOffset> {
+    pub(crate) fn get_corners(&mut self, image: &image::GrayImage) -> Vec<ScrollOffset> {
         let corners;
         if self.enable_corner_fast12.is_none() {
             let fast12_corners = corners::corners_fast12(image, self.corner_threshold);
@@ -409,12 +569,14 @@ impl ScrollScreenshotService {
             }
         }
 
+        // 排除屏蔽区域和变化区域中的特征点
         corners
             .iter()
             .map(|corner| ScrollOffset {
                 x: corner.x as i32,
                 y: corner.y as i32,
             })
+            .filter(|corner| !self.dynamic_mask.is_screen_masked(corner.x, corner.y))
             .collect()
     }
 
@@ -424,40 +586,45 @@ impl ScrollScreenshotService {
         image_corners: &[ScrollOffset],
         edge_position: i32,
         index_edge_position_distance: i32,
-    ) {
-        let mut new_scroll_index = ScrollIndex::new(self.get_descriptor_size());
-
-        new_scroll_index.descriptors = self.get_descriptors(&gray_image, &image_corners);
-
-        new_scroll_index.corners = image_corners.to_vec();
-
-        let mut index_params = HNSWParams::<f32>::default();
-        index_params.ef_search = 32;
-        index_params.ef_build = 16;
-
-        new_scroll_index
-            .descriptors
-            .iter()
-            .enumerate()
-            .for_each(|(i, descriptor)| {
-                new_scroll_index.ann_index.add(descriptor, i).unwrap();
-            });
-
-        new_scroll_index.ann_index.build(Metric::Euclidean).unwrap();
-
+    ) -> ScrollIndexFeature {
         let index_position = if edge_position > 0 {
             self.bottom_image_index_size - index_edge_position_distance
         } else {
             -(self.top_image_index_size - index_edge_position_distance)
         };
 
-        new_scroll_index.position = index_position;
+        // 排除内容中发生过变化的特征点，避免动画等内容参与匹配
+        let frame_position = if edge_position > 0 {
+            edge_position - self.image_scroll_side_size
+        } else {
+            edge_position
+        };
+        let index_corners = image_corners
+            .iter()
+            .filter(|corner| {
+                !self
+                    .dynamic_mask
+                    .is_content_masked(corner.x, corner.y, frame_position)
+            })
+            .copied()
+            .collect::<Vec<_>>();
+
+        let index_feature = ScrollIndexFeature {
+            position: index_position,
+            descriptors: self.get_descriptors(&gray_image, &index_corners),
+            corners: index_corners,
+        };
+
+        let new_scroll_index =
+            ScrollIndex::from_feature(self.get_descriptor_size(), &index_feature);
 
         if edge_position > 0 {
             self.bottom_image_ann_index = new_scroll_index;
         } else {
             self.top_image_ann_index = new_scroll_index;
         }
+
+        index_feature
     }
 
     fn add_index(
@@ -467,8 +634,9 @@ impl ScrollScreenshotService {
         image_corners: Vec<ScrollOffset>,
         edge_position: i32,
         delta_size: i32,
-    ) -> (ScrollImage, i32) {
+    ) -> ScrollImage {
         let mut index_delta_size = 0;
+        let mut index_feature = None;
 
         let image_scroll_side_size = self.image_scroll_side_size;
 
@@ -480,12 +648,12 @@ impl ScrollScreenshotService {
 
         if index_edge_position_distance <= self.min_size_delta {
             index_delta_size = image_scroll_side_size - index_edge_position_distance;
-            self.build_index(
+            index_feature = Some(self.build_index(
                 gray_image,
                 &image_corners,
                 edge_position,
                 index_edge_position_distance,
-            );
+            ));
         }
 
         // 一半的区域在拼接时允许
@@ -498,18 +666,29 @@ impl ScrollScreenshotService {
 
         let crop_region = self.get_crop_region(delta_size + image_overlay_size);
 
-        (
-            ScrollImage {
-                image: image.crop_imm(
-                    crop_region.x,
-                    crop_region.y,
-                    crop_region.width,
-                    crop_region.height,
-                ),
-                overlay_size: image_overlay_size,
-            },
+        // 导出时按 RGBA8 读取像素
+        let image = match image.crop_imm(
+            crop_region.x,
+            crop_region.y,
+            crop_region.width,
+            crop_region.height,
+        ) {
+            image @ DynamicImage::ImageRgba8(_) => image,
+            image => DynamicImage::ImageRgba8(image.to_rgba8()),
+        };
+
+        ScrollImage {
+            data: ScrollImageData::Memory(image),
+            width: crop_region.width,
+            height: crop_region.height,
+            overlay_size: image_overlay_size,
+            delta_size,
             index_delta_size,
-        )
+            index_feature,
+            match_stats: None,
+            frame_index: self.frame_stats.len(),
+            dynamic_mask_snapshot: None,
+        }
     }
 
     fn push_image(
@@ -539,6 +718,17 @@ impl ScrollScreenshotService {
             self.image_width as i32
         };
 
+        // 对齐上一张图片，更新与滚动无关的变化区域
+        let dynamic_mask_snapshot = self.dynamic_mask.snapshot();
+        self.dynamic_mask.update(
+            &gray_image,
+            if self.current_direction == ScrollDirection::Vertical {
+                position_offset.y
+            } else {
+                position_offset.x
+            },
+        );
+
         // 计算边缘位置
         let edge_position = if self.current_direction == ScrollDirection::Vertical {
             if position_offset.y >= 0 {
@@ -564,24 +754,55 @@ impl ScrollScreenshotService {
                 return (edge_position, None); // 没有新增区域或变化太小
             };
 
-        let (cropped_image, index_delta_size) =
+        let mut cropped_image =
             self.add_index(image, gray_image, image_corners, edge_position, delta_size);
+        cropped_image.dynamic_mask_snapshot = Some(dynamic_mask_snapshot);
+        let index_delta_size = cropped_image.index_delta_size;
 
         if is_bottom {
             self.bottom_image_list.push(cropped_image);
             self.bottom_image_size += delta_size;
             self.bottom_image_index_size += index_delta_size;
+            self.store_images(ScrollImageList::Bottom);
 
             (edge_position, Some(ScrollImageList::Bottom))
         } else {
             self.top_image_list.push(cropped_image);
             self.top_image_size -= delta_size;
             self.top_image_index_size += index_delta_size;
+            self.store_images(ScrollImageList::Top);
 
             (edge_position, Some(ScrollImageList::Top))
         }
     }
 
+    /**
+     * 将较早的图片写入临时文件，只在内存中保留最近的几张图片
+     */
+    pub(crate) fn store_images(&mut self, scroll_image_list: ScrollImageList) {
+        let image_list = match scroll_image_list {
+            ScrollImageList::Top => &mut self.top_image_list,
+            ScrollImageList::Bottom => &mut self.bottom_image_list,
+        };
+
+        for scroll_image in image_list.iter_mut().rev().skip(MEMORY_IMAGE_COUNT) {
+            let image = match &scroll_image.data {
+                ScrollImageData::Memory(image) => image,
+                // 更早的图片已经写入
+                ScrollImageData::Store(_) => break,
+            };
+
+            match self.frame_store.push(image) {
+                Ok(handle) => scroll_image.data = ScrollImageData::Store(handle),
+                Err(e) => {
+                    // 写入失败时保留在内存中
+                    log::warn!("[ScrollScreenshotService::store_images] {}", e);
+                    break;
+                }
+            }
+        }
+    }
+
     pub fn get_offsets<'a>(
         &self,
         index: &'a ScrollIndex,
@@ -589,6 +810,26 @@ impl ScrollScreenshotService {
         image_corners: &[ScrollOffset],
         scroll_image_list: ScrollImageList,
     ) -> (Option<(&'a ScrollIndex, usize, usize)>, bool) {
+        let (offsets, is_origin, _) =
+            self.get_offsets_with_stats(index, image_descriptors, image_corners, scroll_image_list);
+
+        (offsets, is_origin)
+    }
+
+    /**
+     * 同 get_offsets，额外返回匹配统计，没有候选匹配时统计为空
+     */
+    pub fn get_offsets_with_stats<'a>(
+        &self,
+        index: &'a ScrollIndex,
+        image_descriptors: &[Vec<f32>],
+        image_corners: &[ScrollOffset],
+        scroll_image_list: ScrollImageList,
+    ) -> (
+        Option<(&'a ScrollIndex, usize, usize)>,
+        bool,
+        Option<ScrollMatchStats>,
+    ) {
         let image_scroll_side_size = if self.current_direction == ScrollDirection::Vertical {
             self.image_height as i32
         } else {
@@ -602,7 +843,7 @@ impl ScrollScreenshotService {
 
         let min_diff_count = AtomicUsize::new(0);
 
-        let offsets: Vec<(i32, &'a ScrollIndex, usize, usize)> = image_descriptors
+        let offsets: Vec<(i32, &'a ScrollIndex, usize, usize, f32)> = image_descriptors
             .par_iter()
             .enumerate()
             .filter_map(|(i, descriptor)| {
@@ -644,7 +885,7 @@ impl ScrollScreenshotService {
                 }
 
                 if dist < 0.1 {
-                    Some((diff, index, idx1, i))
+                    Some((diff, index, idx1, i, dist))
                 } else {
                     None
                 }
@@ -652,23 +893,34 @@ impl ScrollScreenshotService {
             .collect();
 
         if min_diff_count.load(Ordering::Relaxed) > (image_corners.len() as f32 * 0.72) as usize {
-            return (None, true);
+            return (None, true, None);
         }
 
         if offsets.is_empty() {
-            return (None, false);
+            return (None, false, None);
         }
 
+        let match_count = offsets.len();
+
         // 寻找频率最高的偏移作为主要偏移模式
-        let mut offset_counts: std::collections::HashMap<i32, (i32, &ScrollIndex, usize, usize)> =
-            std::collections::HashMap::new();
-        for (offset, scroll_index, origin_position_index, new_position_index) in offsets {
+        let mut offset_counts: std::collections::HashMap<
+            i32,
+            (i32, &ScrollIndex, usize, usize, f32),
+        > = std::collections::HashMap::new();
+        for (offset, scroll_index, origin_position_index, new_position_index, dist) in offsets {
             if let Some(value) = offset_counts.get_mut(&offset) {
                 value.0 += 1;
+                value.4 += dist;
             } else {
                 offset_counts.insert(
                     offset,
-                    (1, scroll_index, origin_position_index, new_position_index),
+                    (
+                        1,
+                        scroll_index,
+                        origin_position_index,
+                        new_position_index,
+                        dist,
+                    ),
                 );
             }
         }
@@ -684,30 +936,50 @@ impl ScrollScreenshotService {
         // );
 
         let mut max_count = 0;
-        let mut second_max_count = 0;
         let mut max_offset = None;
+        let mut max_offset_value = 0;
+        let mut max_distance_sum = 0.0;
 
-        for (_, (count, scroll_index, origin_idx, new_idx)) in &offset_counts {
+        for (offset, (count, scroll_index, origin_idx, new_idx, distance_sum)) in &offset_counts {
             if *count > max_count {
-                second_max_count = max_count;
                 max_count = *count;
                 max_offset = Some((scroll_index, origin_idx, new_idx));
-            } else if *count > second_max_count {
+                max_offset_value = *offset;
+                max_distance_sum = *distance_sum;
+            }
+        }
+
+        // 相邻 1 像素的偏移通常来自同一处内容的边缘，不视为竞争的偏移
+        let mut second_max_count = 0;
+        let mut second_max_offset_value = None;
+        for (offset, (count, _, _, _, _)) in &offset_counts {
+            if (*offset - max_offset_value).abs() > 1 && *count > second_max_count {
                 second_max_count = *count;
+                second_max_offset_value = Some(*offset);
             }
         }
 
         let max_offset = match max_offset {
             Some(offset) => offset,
-            None => return (None, false),
+            None => return (None, false, None),
+        };
+
+        let match_stats = ScrollMatchStats {
+            corner_count: image_corners.len(),
+            match_count,
+            inlier_count: max_count as usize,
+            dominant_offset: max_offset_value,
+            second_offset: second_max_offset_value,
+            second_count: second_max_count as usize,
+            mean_distance: max_distance_sum / max_count as f32,
         };
 
         if max_count < (image_corners.len() as i32 / 10) {
-            return (None, false);
+            return (None, false, Some(match_stats));
         }
 
         if max_count < second_max_count * 2 {
-            return (None, false);
+            return (None, false, Some(match_stats));
         }
 
         let (dominant_scroll_index, dominant_origin_position_index, dominant_new_position_index) =
@@ -720,6 +992,7 @@ impl ScrollScreenshotService {
                 *dominant_new_position_index,
             )),
             false,
+            Some(match_stats),
         )
     }
 
@@ -731,6 +1004,48 @@ impl ScrollScreenshotService {
         Option<(i32, Option<ScrollImageList>)>,
         bool,
         ScrollImageList,
+    ) {
+        let (handle_result, is_origin, result_scroll_image_list, match_stats) =
+            self.handle_image_frame(image, scroll_image_list);
+
+        let (status, edge_position) = match handle_result {
+            Some((edge_position, Some(_))) => (ScrollFrameStatus::Stitched, Some(edge_position)),
+            Some((edge_position, None)) => (ScrollFrameStatus::Unchanged, Some(edge_position)),
+            None if is_origin => (ScrollFrameStatus::Unchanged, None),
+            None => (ScrollFrameStatus::Unmatched, None),
+        };
+
+        if let Some((_, Some(pushed_scroll_image_list))) = handle_result {
+            let pushed_image = match pushed_scroll_image_list {
+                ScrollImageList::Top => self.top_image_list.last_mut(),
+                ScrollImageList::Bottom => self.bottom_image_list.last_mut(),
+            };
+
+            if let Some(pushed_image) = pushed_image {
+                pushed_image.match_stats = match_stats;
+            }
+        }
+
+        self.frame_stats.push(ScrollFrameStats {
+            frame_index: self.frame_stats.len(),
+            status,
+            scroll_image_list: result_scroll_image_list,
+            edge_position,
+            match_stats,
+        });
+
+        (handle_result, is_origin, result_scroll_image_list)
+    }
+
+    fn handle_image_frame(
+        &mut self,
+        image: DynamicImage,
+        scroll_image_list: ScrollImageList,
+    ) -> (
+        Option<(i32, Option<ScrollImageList>)>,
+        bool,
+        ScrollImageList,
+        Option<ScrollMatchStats>,
     ) {
         let image_width = image.width();
         let image_height = image.height();
@@ -740,7 +1055,7 @@ impl ScrollScreenshotService {
             // 因为在 macOS 下，截图使用的是逻辑像素，和物理像素不一样
             self.init_image_size(image_width, image_height);
         } else if image_width != self.image_width || image_height != self.image_height {
-            return (None, false, scroll_image_list);
+            return (None, false, scroll_image_list, None);
         }
 
         let gray_image = self.get_gray_image(&image);
@@ -749,7 +1064,7 @@ impl ScrollScreenshotService {
         let image_corners = self.get_corners(&gray_image);
 
         if image_corners.is_empty() {
-            return (None, false, scroll_image_list);
+            return (None, false, scroll_image_list, None);
         }
 
         let image_descriptors = self.get_descriptors(&gray_image, &image_corners);
@@ -764,28 +1079,16 @@ impl ScrollScreenshotService {
                 ScrollOffset { x: 0, y: 0 },
             );
 
-            let mut new_top_image_ann_index = ScrollIndex::new(self.get_descriptor_size());
-            new_top_image_ann_index.descriptors = image_descriptors;
-            new_top_image_ann_index.corners = image_corners;
-            new_top_image_ann_index
-                .descriptors
-                .iter()
-                .enumerate()
-                .for_each(|(i, descriptor)| {
-                    new_top_image_ann_index
-                        .ann_index
-                        .add(descriptor, i)
-                        .unwrap();
-                });
-
-            new_top_image_ann_index
-                .ann_index
-                .build(Metric::Euclidean)
-                .unwrap();
-
-            self.top_image_ann_index = new_top_image_ann_index;
+            self.top_image_ann_index = ScrollIndex::from_feature(
+                self.get_descriptor_size(),
+                &ScrollIndexFeature {
+                    position: 0,
+                    corners: image_corners,
+                    descriptors: image_descriptors,
+                },
+            );
 
-            return (Some(bottom_image), false, ScrollImageList::Bottom);
+            return (Some(bottom_image), false, ScrollImageList::Bottom, None);
         }
 
         // 优先从指定方向遍历，如果没有则再从另一个方向遍历
@@ -802,7 +1105,7 @@ impl ScrollScreenshotService {
 
         // 从边缘遍历
         let mut offsets;
-        let (first_offsets, is_origin) = self.get_offsets(
+        let (first_offsets, is_origin, first_match_stats) = self.get_offsets_with_stats(
             first_index,
             &image_descriptors,
             &image_corners,
@@ -810,10 +1113,11 @@ impl ScrollScreenshotService {
         );
 
         if is_origin {
-            return (None, true, result_scroll_image_list);
+            return (None, true, result_scroll_image_list, None);
         }
 
         offsets = first_offsets;
+        let mut match_stats = first_match_stats;
 
         // 如果第一个方向没有找到匹配，尝试另一个方向
         if offsets.is_none() && self.try_rollback {
@@ -829,7 +1133,7 @@ impl ScrollScreenshotService {
                 ScrollImageList::Top
             };
 
-            let (second_offsets, is_origin) = self.get_offsets(
+            let (second_offsets, is_origin, second_match_stats) = self.get_offsets_with_stats(
                 second_index,
                 &image_descriptors,
                 &image_corners,
@@ -837,22 +1141,25 @@ impl ScrollScreenshotService {
             );
 
             if is_origin {
-                return (None, true, result_scroll_image_list);
+                return (None, true, result_scroll_image_list, None);
             }
 
             result_scroll_image_list = second_scroll_image_list;
 
             offsets = second_offsets;
+            if second_match_stats.is_some() {
+                match_stats = second_match_stats;
+            }
         }
 
         if offsets.is_none() {
-            return (None, false, result_scroll_image_list);
+            return (None, false, result_scroll_image_list, match_stats);
         }
 
         let (dominant_scroll_index, dominant_origin_position_index, dominant_new_position_index) =
             match offsets {
                 Some(offsets) => offsets,
-                None => return (None, false, scroll_image_list),
+                None => return (None, false, scroll_image_list, match_stats),
             };
 
         let origin_position = dominant_scroll_index.corners[dominant_origin_position_index];
@@ -870,123 +1177,390 @@ impl ScrollScreenshotService {
             )),
             false,
             result_scroll_image_list,
+            match_stats,
         )
     }
 
-    pub fn export(&mut self) -> Option<image::DynamicImage> {
+    /// 撤销指定方向最近拼接的 count 张图片，并重建该方向的索引
+    ///
+    /// 首张图片是两个方向的拼接基准，另一方向仍有图片时不会被撤销
+    ///
+    /// 变化区域和每张图片的匹配结果回退到撤销的第一张图片推入之前
+    ///
+    /// 返回实际撤销的图片数量
+    pub fn undo(&mut self, scroll_image_list: ScrollImageList, count: usize) -> usize {
+        let (removed_count, first_removed_image) = match scroll_image_list {
+            ScrollImageList::Top => {
+                let removed_count = count.min(self.top_image_list.len());
+                let start = self.top_image_list.len() - removed_count;
+                let removed_images = self.top_image_list.drain(start..).collect::<Vec<_>>();
+                for scroll_image in removed_images.iter() {
+                    self.top_image_size += scroll_image.delta_size;
+                    self.top_image_index_size -= scroll_image.index_delta_size;
+                }
+
+                (removed_count, removed_images.into_iter().next())
+            }
+            ScrollImageList::Bottom => {
+                let min_len = if self.top_image_list.is_empty() { 0 } else { 1 };
+                let removed_count = count.min(self.bottom_image_list.len().saturating_sub(min_len));
+                let start = self.bottom_image_list.len() - removed_count;
+                let removed_images = self.bottom_image_list.drain(start..).collect::<Vec<_>>();
+                for scroll_image in removed_images.iter() {
+                    self.bottom_image_size -= scroll_image.delta_size;
+                    self.bottom_image_index_size -= scroll_image.index_delta_size;
+                }
+
+                (removed_count, removed_images.into_iter().next())
+            }
+        };
+
+        if let Some(first_removed_image) = first_removed_image {
+            self.frame_stats.truncate(first_removed_image.frame_index);
+
+            // 恢复的会话没有变化区域的快照，重新检测
+            match first_removed_image.dynamic_mask_snapshot {
+                Some(dynamic_mask_snapshot) => self.dynamic_mask.restore(dynamic_mask_snapshot),
+                None => self.dynamic_mask.clear(),
+            }
+
+            self.rebuild_index(scroll_image_list);
+        }
+
+        removed_count
+    }
+
+    pub(crate) fn rebuild_index(&mut self, scroll_image_list: ScrollImageList) {
+        let descriptor_size = self.get_descriptor_size();
+
         if self.top_image_list.is_empty() && self.bottom_image_list.is_empty() {
-            return None;
+            self.top_image_ann_index = ScrollIndex::new(descriptor_size);
+            self.bottom_image_ann_index = ScrollIndex::new(descriptor_size);
+            return;
         }
 
-        // 计算最终图片尺寸
-        let (total_width, total_height) = if self.current_direction == ScrollDirection::Vertical {
-            (
-                self.image_width as usize,
-                (self.top_image_size + self.bottom_image_size) as usize,
-            )
-        } else {
-            (
-                (self.top_image_size + self.bottom_image_size) as usize,
-                self.image_height as usize,
-            )
+        let image_list = match scroll_image_list {
+            ScrollImageList::Top => &self.top_image_list,
+            ScrollImageList::Bottom => &self.bottom_image_list,
         };
 
-        const RGBA_CHANNEL_COUNT: usize = 4;
+        // 该方向上没有建立过索引时，使用首张图片建立的索引
+        let index_feature = image_list
+            .iter()
+            .rev()
+            .find_map(|scroll_image| scroll_image.index_feature.as_ref())
+            .or_else(|| {
+                self.bottom_image_list
+                    .first()
+                    .and_then(|scroll_image| scroll_image.index_feature.as_ref())
+            });
 
-        // 创建最终大小的图片
-        let mut final_image = unsafe {
-            let mut vec = Vec::with_capacity(total_width * total_height * RGBA_CHANNEL_COUNT);
-            vec.set_len(total_width * total_height * RGBA_CHANNEL_COUNT);
-            vec
+        let scroll_index = match index_feature {
+            Some(index_feature) => ScrollIndex::from_feature(descriptor_size, index_feature),
+            None => ScrollIndex::new(descriptor_size),
         };
 
-        // 当前位置偏移量
-        let mut offset_x: i32 = 0;
-        let mut offset_y: i32 = 0;
+        match scroll_image_list {
+            ScrollImageList::Top => self.top_image_ann_index = scroll_index,
+            ScrollImageList::Bottom => self.bottom_image_ann_index = scroll_index,
+        }
+    }
 
-        // top 会覆盖 bottom，优先从 bottom 开始
+    /// 获取导出图片的尺寸
+    pub fn get_export_size(&self) -> Option<(u32, u32)> {
+        if self.top_image_list.is_empty() && self.bottom_image_list.is_empty() {
+            return None;
+        }
+
+        let scroll_size = (self.top_image_size + self.bottom_image_size) as u32;
         if self.current_direction == ScrollDirection::Vertical {
-            // 垂直方向，从顶部开始
-            offset_y = self.top_image_size as i32;
+            Some((self.image_width, scroll_size))
         } else {
-            // 水平方向，从左侧开始
-            offset_x = self.top_image_size as i32;
+            Some((scroll_size, self.image_height))
         }
+    }
 
-        for scroll_image in self.bottom_image_list.iter() {
-            let img = &scroll_image.image;
-            let overlay_size = scroll_image.overlay_size;
+    /**
+     * 将导出图片沿滚动方向切分为不超过 max_page_size 的若干页
+     */
+    pub fn get_export_pages(&self, max_page_size: u32) -> Vec<CropRegion> {
+        let (total_width, total_height) = match self.get_export_size() {
+            Some(size) => size,
+            None => return vec![],
+        };
 
-            if self.current_direction == ScrollDirection::Vertical {
-                // 垂直拼接
-                snow_shot_app_utils::overlay_image(
-                    &mut final_image,
-                    total_width,
-                    img,
-                    0,
-                    (offset_y - overlay_size) as usize,
-                    RGBA_CHANNEL_COUNT,
-                );
+        let total_size = if self.current_direction == ScrollDirection::Vertical {
+            total_height
+        } else {
+            total_width
+        };
+        let max_page_size = max_page_size.max(1);
+
+        (0..total_size)
+            .step_by(max_page_size as usize)
+            .map(|start| {
+                let page_size = max_page_size.min(total_size - start);
+                if self.current_direction == ScrollDirection::Vertical {
+                    CropRegion::new(0, start, total_width, page_size)
+                } else {
+                    CropRegion::new(start, 0, page_size, total_height)
+                }
+            })
+            .collect()
+    }
 
-                offset_y += (img.height() as i32 - overlay_size) as i32;
+    /**
+     * 计算导出图片在滚动方向上每个位置的来源图片
+     *
+     * 返回图片列表（方向、序号、在导出图片中的起始位置）和每个位置对应的图片下标
+     */
+    pub(crate) fn get_export_layout(&self) -> (Vec<(ScrollImageList, usize, i32)>, Vec<u32>) {
+        let total_size = (self.top_image_size + self.bottom_image_size).max(0);
+        let is_vertical = self.current_direction == ScrollDirection::Vertical;
+
+        let mut layout_images =
+            Vec::with_capacity(self.top_image_list.len() + self.bottom_image_list.len());
+
+        // top 会覆盖 bottom，优先从 bottom 开始
+        let mut offset = self.top_image_size;
+        for (i, scroll_image) in self.bottom_image_list.iter().enumerate() {
+            let side_size = if is_vertical {
+                scroll_image.height
             } else {
-                // 水平拼接
-                snow_shot_app_utils::overlay_image(
-                    &mut final_image,
-                    total_width,
-                    img,
-                    (offset_x - overlay_size) as usize,
-                    0,
-                    RGBA_CHANNEL_COUNT,
-                );
-                offset_x += (img.width() as i32 - overlay_size) as i32;
-            }
+                scroll_image.width
+            } as i32;
+
+            layout_images.push((
+                ScrollImageList::Bottom,
+                i,
+                offset - scroll_image.overlay_size,
+            ));
+            offset += side_size - scroll_image.overlay_size;
         }
 
         // 最先推入的图片优先级最低，所以从尾部开始
-        if self.current_direction == ScrollDirection::Vertical {
-            offset_y = self.top_image_size as i32;
-        } else {
-            offset_x = self.top_image_size as i32;
+        let mut offset = self.top_image_size;
+        for (i, scroll_image) in self.top_image_list.iter().enumerate() {
+            let side_size = if is_vertical {
+                scroll_image.height
+            } else {
+                scroll_image.width
+            } as i32;
+            let actual_size = side_size + scroll_image.overlay_size;
+
+            layout_images.push((ScrollImageList::Top, i, offset - actual_size));
+            offset -= actual_size;
         }
 
-        for scroll_image in self.top_image_list.iter() {
-            let img = &scroll_image.image;
-            let overlay_size = scroll_image.overlay_size;
+        // 后绘制的图片覆盖先绘制的图片
+        let mut position_map = vec![u32::MAX; total_size as usize];
+        for (layout_index, (scroll_image_list, i, start)) in layout_images.iter().enumerate() {
+            let scroll_image = match scroll_image_list {
+                ScrollImageList::Top => &self.top_image_list[*i],
+                ScrollImageList::Bottom => &self.bottom_image_list[*i],
+            };
+            let side_size = if is_vertical {
+                scroll_image.height
+            } else {
+                scroll_image.width
+            } as i32;
 
-            if self.current_direction == ScrollDirection::Vertical {
-                // 垂直拼接
-                let actual_height = img.height() as i32 + overlay_size;
+            let position_start = (*start).clamp(0, total_size) as usize;
+            let position_end = (*start + side_size).clamp(0, total_size) as usize;
+            position_map[position_start..position_end].fill(layout_index as u32);
+        }
 
-                snow_shot_app_utils::overlay_image(
-                    &mut final_image,
-                    total_width,
-                    img,
-                    0,
-                    (offset_y - actual_height) as usize,
-                    RGBA_CHANNEL_COUNT,
-                );
+        (layout_images, position_map)
+    }
 
-                offset_y -= actual_height;
-            } else {
-                let actual_width = img.width() as i32 + overlay_size;
-
-                // 水平拼接
-                snow_shot_app_utils::overlay_image(
-                    &mut final_image,
-                    total_width,
-                    img,
-                    (offset_x - actual_width) as usize,
-                    0,
-                    RGBA_CHANNEL_COUNT,
-                );
-                offset_x -= actual_width;
+    /**
+     * 合成导出图片中的指定区域，返回 RGBA8 像素数据
+     */
+    pub fn export_region(&mut self, region: CropRegion) -> Result<Vec<u8>, String> {
+        let (layout_images, position_map) = self.get_export_layout();
+        self.export_region_with_layout(&layout_images, &position_map, region)
+    }
+
+    fn export_region_with_layout(
+        &mut self,
+        layout_images: &[(ScrollImageList, usize, i32)],
+        position_map: &[u32],
+        region: CropRegion,
+    ) -> Result<Vec<u8>, String> {
+        let is_vertical = self.current_direction == ScrollDirection::Vertical;
+        let region_row_size = region.width as usize * RGBA_CHANNEL_COUNT;
+        let mut region_pixels = vec![0u8; region_row_size * region.height as usize];
+        let mut image_pixels = Vec::new();
+
+        let (region_start, region_end) = if is_vertical {
+            (region.y, region.y + region.height)
+        } else {
+            (region.x, region.x + region.width)
+        };
+
+        let mut position = region_start;
+        while position < region_end {
+            // 找到来源相同的连续区域
+            let layout_index = position_map[position as usize];
+            let mut position_end = position + 1;
+            while position_end < region_end && position_map[position_end as usize] == layout_index {
+                position_end += 1;
+            }
+
+            if layout_index == u32::MAX {
+                position = position_end;
+                continue;
+            }
+
+            let (scroll_image_list, i, start) = layout_images[layout_index as usize];
+            let scroll_image = match scroll_image_list {
+                ScrollImageList::Top => &self.top_image_list[i],
+                ScrollImageList::Bottom => &self.bottom_image_list[i],
+            };
+            let image_row_size = scroll_image.width as usize * RGBA_CHANNEL_COUNT;
+            let source_start = (position as i32 - start) as u32;
+            let source_size = position_end - position;
+
+            // 垂直方向取连续的行，水平方向取区域内的所有行再截取列
+            let (row_start, row_count, column_start, column_count, target_row, target_column) =
+                if is_vertical {
+                    (
+                        source_start,
+                        source_size,
+                        region.x,
+                        region.width,
+                        position - region_start,
+                        0,
+                    )
+                } else {
+                    (
+                        region.y,
+                        region.height,
+                        source_start,
+                        source_size,
+                        0,
+                        position - region_start,
+                    )
+                };
+
+            image_pixels.resize(image_row_size * row_count as usize, 0);
+            scroll_image.read_rows(&mut self.frame_store, row_start, &mut image_pixels)?;
+
+            let column_offset = column_start as usize * RGBA_CHANNEL_COUNT;
+            let column_bytes = column_count as usize * RGBA_CHANNEL_COUNT;
+            let target_column_offset = target_column as usize * RGBA_CHANNEL_COUNT;
+            region_pixels
+                .par_chunks_exact_mut(region_row_size)
+                .skip(target_row as usize)
+                .take(row_count as usize)
+                .zip(image_pixels.par_chunks_exact(image_row_size))
+                .for_each(|(region_row, image_row)| {
+                    region_row[target_column_offset..target_column_offset + column_bytes]
+                        .copy_from_slice(&image_row[column_offset..column_offset + column_bytes]);
+                });
+
+            position = position_end;
+        }
+
+        Ok(region_pixels)
+    }
+
+    /**
+     * 将导出图片的指定区域逐段编码为 PNG 并写入 writer，避免一次性合成整张图片
+     */
+    pub fn export_png<W: Write>(
+        &mut self,
+        writer: W,
+        region: CropRegion,
+        encode_options: &EncodeOptions,
+        capture_metadata: Option<&CaptureMetadata>,
+    ) -> Result<(), String> {
+        let (layout_images, position_map) = self.get_export_layout();
+
+        let mut encoder = png::Encoder::new(writer, region.width, region.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encode_options.apply_png_compression(&mut encoder);
+
+        let mut png_writer = match encoder.write_header() {
+            Ok(png_writer) => png_writer,
+            Err(e) => {
+                return Err(format!(
+                    "[ScrollScreenshotService::export_png] Failed to write header: {}",
+                    e
+                ));
+            }
+        };
+        if let Some(capture_metadata) = capture_metadata {
+            for (chunk_type, chunk_data) in capture_metadata.to_png_chunks() {
+                if let Err(e) =
+                    png_writer.write_chunk(png::chunk::ChunkType(chunk_type), &chunk_data)
+                {
+                    return Err(format!(
+                        "[ScrollScreenshotService::export_png] Failed to write metadata: {}",
+                        e
+                    ));
+                }
             }
         }
+        let mut stream_writer = match png_writer.stream_writer() {
+            Ok(stream_writer) => stream_writer,
+            Err(e) => {
+                return Err(format!(
+                    "[ScrollScreenshotService::export_png] Failed to create stream writer: {}",
+                    e
+                ));
+            }
+        };
+
+        for band_start in (region.y..region.y + region.height).step_by(EXPORT_BAND_SIZE as usize) {
+            let band_region = CropRegion::new(
+                region.x,
+                band_start,
+                region.width,
+                EXPORT_BAND_SIZE.min(region.y + region.height - band_start),
+            );
+            let band_pixels =
+                self.export_region_with_layout(&layout_images, &position_map, band_region)?;
+
+            if let Err(e) = stream_writer.write_all(&band_pixels) {
+                return Err(format!(
+                    "[ScrollScreenshotService::export_png] Failed to write image: {}",
+                    e
+                ));
+            }
+        }
+
+        if let Err(e) = stream_writer.finish() {
+            return Err(format!(
+                "[ScrollScreenshotService::export_png] Failed to finish image: {}",
+                e
+            ));
+        }
+
+        Ok(())
+    }
+
+    /**
+     * 将整张图片导出到内存
+     *
+     * 会分配 width × height 的 RGBA 缓冲区，保存到文件时使用 export_png 或 export_region 分段导出，
+     * 只有写入剪贴板、传给前端等必须持有完整像素的场景才使用该函数
+     */
+    pub fn export(&mut self) -> Option<image::DynamicImage> {
+        let (total_width, total_height) = self.get_export_size()?;
+
+        let final_image = match self.export_region(CropRegion::new(0, 0, total_width, total_height))
+        {
+            Ok(final_image) => final_image,
+            Err(e) => {
+                log::error!("[ScrollScreenshotService::export] {}", e);
+                return None;
+            }
+        };
 
         Some(image::DynamicImage::ImageRgba8(
-            image::RgbaImage::from_raw(total_width as u32, total_height as u32, final_image)
-                .unwrap(),
+            image::RgbaImage::from_raw(total_width, total_height, final_image).unwrap(),
         ))
     }
 }
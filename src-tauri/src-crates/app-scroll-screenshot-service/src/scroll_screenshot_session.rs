@@ -0,0 +1,462 @@
+use image::DynamicImage;
+use serde::{Deserialize, Serialize};
+use snow_shot_app_shared::ElementRect;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::scroll_screenshot_service::{
+    CropRegion, ScrollDirection, ScrollFrameStats, ScrollImage, ScrollImageData, ScrollImageList,
+    ScrollIndexFeature, ScrollMatchStats, ScrollScreenshotService,
+};
+
+const SESSION_VERSION: u32 = 1;
+const SESSION_FILE_NAME: &str = "session.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct ScrollScreenshotSessionParams {
+    pub sample_rate: f32,
+    pub min_sample_size: u32,
+    pub max_sample_size: u32,
+    pub corner_threshold: u8,
+    pub descriptor_patch_size: usize,
+    pub min_size_delta: i32,
+    pub try_rollback: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScrollScreenshotSessionImage {
+    pub scroll_image_list: ScrollImageList,
+    pub file_name: String,
+    pub width: u32,
+    pub height: u32,
+    pub overlay_size: i32,
+    pub delta_size: i32,
+    pub index_delta_size: i32,
+    /// 图片建立索引时的位置，加载时据此重建索引
+    pub index_position: Option<i32>,
+    #[serde(default)]
+    pub match_stats: Option<ScrollMatchStats>,
+    /// 推入时图片的处理序号，撤销时据此回退匹配结果
+    #[serde(default)]
+    pub frame_index: usize,
+}
+
+/**
+ * 滚动截图会话，图片单独保存为 PNG 文件
+ * 索引不保存，加载时根据导出的图片重建
+ */
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScrollScreenshotSession {
+    pub version: u32,
+    pub id: String,
+    /// 创建时间（毫秒时间戳）
+    pub created_at: u64,
+    pub direction: ScrollDirection,
+    pub image_width: u32,
+    pub image_height: u32,
+    pub top_image_size: i32,
+    pub top_image_index_size: i32,
+    pub bottom_image_size: i32,
+    pub bottom_image_index_size: i32,
+    pub enable_corner_fast12: Option<bool>,
+    pub params: ScrollScreenshotSessionParams,
+    /// 调用方指定的屏蔽区域（图片坐标）
+    #[serde(default)]
+    pub mask_rects: Vec<ElementRect>,
+    pub images: Vec<ScrollScreenshotSessionImage>,
+    /// 每张处理过的图片的匹配结果
+    #[serde(default)]
+    pub frame_stats: Vec<ScrollFrameStats>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ScrollScreenshotSessionInfo {
+    pub id: String,
+    pub created_at: u64,
+    pub direction: ScrollDirection,
+    pub image_count: usize,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ScrollScreenshotSession {
+    pub fn info(&self) -> ScrollScreenshotSessionInfo {
+        let scroll_size = (self.top_image_size + self.bottom_image_size) as u32;
+        let (width, height) = if self.direction == ScrollDirection::Vertical {
+            (self.image_width, scroll_size)
+        } else {
+            (scroll_size, self.image_height)
+        };
+
+        ScrollScreenshotSessionInfo {
+            id: self.id.clone(),
+            created_at: self.created_at,
+            direction: self.direction,
+            image_count: self.images.len(),
+            width,
+            height,
+        }
+    }
+
+    pub fn read(session_dir: &Path) -> Result<Self, String> {
+        let session_file_path = session_dir.join(SESSION_FILE_NAME);
+        let content = match fs::read_to_string(&session_file_path) {
+            Ok(content) => content,
+            Err(e) => {
+                return Err(format!(
+                    "[ScrollScreenshotSession::read] Failed to read {}: {}",
+                    session_file_path.display(),
+                    e
+                ));
+            }
+        };
+
+        let session: Self = match serde_json::from_str(&content) {
+            Ok(session) => session,
+            Err(e) => {
+                return Err(format!(
+                    "[ScrollScreenshotSession::read] Failed to parse {}: {}",
+                    session_file_path.display(),
+                    e
+                ));
+            }
+        };
+
+        if session.version != SESSION_VERSION {
+            return Err(format!(
+                "[ScrollScreenshotSession::read] Unsupported session version: {}",
+                session.version
+            ));
+        }
+
+        Ok(session)
+    }
+}
+
+/**
+ * 列出 sessions_dir 下的所有会话，按创建时间倒序排列
+ */
+pub fn list_sessions(sessions_dir: &Path) -> Vec<ScrollScreenshotSessionInfo> {
+    let read_dir = match fs::read_dir(sessions_dir) {
+        Ok(read_dir) => read_dir,
+        Err(_) => return vec![],
+    };
+
+    let mut session_info_list: Vec<ScrollScreenshotSessionInfo> = read_dir
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| match ScrollScreenshotSession::read(&entry.path()) {
+            Ok(session) => Some(session.info()),
+            Err(e) => {
+                log::warn!("[list_sessions] {}", e);
+                None
+            }
+        })
+        .collect();
+
+    session_info_list.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    session_info_list
+}
+
+impl ScrollScreenshotService {
+    /**
+     * 将当前的滚动截图保存到 session_dir
+     */
+    pub fn save_session(
+        &mut self,
+        session_dir: &Path,
+        id: String,
+    ) -> Result<ScrollScreenshotSessionInfo, String> {
+        if self.top_image_list.is_empty() && self.bottom_image_list.is_empty() {
+            return Err(String::from(
+                "[ScrollScreenshotService::save_session] No image to save",
+            ));
+        }
+
+        if let Err(e) = fs::create_dir_all(session_dir) {
+            return Err(format!(
+                "[ScrollScreenshotService::save_session] Failed to create directory {}: {}",
+                session_dir.display(),
+                e
+            ));
+        }
+
+        let mut images =
+            Vec::with_capacity(self.top_image_list.len() + self.bottom_image_list.len());
+        for (scroll_image_list, image_list) in [
+            (ScrollImageList::Top, &self.top_image_list),
+            (ScrollImageList::Bottom, &self.bottom_image_list),
+        ] {
+            for (i, scroll_image) in image_list.iter().enumerate() {
+                let file_name = match scroll_image_list {
+                    ScrollImageList::Top => format!("top_{}.png", i),
+                    ScrollImageList::Bottom => format!("bottom_{}.png", i),
+                };
+
+                let stored_image;
+                let image = match &scroll_image.data {
+                    ScrollImageData::Memory(image) => image,
+                    ScrollImageData::Store(handle) => {
+                        stored_image = self.frame_store.read(handle)?;
+                        &stored_image
+                    }
+                };
+
+                if let Err(e) =
+                    image.save_with_format(session_dir.join(&file_name), image::ImageFormat::Png)
+                {
+                    return Err(format!(
+                        "[ScrollScreenshotService::save_session] Failed to save image {}: {}",
+                        file_name, e
+                    ));
+                }
+
+                images.push(ScrollScreenshotSessionImage {
+                    scroll_image_list,
+                    file_name,
+                    width: scroll_image.width,
+                    height: scroll_image.height,
+                    overlay_size: scroll_image.overlay_size,
+                    delta_size: scroll_image.delta_size,
+                    index_delta_size: scroll_image.index_delta_size,
+                    index_position: scroll_image
+                        .index_feature
+                        .as_ref()
+                        .map(|index_feature| index_feature.position),
+                    match_stats: scroll_image.match_stats,
+                    frame_index: scroll_image.frame_index,
+                });
+            }
+        }
+
+        let session = ScrollScreenshotSession {
+            version: SESSION_VERSION,
+            id,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or(0),
+            direction: self.current_direction,
+            image_width: self.image_width,
+            image_height: self.image_height,
+            top_image_size: self.top_image_size,
+            top_image_index_size: self.top_image_index_size,
+            bottom_image_size: self.bottom_image_size,
+            bottom_image_index_size: self.bottom_image_index_size,
+            enable_corner_fast12: self.enable_corner_fast12,
+            params: ScrollScreenshotSessionParams {
+                sample_rate: self.sample_rate,
+                min_sample_size: self.min_sample_size,
+                max_sample_size: self.max_sample_size,
+                corner_threshold: self.corner_threshold,
+                descriptor_patch_size: self.descriptor_patch_size,
+                min_size_delta: self.min_size_delta,
+                try_rollback: self.try_rollback,
+            },
+            mask_rects: self.dynamic_mask.mask_rects().to_vec(),
+            images,
+            frame_stats: self.frame_stats.clone(),
+        };
+
+        let content = match serde_json::to_string(&session) {
+            Ok(content) => content,
+            Err(e) => {
+                return Err(format!(
+                    "[ScrollScreenshotService::save_session] Failed to serialize session: {}",
+                    e
+                ));
+            }
+        };
+
+        // 最后写入会话文件，图片未写完时会话不会被列出
+        if let Err(e) = fs::write(session_dir.join(SESSION_FILE_NAME), content) {
+            return Err(format!(
+                "[ScrollScreenshotService::save_session] Failed to write session: {}",
+                e
+            ));
+        }
+
+        Ok(session.info())
+    }
+
+    /**
+     * 从 session_dir 恢复滚动截图，恢复后可以继续截图或导出
+     */
+    pub fn restore_session(
+        &mut self,
+        session_dir: &Path,
+    ) -> Result<ScrollScreenshotSessionInfo, String> {
+        let session = ScrollScreenshotSession::read(session_dir)?;
+
+        let params = session.params;
+        self.init(
+            session.direction,
+            params.sample_rate,
+            params.min_sample_size,
+            params.max_sample_size,
+            params.corner_threshold,
+            params.descriptor_patch_size,
+            params.min_size_delta,
+            params.try_rollback,
+        );
+        self.init_image_size(session.image_width, session.image_height);
+        self.set_mask_rects(session.mask_rects.clone());
+        self.enable_corner_fast12 = session.enable_corner_fast12;
+        self.top_image_size = session.top_image_size;
+        self.top_image_index_size = session.top_image_index_size;
+        self.bottom_image_size = session.bottom_image_size;
+        self.bottom_image_index_size = session.bottom_image_index_size;
+        self.frame_stats = session.frame_stats.clone();
+
+        // 逐张解码并写入临时文件，避免所有图片同时保留在内存中
+        for session_image in session.images.iter() {
+            let scroll_image = match self.load_session_image(session_dir, session_image) {
+                Ok(scroll_image) => scroll_image,
+                Err(e) => {
+                    self.clear();
+                    return Err(e);
+                }
+            };
+
+            match session_image.scroll_image_list {
+                ScrollImageList::Top => self.top_image_list.push(scroll_image),
+                ScrollImageList::Bottom => self.bottom_image_list.push(scroll_image),
+            }
+        }
+
+        // 重建索引特征，撤销时也依赖这些特征
+        let mut top_index_position_list = Vec::new();
+        let mut bottom_index_position_list = Vec::new();
+        for session_image in session.images.iter() {
+            match session_image.scroll_image_list {
+                ScrollImageList::Top => top_index_position_list.push(session_image.index_position),
+                ScrollImageList::Bottom => {
+                    bottom_index_position_list.push(session_image.index_position)
+                }
+            }
+        }
+
+        for (i, index_position) in top_index_position_list.into_iter().enumerate() {
+            if let Some(index_position) = index_position {
+                self.top_image_list[i].index_feature =
+                    Some(self.build_index_feature(index_position)?);
+            }
+        }
+        for (i, index_position) in bottom_index_position_list.into_iter().enumerate() {
+            if let Some(index_position) = index_position {
+                self.bottom_image_list[i].index_feature =
+                    Some(self.build_index_feature(index_position)?);
+            }
+        }
+
+        self.rebuild_index(ScrollImageList::Top);
+        self.rebuild_index(ScrollImageList::Bottom);
+
+        Ok(session.info())
+    }
+
+    /**
+     * 读取会话中的一张图片并写入临时文件，写入失败时保留在内存中
+     */
+    fn load_session_image(
+        &mut self,
+        session_dir: &Path,
+        session_image: &ScrollScreenshotSessionImage,
+    ) -> Result<ScrollImage, String> {
+        let image = match image::open(session_dir.join(&session_image.file_name)) {
+            Ok(image) => DynamicImage::ImageRgba8(image.to_rgba8()),
+            Err(e) => {
+                return Err(format!(
+                    "[ScrollScreenshotService::load_session_image] Failed to open image {}: {}",
+                    session_image.file_name, e
+                ));
+            }
+        };
+
+        if image.width() != session_image.width || image.height() != session_image.height {
+            return Err(format!(
+                "[ScrollScreenshotService::load_session_image] Invalid image size: {}",
+                session_image.file_name
+            ));
+        }
+
+        let data = match self.frame_store.push(&image) {
+            Ok(handle) => ScrollImageData::Store(handle),
+            Err(e) => {
+                log::warn!("[ScrollScreenshotService::load_session_image] {}", e);
+                ScrollImageData::Memory(image)
+            }
+        };
+
+        Ok(ScrollImage {
+            data,
+            width: session_image.width,
+            height: session_image.height,
+            overlay_size: session_image.overlay_size,
+            delta_size: session_image.delta_size,
+            index_delta_size: session_image.index_delta_size,
+            index_feature: None,
+            match_stats: session_image.match_stats,
+            frame_index: session_image.frame_index,
+            dynamic_mask_snapshot: None,
+        })
+    }
+
+    /**
+     * 从导出的图片中取出 position 处的完整截图区域，重新提取特征
+     */
+    fn build_index_feature(&mut self, position: i32) -> Result<ScrollIndexFeature, String> {
+        let (total_width, total_height) = match self.get_export_size() {
+            Some(size) => size,
+            None => {
+                return Err(String::from(
+                    "[ScrollScreenshotService::build_index_feature] No image",
+                ));
+            }
+        };
+
+        // 截图区域超出导出图片时向内移动，索引位置使用移动后的起点
+        let (max_start, region_size) = if self.current_direction == ScrollDirection::Vertical {
+            (
+                total_height.saturating_sub(self.image_height),
+                self.image_height.min(total_height),
+            )
+        } else {
+            (
+                total_width.saturating_sub(self.image_width),
+                self.image_width.min(total_width),
+            )
+        };
+        let start = ((position + self.top_image_size).max(0) as u32).min(max_start);
+        let position = start as i32 - self.top_image_size;
+
+        let region = if self.current_direction == ScrollDirection::Vertical {
+            CropRegion::new(0, start, self.image_width, region_size)
+        } else {
+            CropRegion::new(start, 0, region_size, self.image_height)
+        };
+
+        let region_pixels = self.export_region(region)?;
+        let region_image =
+            match image::RgbaImage::from_raw(region.width, region.height, region_pixels) {
+                Some(region_image) => DynamicImage::ImageRgba8(region_image),
+                None => {
+                    return Err(String::from(
+                        "[ScrollScreenshotService::build_index_feature] Failed to create image",
+                    ));
+                }
+            };
+
+        let gray_image = self.get_gray_image(&region_image);
+        let corners = self.get_corners(&gray_image);
+        let descriptors = self.get_descriptors(&gray_image, &corners);
+
+        Ok(ScrollIndexFeature {
+            position,
+            corners,
+            descriptors,
+        })
+    }
+}
@@ -0,0 +1,368 @@
+#![allow(dead_code)]
+
+use image::{DynamicImage, Rgba, RgbaImage};
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_service::{
+    ScrollDirection, ScrollImageList, ScrollScreenshotService,
+};
+
+/// 与设置中的默认值保持一致
+pub const DEFAULT_SAMPLE_RATE: f32 = 1.0;
+pub const DEFAULT_MIN_SAMPLE_SIZE: u32 = 128;
+pub const DEFAULT_MAX_SAMPLE_SIZE: u32 = 128;
+pub const DEFAULT_CORNER_THRESHOLD: u8 = 24;
+pub const DEFAULT_DESCRIPTOR_PATCH_SIZE: usize = 28;
+pub const DEFAULT_MIN_SIZE_DELTA_RATIO: f32 = 0.8;
+
+/// 固定种子的伪随机数，保证生成的图片可以复现
+pub struct SyntheticRng(u64);
+
+impl SyntheticRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed ^ 0x9E37_79B9_7F4A_7C15)
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) as u32
+    }
+
+    /// 返回 [min, max) 内的随机数
+    pub fn range(&mut self, min: u32, max: u32) -> u32 {
+        min + self.next_u32() % (max - min).max(1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyntheticContent {
+    /// 浅色背景上的多行文字
+    Text,
+    /// 深色背景上带缩进和高亮的代码
+    Code,
+    /// 渐变背景上的零散图形
+    Gradient,
+    /// 大面积空白，只有少量内容
+    LowTexture,
+}
+
+pub const SYNTHETIC_CONTENT_LIST: [SyntheticContent; 4] = [
+    SyntheticContent::Text,
+    SyntheticContent::Code,
+    SyntheticContent::Gradient,
+    SyntheticContent::LowTexture,
+];
+
+fn fill_rect(image: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32, color: Rgba<u8>) {
+    for py in y..(y + height).min(image.height()) {
+        for px in x..(x + width).min(image.width()) {
+            image.put_pixel(px, py, color);
+        }
+    }
+}
+
+/// 随机生成 5x7 的字形，模拟文字
+fn draw_glyph(
+    image: &mut RgbaImage,
+    rng: &mut SyntheticRng,
+    x: u32,
+    y: u32,
+    scale: u32,
+    color: Rgba<u8>,
+) {
+    for row in 0..7 {
+        let bits = rng.next_u32();
+        for column in 0..5 {
+            if bits & (1 << column) != 0 {
+                fill_rect(
+                    image,
+                    x + column * scale,
+                    y + row * scale,
+                    scale,
+                    scale,
+                    color,
+                );
+            }
+        }
+    }
+}
+
+/// 从 x 开始绘制若干个单词，直到填满 max_width
+fn draw_words(
+    image: &mut RgbaImage,
+    rng: &mut SyntheticRng,
+    x: u32,
+    y: u32,
+    max_width: u32,
+    scale: u32,
+    color: Rgba<u8>,
+) {
+    let glyph_width = 6 * scale;
+    let mut cursor = x;
+    while cursor + glyph_width < x + max_width {
+        let word_length = rng.range(2, 9);
+        for _ in 0..word_length {
+            if cursor + glyph_width >= x + max_width {
+                break;
+            }
+            draw_glyph(image, rng, cursor, y, scale, color);
+            cursor += glyph_width;
+        }
+        cursor += glyph_width;
+    }
+}
+
+/**
+ * 生成垂直方向的长图
+ */
+pub fn generate_image(content: SyntheticContent, width: u32, height: u32, seed: u64) -> RgbaImage {
+    let mut rng = SyntheticRng::new(seed);
+
+    match content {
+        SyntheticContent::Text => {
+            let mut image = RgbaImage::from_pixel(width, height, Rgba([250, 250, 248, 255]));
+            let mut y = 12;
+            while y + 14 < height {
+                // 段落之间留空
+                if rng.range(0, 8) == 0 {
+                    y += 18;
+                    continue;
+                }
+
+                let line_width = rng.range(width / 2, width - 16);
+                draw_words(
+                    &mut image,
+                    &mut rng,
+                    8,
+                    y,
+                    line_width,
+                    2,
+                    Rgba([30, 30, 30, 255]),
+                );
+                y += 20;
+            }
+            image
+        }
+        SyntheticContent::Code => {
+            let mut image = RgbaImage::from_pixel(width, height, Rgba([30, 31, 34, 255]));
+            let palette = [
+                Rgba([206, 145, 120, 255]),
+                Rgba([86, 156, 214, 255]),
+                Rgba([220, 220, 170, 255]),
+                Rgba([156, 220, 254, 255]),
+                Rgba([106, 153, 85, 255]),
+            ];
+            let mut indent: u32 = 0;
+            let mut y = 6;
+            while y + 8 < height {
+                // 行号
+                draw_glyph(&mut image, &mut rng, 4, y, 1, Rgba([110, 118, 129, 255]));
+
+                indent = match rng.range(0, 4) {
+                    0 => indent.saturating_sub(1),
+                    1 => (indent + 1).min(6),
+                    _ => indent,
+                };
+
+                let mut cursor = 20 + indent * 12;
+                let line_end = rng.range(cursor + 12, width.max(cursor + 13));
+                while cursor + 6 < line_end.min(width) {
+                    let token_length = rng.range(1, 7) * 6;
+                    let color = palette[rng.range(0, palette.len() as u32) as usize];
+                    draw_words(&mut image, &mut rng, cursor, y, token_length, 1, color);
+                    cursor += token_length + 6;
+                }
+                y += 12;
+            }
+            image
+        }
+        SyntheticContent::Gradient => {
+            let mut image = RgbaImage::new(width, height);
+            for (x, y, pixel) in image.enumerate_pixels_mut() {
+                *pixel = Rgba([
+                    (x * 255 / width.max(1)) as u8,
+                    (y % 512 / 2) as u8,
+                    ((x + y) % 256) as u8,
+                    255,
+                ]);
+            }
+
+            // 零散的图形，保证渐变区域也有特征点
+            let mut y = 0;
+            while y < height {
+                let shape_size = rng.range(8, 24);
+                let x = rng.range(0, width.saturating_sub(shape_size).max(1));
+                let color = Rgba([
+                    rng.range(0, 256) as u8,
+                    rng.range(0, 256) as u8,
+                    rng.range(0, 256) as u8,
+                    255,
+                ]);
+                fill_rect(&mut image, x, y, shape_size, shape_size, color);
+                y += rng.range(12, 40);
+            }
+            image
+        }
+        SyntheticContent::LowTexture => {
+            let mut image = RgbaImage::from_pixel(width, height, Rgba([255, 255, 255, 255]));
+            let mut y = rng.range(16, 64);
+            while y + 14 < height {
+                let line_width = rng.range(width / 4, width / 2);
+                draw_words(
+                    &mut image,
+                    &mut rng,
+                    16,
+                    y,
+                    line_width,
+                    2,
+                    Rgba([60, 60, 60, 255]),
+                );
+                y += rng.range(60, 120);
+            }
+            image
+        }
+    }
+}
+
+/**
+ * 生成指定方向的长图，水平方向由垂直方向的图片旋转得到
+ *
+ * frame_width 和 frame_height 为截图区域的尺寸，scroll_size 为滚动方向的总尺寸
+ */
+pub fn generate_source(
+    content: SyntheticContent,
+    direction: ScrollDirection,
+    cross_size: u32,
+    scroll_size: u32,
+    seed: u64,
+) -> RgbaImage {
+    let image = generate_image(content, cross_size, scroll_size, seed);
+
+    match direction {
+        ScrollDirection::Vertical => image,
+        ScrollDirection::Horizontal => image::imageops::rotate90(&image),
+    }
+}
+
+/**
+ * 生成滚动位置，每次滚动 step 并随机抖动 jitter
+ */
+pub fn jittered_positions(
+    rng: &mut SyntheticRng,
+    start: u32,
+    end: u32,
+    step: u32,
+    jitter: u32,
+) -> Vec<u32> {
+    let mut positions = vec![start];
+    let mut position = start;
+    while position < end {
+        let delta = step - jitter + rng.range(0, jitter * 2 + 1);
+        position = (position + delta.max(1)).min(end);
+        positions.push(position);
+    }
+    positions
+}
+
+/**
+ * 在滚动位置 position 处截取一张图片
+ */
+pub fn slice_frame(
+    source: &RgbaImage,
+    direction: ScrollDirection,
+    frame_side_size: u32,
+    position: u32,
+) -> RgbaImage {
+    match direction {
+        ScrollDirection::Vertical => {
+            image::imageops::crop_imm(source, 0, position, source.width(), frame_side_size)
+                .to_image()
+        }
+        ScrollDirection::Horizontal => {
+            image::imageops::crop_imm(source, position, 0, frame_side_size, source.height())
+                .to_image()
+        }
+    }
+}
+
+/**
+ * 为图片添加随机噪声，模拟视频压缩或缩放带来的误差
+ */
+pub fn add_noise(image: &mut RgbaImage, rng: &mut SyntheticRng, amplitude: u8) {
+    for pixel in image.pixels_mut() {
+        for channel in pixel.0.iter_mut().take(3) {
+            let noise = rng.range(0, amplitude as u32 * 2 + 1) as i32 - amplitude as i32;
+            *channel = (*channel as i32 + noise).clamp(0, 255) as u8;
+        }
+    }
+}
+
+/**
+ * 按截图工具的默认参数初始化拼接服务
+ */
+pub fn create_service(direction: ScrollDirection, frame_side_size: u32) -> ScrollScreenshotService {
+    let mut service = ScrollScreenshotService::new();
+    service.init(
+        direction,
+        DEFAULT_SAMPLE_RATE,
+        DEFAULT_MIN_SAMPLE_SIZE,
+        DEFAULT_MAX_SAMPLE_SIZE,
+        DEFAULT_CORNER_THRESHOLD,
+        DEFAULT_DESCRIPTOR_PATCH_SIZE,
+        (frame_side_size as f32 * DEFAULT_MIN_SIZE_DELTA_RATIO).ceil() as i32,
+        true,
+    );
+    service
+}
+
+/**
+ * 依次拼接图片，返回未能拼接的图片序号
+ */
+pub fn stitch_frames(
+    service: &mut ScrollScreenshotService,
+    frames: Vec<(RgbaImage, ScrollImageList)>,
+) -> Vec<usize> {
+    let mut unmatched_frames = vec![];
+    for (i, (frame, scroll_image_list)) in frames.into_iter().enumerate() {
+        let (handle_result, is_origin, _) =
+            service.handle_image(DynamicImage::ImageRgba8(frame), scroll_image_list);
+
+        if handle_result.is_none() && !is_origin {
+            unmatched_frames.push(i);
+        }
+    }
+    unmatched_frames
+}
+
+/**
+ * 比较导出的图片和原图中对应的区域，返回最大的通道差异
+ */
+pub fn max_channel_difference(
+    export_image: &RgbaImage,
+    source: &RgbaImage,
+    direction: ScrollDirection,
+    source_start: u32,
+) -> Option<u8> {
+    let (x, y) = match direction {
+        ScrollDirection::Vertical => (0, source_start),
+        ScrollDirection::Horizontal => (source_start, 0),
+    };
+    if x + export_image.width() > source.width() || y + export_image.height() > source.height() {
+        return None;
+    }
+
+    let expected =
+        image::imageops::crop_imm(source, x, y, export_image.width(), export_image.height())
+            .to_image();
+
+    Some(
+        export_image
+            .as_raw()
+            .iter()
+            .zip(expected.as_raw().iter())
+            .map(|(a, b)| a.abs_diff(*b))
+            .max()
+            .unwrap_or(0),
+    )
+}
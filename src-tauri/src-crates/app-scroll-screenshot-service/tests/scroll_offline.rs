@@ -0,0 +1,76 @@
+mod common;
+
+use common::*;
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_offline_service::{
+    ScrollScreenshotOfflineParams, list_image_files,
+};
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_service::{
+    ScrollDirection, ScrollScreenshotService,
+};
+
+const FRAME_CROSS_SIZE: u32 = 320;
+const FRAME_SIDE_SIZE: u32 = 240;
+const SOURCE_SCROLL_SIZE: u32 = 1200;
+
+#[test]
+fn test_stitch_image_files() {
+    let source = generate_source(
+        SyntheticContent::Code,
+        ScrollDirection::Vertical,
+        FRAME_CROSS_SIZE,
+        SOURCE_SCROLL_SIZE,
+        9000,
+    );
+    let mut rng = SyntheticRng::new(9000);
+    let positions = jittered_positions(&mut rng, 0, SOURCE_SCROLL_SIZE - FRAME_SIDE_SIZE, 64, 24);
+
+    let dir = std::env::temp_dir().join(format!("scroll_offline_test_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    // 按文件名排序后为截取的顺序，其他文件被忽略
+    for (i, position) in positions.iter().enumerate() {
+        slice_frame(
+            &source,
+            ScrollDirection::Vertical,
+            FRAME_SIDE_SIZE,
+            *position,
+        )
+        .save(dir.join(format!("frame_{:03}.png", i)))
+        .unwrap();
+    }
+    std::fs::write(dir.join("notes.txt"), b"").unwrap();
+
+    let file_list = list_image_files(&dir).unwrap();
+    assert_eq!(file_list.len(), positions.len());
+
+    let mut service = ScrollScreenshotService::new();
+    let result = service
+        .stitch_image_files(
+            &ScrollScreenshotOfflineParams {
+                direction: ScrollDirection::Vertical,
+                sample_rate: DEFAULT_SAMPLE_RATE,
+                min_sample_size: DEFAULT_MIN_SAMPLE_SIZE,
+                max_sample_size: DEFAULT_MAX_SAMPLE_SIZE,
+                corner_threshold: DEFAULT_CORNER_THRESHOLD,
+                descriptor_patch_size: DEFAULT_DESCRIPTOR_PATCH_SIZE,
+                min_size_delta: None,
+                try_rollback: true,
+            },
+            &file_list,
+        )
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(result.frame_count, positions.len());
+    assert_eq!(result.unmatched_count, 0);
+    assert_eq!(
+        (result.width, result.height),
+        (FRAME_CROSS_SIZE, SOURCE_SCROLL_SIZE)
+    );
+
+    let export_image = service.export().unwrap().to_rgba8();
+    assert_eq!(
+        (export_image.width(), export_image.height()),
+        (source.width(), source.height())
+    );
+    assert!(export_image.as_raw() == source.as_raw());
+}
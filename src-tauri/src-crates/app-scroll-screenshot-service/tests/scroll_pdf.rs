@@ -0,0 +1,123 @@
+mod common;
+
+use common::*;
+use image::{DynamicImage, Rgba, RgbaImage};
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_pdf_service::ScrollScreenshotPdfOptions;
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_service::{
+    ScrollDirection, ScrollImageList, ScrollScreenshotService,
+};
+
+const IMAGE_CROSS_SIZE: u32 = 64;
+const IMAGE_SCROLL_SIZE: u32 = 250;
+/// 每页包含的像素数，在页面边界之前 20 像素内寻找空白行
+const PAGE_SIZE: u32 = 100;
+
+/**
+ * 生成除指定的行（列）外都是随机像素的图片，指定的行（列）为空白
+ */
+fn blank_line_image(direction: ScrollDirection, blank_lines: &[u32], seed: u64) -> RgbaImage {
+    let mut rng = SyntheticRng::new(seed);
+    let (width, height) = match direction {
+        ScrollDirection::Vertical => (IMAGE_CROSS_SIZE, IMAGE_SCROLL_SIZE),
+        ScrollDirection::Horizontal => (IMAGE_SCROLL_SIZE, IMAGE_CROSS_SIZE),
+    };
+
+    RgbaImage::from_fn(width, height, |x, y| {
+        let line = match direction {
+            ScrollDirection::Vertical => y,
+            ScrollDirection::Horizontal => x,
+        };
+
+        if blank_lines.contains(&line) {
+            Rgba([255, 255, 255, 255])
+        } else {
+            let value = rng.range(0, 256) as u8;
+            Rgba([value, value, value, 255])
+        }
+    })
+}
+
+fn create_pdf_service(image: RgbaImage, direction: ScrollDirection) -> ScrollScreenshotService {
+    let mut service = create_service(direction, IMAGE_SCROLL_SIZE);
+    let (handle_result, _, _) =
+        service.handle_image(DynamicImage::ImageRgba8(image), ScrollImageList::Bottom);
+    assert!(handle_result.is_some());
+
+    service
+}
+
+/// 72 DPI 下 1 像素为 1 磅，页面内容区域在滚动方向上正好放下 PAGE_SIZE 个像素
+fn pdf_options(direction: ScrollDirection) -> ScrollScreenshotPdfOptions {
+    let to_millimeters = |size: f32| size / 72.0 * 25.4;
+    let scroll_page_size = to_millimeters(PAGE_SIZE as f32 + 0.5);
+    let cross_page_size = to_millimeters(IMAGE_CROSS_SIZE as f32 * 2.0);
+
+    let (page_width, page_height) = match direction {
+        ScrollDirection::Vertical => (cross_page_size, scroll_page_size),
+        ScrollDirection::Horizontal => (scroll_page_size, cross_page_size),
+    };
+
+    ScrollScreenshotPdfOptions {
+        page_width,
+        page_height,
+        margin: 0.0,
+        dpi: 72.0,
+    }
+}
+
+/// 返回每页在滚动方向上的起止位置
+fn page_ranges(
+    service: &mut ScrollScreenshotService,
+    direction: ScrollDirection,
+) -> Vec<(u32, u32)> {
+    service
+        .get_pdf_pages(&pdf_options(direction))
+        .unwrap()
+        .into_iter()
+        .map(|page| match direction {
+            ScrollDirection::Vertical => {
+                assert_eq!((page.x, page.width), (0, IMAGE_CROSS_SIZE));
+                (page.y, page.y + page.height)
+            }
+            ScrollDirection::Horizontal => {
+                assert_eq!((page.y, page.height), (0, IMAGE_CROSS_SIZE));
+                (page.x, page.x + page.width)
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_pdf_pages_break_on_blank_lines() {
+    for direction in [ScrollDirection::Vertical, ScrollDirection::Horizontal] {
+        // 第一页边界之前有空白行，第二页边界附近没有空白行，最后一页不满一页
+        let image = blank_line_image(direction, &[30, 90], 8000);
+        let mut service = create_pdf_service(image, direction);
+
+        assert_eq!(
+            page_ranges(&mut service, direction),
+            vec![(0, 90), (90, 190), (190, IMAGE_SCROLL_SIZE)],
+            "{:?}",
+            direction
+        );
+
+        let mut pdf_data = Vec::new();
+        let page_count = service
+            .export_pdf(&mut pdf_data, &pdf_options(direction))
+            .unwrap();
+        assert_eq!(page_count, 3);
+        assert!(pdf_data.starts_with(b"%PDF-"));
+    }
+}
+
+#[test]
+fn test_pdf_pages_without_blank_lines() {
+    let image = blank_line_image(ScrollDirection::Vertical, &[], 8100);
+    let mut service = create_pdf_service(image, ScrollDirection::Vertical);
+
+    // 找不到空白行时按页面尺寸切分
+    assert_eq!(
+        page_ranges(&mut service, ScrollDirection::Vertical),
+        vec![(0, 100), (100, 200), (200, IMAGE_SCROLL_SIZE)]
+    );
+}
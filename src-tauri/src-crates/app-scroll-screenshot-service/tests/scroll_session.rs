@@ -0,0 +1,71 @@
+mod common;
+
+use common::*;
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_service::{
+    ScrollDirection, ScrollImageList, ScrollScreenshotService,
+};
+
+const FRAME_CROSS_SIZE: u32 = 320;
+const FRAME_SIDE_SIZE: u32 = 240;
+const SOURCE_SCROLL_SIZE: u32 = 1200;
+
+#[test]
+fn test_save_and_restore_session() {
+    let source = generate_source(
+        SyntheticContent::Code,
+        ScrollDirection::Vertical,
+        FRAME_CROSS_SIZE,
+        SOURCE_SCROLL_SIZE,
+        9100,
+    );
+    let mut rng = SyntheticRng::new(9100);
+    let positions = jittered_positions(&mut rng, 0, SOURCE_SCROLL_SIZE - FRAME_SIDE_SIZE, 64, 24);
+    let frames = positions
+        .iter()
+        .map(|position| {
+            (
+                slice_frame(
+                    &source,
+                    ScrollDirection::Vertical,
+                    FRAME_SIDE_SIZE,
+                    *position,
+                ),
+                ScrollImageList::Bottom,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut service = create_service(ScrollDirection::Vertical, FRAME_SIDE_SIZE);
+    assert!(stitch_frames(&mut service, frames.clone()).is_empty());
+
+    let session_dir =
+        std::env::temp_dir().join(format!("scroll_session_test_{}", std::process::id()));
+    let saved_info = service
+        .save_session(&session_dir, String::from("test"))
+        .unwrap();
+
+    let mut restored_service = ScrollScreenshotService::new();
+    let restored_info = restored_service.restore_session(&session_dir).unwrap();
+    std::fs::remove_dir_all(&session_dir).unwrap();
+
+    assert_eq!(restored_info.image_count, saved_info.image_count);
+    assert_eq!(
+        serde_json::to_string(&restored_service.frame_stats).unwrap(),
+        serde_json::to_string(&service.frame_stats).unwrap()
+    );
+    assert!(
+        restored_service.export().unwrap().to_rgba8().as_raw()
+            == service.export().unwrap().to_rgba8().as_raw()
+    );
+
+    // 恢复后撤销，匹配结果回退到撤销的图片推入之前
+    let frame_index = service.bottom_image_list[service.bottom_image_list.len() - 2].frame_index;
+    assert_eq!(restored_service.undo(ScrollImageList::Bottom, 2), 2);
+    assert_eq!(restored_service.frame_stats.len(), frame_index);
+
+    // 重新截取撤销的区域
+    let undo_frames = frames[frame_index..].to_vec();
+    assert!(stitch_frames(&mut restored_service, undo_frames).is_empty());
+    assert_eq!(restored_service.frame_stats.len(), frames.len());
+    assert!(restored_service.export().unwrap().to_rgba8().as_raw() == source.as_raw());
+}
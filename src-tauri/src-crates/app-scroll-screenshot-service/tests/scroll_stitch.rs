@@ -0,0 +1,413 @@
+mod common;
+
+use common::*;
+use image::{DynamicImage, Rgba, RgbaImage};
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_service::{
+    ScrollDirection, ScrollImageList,
+};
+
+const FRAME_CROSS_SIZE: u32 = 320;
+const FRAME_SIDE_SIZE: u32 = 240;
+const SOURCE_SCROLL_SIZE: u32 = 1600;
+
+/**
+ * 从中间开始向后滚动到底，再向前滚动到顶，返回每张图片和拼接方向
+ */
+fn slice_round_trip(
+    source: &RgbaImage,
+    direction: ScrollDirection,
+    rng: &mut SyntheticRng,
+    step: u32,
+    jitter: u32,
+    noise: u8,
+) -> Vec<(RgbaImage, ScrollImageList)> {
+    let start = (SOURCE_SCROLL_SIZE - FRAME_SIDE_SIZE) / 2;
+    let end = SOURCE_SCROLL_SIZE - FRAME_SIDE_SIZE;
+
+    let bottom_positions = jittered_positions(rng, start, end, step, jitter);
+    let top_positions = jittered_positions(rng, 0, start, step, jitter);
+
+    let frame_list = bottom_positions
+        .into_iter()
+        .map(|position| (position, ScrollImageList::Bottom))
+        .chain(
+            top_positions
+                .into_iter()
+                .skip(1)
+                .map(|position| (start - position, ScrollImageList::Top)),
+        );
+
+    frame_list
+        .map(|(position, scroll_image_list)| {
+            let mut frame = slice_frame(source, direction, FRAME_SIDE_SIZE, position);
+            if noise > 0 {
+                add_noise(&mut frame, rng, noise);
+            }
+            (frame, scroll_image_list)
+        })
+        .collect()
+}
+
+fn assert_round_trip(content: SyntheticContent, direction: ScrollDirection, seed: u64) {
+    let source = generate_source(
+        content,
+        direction,
+        FRAME_CROSS_SIZE,
+        SOURCE_SCROLL_SIZE,
+        seed,
+    );
+    let mut rng = SyntheticRng::new(seed);
+    let frames = slice_round_trip(&source, direction, &mut rng, 64, 24, 0);
+    let frame_count = frames.len();
+
+    let mut service = create_service(direction, FRAME_SIDE_SIZE);
+    let unmatched_frames = stitch_frames(&mut service, frames);
+    assert!(
+        unmatched_frames.is_empty(),
+        "{:?} {:?}: {} of {} frames unmatched: {:?}",
+        content,
+        direction,
+        unmatched_frames.len(),
+        frame_count,
+        unmatched_frames
+    );
+
+    let export_image = service.export().unwrap().to_rgba8();
+    assert_eq!(
+        (export_image.width(), export_image.height()),
+        (source.width(), source.height()),
+        "{:?} {:?}: unexpected export size",
+        content,
+        direction
+    );
+    assert!(
+        export_image.as_raw() == source.as_raw(),
+        "{:?} {:?}: export differs from source",
+        content,
+        direction
+    );
+}
+
+#[test]
+fn test_export_reproduces_source_vertical() {
+    for (i, content) in SYNTHETIC_CONTENT_LIST.iter().enumerate() {
+        assert_round_trip(*content, ScrollDirection::Vertical, 1000 + i as u64);
+    }
+}
+
+#[test]
+fn test_export_reproduces_source_horizontal() {
+    for (i, content) in SYNTHETIC_CONTENT_LIST.iter().enumerate() {
+        assert_round_trip(*content, ScrollDirection::Horizontal, 2000 + i as u64);
+    }
+}
+
+#[test]
+fn test_export_with_noise() {
+    const NOISE: u8 = 3;
+
+    for direction in [ScrollDirection::Vertical, ScrollDirection::Horizontal] {
+        let source = generate_source(
+            SyntheticContent::Text,
+            direction,
+            FRAME_CROSS_SIZE,
+            SOURCE_SCROLL_SIZE,
+            3000,
+        );
+        let mut rng = SyntheticRng::new(3000);
+        let frames = slice_round_trip(&source, direction, &mut rng, 64, 24, NOISE);
+
+        let mut service = create_service(direction, FRAME_SIDE_SIZE);
+        stitch_frames(&mut service, frames);
+
+        // 噪声不影响拼接位置，像素差异不超过噪声幅度
+        let export_image = service.export().unwrap().to_rgba8();
+        assert_eq!(
+            (export_image.width(), export_image.height()),
+            (source.width(), source.height()),
+            "{:?}: unexpected export size",
+            direction
+        );
+
+        let source_start =
+            (SOURCE_SCROLL_SIZE - FRAME_SIDE_SIZE) / 2 - service.top_image_size as u32;
+        let difference = max_channel_difference(&export_image, &source, direction, source_start);
+        assert!(
+            difference
+                .map(|difference| difference <= NOISE)
+                .unwrap_or(false),
+            "{:?}: export differs from source by {:?}",
+            direction,
+            difference
+        );
+    }
+}
+
+#[test]
+fn test_unchanged_frame_is_not_stitched() {
+    let source = generate_source(
+        SyntheticContent::Code,
+        ScrollDirection::Vertical,
+        FRAME_CROSS_SIZE,
+        SOURCE_SCROLL_SIZE,
+        4000,
+    );
+    let frame = slice_frame(&source, ScrollDirection::Vertical, FRAME_SIDE_SIZE, 400);
+
+    let mut service = create_service(ScrollDirection::Vertical, FRAME_SIDE_SIZE);
+    stitch_frames(
+        &mut service,
+        vec![
+            (frame.clone(), ScrollImageList::Bottom),
+            (frame.clone(), ScrollImageList::Bottom),
+            (frame.clone(), ScrollImageList::Top),
+        ],
+    );
+
+    assert_eq!(service.bottom_image_list.len(), 1);
+    assert!(service.top_image_list.is_empty());
+    assert!(service.export().unwrap().to_rgba8().as_raw() == frame.as_raw());
+}
+
+#[test]
+fn test_textureless_frame_is_rejected() {
+    let frame = RgbaImage::from_pixel(
+        FRAME_CROSS_SIZE,
+        FRAME_SIDE_SIZE,
+        Rgba([240, 240, 240, 255]),
+    );
+
+    let mut service = create_service(ScrollDirection::Vertical, FRAME_SIDE_SIZE);
+    let (handle_result, is_origin, _) =
+        service.handle_image(DynamicImage::ImageRgba8(frame), ScrollImageList::Bottom);
+
+    assert!(handle_result.is_none());
+    assert!(!is_origin);
+    assert!(service.export().is_none());
+}
+
+#[test]
+fn test_horizontal_top_frame_keeps_leading_edge() {
+    const POSITION: u32 = 600;
+    const DELTA: u32 = 96;
+
+    let source = generate_source(
+        SyntheticContent::Text,
+        ScrollDirection::Horizontal,
+        FRAME_CROSS_SIZE,
+        SOURCE_SCROLL_SIZE,
+        5000,
+    );
+
+    let mut service = create_service(ScrollDirection::Horizontal, FRAME_SIDE_SIZE);
+    let unmatched_frames = stitch_frames(
+        &mut service,
+        vec![
+            (
+                slice_frame(
+                    &source,
+                    ScrollDirection::Horizontal,
+                    FRAME_SIDE_SIZE,
+                    POSITION,
+                ),
+                ScrollImageList::Bottom,
+            ),
+            (
+                slice_frame(
+                    &source,
+                    ScrollDirection::Horizontal,
+                    FRAME_SIDE_SIZE,
+                    POSITION - DELTA,
+                ),
+                ScrollImageList::Top,
+            ),
+        ],
+    );
+    assert!(unmatched_frames.is_empty());
+    assert_eq!(service.top_image_size, DELTA as i32);
+
+    // 向左滚动时应保留新图片左侧的内容
+    let export_image = service.export().unwrap().to_rgba8();
+    let expected = image::imageops::crop_imm(
+        &source,
+        POSITION - DELTA,
+        0,
+        FRAME_SIDE_SIZE + DELTA,
+        source.height(),
+    )
+    .to_image();
+    assert!(export_image.as_raw() == expected.as_raw());
+}
+
+#[test]
+fn test_adjacent_offsets_do_not_compete() {
+    let source = generate_source(
+        SyntheticContent::Text,
+        ScrollDirection::Vertical,
+        FRAME_CROSS_SIZE,
+        SOURCE_SCROLL_SIZE,
+        6000,
+    );
+    let mut rng = SyntheticRng::new(6000);
+    let frames = slice_round_trip(&source, ScrollDirection::Vertical, &mut rng, 64, 24, 3);
+    let frame_count = frames.len();
+
+    let mut service = create_service(ScrollDirection::Vertical, FRAME_SIDE_SIZE);
+    let unmatched_frames = stitch_frames(&mut service, frames);
+    assert!(
+        unmatched_frames.is_empty(),
+        "{} of {} frames unmatched: {:?}",
+        unmatched_frames.len(),
+        frame_count,
+        unmatched_frames
+    );
+
+    // 噪声会让匹配落在主偏移相邻 1 像素的位置，这些票数不应作为竞争的偏移
+    for frame_stats in &service.frame_stats {
+        if let Some(match_stats) = frame_stats.match_stats {
+            if let Some(second_offset) = match_stats.second_offset {
+                assert!(
+                    (second_offset - match_stats.dominant_offset).abs() > 1,
+                    "frame {}: second offset {} is adjacent to {}",
+                    frame_stats.frame_index,
+                    second_offset,
+                    match_stats.dominant_offset
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_dynamic_mask_after_clear() {
+    // 图片中固定位置的区域每次都不同，模拟动画广告
+    const DYNAMIC_MIN_Y: u32 = 96;
+    const DYNAMIC_MAX_Y: u32 = 144;
+    const DYNAMIC_MAX_X: u32 = FRAME_CROSS_SIZE / 2;
+
+    let source = generate_source(
+        SyntheticContent::Text,
+        ScrollDirection::Vertical,
+        FRAME_CROSS_SIZE,
+        SOURCE_SCROLL_SIZE,
+        7000,
+    );
+    let mut rng = SyntheticRng::new(7000);
+    let mut frames = || {
+        [400, 464, 528]
+            .into_iter()
+            .map(|position| {
+                let mut frame = slice_frame(
+                    &source,
+                    ScrollDirection::Vertical,
+                    FRAME_SIDE_SIZE,
+                    position,
+                );
+                for y in DYNAMIC_MIN_Y..DYNAMIC_MAX_Y {
+                    for x in 0..DYNAMIC_MAX_X {
+                        let value = rng.range(0, 256) as u8;
+                        frame.put_pixel(x, y, Rgba([value, value, value, 255]));
+                    }
+                }
+                (frame, ScrollImageList::Bottom)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut service = create_service(ScrollDirection::Vertical, FRAME_SIDE_SIZE);
+    for round in 0..2 {
+        let unmatched_frames = stitch_frames(&mut service, frames());
+        assert!(
+            unmatched_frames.is_empty(),
+            "round {}: frames unmatched: {:?}",
+            round,
+            unmatched_frames
+        );
+        assert!(
+            service
+                .dynamic_mask
+                .is_screen_masked(0, (DYNAMIC_MIN_Y + DYNAMIC_MAX_Y) as i32 / 2),
+            "round {}: dynamic region is not masked",
+            round
+        );
+
+        // 清除后重新截取，变化区域应重新检测
+        service.clear();
+    }
+}
+
+#[test]
+fn test_undo_then_capture_again() {
+    const DYNAMIC_MIN_Y: u32 = 96;
+    const DYNAMIC_MAX_Y: u32 = 144;
+
+    let source = generate_source(
+        SyntheticContent::Text,
+        ScrollDirection::Vertical,
+        FRAME_CROSS_SIZE,
+        SOURCE_SCROLL_SIZE,
+        7100,
+    );
+    let mut rng = SyntheticRng::new(7100);
+    let mut frames = |positions: &[u32], is_dynamic: bool| {
+        positions
+            .iter()
+            .map(|position| {
+                let mut frame = slice_frame(
+                    &source,
+                    ScrollDirection::Vertical,
+                    FRAME_SIDE_SIZE,
+                    *position,
+                );
+                // 撤销的图片中固定位置的区域每次都不同
+                if is_dynamic {
+                    for y in DYNAMIC_MIN_Y..DYNAMIC_MAX_Y {
+                        for x in 0..FRAME_CROSS_SIZE / 2 {
+                            let value = rng.range(0, 256) as u8;
+                            frame.put_pixel(x, y, Rgba([value, value, value, 255]));
+                        }
+                    }
+                }
+                (frame, ScrollImageList::Bottom)
+            })
+            .collect::<Vec<_>>()
+    };
+    let dynamic_position = (DYNAMIC_MIN_Y + DYNAMIC_MAX_Y) as i32 / 2;
+
+    let mut service = create_service(ScrollDirection::Vertical, FRAME_SIDE_SIZE);
+    assert!(stitch_frames(&mut service, frames(&[400, 464, 528], false)).is_empty());
+    assert!(!service.dynamic_mask.is_screen_masked(0, dynamic_position));
+
+    assert!(stitch_frames(&mut service, frames(&[592, 656], true)).is_empty());
+    assert!(service.dynamic_mask.is_screen_masked(0, dynamic_position));
+    assert_eq!(service.frame_stats.len(), 5);
+
+    // 撤销后变化区域和匹配结果回退到撤销的图片推入之前
+    assert_eq!(service.undo(ScrollImageList::Bottom, 2), 2);
+    assert_eq!(service.bottom_image_list.len(), 3);
+    assert_eq!(service.frame_stats.len(), 3);
+    assert!(!service.dynamic_mask.is_screen_masked(0, dynamic_position));
+
+    // 重新截取撤销的区域
+    assert!(stitch_frames(&mut service, frames(&[592, 656], false)).is_empty());
+    assert!(!service.dynamic_mask.is_screen_masked(0, dynamic_position));
+    assert_eq!(
+        service
+            .frame_stats
+            .iter()
+            .map(|frame_stats| frame_stats.frame_index)
+            .collect::<Vec<_>>(),
+        vec![0, 1, 2, 3, 4]
+    );
+
+    let export_image = service.export().unwrap().to_rgba8();
+    let expected_image = image::imageops::crop_imm(
+        &source,
+        0,
+        400,
+        FRAME_CROSS_SIZE,
+        656 + FRAME_SIDE_SIZE - 400,
+    )
+    .to_image();
+    assert!(export_image.as_raw() == expected_image.as_raw());
+}
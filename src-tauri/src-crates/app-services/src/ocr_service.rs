@@ -1,37 +1,360 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 use num_cpus;
 use ort::session::builder::SessionBuilder;
 use paddle_ocr_rs::ocr_lite::OcrLite;
 use serde::{Deserialize, Serialize};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio_util::sync::CancellationToken;
 
-pub struct OcrService {
+/// session 池的默认大小，后台任务占用一个 session 时交互识别仍有可用的 session
+const DEFAULT_POOL_SIZE: usize = 2;
+const MAX_POOL_SIZE: usize = 8;
+
+thread_local! {
+    /// 构建 session 的回调是函数指针，无法捕获线程数，创建 session 前写入当前线程
+    static SESSION_THREADS: Cell<usize> = const { Cell::new(1) };
+}
+
+/**
+ * OCR session 池的配置
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OcrSessionPoolOptions {
+    /// 最多同时存在的 session 数量
+    pub pool_size: usize,
+    /// 每个 session 的线程数，为 0 时按物理核心数平分
+    pub threads_per_session: usize,
+}
+
+impl Default for OcrSessionPoolOptions {
+    fn default() -> Self {
+        Self {
+            pool_size: DEFAULT_POOL_SIZE,
+            threads_per_session: 0,
+        }
+    }
+}
+
+impl OcrSessionPoolOptions {
+    pub fn pool_size(&self) -> usize {
+        self.pool_size.clamp(1, MAX_POOL_SIZE)
+    }
+
+    pub fn threads_per_session(&self) -> usize {
+        if self.threads_per_session > 0 {
+            return self.threads_per_session;
+        }
+
+        (num_cpus::get_physical() / self.pool_size()).max(1)
+    }
+}
+
+/**
+ * 识别请求的优先级
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OcrRequestPriority {
+    /// 用户正在等待结果的识别，如截图和固定到屏幕
+    #[default]
+    Interactive,
+    /// 批量识别、建立索引等后台任务，池中有多个 session 时至少保留一个给交互识别
+    Background,
+}
+
+/// 模型文件的路径，写入内存时同时保存文件内容
+type OcrModelFile = (PathBuf, Option<Arc<Vec<u8>>>);
+
+/// 使用检测、方向分类和识别模型的数据以及线程数创建识别器，在阻塞线程中调用
+type OcrCoreFactory = fn(&[u8], &[u8], &[u8], usize) -> Result<OcrLite, String>;
+
+#[derive(Clone)]
+struct OcrModelFiles {
+    det_model: OcrModelFile,
+    cls_model: OcrModelFile,
+    rec_model: OcrModelFile,
+}
+
+struct OcrSession {
+    model: OcrModel,
+    /// 创建时的配置版本，重新初始化后旧的 session 不再放回池中
+    generation: u64,
+    ocr_core: OcrLite,
+}
+
+struct OcrSessionPool {
     hot_start: bool,
-    ocr_core: Option<OcrLite>,
-    det_model: Option<(PathBuf, Option<Vec<u8>>)>,
-    rec_model: Option<(PathBuf, Option<Vec<u8>>)>,
-    cls_model: Option<(PathBuf, Option<Vec<u8>>)>,
+    ocr_model_write_to_memory: bool,
+    options: OcrSessionPoolOptions,
+    generation: u64,
+    /// 已加载的语言包
+    model_files: HashMap<OcrModel, OcrModelFiles>,
+    /// 初始化时指定的模型，识别请求未指定语言包时使用
+    default_model: Option<(PathBuf, OcrModel)>,
+    /// 空闲的 session，最近使用的排在最后
+    idle_sessions: Vec<OcrSession>,
+    /// 限制同时使用的 session 数量，排队的请求按先后顺序获取
+    sessions: Arc<Semaphore>,
+    /// 限制后台任务同时使用的 session 数量
+    background_sessions: Arc<Semaphore>,
+    /// 创建识别器的方法，测试时替换为不加载模型的实现
+    ocr_core_factory: OcrCoreFactory,
+}
+
+pub struct OcrService {
+    pool: Mutex<OcrSessionPool>,
+    /// 排队中的请求，可以通过请求 ID 取消
+    requests: Mutex<HashMap<String, CancellationToken>>,
+}
+
+/**
+ * session 占用的许可
+ */
+struct OcrSessionPermits {
+    _permit: OwnedSemaphorePermit,
+    _background_permit: Option<OwnedSemaphorePermit>,
+}
+
+/**
+ * 从池中取出的 session，释放时放回池中
+ */
+pub struct OcrSessionGuard<'a> {
+    service: &'a OcrService,
+    session: Option<OcrSession>,
+    // session 放回池中后才释放许可，避免排队的请求拿到许可时池中没有 session
+    permits: Option<OcrSessionPermits>,
+}
+
+impl Deref for OcrSessionGuard<'_> {
+    type Target = OcrLite;
+
+    fn deref(&self) -> &Self::Target {
+        &self.session.as_ref().unwrap().ocr_core
+    }
+}
+
+impl DerefMut for OcrSessionGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.session.as_mut().unwrap().ocr_core
+    }
+}
+
+impl OcrSessionGuard<'_> {
+    /**
+     * 在阻塞线程中使用 session，同步的推理不占用异步运行时的线程
+     */
+    pub async fn run_blocking<T, F>(&mut self, f: F) -> Result<T, String>
+    where
+        F: FnOnce(&mut OcrLite) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let Some(mut session) = self.session.take() else {
+            return Err("[OcrSessionGuard::run_blocking] Session is not available".to_string());
+        };
+        let permits = self.permits.take();
+
+        // 许可和 session 一起移动到阻塞线程，等待的 future 被丢弃时，
+        // 阻塞线程结束后先释放 session 再释放许可，识别中的 session 不会被重复使用
+        let (session, permits, result) = tokio::task::spawn_blocking(move || {
+            let result = f(&mut session.ocr_core);
+            (session, permits, result)
+        })
+        .await
+        .map_err(|e| format!("[OcrSessionGuard::run_blocking] Failed to run task: {}", e))?;
+        self.session = Some(session);
+        self.permits = permits;
+
+        Ok(result)
+    }
+}
+
+impl Drop for OcrSessionGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(session) = self.session.take() {
+            self.service.return_session(session);
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy, PartialOrd, Serialize, Deserialize)]
 pub enum OcrModel {
     RapidOcrV4,
     RapidOcrV5,
+    RapidOcrJapanese,
+    RapidOcrKorean,
+    /// 拉丁字母，包括英语、法语、德语等欧洲语言
+    RapidOcrLatin,
+    /// 西里尔字母，包括俄语、乌克兰语等
+    RapidOcrCyrillic,
+    RapidOcrArabic,
+}
+
+/// 中文模型所在的插件
+pub const OCR_PLUGIN_ID_CHINESE: &str = "rapid_ocr";
+
+const CHINESE_DET_MODEL: &str = "ch_PP-OCRv4_det_infer.onnx";
+const MULTILINGUAL_DET_MODEL: &str = "Multilingual_PP-OCRv3_det_infer.onnx";
+const CLS_MODEL: &str = "ch_ppocr_mobile_v2.0_cls_infer.onnx";
+
+/**
+ * OCR 语言包，描述检测、方向分类和识别模型的文件
+ *
+ * 语言包通过插件服务安装，识别模型的字符字典保存在 ONNX 模型的元数据中
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct OcrModelPack {
+    /// 提供模型文件的插件
+    pub plugin_id: &'static str,
+    pub det_model: &'static str,
+    pub cls_model: &'static str,
+    pub rec_model: &'static str,
+}
+
+impl OcrModelPack {
+    const fn multilingual(plugin_id: &'static str, rec_model: &'static str) -> Self {
+        Self {
+            plugin_id,
+            det_model: MULTILINGUAL_DET_MODEL,
+            cls_model: CLS_MODEL,
+            rec_model,
+        }
+    }
+
+    /// 语言包包含的所有模型文件
+    pub fn file_list(&self) -> [&'static str; 3] {
+        [self.det_model, self.cls_model, self.rec_model]
+    }
+}
+
+/// 提供 OCR 模型的插件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OcrModelPlugin {
+    pub id: &'static str,
+    /// 插件包含的所有模型文件
+    pub file_list: Vec<&'static str>,
+    /// 插件提供的模型
+    pub models: Vec<OcrModel>,
+}
+
+impl OcrModel {
+    /// 所有可选的模型
+    pub const ALL: [OcrModel; 7] = [
+        OcrModel::RapidOcrV4,
+        OcrModel::RapidOcrV5,
+        OcrModel::RapidOcrJapanese,
+        OcrModel::RapidOcrKorean,
+        OcrModel::RapidOcrLatin,
+        OcrModel::RapidOcrCyrillic,
+        OcrModel::RapidOcrArabic,
+    ];
+
+    /**
+     * 按插件汇总所有模型的语言包，前端据此注册插件
+     */
+    pub fn plugins() -> Vec<OcrModelPlugin> {
+        let mut plugins: Vec<OcrModelPlugin> = Vec::new();
+        for model in OcrModel::ALL {
+            let pack = model.pack();
+            let plugin = match plugins
+                .iter()
+                .position(|plugin| plugin.id == pack.plugin_id)
+            {
+                Some(index) => &mut plugins[index],
+                None => {
+                    plugins.push(OcrModelPlugin {
+                        id: pack.plugin_id,
+                        file_list: Vec::new(),
+                        models: Vec::new(),
+                    });
+                    plugins.last_mut().unwrap()
+                }
+            };
+
+            for file in pack.file_list() {
+                if !plugin.file_list.contains(&file) {
+                    plugin.file_list.push(file);
+                }
+            }
+            plugin.models.push(model);
+        }
+
+        plugins
+    }
+
+    /**
+     * 获取模型对应的语言包
+     */
+    pub fn pack(&self) -> OcrModelPack {
+        match self {
+            OcrModel::RapidOcrV4 => OcrModelPack {
+                plugin_id: OCR_PLUGIN_ID_CHINESE,
+                det_model: CHINESE_DET_MODEL,
+                cls_model: CLS_MODEL,
+                rec_model: "ch_PP-OCRv4_rec_infer.onnx",
+            },
+            OcrModel::RapidOcrV5 => OcrModelPack {
+                plugin_id: OCR_PLUGIN_ID_CHINESE,
+                det_model: CHINESE_DET_MODEL,
+                cls_model: CLS_MODEL,
+                rec_model: "ch_PP-OCRv5_rec_mobile_infer.onnx",
+            },
+            OcrModel::RapidOcrJapanese => {
+                OcrModelPack::multilingual("rapid_ocr_japanese", "japan_PP-OCRv3_rec_infer.onnx")
+            }
+            OcrModel::RapidOcrKorean => {
+                OcrModelPack::multilingual("rapid_ocr_korean", "korean_PP-OCRv3_rec_infer.onnx")
+            }
+            OcrModel::RapidOcrLatin => {
+                OcrModelPack::multilingual("rapid_ocr_latin", "latin_PP-OCRv3_rec_infer.onnx")
+            }
+            OcrModel::RapidOcrCyrillic => {
+                OcrModelPack::multilingual("rapid_ocr_cyrillic", "cyrillic_PP-OCRv3_rec_infer.onnx")
+            }
+            OcrModel::RapidOcrArabic => {
+                OcrModelPack::multilingual("rapid_ocr_arabic", "arabic_PP-OCRv3_rec_infer.onnx")
+            }
+        }
+    }
 }
 
 impl OcrService {
     pub fn new() -> Self {
+        let options = OcrSessionPoolOptions::default();
+
         Self {
-            hot_start: false,
-            ocr_core: None,
-            det_model: None,
-            rec_model: None,
-            cls_model: None,
+            pool: Mutex::new(OcrSessionPool {
+                hot_start: false,
+                ocr_model_write_to_memory: false,
+                options,
+                generation: 0,
+                model_files: HashMap::new(),
+                default_model: None,
+                idle_sessions: Vec::new(),
+                sessions: Arc::new(Semaphore::new(options.pool_size())),
+                background_sessions: Arc::new(Semaphore::new(Self::background_pool_size(
+                    options.pool_size(),
+                ))),
+                ocr_core_factory: Self::init_ocr_core,
+            }),
+            requests: Mutex::new(HashMap::new()),
         }
     }
 
+    /// 后台任务可以同时使用的 session 数量
+    fn background_pool_size(pool_size: usize) -> usize {
+        if pool_size > 1 { pool_size - 1 } else { 1 }
+    }
+
+    fn lock_pool(&self) -> std::sync::MutexGuard<'_, OcrSessionPool> {
+        self.pool.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     async fn read_model_data(
-        &self,
         det_path: &Path,
         cls_path: &Path,
         rec_path: &Path,
@@ -65,132 +388,633 @@ impl OcrService {
     }
 
     fn build_session(builder: SessionBuilder) -> Result<SessionBuilder, ort::Error> {
-        let num_thread = num_cpus::get_physical();
+        let num_thread = SESSION_THREADS.get();
         Ok(builder
             .with_inter_threads(num_thread)?
             .with_intra_threads(num_thread)?
             .with_optimization_level(ort::session::builder::GraphOptimizationLevel::Level3)?)
     }
 
-    pub async fn init_session(&mut self) -> Result<(), String> {
-        let ((det_path, det_model_data), (cls_path, cls_model_data), (rec_path, rec_model_data)) = (
-            self.det_model
-                .as_ref()
-                .expect("[OcrService::init_ocr_core] Det model is not loaded"),
-            self.cls_model
-                .as_ref()
-                .expect("[OcrService::init_ocr_core] Cls model is not loaded"),
-            self.rec_model
-                .as_ref()
-                .expect("[OcrService::init_ocr_core] Rec model is not loaded"),
-        );
-
+    fn init_ocr_core(
+        det_model_data: &[u8],
+        cls_model_data: &[u8],
+        rec_model_data: &[u8],
+        num_thread: usize,
+    ) -> Result<OcrLite, String> {
         let mut ocr_core = OcrLite::new();
 
-        if let (Some(det_model_data), Some(cls_model_data), Some(rec_model_data)) =
-            (det_model_data, cls_model_data, rec_model_data)
-        {
-            ocr_core.init_models_from_memory_custom(
+        SESSION_THREADS.set(num_thread);
+        ocr_core
+            .init_models_from_memory_custom(
                 det_model_data,
                 cls_model_data,
                 rec_model_data,
                 Self::build_session,
             )
-        } else {
-            let (det_model_data, cls_model_data, rec_model_data) =
-                self.read_model_data(det_path, cls_path, rec_path).await?;
+            .map_err(|e| format!("[OcrService::init_ocr_core] Failed to init models: {}", e))?;
 
-            ocr_core.init_models_from_memory_custom(
-                det_model_data.as_ref(),
-                cls_model_data.as_ref(),
-                rec_model_data.as_ref(),
-                Self::build_session,
-            )
-        }
-        .map_err(|e| format!("[OcrService::init_ocr_core] Failed to init models: {}", e))?;
+        Ok(ocr_core)
+    }
 
-        self.ocr_core.replace(ocr_core);
+    async fn create_session(
+        model: OcrModel,
+        model_files: OcrModelFiles,
+        generation: u64,
+        num_thread: usize,
+        ocr_core_factory: OcrCoreFactory,
+    ) -> Result<OcrSession, String> {
+        let (det_model_data, cls_model_data, rec_model_data) = match (
+            model_files.det_model.1,
+            model_files.cls_model.1,
+            model_files.rec_model.1,
+        ) {
+            (Some(det_model_data), Some(cls_model_data), Some(rec_model_data)) => {
+                (det_model_data, cls_model_data, rec_model_data)
+            }
+            _ => {
+                let (det_model_data, cls_model_data, rec_model_data) = Self::read_model_data(
+                    &model_files.det_model.0,
+                    &model_files.cls_model.0,
+                    &model_files.rec_model.0,
+                )
+                .await?;
 
-        Ok(())
+                (
+                    Arc::new(det_model_data),
+                    Arc::new(cls_model_data),
+                    Arc::new(rec_model_data),
+                )
+            }
+        };
+
+        // 加载模型是同步的，在阻塞线程中创建 session，线程数写入该线程
+        let ocr_core = tokio::task::spawn_blocking(move || {
+            ocr_core_factory(
+                det_model_data.as_slice(),
+                cls_model_data.as_slice(),
+                rec_model_data.as_slice(),
+                num_thread,
+            )
+        })
+        .await
+        .map_err(|e| format!("[OcrService::create_session] Failed to run task: {}", e))??;
+
+        Ok(OcrSession {
+            model,
+            generation,
+            ocr_core,
+        })
     }
 
     pub async fn init_models(
-        &mut self,
+        &self,
         orc_plugin_path: PathBuf,
         model: OcrModel,
         hot_start: bool,
         ocr_model_write_to_memory: bool,
+        pool_options: OcrSessionPoolOptions,
     ) -> Result<(), String> {
         log::info!(
-            "[OcrService::init_models] orc_plugin_path: {:?}, model: {:?}, hot_start: {:?}, ocr_model_write_to_memory: {:?}",
+            "[OcrService::init_models] orc_plugin_path: {:?}, model: {:?}, hot_start: {:?}, ocr_model_write_to_memory: {:?}, pool_options: {:?}",
             orc_plugin_path,
             model,
             hot_start,
-            ocr_model_write_to_memory
+            ocr_model_write_to_memory,
+            pool_options
+        );
+
+        let model_files =
+            Self::load_model_files(&orc_plugin_path, model, ocr_model_write_to_memory).await?;
+
+        let released_sessions = {
+            let mut pool = self.lock_pool();
+
+            // 正在使用的 session 仍然使用旧的许可，释放后不再放回池中
+            if pool.options.pool_size() != pool_options.pool_size() {
+                pool.sessions = Arc::new(Semaphore::new(pool_options.pool_size()));
+                pool.background_sessions = Arc::new(Semaphore::new(Self::background_pool_size(
+                    pool_options.pool_size(),
+                )));
+            }
+
+            pool.hot_start = hot_start;
+            pool.ocr_model_write_to_memory = ocr_model_write_to_memory;
+            pool.options = pool_options;
+            pool.generation += 1;
+            pool.model_files.clear();
+            pool.model_files.insert(model, model_files);
+            pool.default_model = Some((orc_plugin_path, model));
+
+            std::mem::take(&mut pool.idle_sessions)
+        };
+        drop(released_sessions);
+
+        if hot_start {
+            self.warm_up().await?;
+        }
+
+        Ok(())
+    }
+
+    /// 初始化时指定的模型，未初始化时为空
+    pub fn default_model(&self) -> Option<OcrModel> {
+        self.lock_pool()
+            .default_model
+            .as_ref()
+            .map(|(_, model)| *model)
+    }
+
+    /// 每个 session 识别时使用的线程数
+    pub fn threads_per_session(&self) -> usize {
+        self.lock_pool().options.threads_per_session()
+    }
+
+    async fn load_model_files(
+        orc_plugin_path: &Path,
+        model: OcrModel,
+        ocr_model_write_to_memory: bool,
+    ) -> Result<OcrModelFiles, String> {
+        let pack = model.pack();
+        if let Some(missing_file) = pack
+            .file_list()
+            .into_iter()
+            .find(|file| !orc_plugin_path.join(file).exists())
+        {
+            return Err(format!(
+                "[OcrService::load_model_files] Model pack {} is not installed, missing file: {}",
+                pack.plugin_id, missing_file
+            ));
+        }
+
+        let (det_model_path, cls_model_path, rec_model_path) = (
+            orc_plugin_path.join(pack.det_model),
+            orc_plugin_path.join(pack.cls_model),
+            orc_plugin_path.join(pack.rec_model),
         );
 
         // 加载模型到内存
-        let (det_model_path, cls_model_path, rec_model_path) = match model {
-            OcrModel::RapidOcrV4 => (
-                orc_plugin_path.join("ch_PP-OCRv4_det_infer.onnx"),
-                orc_plugin_path.join("ch_ppocr_mobile_v2.0_cls_infer.onnx"),
-                orc_plugin_path.join("ch_PP-OCRv4_rec_infer.onnx"),
-            ),
-            OcrModel::RapidOcrV5 => (
-                orc_plugin_path.join("ch_PP-OCRv4_det_infer.onnx"),
-                orc_plugin_path.join("ch_ppocr_mobile_v2.0_cls_infer.onnx"),
-                orc_plugin_path.join("ch_PP-OCRv5_rec_mobile_infer.onnx"),
-            ),
+        if ocr_model_write_to_memory {
+            let (det_result, cls_result, rec_result) =
+                Self::read_model_data(&det_model_path, &cls_model_path, &rec_model_path).await?;
+
+            Ok(OcrModelFiles {
+                det_model: (det_model_path, Some(Arc::new(det_result))),
+                cls_model: (cls_model_path, Some(Arc::new(cls_result))),
+                rec_model: (rec_model_path, Some(Arc::new(rec_result))),
+            })
+        } else {
+            Ok(OcrModelFiles {
+                det_model: (det_model_path, None),
+                cls_model: (cls_model_path, None),
+                rec_model: (rec_model_path, None),
+            })
+        }
+    }
+
+    /**
+     * 加载识别请求指定的语言包，已加载时不做处理
+     */
+    pub async fn prepare_model(
+        &self,
+        orc_plugin_path: PathBuf,
+        model: OcrModel,
+    ) -> Result<(), String> {
+        let ocr_model_write_to_memory = {
+            let pool = self.lock_pool();
+            if pool.model_files.contains_key(&model) {
+                return Ok(());
+            }
+
+            pool.ocr_model_write_to_memory
         };
 
-        let (det_model_config, cls_model_config, rec_model_config) = if ocr_model_write_to_memory {
-            let (det_result, cls_result, rec_result) = self
-                .read_model_data(&det_model_path, &cls_model_path, &rec_model_path)
-                .await?;
+        log::info!(
+            "[OcrService::prepare_model] orc_plugin_path: {:?}, model: {:?}",
+            orc_plugin_path,
+            model
+        );
+
+        let model_files =
+            Self::load_model_files(&orc_plugin_path, model, ocr_model_write_to_memory).await?;
+        self.lock_pool().model_files.insert(model, model_files);
+
+        Ok(())
+    }
+
+    /**
+     * 热启动时预先创建初始化时指定的模型的 session
+     */
+    async fn warm_up(&self) -> Result<(), String> {
+        let (model, model_files, generation, num_thread, ocr_core_factory) = {
+            let pool = self.lock_pool();
+            let Some((_, model)) = pool.default_model else {
+                return Ok(());
+            };
+            if pool
+                .idle_sessions
+                .iter()
+                .any(|session| session.model == model)
+            {
+                return Ok(());
+            }
+            let Some(model_files) = pool.model_files.get(&model).cloned() else {
+                return Ok(());
+            };
 
             (
-                Some((det_model_path, Some(det_result))),
-                Some((cls_model_path, Some(cls_result))),
-                Some((rec_model_path, Some(rec_result))),
+                model,
+                model_files,
+                pool.generation,
+                pool.options.threads_per_session(),
+                pool.ocr_core_factory,
             )
-        } else {
+        };
+
+        let session =
+            Self::create_session(model, model_files, generation, num_thread, ocr_core_factory)
+                .await?;
+        self.return_session(session);
+
+        Ok(())
+    }
+
+    /**
+     * 从池中取出指定语言包的 session，池中的 session 都在使用时排队等待
+     * cancel_token 被取消时停止排队
+     */
+    pub async fn acquire_session(
+        &self,
+        model: OcrModel,
+        priority: OcrRequestPriority,
+        cancel_token: Option<&CancellationToken>,
+    ) -> Result<OcrSessionGuard<'_>, String> {
+        let (sessions, background_sessions) = {
+            let pool = self.lock_pool();
+            (pool.sessions.clone(), pool.background_sessions.clone())
+        };
+
+        let acquire_permits = async {
+            let background_permit = match priority {
+                OcrRequestPriority::Background => Some(background_sessions.acquire_owned().await?),
+                OcrRequestPriority::Interactive => None,
+            };
+            let permit = sessions.clone().acquire_owned().await?;
+
+            Ok::<_, tokio::sync::AcquireError>((permit, background_permit))
+        };
+        let permits = match cancel_token {
+            Some(cancel_token) => tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    return Err("[OcrService::acquire_session] Request cancelled".to_string());
+                }
+                permits = acquire_permits => permits,
+            },
+            None => acquire_permits.await,
+        };
+        let (permit, background_permit) = permits.map_err(|e| {
+            format!(
+                "[OcrService::acquire_session] Failed to acquire session: {}",
+                e
+            )
+        })?;
+
+        let (model_files, generation, num_thread, ocr_core_factory) = {
+            let mut pool = self.lock_pool();
+
+            if let Some(index) = pool
+                .idle_sessions
+                .iter()
+                .rposition(|session| session.model == model)
+            {
+                let session = pool.idle_sessions.remove(index);
+                return Ok(OcrSessionGuard {
+                    service: self,
+                    session: Some(session),
+                    permits: Some(OcrSessionPermits {
+                        _permit: permit,
+                        _background_permit: background_permit,
+                    }),
+                });
+            }
+
+            // 池已满时，释放最久未使用的其他语言包的 session
+            let pool_size = pool.options.pool_size();
+            let active_count = pool_size - sessions.available_permits().min(pool_size);
+            if !pool.idle_sessions.is_empty() && pool.idle_sessions.len() + active_count > pool_size
+            {
+                pool.idle_sessions.remove(0);
+            }
+
+            let Some(model_files) = pool.model_files.get(&model).cloned() else {
+                return Err(format!(
+                    "[OcrService::acquire_session] Model {:?} is not loaded",
+                    model
+                ));
+            };
+
             (
-                Some((det_model_path, None)),
-                Some((cls_model_path, None)),
-                Some((rec_model_path, None)),
+                model_files,
+                pool.generation,
+                pool.options.threads_per_session(),
+                pool.ocr_core_factory,
             )
         };
 
-        self.det_model = det_model_config;
-        self.cls_model = cls_model_config;
-        self.rec_model = rec_model_config;
-        self.hot_start = hot_start;
+        let session =
+            Self::create_session(model, model_files, generation, num_thread, ocr_core_factory)
+                .await?;
 
-        if self.hot_start {
-            self.init_session().await?;
-        } else {
-            self.ocr_core.take();
+        Ok(OcrSessionGuard {
+            service: self,
+            session: Some(session),
+            permits: Some(OcrSessionPermits {
+                _permit: permit,
+                _background_permit: background_permit,
+            }),
+        })
+    }
+
+    /**
+     * 放回使用完的 session，配置变化后创建的 session 直接释放
+     */
+    fn return_session(&self, session: OcrSession) {
+        let mut pool = self.lock_pool();
+        if session.generation != pool.generation {
+            return;
         }
 
-        Ok(())
+        pool.idle_sessions.push(session);
+        let pool_size = pool.options.pool_size();
+        if pool.idle_sessions.len() > pool_size {
+            let overflow = pool.idle_sessions.len() - pool_size;
+            pool.idle_sessions.drain(..overflow);
+        }
     }
 
-    /// 释放 onnx session，并初始化新的 session
-    pub async fn release_session(&mut self) -> Result<(), String> {
-        if self.hot_start {
-            self.init_session().await?;
-        } else {
-            self.ocr_core.take();
+    /// 释放空闲的 onnx session，热启动时重新初始化一个 session
+    pub async fn release_session(&self) -> Result<(), String> {
+        let (released_sessions, hot_start) = {
+            let mut pool = self.lock_pool();
+            (std::mem::take(&mut pool.idle_sessions), pool.hot_start)
+        };
+        drop(released_sessions);
+
+        if hot_start {
+            self.warm_up().await?;
         }
 
         Ok(())
     }
 
-    pub async fn get_session(&mut self) -> Result<&mut OcrLite, String> {
-        if self.ocr_core.is_none() {
-            self.init_session().await?;
+    /**
+     * 登记可以取消的请求，请求结束后需要调用 finish_request
+     */
+    pub fn register_request(&self, request_id: &str) -> CancellationToken {
+        let mut requests = self.requests.lock().unwrap_or_else(|e| e.into_inner());
+
+        requests.entry(request_id.to_string()).or_default().clone()
+    }
+
+    pub fn cancel_request(&self, request_id: &str) {
+        let requests = self.requests.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(cancel_token) = requests.get(request_id) {
+            cancel_token.cancel();
         }
+    }
+
+    pub fn finish_request(&self, request_id: &str) {
+        let mut requests = self.requests.lock().unwrap_or_else(|e| e.into_inner());
+        requests.remove(request_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    /// 判断请求仍在排队时等待的时间
+    const QUEUE_TIMEOUT: Duration = Duration::from_millis(100);
+
+    /// 不加载模型，直接创建空的识别器
+    fn stub_ocr_core(_: &[u8], _: &[u8], _: &[u8], _: usize) -> Result<OcrLite, String> {
+        Ok(OcrLite::new())
+    }
+
+    /// 创建只包含空模型文件的语言包目录
+    fn stub_plugin_path(name: &str) -> PathBuf {
+        let plugin_path = std::env::temp_dir().join(format!("snow_shot_ocr_service_{}", name));
+        std::fs::create_dir_all(&plugin_path).unwrap();
+        for file in OcrModel::RapidOcrV4.pack().file_list() {
+            std::fs::write(plugin_path.join(file), b"").unwrap();
+        }
+
+        plugin_path
+    }
+
+    async fn init_stub_models(service: &OcrService, name: &str, pool_size: usize) {
+        service
+            .init_models(
+                stub_plugin_path(name),
+                OcrModel::RapidOcrV4,
+                false,
+                false,
+                OcrSessionPoolOptions {
+                    pool_size,
+                    threads_per_session: 1,
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    async fn stub_service(name: &str, pool_size: usize) -> OcrService {
+        let service = OcrService::new();
+        service.lock_pool().ocr_core_factory = stub_ocr_core;
+        init_stub_models(&service, name, pool_size).await;
+
+        service
+    }
+
+    async fn acquire(
+        service: &OcrService,
+        priority: OcrRequestPriority,
+    ) -> Result<OcrSessionGuard<'_>, String> {
+        service
+            .acquire_session(OcrModel::RapidOcrV4, priority, None)
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_pool_size_limits_sessions() {
+        let service = stub_service("pool_size", 2).await;
+
+        let first = acquire(&service, OcrRequestPriority::Interactive)
+            .await
+            .unwrap();
+        let second = acquire(&service, OcrRequestPriority::Interactive)
+            .await
+            .unwrap();
+        // 池中的 session 都在使用时排队
+        assert!(
+            timeout(
+                QUEUE_TIMEOUT,
+                acquire(&service, OcrRequestPriority::Interactive)
+            )
+            .await
+            .is_err()
+        );
+
+        drop(first);
+        let third = timeout(
+            QUEUE_TIMEOUT,
+            acquire(&service, OcrRequestPriority::Interactive),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        drop(second);
+        drop(third);
+        assert_eq!(service.lock_pool().idle_sessions.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_background_requests_keep_interactive_session() {
+        let service = stub_service("background", 2).await;
 
-        Ok(self.ocr_core.as_mut().unwrap())
+        let background = acquire(&service, OcrRequestPriority::Background)
+            .await
+            .unwrap();
+        assert!(
+            timeout(
+                QUEUE_TIMEOUT,
+                acquire(&service, OcrRequestPriority::Background)
+            )
+            .await
+            .is_err()
+        );
+
+        // 后台任务排队时交互识别仍有可用的 session
+        let interactive = timeout(
+            QUEUE_TIMEOUT,
+            acquire(&service, OcrRequestPriority::Interactive),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        drop(background);
+        assert!(
+            timeout(
+                QUEUE_TIMEOUT,
+                acquire(&service, OcrRequestPriority::Background)
+            )
+            .await
+            .unwrap()
+            .is_ok()
+        );
+        drop(interactive);
+    }
+
+    #[tokio::test]
+    async fn test_reinit_releases_old_sessions() {
+        let service = stub_service("generation", 2).await;
+
+        let old_session = acquire(&service, OcrRequestPriority::Interactive)
+            .await
+            .unwrap();
+        let idle_session = acquire(&service, OcrRequestPriority::Interactive)
+            .await
+            .unwrap();
+        drop(idle_session);
+        assert_eq!(service.lock_pool().idle_sessions.len(), 1);
+
+        // 重新初始化后空闲的 session 被释放，正在使用的 session 不再放回池中
+        init_stub_models(&service, "generation", 1).await;
+        assert!(service.lock_pool().idle_sessions.is_empty());
+        drop(old_session);
+        assert!(service.lock_pool().idle_sessions.is_empty());
+
+        let new_session = acquire(&service, OcrRequestPriority::Interactive)
+            .await
+            .unwrap();
+        assert!(
+            timeout(
+                QUEUE_TIMEOUT,
+                acquire(&service, OcrRequestPriority::Interactive)
+            )
+            .await
+            .is_err()
+        );
+        drop(new_session);
+        assert_eq!(service.lock_pool().idle_sessions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_queued_request() {
+        let service = stub_service("cancel", 1).await;
+
+        let session = acquire(&service, OcrRequestPriority::Interactive)
+            .await
+            .unwrap();
+        let cancel_token = service.register_request("request");
+        let queued_request = service.acquire_session(
+            OcrModel::RapidOcrV4,
+            OcrRequestPriority::Interactive,
+            Some(&cancel_token),
+        );
+        service.cancel_request("request");
+        assert!(queued_request.await.is_err());
+        service.finish_request("request");
+
+        drop(session);
+        assert!(
+            acquire(&service, OcrRequestPriority::Interactive)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_blocking_keeps_permit_until_finished() {
+        let service = stub_service("run_blocking", 1).await;
+
+        let mut session = acquire(&service, OcrRequestPriority::Interactive)
+            .await
+            .unwrap();
+        let (started_sender, started_receiver) = mpsc::channel();
+        let (finish_sender, finish_receiver) = mpsc::channel::<()>();
+        let run_future = session.run_blocking(move |_| {
+            started_sender.send(()).unwrap();
+            finish_receiver.recv().unwrap();
+        });
+
+        // 等待识别的 future 被丢弃后，阻塞线程仍在使用 session
+        assert!(timeout(QUEUE_TIMEOUT, run_future).await.is_err());
+        started_receiver.recv().unwrap();
+        drop(session);
+        assert!(
+            timeout(
+                QUEUE_TIMEOUT,
+                acquire(&service, OcrRequestPriority::Interactive)
+            )
+            .await
+            .is_err()
+        );
+
+        // 识别结束后释放许可
+        finish_sender.send(()).unwrap();
+        assert!(
+            timeout(
+                Duration::from_secs(5),
+                acquire(&service, OcrRequestPriority::Interactive)
+            )
+            .await
+            .unwrap()
+            .is_ok()
+        );
     }
 }
@@ -92,38 +92,9 @@ impl VideoRecordService {
 
     pub fn init(&mut self, ffmpeg_plugin_dir: &Path) {
         if self.ffmpeg_path.is_none() {
-            #[cfg(target_os = "windows")]
+            #[cfg(any(target_os = "windows", target_os = "macos"))]
             {
-                self.ffmpeg_path = Some(ffmpeg_plugin_dir.join("ffmpeg.exe"));
-            }
-
-            #[cfg(target_os = "macos")]
-            {
-                use std::fs;
-                use std::os::unix::fs::PermissionsExt;
-
-                let ffmpeg_path = ffmpeg_plugin_dir.join("ffmpeg");
-
-                // 为 ffmpeg 文件添加可执行权限
-                if ffmpeg_path.exists() {
-                    if let Ok(metadata) = fs::metadata(&ffmpeg_path) {
-                        let mut permissions = metadata.permissions();
-                        permissions.set_mode(0o755); // 设置可执行权限 (rwxr-xr-x)
-
-                        if let Err(e) = fs::set_permissions(&ffmpeg_path, permissions) {
-                            eprintln!(
-                                "[VideoRecordService] Failed to set executable permissions for ffmpeg: {}",
-                                e
-                            );
-                        } else {
-                            println!(
-                                "[VideoRecordService] Successfully set executable permissions for ffmpeg"
-                            );
-                        }
-                    }
-                }
-
-                self.ffmpeg_path = Some(ffmpeg_path);
+                self.ffmpeg_path = Some(snow_shot_app_utils::get_ffmpeg_path(ffmpeg_plugin_dir));
             }
         }
     }
@@ -0,0 +1,761 @@
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::escape_xml;
+use crate::image_encoder::{ImageEncoder, WebpMetadataChunks, add_webp_metadata_chunks};
+
+/// 写入 XMP 的自定义命名空间
+pub const CAPTURE_METADATA_XMP_NAMESPACE: &str = "https://snowshot.top/ns/capture/1.0/";
+const CAPTURE_METADATA_XMP_PREFIX: &str = "snowshot";
+
+const APP_NAME: &str = "Snow Shot";
+
+/// 文本字段的最大字符数，避免超出 JPEG 段的长度限制
+const MAX_TEXT_CHARS: usize = 512;
+
+const PNG_SIGNATURE_LEN: usize = 8;
+const PNG_CHUNK_HEADER_LEN: usize = 8;
+const PNG_CHUNK_CRC_LEN: usize = 4;
+const PNG_XMP_KEYWORD: &str = "XML:com.adobe.xmp";
+
+const JPEG_APP0_MARKER: u8 = 0xe0;
+const JPEG_APP1_MARKER: u8 = 0xe1;
+const JPEG_EXIF_HEADER: &[u8] = b"Exif\0\0";
+const JPEG_XMP_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+/// JPEG 段的最大长度，包含 2 字节的长度字段
+const JPEG_MAX_SEGMENT_LEN: usize = u16::MAX as usize;
+
+const EXIF_TYPE_ASCII: u16 = 2;
+const EXIF_TYPE_LONG: u16 = 4;
+const EXIF_TYPE_UNDEFINED: u16 = 7;
+
+const EXIF_TAG_IMAGE_DESCRIPTION: u16 = 0x010e;
+const EXIF_TAG_SOFTWARE: u16 = 0x0131;
+const EXIF_TAG_DATE_TIME: u16 = 0x0132;
+const EXIF_TAG_EXIF_IFD_POINTER: u16 = 0x8769;
+const EXIF_TAG_EXIF_VERSION: u16 = 0x9000;
+const EXIF_TAG_DATE_TIME_ORIGINAL: u16 = 0x9003;
+const EXIF_TAG_OFFSET_TIME_ORIGINAL: u16 = 0x9011;
+
+/// TIFF 头的长度，IFD0 紧随其后
+const TIFF_HEADER_LEN: usize = 8;
+const TIFF_IFD_ENTRY_LEN: usize = 12;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureMode {
+    Region,
+    Window,
+    Scroll,
+    FullScreen,
+    VideoRecord,
+}
+
+impl CaptureMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CaptureMode::Region => "Region",
+            CaptureMode::Window => "Window",
+            CaptureMode::Scroll => "Scroll",
+            CaptureMode::FullScreen => "FullScreen",
+            CaptureMode::VideoRecord => "VideoRecord",
+        }
+    }
+}
+
+/**
+ * 截图的来源信息，保存时写入图片文件
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct CaptureMetadata {
+    /// 截图时间，Unix 时间戳（毫秒）
+    pub capture_time: Option<u64>,
+    /// 截取窗口的标题
+    pub window_title: Option<String>,
+    /// 截取窗口的进程名称
+    pub process_name: Option<String>,
+    /// 截取的显示器名称
+    pub monitor_name: Option<String>,
+    pub capture_mode: Option<CaptureMode>,
+    pub app_version: Option<String>,
+}
+
+/**
+ * 写入截图元数据的隐私设置
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct CaptureMetadataOptions {
+    /// 是否写入截图元数据，AVIF 和 JPEG XL 不支持
+    pub enable: bool,
+    /// 是否写入窗口标题和进程名称
+    pub include_window_info: bool,
+}
+
+impl CaptureMetadataOptions {
+    /**
+     * 按隐私设置过滤元数据，未开启时返回 None
+     */
+    pub fn apply(&self, metadata: CaptureMetadata) -> Option<CaptureMetadata> {
+        if !self.enable {
+            return None;
+        }
+
+        if self.include_window_info {
+            Some(metadata)
+        } else {
+            Some(CaptureMetadata {
+                window_title: None,
+                process_name: None,
+                ..metadata
+            })
+        }
+    }
+}
+
+/**
+ * UTC 日期和时间
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct UtcDateTime {
+    pub(crate) year: i64,
+    pub(crate) month: u32,
+    pub(crate) day: u32,
+    pub(crate) hour: u32,
+    pub(crate) minute: u32,
+    pub(crate) second: u32,
+    pub(crate) millisecond: u32,
+}
+
+impl UtcDateTime {
+    pub(crate) fn from_timestamp_millis(timestamp: u64) -> Self {
+        let seconds = (timestamp / 1000) as i64;
+        let days = seconds.div_euclid(86400);
+        let seconds_of_day = seconds.rem_euclid(86400) as u32;
+
+        // Howard Hinnant 的 civil_from_days 算法
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let day_of_era = z - era * 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        } as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Self {
+            year,
+            month,
+            day,
+            hour: seconds_of_day / 3600,
+            minute: seconds_of_day / 60 % 60,
+            second: seconds_of_day % 60,
+            millisecond: (timestamp % 1000) as u32,
+        }
+    }
+
+    /// EXIF 的日期格式，如 2024:01:02 03:04:05
+    fn to_exif_string(self) -> String {
+        format!(
+            "{:04}:{:02}:{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+
+    /// ISO 8601 格式，如 2024-01-02T03:04:05Z
+    fn to_iso_string(self) -> String {
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+/**
+ * 去掉控制字符并限制长度
+ */
+fn sanitize_text(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_TEXT_CHARS)
+        .collect()
+}
+
+struct ExifEntry {
+    tag: u16,
+    field_type: u16,
+    count: u32,
+    value: Vec<u8>,
+}
+
+impl ExifEntry {
+    fn ascii(tag: u16, text: &str) -> Self {
+        let mut value = text.as_bytes().to_vec();
+        value.push(0);
+
+        Self {
+            tag,
+            field_type: EXIF_TYPE_ASCII,
+            count: value.len() as u32,
+            value,
+        }
+    }
+
+    fn long(tag: u16, value: u32) -> Self {
+        Self {
+            tag,
+            field_type: EXIF_TYPE_LONG,
+            count: 1,
+            value: value.to_le_bytes().to_vec(),
+        }
+    }
+
+    fn undefined(tag: u16, value: &[u8]) -> Self {
+        Self {
+            tag,
+            field_type: EXIF_TYPE_UNDEFINED,
+            count: value.len() as u32,
+            value: value.to_vec(),
+        }
+    }
+}
+
+/**
+ * IFD 及其数据区的长度，不超过 4 字节的值直接存放在条目中
+ */
+fn exif_ifd_len(entries: &[ExifEntry]) -> usize {
+    let data_len: usize = entries
+        .iter()
+        .filter(|entry| entry.value.len() > 4)
+        .map(|entry| entry.value.len().next_multiple_of(2))
+        .sum();
+
+    2 + entries.len() * TIFF_IFD_ENTRY_LEN + 4 + data_len
+}
+
+/**
+ * 在 buf 末尾写入 IFD 和数据区，偏移量相对于 TIFF 头
+ */
+fn write_exif_ifd(buf: &mut Vec<u8>, entries: &[ExifEntry], next_ifd_offset: u32) {
+    let data_offset = buf.len() + 2 + entries.len() * TIFF_IFD_ENTRY_LEN + 4;
+    let mut data_area = Vec::new();
+
+    buf.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    for entry in entries {
+        buf.extend_from_slice(&entry.tag.to_le_bytes());
+        buf.extend_from_slice(&entry.field_type.to_le_bytes());
+        buf.extend_from_slice(&entry.count.to_le_bytes());
+
+        if entry.value.len() <= 4 {
+            let mut value = [0u8; 4];
+            value[..entry.value.len()].copy_from_slice(&entry.value);
+            buf.extend_from_slice(&value);
+        } else {
+            buf.extend_from_slice(&((data_offset + data_area.len()) as u32).to_le_bytes());
+            data_area.extend_from_slice(&entry.value);
+            // 值的偏移量需要是偶数
+            if entry.value.len() % 2 == 1 {
+                data_area.push(0);
+            }
+        }
+    }
+    buf.extend_from_slice(&next_ifd_offset.to_le_bytes());
+    buf.extend_from_slice(&data_area);
+}
+
+/**
+ * 当前时间的 Unix 时间戳（毫秒）
+ */
+pub fn current_timestamp_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+impl CaptureMetadata {
+    /**
+     * 创建截图元数据
+     * @param capture_time 截图时间，Unix 时间戳（毫秒），由调用方在截图时记录，不使用保存的时间
+     */
+    pub fn new(
+        app_handle: &tauri::AppHandle,
+        capture_mode: CaptureMode,
+        capture_time: u64,
+    ) -> Self {
+        Self {
+            capture_time: Some(capture_time),
+            capture_mode: Some(capture_mode),
+            app_version: Some(app_handle.package_info().version.to_string()),
+            ..Self::default()
+        }
+    }
+
+    fn capture_date_time(&self) -> Option<UtcDateTime> {
+        self.capture_time.map(UtcDateTime::from_timestamp_millis)
+    }
+
+    fn software(&self) -> String {
+        match &self.app_version {
+            Some(app_version) => format!("{} {}", APP_NAME, sanitize_text(app_version)),
+            None => APP_NAME.to_string(),
+        }
+    }
+
+    /**
+     * 生成 PNG 文本块的关键字和内容
+     */
+    fn png_text_entries(&self) -> Vec<(&'static str, String)> {
+        let mut entries = vec![("Software", self.software())];
+        if let Some(date_time) = self.capture_date_time() {
+            entries.push(("Creation Time", date_time.to_iso_string()));
+        }
+        if let Some(window_title) = &self.window_title {
+            entries.push(("Title", sanitize_text(window_title)));
+        }
+        if let Some(process_name) = &self.process_name {
+            entries.push(("Process Name", sanitize_text(process_name)));
+        }
+        if let Some(monitor_name) = &self.monitor_name {
+            entries.push(("Monitor", sanitize_text(monitor_name)));
+        }
+        if let Some(capture_mode) = self.capture_mode {
+            entries.push(("Capture Mode", capture_mode.as_str().to_string()));
+        }
+        entries
+    }
+
+    /**
+     * 生成 EXIF 数据（TIFF 格式，小端序）
+     *
+     * EXIF 的 ASCII 字段只写入 ASCII 字符的窗口标题，完整信息写入 XMP
+     */
+    pub fn to_exif(&self) -> Vec<u8> {
+        let date_time = self.capture_date_time().map(UtcDateTime::to_exif_string);
+
+        let mut ifd0_entries = Vec::new();
+        if let Some(window_title) = &self.window_title {
+            let window_title = sanitize_text(window_title);
+            if !window_title.is_empty() && window_title.is_ascii() {
+                ifd0_entries.push(ExifEntry::ascii(EXIF_TAG_IMAGE_DESCRIPTION, &window_title));
+            }
+        }
+        let software = self.software();
+        if software.is_ascii() {
+            ifd0_entries.push(ExifEntry::ascii(EXIF_TAG_SOFTWARE, &software));
+        }
+        if let Some(date_time) = &date_time {
+            ifd0_entries.push(ExifEntry::ascii(EXIF_TAG_DATE_TIME, date_time));
+        }
+        // 先占位，写入前再更新 Exif IFD 的偏移量
+        ifd0_entries.push(ExifEntry::long(EXIF_TAG_EXIF_IFD_POINTER, 0));
+
+        let mut exif_entries = vec![ExifEntry::undefined(EXIF_TAG_EXIF_VERSION, b"0232")];
+        if let Some(date_time) = &date_time {
+            exif_entries.push(ExifEntry::ascii(EXIF_TAG_DATE_TIME_ORIGINAL, date_time));
+            exif_entries.push(ExifEntry::ascii(EXIF_TAG_OFFSET_TIME_ORIGINAL, "+00:00"));
+        }
+
+        let exif_ifd_offset = (TIFF_HEADER_LEN + exif_ifd_len(&ifd0_entries)) as u32;
+        if let Some(pointer) = ifd0_entries.last_mut() {
+            pointer.value = exif_ifd_offset.to_le_bytes().to_vec();
+        }
+
+        let mut buf = Vec::with_capacity(exif_ifd_offset as usize + exif_ifd_len(&exif_entries));
+        buf.extend_from_slice(b"II*\0");
+        buf.extend_from_slice(&(TIFF_HEADER_LEN as u32).to_le_bytes());
+        write_exif_ifd(&mut buf, &ifd0_entries, 0);
+        write_exif_ifd(&mut buf, &exif_entries, 0);
+
+        buf
+    }
+
+    /**
+     * 生成 XMP 数据包
+     */
+    pub fn to_xmp(&self) -> String {
+        let mut attributes = vec![(
+            String::from("xmp:CreatorTool"),
+            escape_xml(&self.software()),
+        )];
+        if let Some(date_time) = self.capture_date_time() {
+            attributes.push((String::from("xmp:CreateDate"), date_time.to_iso_string()));
+        }
+
+        let mut custom_attribute = |name: &str, value: &str| {
+            attributes.push((
+                format!("{}:{}", CAPTURE_METADATA_XMP_PREFIX, name),
+                escape_xml(&sanitize_text(value)),
+            ));
+        };
+        if let Some(capture_mode) = self.capture_mode {
+            custom_attribute("CaptureMode", capture_mode.as_str());
+        }
+        if let Some(window_title) = &self.window_title {
+            custom_attribute("WindowTitle", window_title);
+        }
+        if let Some(process_name) = &self.process_name {
+            custom_attribute("ProcessName", process_name);
+        }
+        if let Some(monitor_name) = &self.monitor_name {
+            custom_attribute("Monitor", monitor_name);
+        }
+
+        let attributes = attributes
+            .iter()
+            .map(|(name, value)| format!("\n    {}=\"{}\"", name, value))
+            .collect::<String>();
+        let title = match &self.window_title {
+            Some(window_title) => format!(
+                "\n   <dc:title><rdf:Alt><rdf:li xml:lang=\"x-default\">{}</rdf:li></rdf:Alt></dc:title>",
+                escape_xml(&sanitize_text(window_title))
+            ),
+            None => String::new(),
+        };
+
+        format!(
+            concat!(
+                "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n",
+                "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n",
+                " <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n",
+                "  <rdf:Description rdf:about=\"\"\n",
+                "    xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\"\n",
+                "    xmlns:dc=\"http://purl.org/dc/elements/1.1/\"\n",
+                "    xmlns:{}=\"{}\"{}>{}\n",
+                "  </rdf:Description>\n",
+                " </rdf:RDF>\n",
+                "</x:xmpmeta>\n",
+                "<?xpacket end=\"w\"?>"
+            ),
+            CAPTURE_METADATA_XMP_PREFIX, CAPTURE_METADATA_XMP_NAMESPACE, attributes, title
+        )
+    }
+
+    /**
+     * 生成 PNG 元数据块的类型和内容，需要写在 IHDR 之后、IDAT 之前
+     */
+    pub fn to_png_chunks(&self) -> Vec<([u8; 4], Vec<u8>)> {
+        let mut chunks = self
+            .png_text_entries()
+            .into_iter()
+            .map(|(keyword, text)| png_text_chunk(keyword, &text))
+            .collect::<Vec<_>>();
+        chunks.push(png_text_chunk(PNG_XMP_KEYWORD, &self.to_xmp()));
+        chunks.push((*b"eXIf", self.to_exif()));
+        chunks
+    }
+}
+
+fn push_png_chunk(buf: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    buf.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    buf.extend_from_slice(chunk_type);
+    buf.extend_from_slice(data);
+
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(chunk_type);
+    hasher.update(data);
+    buf.extend_from_slice(&hasher.finalize().to_be_bytes());
+}
+
+/**
+ * 生成 PNG 文本块，ASCII 内容使用 tEXt，其他内容使用 UTF-8 的 iTXt
+ */
+fn png_text_chunk(keyword: &str, text: &str) -> ([u8; 4], Vec<u8>) {
+    let mut data = Vec::with_capacity(keyword.len() + text.len() + 5);
+    data.extend_from_slice(keyword.as_bytes());
+    data.push(0);
+
+    if text.is_ascii() {
+        data.extend_from_slice(text.as_bytes());
+        (*b"tEXt", data)
+    } else {
+        // 不压缩，语言标签和翻译后的关键字为空
+        data.extend_from_slice(&[0, 0, 0, 0]);
+        data.extend_from_slice(text.as_bytes());
+        (*b"iTXt", data)
+    }
+}
+
+/**
+ * 在 PNG 的 IHDR 块后插入元数据块，eXIf 块需要在 IDAT 之前
+ */
+fn embed_png_metadata(data: &[u8], metadata: &CaptureMetadata) -> Result<Vec<u8>, String> {
+    let ihdr_end = PNG_SIGNATURE_LEN + PNG_CHUNK_HEADER_LEN + 13 + PNG_CHUNK_CRC_LEN;
+    if data.len() < ihdr_end
+        || &data[..PNG_SIGNATURE_LEN] != b"\x89PNG\r\n\x1a\n"
+        || &data[PNG_SIGNATURE_LEN + 4..PNG_SIGNATURE_LEN + PNG_CHUNK_HEADER_LEN] != b"IHDR"
+    {
+        return Err(String::from("[embed_png_metadata] Invalid PNG data"));
+    }
+
+    let mut chunks = Vec::new();
+    for (chunk_type, chunk_data) in metadata.to_png_chunks() {
+        push_png_chunk(&mut chunks, &chunk_type, &chunk_data);
+    }
+
+    let mut buf = Vec::with_capacity(data.len() + chunks.len());
+    buf.extend_from_slice(&data[..ihdr_end]);
+    buf.extend_from_slice(&chunks);
+    buf.extend_from_slice(&data[ihdr_end..]);
+
+    Ok(buf)
+}
+
+fn push_jpeg_app1_segment(buf: &mut Vec<u8>, header: &[u8], payload: &[u8]) -> bool {
+    let segment_len = 2 + header.len() + payload.len();
+    if segment_len > JPEG_MAX_SEGMENT_LEN {
+        log::warn!("[embed_jpeg_metadata] Metadata is too large for a JPEG segment");
+        return false;
+    }
+
+    buf.extend_from_slice(&[0xff, JPEG_APP1_MARKER]);
+    buf.extend_from_slice(&(segment_len as u16).to_be_bytes());
+    buf.extend_from_slice(header);
+    buf.extend_from_slice(payload);
+    true
+}
+
+/**
+ * 在 JPEG 的 SOI 和 JFIF（APP0）段后插入 EXIF 和 XMP（APP1）段
+ */
+fn embed_jpeg_metadata(data: &[u8], metadata: &CaptureMetadata) -> Result<Vec<u8>, String> {
+    let invalid_data_error = || String::from("[embed_jpeg_metadata] Invalid JPEG data");
+
+    if data.len() < 4 || data[0..2] != [0xff, 0xd8] {
+        return Err(invalid_data_error());
+    }
+
+    let mut insert_offset = 2;
+    while data.get(insert_offset..insert_offset + 2) == Some(&[0xff, JPEG_APP0_MARKER]) {
+        let segment_len = data
+            .get(insert_offset + 2..insert_offset + 4)
+            .map(|len| u16::from_be_bytes([len[0], len[1]]) as usize)
+            .ok_or_else(invalid_data_error)?;
+        insert_offset += 2 + segment_len;
+    }
+    if insert_offset > data.len() {
+        return Err(invalid_data_error());
+    }
+
+    let mut segments = Vec::new();
+    push_jpeg_app1_segment(&mut segments, JPEG_EXIF_HEADER, &metadata.to_exif());
+    push_jpeg_app1_segment(&mut segments, JPEG_XMP_HEADER, metadata.to_xmp().as_bytes());
+
+    let mut buf = Vec::with_capacity(data.len() + segments.len());
+    buf.extend_from_slice(&data[..insert_offset]);
+    buf.extend_from_slice(&segments);
+    buf.extend_from_slice(&data[insert_offset..]);
+
+    Ok(buf)
+}
+
+/**
+ * 在编码后的图片数据中写入截图元数据
+ *
+ * PNG 写入文本块、XMP 和 eXIf 块，JPEG 和 WebP 写入 EXIF 和 XMP
+ *
+ * AVIF 和 JPEG XL 暂不支持写入元数据，保持不变，设置项的说明中已提示
+ */
+pub fn embed_capture_metadata(
+    data: Vec<u8>,
+    encoder: ImageEncoder,
+    metadata: &CaptureMetadata,
+) -> Result<Vec<u8>, String> {
+    match encoder {
+        ImageEncoder::Png => embed_png_metadata(&data, metadata),
+        ImageEncoder::Jpeg => embed_jpeg_metadata(&data, metadata),
+        ImageEncoder::Webp => {
+            let exif = metadata.to_exif();
+            let xmp = metadata.to_xmp();
+
+            add_webp_metadata_chunks(
+                &data,
+                &WebpMetadataChunks {
+                    icc_profile: None,
+                    exif: Some(&exif),
+                    xmp: Some(xmp.as_bytes()),
+                },
+            )
+        }
+        ImageEncoder::Avif | ImageEncoder::Jxl => {
+            log::debug!(
+                "[embed_capture_metadata] Capture metadata is not supported for {:?}",
+                encoder
+            );
+            Ok(data)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EncodeOptions;
+    use crate::image_encoder::encode_image;
+    use image::{DynamicImage, ImageDecoder};
+    use std::io::Cursor;
+
+    fn test_metadata() -> CaptureMetadata {
+        CaptureMetadata {
+            // 2024-02-29T23:59:59Z
+            capture_time: Some(1_709_251_199_000),
+            window_title: Some(String::from("测试 <Window> & \"Title\"")),
+            process_name: Some(String::from("snow-shot.exe")),
+            monitor_name: Some(String::from("DISPLAY1")),
+            capture_mode: Some(CaptureMode::Window),
+            app_version: Some(String::from("0.7.9")),
+        }
+    }
+
+    fn test_image() -> DynamicImage {
+        DynamicImage::ImageRgb8(image::RgbImage::from_fn(9, 7, |x, y| {
+            image::Rgb([(x * 20) as u8, (y * 30) as u8, 64])
+        }))
+    }
+
+    fn encode_with_metadata(encoder: ImageEncoder) -> Vec<u8> {
+        let image_data = encode_image(
+            &test_image(),
+            encoder,
+            &EncodeOptions {
+                lossless: Some(encoder != ImageEncoder::Jpeg),
+                ..EncodeOptions::default()
+            },
+        )
+        .unwrap();
+
+        embed_capture_metadata(image_data, encoder, &test_metadata()).unwrap()
+    }
+
+    #[test]
+    fn test_utc_date_time() {
+        let epoch = UtcDateTime::from_timestamp_millis(0);
+        assert_eq!(epoch.to_exif_string(), "1970:01:01 00:00:00");
+
+        let leap_day = UtcDateTime::from_timestamp_millis(1_709_251_199_999);
+        assert_eq!(leap_day.to_iso_string(), "2024-02-29T23:59:59Z");
+
+        let next_day = UtcDateTime::from_timestamp_millis(1_709_251_200_000);
+        assert_eq!(next_day.to_exif_string(), "2024:03:01 00:00:00");
+    }
+
+    #[test]
+    fn test_options_strip_window_info() {
+        let options = CaptureMetadataOptions {
+            enable: true,
+            include_window_info: false,
+        };
+        let metadata = options.apply(test_metadata()).unwrap();
+        assert_eq!(metadata.window_title, None);
+        assert_eq!(metadata.process_name, None);
+        assert_eq!(metadata.monitor_name.as_deref(), Some("DISPLAY1"));
+
+        assert_eq!(
+            CaptureMetadataOptions::default().apply(test_metadata()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_xmp_escapes_text() {
+        let xmp = test_metadata().to_xmp();
+
+        assert!(xmp.contains("xmp:CreateDate=\"2024-02-29T23:59:59Z\""));
+        assert!(xmp.contains("snowshot:CaptureMode=\"Window\""));
+        assert!(xmp.contains("测试 &lt;Window&gt; &amp; &quot;Title&quot;"));
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(escape_xml("a<b & \"c\"\u{1}"), "a&lt;b &amp; &quot;c&quot;");
+    }
+
+    #[test]
+    fn test_png_embeds_text_and_exif() {
+        let png_data = encode_with_metadata(ImageEncoder::Png);
+
+        let decoder = png::Decoder::new(Cursor::new(png_data.as_slice()));
+        let mut reader = decoder.read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size().unwrap()];
+        reader.next_frame(&mut buf).unwrap();
+
+        let info = reader.info();
+        let text = |keyword: &str| {
+            info.uncompressed_latin1_text
+                .iter()
+                .find(|chunk| chunk.keyword == keyword)
+                .map(|chunk| chunk.text.clone())
+        };
+        assert_eq!(text("Software").as_deref(), Some("Snow Shot 0.7.9"));
+        assert_eq!(
+            text("Creation Time").as_deref(),
+            Some("2024-02-29T23:59:59Z")
+        );
+        assert_eq!(text("Capture Mode").as_deref(), Some("Window"));
+
+        let title = info
+            .utf8_text
+            .iter()
+            .find(|chunk| chunk.keyword == "Title")
+            .unwrap();
+        assert_eq!(title.get_text().unwrap(), "测试 <Window> & \"Title\"");
+        assert!(
+            info.utf8_text
+                .iter()
+                .any(|chunk| chunk.keyword == PNG_XMP_KEYWORD)
+        );
+        assert_eq!(
+            info.exif_metadata.as_deref(),
+            Some(test_metadata().to_exif().as_slice())
+        );
+    }
+
+    #[test]
+    fn test_jpeg_and_webp_embed_exif() {
+        let exif = test_metadata().to_exif();
+
+        let jpeg_data = encode_with_metadata(ImageEncoder::Jpeg);
+        let mut jpeg_decoder =
+            image::codecs::jpeg::JpegDecoder::new(Cursor::new(jpeg_data.as_slice())).unwrap();
+        assert_eq!(jpeg_decoder.exif_metadata().unwrap(), Some(exif.clone()));
+        assert!(image::load_from_memory(&jpeg_data).is_ok());
+
+        let webp_data = encode_with_metadata(ImageEncoder::Webp);
+        let mut webp_decoder =
+            image::codecs::webp::WebPDecoder::new(Cursor::new(webp_data.as_slice())).unwrap();
+        assert_eq!(webp_decoder.exif_metadata().unwrap(), Some(exif));
+        assert_eq!(
+            image::load_from_memory(&webp_data).unwrap().to_rgb8(),
+            test_image().to_rgb8()
+        );
+    }
+
+    #[test]
+    fn test_exif_layout() {
+        let exif = test_metadata().to_exif();
+        assert_eq!(&exif[..8], b"II*\0\x08\0\0\0");
+
+        // 非 ASCII 的窗口标题不写入 ImageDescription
+        let ifd0_count = u16::from_le_bytes([exif[8], exif[9]]) as usize;
+        let tags = (0..ifd0_count)
+            .map(|index| {
+                let offset = 10 + index * TIFF_IFD_ENTRY_LEN;
+                u16::from_le_bytes([exif[offset], exif[offset + 1]])
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tags,
+            vec![
+                EXIF_TAG_SOFTWARE,
+                EXIF_TAG_DATE_TIME,
+                EXIF_TAG_EXIF_IFD_POINTER
+            ]
+        );
+    }
+}
@@ -0,0 +1,432 @@
+use image::DynamicImage;
+use moxcms::{Layout, RenderingIntent, TransformOptions};
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::slice::{ParallelSlice, ParallelSliceMut};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use xcap::Monitor;
+
+/// 并行转换时每个任务处理的像素数量
+const CONVERT_CHUNK_PIXEL_COUNT: usize = 64 * 1024;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub enum ColorProfileMode {
+    /// 不处理显示器的色彩配置文件，保存未标记色彩空间的图像
+    #[default]
+    Ignore,
+    /// 按显示器的色彩配置文件将图像转换为 sRGB
+    ConvertToSrgb,
+    /// 在图像文件中嵌入显示器的 ICC 配置文件，格式不支持时转换为 sRGB
+    Embed,
+}
+
+impl ColorProfileMode {
+    /**
+     * 捕获的图像交给前端绘制时无法携带配置文件，嵌入改为转换为 sRGB
+     */
+    pub fn without_embed(self) -> Self {
+        match self {
+            Self::Embed => Self::ConvertToSrgb,
+            mode => mode,
+        }
+    }
+}
+
+/**
+ * 显示器的 ICC 色彩配置文件
+ */
+#[derive(Debug, Clone)]
+pub struct ColorProfile {
+    icc_data: Vec<u8>,
+    profile: moxcms::ColorProfile,
+}
+
+impl ColorProfile {
+    pub fn from_icc_data(icc_data: Vec<u8>) -> Result<Self, String> {
+        let profile = match moxcms::ColorProfile::new_from_slice(&icc_data) {
+            Ok(profile) => profile,
+            Err(e) => {
+                return Err(format!(
+                    "[ColorProfile::from_icc_data] Failed to parse ICC profile: {}",
+                    e
+                ));
+            }
+        };
+
+        Ok(Self { icc_data, profile })
+    }
+
+    pub fn icc_data(&self) -> &[u8] {
+        &self.icc_data
+    }
+
+    fn create_transform(
+        &self,
+        layout: Layout,
+    ) -> Result<Box<moxcms::Transform8BitExecutor>, String> {
+        match self.profile.create_transform_8bit(
+            layout,
+            &moxcms::ColorProfile::new_srgb(),
+            layout,
+            TransformOptions {
+                rendering_intent: RenderingIntent::RelativeColorimetric,
+                ..TransformOptions::default()
+            },
+        ) {
+            Ok(transform) => Ok(transform),
+            Err(e) => Err(format!(
+                "[ColorProfile::create_transform] Failed to create transform: {}",
+                e
+            )),
+        }
+    }
+
+    /**
+     * 配置文件是否与 sRGB 一致
+     *
+     * 转换一组灰阶和原色，结果与原值相同时跳过转换
+     */
+    pub fn is_srgb(&self) -> bool {
+        let transform = match self.create_transform(Layout::Rgb) {
+            Ok(transform) => transform,
+            Err(_) => return false,
+        };
+
+        let mut probe = Vec::with_capacity(256 * 3 * 4);
+        for value in 0..=255u8 {
+            probe.extend_from_slice(&[value, value, value]);
+            probe.extend_from_slice(&[value, 0, 0]);
+            probe.extend_from_slice(&[0, value, 0]);
+            probe.extend_from_slice(&[0, 0, value]);
+        }
+
+        let mut converted = vec![0; probe.len()];
+        if transform.transform(&probe, &mut converted).is_err() {
+            return false;
+        }
+
+        probe
+            .iter()
+            .zip(converted.iter())
+            .all(|(source, target)| source.abs_diff(*target) <= 1)
+    }
+
+    /**
+     * 将使用该配置文件的图像转换为 sRGB
+     */
+    pub fn convert_to_srgb(&self, image: &DynamicImage) -> Result<DynamicImage, String> {
+        let source_image = match image {
+            DynamicImage::ImageRgb8(_) | DynamicImage::ImageRgba8(_) => Cow::Borrowed(image),
+            _ => Cow::Owned(DynamicImage::ImageRgba8(image.to_rgba8())),
+        };
+        let (layout, channels) = if source_image.color().has_alpha() {
+            (Layout::Rgba, 4)
+        } else {
+            (Layout::Rgb, 3)
+        };
+
+        let transform = self.create_transform(layout)?;
+        let source = source_image.as_bytes();
+        let mut target = vec![0; source.len()];
+
+        let chunk_len = CONVERT_CHUNK_PIXEL_COUNT * channels;
+        if let Err(e) = target
+            .par_chunks_mut(chunk_len)
+            .zip(source.par_chunks(chunk_len))
+            .try_for_each(|(target, source)| transform.transform(source, target))
+        {
+            return Err(format!(
+                "[ColorProfile::convert_to_srgb] Failed to convert image: {}",
+                e
+            ));
+        }
+
+        let (width, height) = (source_image.width(), source_image.height());
+        let converted_image = match layout {
+            Layout::Rgb => {
+                image::RgbImage::from_raw(width, height, target).map(DynamicImage::ImageRgb8)
+            }
+            _ => image::RgbaImage::from_raw(width, height, target).map(DynamicImage::ImageRgba8),
+        };
+
+        match converted_image {
+            Some(converted_image) => Ok(converted_image),
+            None => Err(String::from(
+                "[ColorProfile::convert_to_srgb] Failed to create converted image",
+            )),
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn get_monitor_icc_data(monitor: &Monitor) -> Result<Option<Vec<u8>>, String> {
+    use widestring::U16CString;
+    use windows::Win32::Graphics::Gdi::{CreateDCW, DeleteDC};
+    use windows::Win32::UI::ColorSystem::GetICMProfileW;
+    use windows::core::{PCWSTR, PWSTR, w};
+
+    use crate::monitor_info::MonitorInfo;
+
+    let device_name = MonitorInfo::get_device_name(monitor)?;
+    let device_name = match U16CString::from_str(&device_name) {
+        Ok(device_name) => device_name,
+        Err(e) => {
+            return Err(format!(
+                "[get_monitor_icc_data] Invalid device name: {:?}",
+                e
+            ));
+        }
+    };
+
+    let hdc = unsafe {
+        CreateDCW(
+            w!("DISPLAY"),
+            PCWSTR(device_name.as_ptr()),
+            PCWSTR::null(),
+            None,
+        )
+    };
+    if hdc.is_invalid() {
+        return Err(format!(
+            "[get_monitor_icc_data] Failed to create device context: {}",
+            device_name.to_string_lossy()
+        ));
+    }
+
+    // 先获取文件路径的长度
+    let mut buffer_size = 0;
+    let _ = unsafe { GetICMProfileW(hdc, &mut buffer_size, None) };
+    let mut buffer = vec![0u16; buffer_size as usize];
+    let result = buffer_size > 0
+        && unsafe { GetICMProfileW(hdc, &mut buffer_size, Some(PWSTR(buffer.as_mut_ptr()))) }
+            .as_bool();
+    let _ = unsafe { DeleteDC(hdc) };
+
+    if !result {
+        return Ok(None);
+    }
+
+    let profile_path = U16CString::from_vec_truncate(buffer).to_string_lossy();
+    match std::fs::read(&profile_path) {
+        Ok(data) => Ok(Some(data)),
+        Err(e) => Err(format!(
+            "[get_monitor_icc_data] Failed to read ICC profile: {} {}",
+            e, profile_path
+        )),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn get_monitor_icc_data(monitor: &Monitor) -> Result<Option<Vec<u8>>, String> {
+    use std::ffi::c_void;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    unsafe extern "C" {
+        fn CGDisplayCopyColorSpace(display: u32) -> *const c_void;
+        fn CGColorSpaceCopyICCData(space: *const c_void) -> *const c_void;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    unsafe extern "C" {
+        fn CFDataGetLength(data: *const c_void) -> isize;
+        fn CFDataGetBytePtr(data: *const c_void) -> *const u8;
+        fn CFRelease(cf: *const c_void);
+    }
+
+    let display_id = match monitor.id() {
+        Ok(display_id) => display_id,
+        Err(e) => {
+            return Err(format!(
+                "[get_monitor_icc_data] Failed to get display id: {}",
+                e
+            ));
+        }
+    };
+
+    unsafe {
+        let color_space = CGDisplayCopyColorSpace(display_id);
+        if color_space.is_null() {
+            return Ok(None);
+        }
+
+        let icc_data = CGColorSpaceCopyICCData(color_space);
+        CFRelease(color_space);
+        if icc_data.is_null() {
+            return Ok(None);
+        }
+
+        let data = std::slice::from_raw_parts(
+            CFDataGetBytePtr(icc_data),
+            CFDataGetLength(icc_data) as usize,
+        )
+        .to_vec();
+        CFRelease(icc_data);
+
+        Ok(Some(data))
+    }
+}
+
+/**
+ * 通过 colord 获取显示器的默认配置文件
+ */
+#[cfg(target_os = "linux")]
+fn get_monitor_icc_data(monitor: &Monitor) -> Result<Option<Vec<u8>>, String> {
+    use zbus::blocking::{Connection, Proxy};
+    use zbus::zvariant::OwnedObjectPath;
+
+    const COLORD_SERVICE: &str = "org.freedesktop.ColorManager";
+
+    let output_name = match monitor.name() {
+        Ok(output_name) => output_name,
+        Err(e) => {
+            return Err(format!(
+                "[get_monitor_icc_data] Failed to get output name: {}",
+                e
+            ));
+        }
+    };
+
+    let result: zbus::Result<Option<String>> = (|| {
+        let connection = Connection::system()?;
+        let manager = Proxy::new(
+            &connection,
+            COLORD_SERVICE,
+            "/org/freedesktop/ColorManager",
+            COLORD_SERVICE,
+        )?;
+
+        // colord 中没有该显示器时返回错误，视为没有配置文件
+        let device_path: OwnedObjectPath = match manager.call(
+            "FindDeviceByProperty",
+            &("XRANDR_name", output_name.as_str()),
+        ) {
+            Ok(device_path) => device_path,
+            Err(_) => return Ok(None),
+        };
+        let device = Proxy::new(
+            &connection,
+            COLORD_SERVICE,
+            device_path,
+            "org.freedesktop.ColorManager.Device",
+        )?;
+
+        // 第一个配置文件为默认配置文件
+        let profile_paths: Vec<OwnedObjectPath> = device.get_property("Profiles")?;
+        let profile_path = match profile_paths.into_iter().next() {
+            Some(profile_path) => profile_path,
+            None => return Ok(None),
+        };
+        let profile = Proxy::new(
+            &connection,
+            COLORD_SERVICE,
+            profile_path,
+            "org.freedesktop.ColorManager.Profile",
+        )?;
+
+        Ok(Some(profile.get_property("Filename")?))
+    })();
+
+    let profile_path = match result {
+        Ok(Some(profile_path)) if !profile_path.is_empty() => profile_path,
+        Ok(_) => return Ok(None),
+        Err(e) => {
+            return Err(format!(
+                "[get_monitor_icc_data] Failed to query colord: {}",
+                e
+            ));
+        }
+    };
+
+    match std::fs::read(&profile_path) {
+        Ok(data) => Ok(Some(data)),
+        Err(e) => Err(format!(
+            "[get_monitor_icc_data] Failed to read ICC profile: {} {}",
+            e, profile_path
+        )),
+    }
+}
+
+/**
+ * 获取显示器的 ICC 配置文件，没有配置文件或配置文件与 sRGB 一致时返回 None
+ */
+pub fn get_monitor_color_profile(monitor: &Monitor) -> Result<Option<ColorProfile>, String> {
+    let icc_data = match get_monitor_icc_data(monitor)? {
+        Some(icc_data) => icc_data,
+        None => return Ok(None),
+    };
+
+    let color_profile = ColorProfile::from_icc_data(icc_data)?;
+    if color_profile.is_srgb() {
+        return Ok(None);
+    }
+
+    Ok(Some(color_profile))
+}
+
+/**
+ * 按处理方式处理捕获到的图像，返回处理后的图像和保存时需要嵌入的配置文件
+ */
+pub fn apply_color_profile_mode(
+    image: DynamicImage,
+    color_profile: Option<ColorProfile>,
+    mode: ColorProfileMode,
+) -> (DynamicImage, Option<ColorProfile>) {
+    let color_profile = match color_profile {
+        Some(color_profile) => color_profile,
+        None => return (image, None),
+    };
+
+    match mode {
+        ColorProfileMode::Ignore => (image, None),
+        ColorProfileMode::Embed => (image, Some(color_profile)),
+        ColorProfileMode::ConvertToSrgb => match color_profile.convert_to_srgb(&image) {
+            Ok(image) => (image, None),
+            Err(e) => {
+                log::error!("[apply_color_profile_mode] {}", e);
+                (image, None)
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display_p3_profile() -> ColorProfile {
+        ColorProfile::from_icc_data(moxcms::ColorProfile::new_display_p3().encode().unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_srgb_profile_is_detected() {
+        let srgb_profile =
+            ColorProfile::from_icc_data(moxcms::ColorProfile::new_srgb().encode().unwrap())
+                .unwrap();
+
+        assert!(srgb_profile.is_srgb());
+        assert!(!display_p3_profile().is_srgb());
+    }
+
+    #[test]
+    fn test_convert_display_p3_to_srgb() {
+        let image = DynamicImage::ImageRgba8(image::RgbaImage::from_fn(4, 4, |x, _| {
+            if x < 2 {
+                image::Rgba([0, 255, 0, 128])
+            } else {
+                image::Rgba([128, 128, 128, 255])
+            }
+        }));
+
+        let converted = display_p3_profile().convert_to_srgb(&image).unwrap();
+        let converted = converted.as_rgba8().unwrap();
+
+        // P3 的纯绿色超出 sRGB 色域，红色和蓝色通道被裁剪为 0，绿色保持最大
+        let green = converted.get_pixel(0, 0);
+        assert_eq!(green[1], 255);
+        assert_eq!(green[3], 128);
+        // 两者的白点和传递函数相同，灰色保持不变
+        let gray = converted.get_pixel(3, 3);
+        assert!(gray.0[..3].iter().all(|value| value.abs_diff(128) <= 1));
+    }
+}
@@ -0,0 +1,664 @@
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::Manager;
+
+use crate::capture_metadata::{CaptureMode, UtcDateTime, current_timestamp_millis};
+
+/// 保存每日计数的文件名，位于应用数据目录
+const DAILY_COUNTER_FILE_NAME: &str = "file_name_counter";
+/// 令牌替换后的最大字符数，避免窗口标题过长
+const MAX_TOKEN_CHARS: usize = 128;
+/// 文件名的最大字节数
+const MAX_FILE_NAME_BYTES: usize = 255;
+const DEFAULT_RANDOM_LEN: usize = 8;
+const MAX_RANDOM_LEN: usize = 32;
+const MAX_COUNTER_WIDTH: usize = 10;
+/// 解决重名时最多尝试的次数
+const MAX_COLLISION_ATTEMPTS: u32 = 10000;
+const UNKNOWN_TOKEN_VALUE: &str = "unknown";
+const RANDOM_CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+const WINDOWS_INVALID_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+const WINDOWS_RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// 保证读写每日计数文件时不会相互覆盖
+static DAILY_COUNTER_LOCK: Mutex<()> = Mutex::new(());
+
+/**
+ * 渲染文件名模板时使用的截图信息
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct FileNameTemplateContext {
+    /// 截图时间，Unix 时间戳（毫秒），为空时使用当前时间
+    pub capture_time: Option<u64>,
+    /// 本地时间相对 UTC 的偏移（分钟），为空时使用 UTC
+    pub utc_offset_minutes: Option<i32>,
+    pub window_title: Option<String>,
+    pub process_name: Option<String>,
+    /// 显示器序号，从 1 开始
+    pub monitor_index: Option<usize>,
+    pub capture_mode: Option<CaptureMode>,
+}
+
+impl FileNameTemplateContext {
+    fn local_date_time(&self) -> UtcDateTime {
+        let capture_time = self.capture_time.unwrap_or_else(current_timestamp_millis) as i64;
+        let offset = self.utc_offset_minutes.unwrap_or(0) as i64 * 60 * 1000;
+
+        UtcDateTime::from_timestamp_millis((capture_time + offset).max(0) as u64)
+    }
+
+    /// 每日计数使用的日期，如 2024-01-02
+    fn date_key(&self) -> String {
+        let date_time = self.local_date_time();
+        format!(
+            "{:04}-{:02}-{:02}",
+            date_time.year, date_time.month, date_time.day
+        )
+    }
+}
+
+/**
+ * 渲染模板时使用的每日计数
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DailyCounter {
+    /// 计数对应的日期，如 2024-01-02
+    pub date_key: String,
+    pub counter: u32,
+}
+
+/**
+ * 渲染后的保存路径
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ResolvedFilePath {
+    pub file_path: PathBuf,
+    /// 模板使用了每日计数时，写入文件后需要提交的计数
+    pub daily_counter: Option<DailyCounter>,
+}
+
+impl ResolvedFilePath {
+    /**
+     * 文件写入成功后提交每日计数，提交失败只记录日志
+     */
+    pub fn commit(&self, app_handle: &tauri::AppHandle) {
+        if let Some(daily_counter) = &self.daily_counter
+            && let Err(e) = commit_daily_counter(app_handle, daily_counter)
+        {
+            log::warn!("[ResolvedFilePath::commit] {}", e);
+        }
+    }
+}
+
+/**
+ * 文件名中不允许出现的字符和名称
+ */
+#[derive(Debug, Clone, Copy)]
+struct FileNameRules {
+    invalid_chars: &'static [char],
+    /// Windows 不允许保留设备名，且文件名不能以空格或点结尾
+    windows: bool,
+}
+
+const WINDOWS_FILE_NAME_RULES: FileNameRules = FileNameRules {
+    invalid_chars: WINDOWS_INVALID_CHARS,
+    windows: true,
+};
+
+const MACOS_FILE_NAME_RULES: FileNameRules = FileNameRules {
+    invalid_chars: &['/', ':'],
+    windows: false,
+};
+
+const UNIX_FILE_NAME_RULES: FileNameRules = FileNameRules {
+    invalid_chars: &['/'],
+    windows: false,
+};
+
+fn current_file_name_rules() -> FileNameRules {
+    if cfg!(target_os = "windows") {
+        WINDOWS_FILE_NAME_RULES
+    } else if cfg!(target_os = "macos") {
+        MACOS_FILE_NAME_RULES
+    } else {
+        UNIX_FILE_NAME_RULES
+    }
+}
+
+fn sanitize_file_name_with(name: &str, rules: FileNameRules) -> String {
+    let mut file_name = name
+        .chars()
+        .map(|c| {
+            if c.is_control() || rules.invalid_chars.contains(&c) {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect::<String>();
+
+    if rules.windows {
+        file_name = file_name.trim_end_matches([' ', '.']).to_string();
+
+        let stem = file_name.split('.').next().unwrap_or_default();
+        if WINDOWS_RESERVED_NAMES
+            .iter()
+            .any(|reserved| reserved.eq_ignore_ascii_case(stem.trim_end()))
+        {
+            file_name.insert(0, '_');
+        }
+    }
+
+    // 按字符边界截断到文件系统允许的长度
+    if file_name.len() > MAX_FILE_NAME_BYTES {
+        let mut end = MAX_FILE_NAME_BYTES;
+        while !file_name.is_char_boundary(end) {
+            end -= 1;
+        }
+        file_name.truncate(end);
+    }
+
+    file_name
+}
+
+/**
+ * 按当前系统的规则替换文件名中的非法字符
+ */
+pub fn sanitize_file_name(name: &str) -> String {
+    sanitize_file_name_with(name, current_file_name_rules())
+}
+
+/**
+ * 令牌的替换值作为单个文件名处理，不能包含路径分隔符
+ */
+fn sanitize_token_value(value: &str) -> String {
+    let value = value
+        .trim()
+        .chars()
+        .take(MAX_TOKEN_CHARS)
+        .collect::<String>()
+        .replace(['/', '\\'], "_");
+    let value = sanitize_file_name(&value);
+
+    if value.is_empty() {
+        UNKNOWN_TOKEN_VALUE.to_string()
+    } else {
+        value
+    }
+}
+
+/**
+ * 按 dayjs 的格式化规则格式化日期，如 YYYY-MM-DD_HH-mm-ss
+ */
+fn format_date_time(format: &str, date_time: &UtcDateTime) -> String {
+    let chars = format.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(format.len() + 8);
+
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        let run_len = chars[index..].iter().take_while(|&&next| next == c).count();
+        let run = &chars[index..index + run_len];
+        let hour_12 = match date_time.hour % 12 {
+            0 => 12,
+            hour => hour,
+        };
+
+        let formatted = match (c, run_len) {
+            ('Y', 4) => Some(format!("{:04}", date_time.year)),
+            ('Y', 2) => Some(format!("{:02}", date_time.year.rem_euclid(100))),
+            ('M', 1) => Some(date_time.month.to_string()),
+            ('M', 2) => Some(format!("{:02}", date_time.month)),
+            ('D', 1) => Some(date_time.day.to_string()),
+            ('D', 2) => Some(format!("{:02}", date_time.day)),
+            ('H', 1) => Some(date_time.hour.to_string()),
+            ('H', 2) => Some(format!("{:02}", date_time.hour)),
+            ('h', 1) => Some(hour_12.to_string()),
+            ('h', 2) => Some(format!("{:02}", hour_12)),
+            ('m', 1) => Some(date_time.minute.to_string()),
+            ('m', 2) => Some(format!("{:02}", date_time.minute)),
+            ('s', 1) => Some(date_time.second.to_string()),
+            ('s', 2) => Some(format!("{:02}", date_time.second)),
+            ('S', 1..=3) => Some(
+                format!("{:03}", date_time.millisecond)
+                    .chars()
+                    .take(run_len)
+                    .collect(),
+            ),
+            ('A', 1) => Some(String::from(if date_time.hour < 12 { "AM" } else { "PM" })),
+            ('a', 1) => Some(String::from(if date_time.hour < 12 { "am" } else { "pm" })),
+            _ => None,
+        };
+
+        match formatted {
+            Some(formatted) => result.push_str(&formatted),
+            None => result.extend(run),
+        }
+        index += run_len;
+    }
+
+    result
+}
+
+fn is_date_format(content: &str) -> bool {
+    !content.is_empty() && content.chars().all(|c| "YMDHhmsSAa-_:/. ".contains(c))
+}
+
+fn random_id(len: usize) -> String {
+    let mut result = String::with_capacity(len);
+    while result.len() < len {
+        // RandomState 每次创建时使用不同的随机种子
+        let mut bits = RandomState::new().build_hasher().finish();
+        for _ in 0..8 {
+            if result.len() >= len {
+                break;
+            }
+            result.push(RANDOM_CHARSET[(bits % RANDOM_CHARSET.len() as u64) as usize] as char);
+            bits /= RANDOM_CHARSET.len() as u64;
+        }
+    }
+    result
+}
+
+/**
+ * 将令牌的数字参数解析为长度，超出范围时使用默认值
+ */
+fn parse_token_len(argument: Option<&str>, default: usize, max: usize) -> usize {
+    argument
+        .and_then(|argument| argument.trim().parse::<usize>().ok())
+        .map(|len| len.min(max))
+        .unwrap_or(default)
+}
+
+/**
+ * 获取令牌的替换值，不支持的令牌返回 None
+ */
+fn render_token(
+    content: &str,
+    context: &FileNameTemplateContext,
+    date_time: &UtcDateTime,
+    counter: u32,
+) -> Option<String> {
+    if is_date_format(content) {
+        // 日期格式中的 / 作为目录分隔符保留
+        return Some(
+            format_date_time(content, date_time)
+                .split('/')
+                .map(sanitize_file_name)
+                .collect::<Vec<_>>()
+                .join("/"),
+        );
+    }
+
+    let (name, argument) = match content.split_once(':') {
+        Some((name, argument)) => (name.trim(), Some(argument)),
+        None => (content.trim(), None),
+    };
+
+    let value = match name {
+        "WINDOW_TITLE" => sanitize_token_value(context.window_title.as_deref().unwrap_or("")),
+        // FOCUS_WINDOW_APP_NAME 为旧版本的进程名称变量
+        "PROCESS_NAME" | "FOCUS_WINDOW_APP_NAME" => {
+            sanitize_token_value(context.process_name.as_deref().unwrap_or(""))
+        }
+        "MONITOR_INDEX" => match context.monitor_index {
+            Some(monitor_index) => monitor_index.to_string(),
+            None => UNKNOWN_TOKEN_VALUE.to_string(),
+        },
+        "CAPTURE_MODE" => match context.capture_mode {
+            Some(capture_mode) => capture_mode.as_str().to_string(),
+            None => UNKNOWN_TOKEN_VALUE.to_string(),
+        },
+        "COUNTER" => format!(
+            "{:0width$}",
+            counter,
+            width = parse_token_len(argument, 1, MAX_COUNTER_WIDTH)
+        ),
+        "RANDOM" => random_id(parse_token_len(
+            argument,
+            DEFAULT_RANDOM_LEN,
+            MAX_RANDOM_LEN,
+        )),
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/**
+ * 渲染文件名模板，模板中的 {{...}} 为令牌：
+ * - 日期格式，如 {{YYYY-MM-DD_HH-mm-ss}}
+ * - {{WINDOW_TITLE}}、{{PROCESS_NAME}}、{{MONITOR_INDEX}}、{{CAPTURE_MODE}}
+ * - {{COUNTER}} 每日计数，{{COUNTER:3}} 补零到 3 位
+ * - {{RANDOM}} 随机 ID，{{RANDOM:4}} 指定长度
+ *
+ * 不支持的令牌保持不变，令牌以外的内容不做处理
+ */
+pub fn render_file_name_template(
+    template: &str,
+    context: &FileNameTemplateContext,
+    counter: u32,
+) -> String {
+    let date_time = context.local_date_time();
+    let mut result = String::with_capacity(template.len() + 32);
+
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        result.push_str(&rest[..start]);
+
+        let after_start = &rest[start + 2..];
+        let Some(end) = after_start.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let content = &after_start[..end];
+        match render_token(content, context, &date_time, counter) {
+            Some(value) => result.push_str(&value),
+            None => result.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after_start[end + 2..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+fn template_uses_counter(template: &str) -> bool {
+    template.contains("{{COUNTER}}") || template.contains("{{COUNTER:")
+}
+
+fn get_daily_counter_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    match app_handle.path().app_data_dir() {
+        Ok(app_data_dir) => Ok(app_data_dir.join(DAILY_COUNTER_FILE_NAME)),
+        Err(e) => Err(format!(
+            "[get_daily_counter_path] Failed to get app data dir: {}",
+            e
+        )),
+    }
+}
+
+/**
+ * 读取日期对应的下一个计数，不更新计数文件
+ *
+ * 计数文件的内容为「日期 计数」，日期变化后从 1 重新开始
+ */
+fn read_daily_counter(counter_path: &Path, date_key: &str) -> u32 {
+    std::fs::read_to_string(counter_path)
+        .ok()
+        .and_then(|content| {
+            let (date, counter) = content.trim().split_once(' ')?;
+            (date == date_key).then(|| counter.parse::<u32>().ok())?
+        })
+        .unwrap_or(0)
+        .saturating_add(1)
+}
+
+/**
+ * 保存已使用的计数，计数文件中已有更大的计数时不更新
+ */
+fn write_daily_counter(counter_path: &Path, daily_counter: &DailyCounter) -> Result<(), String> {
+    let _guard = DAILY_COUNTER_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    if read_daily_counter(counter_path, &daily_counter.date_key) > daily_counter.counter {
+        return Ok(());
+    }
+
+    if let Some(parent_dir) = counter_path.parent()
+        && let Err(e) = std::fs::create_dir_all(parent_dir)
+    {
+        return Err(format!(
+            "[write_daily_counter] Failed to create directory {}: {}",
+            parent_dir.display(),
+            e
+        ));
+    }
+
+    if let Err(e) = std::fs::write(
+        counter_path,
+        format!("{} {}", daily_counter.date_key, daily_counter.counter),
+    ) {
+        return Err(format!(
+            "[write_daily_counter] Failed to save counter {}: {}",
+            counter_path.display(),
+            e
+        ));
+    }
+
+    Ok(())
+}
+
+/**
+ * 文件写入成功后提交渲染时使用的每日计数
+ */
+pub fn commit_daily_counter(
+    app_handle: &tauri::AppHandle,
+    daily_counter: &DailyCounter,
+) -> Result<(), String> {
+    write_daily_counter(&get_daily_counter_path(app_handle)?, daily_counter)
+}
+
+/**
+ * 文件已存在时在文件名后追加 _2、_3 等序号
+ */
+pub fn resolve_file_path_collision(file_path: PathBuf) -> PathBuf {
+    if !file_path.exists() {
+        return file_path;
+    }
+
+    let file_stem = file_path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let extension = file_path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_string());
+
+    for index in 2..MAX_COLLISION_ATTEMPTS {
+        let file_name = match &extension {
+            Some(extension) => format!("{}_{}.{}", file_stem, index, extension),
+            None => format!("{}_{}", file_stem, index),
+        };
+        let candidate = file_path.with_file_name(file_name);
+        if !candidate.exists() {
+            return candidate;
+        }
+    }
+
+    log::warn!(
+        "[resolve_file_path_collision] Too many files named {}",
+        file_path.display()
+    );
+    file_path
+}
+
+/**
+ * 渲染保存路径中的模板并避免覆盖已有文件
+ *
+ * 不更新每日计数，文件写入成功后调用 commit_daily_counter 提交
+ */
+pub fn resolve_file_path_template(
+    app_handle: &tauri::AppHandle,
+    path_template: &str,
+    context: &FileNameTemplateContext,
+) -> Result<ResolvedFilePath, String> {
+    let context = FileNameTemplateContext {
+        capture_time: Some(
+            context
+                .capture_time
+                .unwrap_or_else(current_timestamp_millis),
+        ),
+        ..context.clone()
+    };
+
+    let daily_counter = if template_uses_counter(path_template) {
+        let date_key = context.date_key();
+        Some(DailyCounter {
+            counter: read_daily_counter(&get_daily_counter_path(app_handle)?, &date_key),
+            date_key,
+        })
+    } else {
+        None
+    };
+
+    let file_path = render_file_name_template(
+        path_template,
+        &context,
+        daily_counter
+            .as_ref()
+            .map(|daily_counter| daily_counter.counter)
+            .unwrap_or(0),
+    );
+
+    Ok(ResolvedFilePath {
+        file_path: resolve_file_path_collision(Path::new(&file_path).to_path_buf()),
+        daily_counter,
+    })
+}
+
+/**
+ * 预览模板渲染的结果，不更新每日计数
+ */
+pub fn preview_file_name_template(
+    app_handle: &tauri::AppHandle,
+    template: &str,
+    context: &FileNameTemplateContext,
+) -> Result<String, String> {
+    let counter = if template_uses_counter(template) {
+        read_daily_counter(&get_daily_counter_path(app_handle)?, &context.date_key())
+    } else {
+        0
+    };
+
+    Ok(render_file_name_template(template, context, counter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_context() -> FileNameTemplateContext {
+        FileNameTemplateContext {
+            // 2024-02-29T23:59:58.042Z
+            capture_time: Some(1_709_251_198_042),
+            utc_offset_minutes: Some(8 * 60),
+            window_title: Some(String::from("a/b: <c>")),
+            process_name: Some(String::from("  ")),
+            monitor_index: Some(2),
+            capture_mode: Some(CaptureMode::FullScreen),
+        }
+    }
+
+    #[test]
+    fn test_render_date_time() {
+        let context = test_context();
+
+        assert_eq!(
+            render_file_name_template("SnowShot_{{YYYY-MM-DD_HH-mm-ss}}", &context, 0),
+            "SnowShot_2024-03-01_07-59-58"
+        );
+        assert_eq!(
+            render_file_name_template("{{YY/M/D}} {{h_mm_ss.SSS A}}", &context, 0),
+            "24/3/1 7_59_58.042 AM"
+        );
+    }
+
+    #[test]
+    fn test_render_tokens() {
+        let context = test_context();
+
+        assert_eq!(
+            render_file_name_template(
+                "{{CAPTURE_MODE}}_{{MONITOR_INDEX}}_{{COUNTER:3}}_{{PROCESS_NAME}}",
+                &context,
+                7
+            ),
+            "FullScreen_2_007_unknown"
+        );
+        assert_eq!(
+            render_file_name_template("{{FOCUS_WINDOW_APP_NAME}}/{{UNKNOWN}}{{", &context, 0),
+            "unknown/{{UNKNOWN}}{{"
+        );
+
+        let window_title = render_file_name_template("{{WINDOW_TITLE}}", &context, 0);
+        assert!(!window_title.contains('/'));
+        assert!(window_title.starts_with("a_b"));
+
+        let random = render_file_name_template("{{RANDOM:12}}", &context, 0);
+        assert_eq!(random.len(), 12);
+        assert!(random.bytes().all(|c| RANDOM_CHARSET.contains(&c)));
+    }
+
+    #[test]
+    fn test_sanitize_windows_file_name() {
+        let rules = WINDOWS_FILE_NAME_RULES;
+
+        assert_eq!(sanitize_file_name_with("a<b>:c?. ", rules), "a_b__c_");
+        assert_eq!(sanitize_file_name_with("con.png", rules), "_con.png");
+        assert_eq!(sanitize_file_name_with("console.png", rules), "console.png");
+        assert!(sanitize_file_name_with(&"长".repeat(200), rules).len() <= MAX_FILE_NAME_BYTES);
+
+        assert_eq!(
+            sanitize_file_name_with("a:b/c", MACOS_FILE_NAME_RULES),
+            "a_b_c"
+        );
+        assert_eq!(
+            sanitize_file_name_with("a:b/c. ", UNIX_FILE_NAME_RULES),
+            "a:b_c. "
+        );
+    }
+
+    #[test]
+    fn test_resolve_file_path_collision() {
+        let dir = std::env::temp_dir().join(format!("snow_shot_file_name_{}", random_id(8)));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let file_path = dir.join("capture.png");
+        assert_eq!(resolve_file_path_collision(file_path.clone()), file_path);
+
+        std::fs::write(&file_path, b"").unwrap();
+        std::fs::write(dir.join("capture_2.png"), b"").unwrap();
+        assert_eq!(
+            resolve_file_path_collision(file_path),
+            dir.join("capture_3.png")
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_daily_counter() {
+        let dir = std::env::temp_dir().join(format!("snow_shot_file_name_{}", random_id(8)));
+        let counter_path = dir.join(DAILY_COUNTER_FILE_NAME);
+        let daily_counter = |date_key: &str, counter| DailyCounter {
+            date_key: String::from(date_key),
+            counter,
+        };
+
+        // 只有提交后计数才会增加
+        assert_eq!(read_daily_counter(&counter_path, "2024-03-01"), 1);
+        assert_eq!(read_daily_counter(&counter_path, "2024-03-01"), 1);
+
+        write_daily_counter(&counter_path, &daily_counter("2024-03-01", 3)).unwrap();
+        assert_eq!(read_daily_counter(&counter_path, "2024-03-01"), 4);
+
+        // 较早渲染的计数不会覆盖较大的计数
+        write_daily_counter(&counter_path, &daily_counter("2024-03-01", 2)).unwrap();
+        assert_eq!(read_daily_counter(&counter_path, "2024-03-01"), 4);
+
+        assert_eq!(read_daily_counter(&counter_path, "2024-03-02"), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,870 @@
+use half::f16;
+use image::{DynamicImage, ImageFormat, Rgba32FImage, RgbaImage};
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::slice::{ParallelSlice, ParallelSliceMut};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::path::PathBuf;
+use tokio::fs;
+use zune_core::bit_depth::BitDepth;
+use zune_core::colorspace::ColorSpace;
+use zune_core::options::EncoderOptions;
+use zune_jpegxl::JxlSimpleEncoder;
+
+use crate::capture_metadata::{CaptureMetadata, embed_capture_metadata};
+use crate::hdr_tone_mapping::{HdrToneMapper, HdrToneMappingOptions};
+use crate::monitor_info::CorrectHdrColorAlgorithm;
+
+/// scRGB 中 1.0 对应的亮度（尼特）
+pub const SCRGB_REFERENCE_WHITE_NITS: f32 = 80.0;
+/// PQ 能表示的最大亮度（尼特）
+const PQ_MAX_NITS: f32 = 10000.0;
+/// HLG 参考显示器的峰值亮度（尼特）
+const HLG_NOMINAL_PEAK_NITS: f32 = 1000.0;
+/// BT.2408 建议的 HDR 参考白（尼特），HLG 输出时 SDR 白色映射到该亮度
+const HDR_REFERENCE_WHITE_NITS: f32 = 203.0;
+const HLG_SYSTEM_GAMMA: f32 = 1.2;
+
+/// Rgba16F 每个像素占 8 字节
+const RGBA16F_PIXEL_BYTE_COUNT: usize = 8;
+const RGBA_CHANNEL_COUNT: usize = 4;
+
+/// ITU-T H.273 中的编号
+const CICP_COLOR_PRIMARIES_BT2020: u8 = 9;
+const CICP_TRANSFER_FUNCTION_PQ: u8 = 16;
+const CICP_TRANSFER_FUNCTION_HLG: u8 = 18;
+const CICP_MATRIX_COEFFICIENTS_RGB: u8 = 0;
+
+/// JPEG XL 规范中颜色编码的编号
+const JXL_COLOUR_SPACE_RGB: u32 = 0;
+const JXL_WHITE_POINT_D65: u32 = 1;
+const JXL_PRIMARIES_BT2100: u32 = 9;
+const JXL_TRANSFER_FUNCTION_PQ: u32 = 16;
+const JXL_TRANSFER_FUNCTION_HLG: u32 = 18;
+const JXL_RENDERING_INTENT_RELATIVE: u32 = 1;
+const JXL_EXTRA_CHANNEL_ALPHA: u32 = 0;
+
+/// BT.709 到 BT.2020 的色域转换矩阵（BT.2087）
+const BT709_TO_BT2020: [[f32; 3]; 3] = [
+    [0.627_404, 0.329_282, 0.043_314],
+    [0.069_097, 0.919_540, 0.011_361],
+    [0.016_392, 0.088_013, 0.895_595],
+];
+
+/// HDR 图片保存为 PNG 和 JPEG XL 时使用的传递函数
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum HdrTransferFunction {
+    /// SMPTE ST 2084，保存屏幕上显示的绝对亮度
+    Pq,
+    /// ARIB STD-B67，SDR 白色映射到 203 尼特
+    Hlg,
+}
+
+/**
+ * 保留浮点数据的 HDR 截图
+ *
+ * 像素为 Windows Graphics Capture 输出的 scRGB 数据：
+ * 小端序的 Rgba16F，BT.709 色域，线性，1.0 对应 80 尼特
+ */
+pub struct HdrImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+    /// 显示器的 SDR 白色亮度（尼特）
+    sdr_white_nits: f32,
+}
+
+/// 将线性颜色值转换为 sRGB 编码值
+#[inline]
+fn linear_to_srgb(linear: f32) -> f32 {
+    let srgb = if linear <= 0.0031308 {
+        12.92 * linear
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    };
+
+    srgb.clamp(0.0, 1.0)
+}
+
+#[inline]
+fn bt709_to_bt2020(rgb: [f32; 3]) -> [f32; 3] {
+    BT709_TO_BT2020.map(|row| row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2])
+}
+
+/// SMPTE ST 2084 常量
+const PQ_M1: f32 = 2610.0 / 16384.0;
+const PQ_M2: f32 = 2523.0 / 4096.0 * 128.0;
+const PQ_C1: f32 = 3424.0 / 4096.0;
+const PQ_C2: f32 = 2413.0 / 4096.0 * 32.0;
+const PQ_C3: f32 = 2392.0 / 4096.0 * 32.0;
+
+/**
+ * PQ 的逆 EOTF，将绝对亮度（尼特）转换为 [0, 1] 的信号值
+ */
+#[inline]
+pub fn pq_inverse_eotf(nits: f32) -> f32 {
+    let y = (nits / PQ_MAX_NITS).clamp(0.0, 1.0).powf(PQ_M1);
+    ((PQ_C1 + PQ_C2 * y) / (1.0 + PQ_C3 * y)).powf(PQ_M2)
+}
+
+/**
+ * PQ 的 EOTF，将 [0, 1] 的信号值转换为绝对亮度（尼特）
+ */
+#[inline]
+pub fn pq_eotf(signal: f32) -> f32 {
+    let e = signal.clamp(0.0, 1.0).powf(1.0 / PQ_M2);
+    ((e - PQ_C1).max(0.0) / (PQ_C2 - PQ_C3 * e)).powf(1.0 / PQ_M1) * PQ_MAX_NITS
+}
+
+/**
+ * HLG 的 OETF，将 [0, 1] 的场景线性值转换为 [0, 1] 的信号值
+ */
+#[inline]
+pub fn hlg_oetf(scene_linear: f32) -> f32 {
+    const A: f32 = 0.178_832_77;
+    const B: f32 = 0.284_668_92;
+    const C: f32 = 0.559_910_7;
+
+    let e = scene_linear.clamp(0.0, 1.0);
+    if e <= 1.0 / 12.0 {
+        (3.0 * e).sqrt()
+    } else {
+        A * (12.0 * e - B).ln() + C
+    }
+}
+
+/**
+ * 将 BT.2020 色域的显示亮度（尼特）转换为 HLG 信号值
+ *
+ * 先通过逆 OOTF 还原为场景线性值，参考显示器峰值亮度为 1000 尼特
+ */
+#[inline]
+fn hlg_encode_display_light(rgb_nits: [f32; 3]) -> [f32; 3] {
+    let display = rgb_nits.map(|nits| (nits / HLG_NOMINAL_PEAK_NITS).clamp(0.0, 1.0));
+    let luminance = 0.2627 * display[0] + 0.6780 * display[1] + 0.0593 * display[2];
+    if luminance <= 0.0 {
+        return [0.0; 3];
+    }
+
+    let scene_scale = luminance.powf((1.0 - HLG_SYSTEM_GAMMA) / HLG_SYSTEM_GAMMA);
+    display.map(|value| hlg_oetf(value * scene_scale))
+}
+
+#[inline]
+fn to_u16_sample(value: f32) -> u16 {
+    (value.clamp(0.0, 1.0) * u16::MAX as f32).round() as u16
+}
+
+/**
+ * 按 JPEG XL 的位序（低位在前）写入码流
+ */
+#[derive(Default)]
+struct JxlBitWriter {
+    data: Vec<u8>,
+    bit_count: usize,
+}
+
+impl JxlBitWriter {
+    fn put_bits(&mut self, bit_count: usize, value: u32) {
+        for bit in 0..bit_count {
+            if self.bit_count.is_multiple_of(8) {
+                self.data.push(0);
+            }
+            if (value >> bit) & 1 == 1 {
+                *self.data.last_mut().unwrap() |= 1 << (self.bit_count % 8);
+            }
+            self.bit_count += 1;
+        }
+    }
+
+    /// 写入 Enum 字段，编码为 U32(Val(0), Val(1), BitsOffset(4, 2), BitsOffset(6, 18))
+    fn put_enum(&mut self, value: u32) {
+        match value {
+            0 | 1 => self.put_bits(2, value),
+            2..18 => {
+                self.put_bits(2, 2);
+                self.put_bits(4, value - 2);
+            }
+            _ => {
+                self.put_bits(2, 3);
+                self.put_bits(6, value - 18);
+            }
+        }
+    }
+
+    /// 写入 SizeHeader 中的尺寸，编码为 U32(Bits(9), Bits(13), Bits(18), Bits(30)) + 1
+    fn put_size(&mut self, size: u32) {
+        let value = size - 1;
+        let (selector, bit_count) = match value {
+            0..0x200 => (0, 9),
+            0x200..0x2000 => (1, 13),
+            0x2000..0x40000 => (2, 18),
+            _ => (3, 30),
+        };
+        self.put_bits(2, selector);
+        self.put_bits(bit_count, value);
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+/**
+ * 生成 16 位 RGBA 图片的 JPEG XL 图像头，和 zune-jpegxl 写入的字段一致
+ *
+ * transfer_function 为空时颜色编码为默认的 sRGB，否则为 BT.2100 PQ / HLG，
+ * 同时在色调映射中写入传递函数对应的峰值亮度
+ */
+fn jxl_image_header(
+    width: u32,
+    height: u32,
+    transfer_function: Option<HdrTransferFunction>,
+) -> Vec<u8> {
+    let mut writer = JxlBitWriter::default();
+    writer.put_bits(16, 0x0AFF);
+
+    // SizeHeader
+    writer.put_bits(1, 0);
+    writer.put_size(height);
+    writer.put_bits(3, 0);
+    writer.put_size(width);
+
+    // ImageMetadata，HDR 时通过 extra_fields 写入色调映射
+    writer.put_bits(1, 0);
+    writer.put_bits(1, transfer_function.is_some() as u32);
+    if transfer_function.is_some() {
+        // 方向、固有尺寸、预览图、动画
+        writer.put_bits(3, 0);
+        writer.put_bits(1, 0);
+        writer.put_bits(1, 0);
+        writer.put_bits(1, 0);
+    }
+    // 16 位整数
+    writer.put_bits(1, 0);
+    writer.put_bits(2, 3);
+    writer.put_bits(6, 15);
+    writer.put_bits(1, 0);
+    // 一个 alpha 通道，zune-jpegxl 写入默认参数（8 位），HDR 时改为 16 位
+    writer.put_bits(2, 1);
+    if transfer_function.is_some() {
+        writer.put_bits(1, 0);
+        writer.put_enum(JXL_EXTRA_CHANNEL_ALPHA);
+        writer.put_bits(1, 0);
+        writer.put_bits(2, 3);
+        writer.put_bits(6, 15);
+        // dim_shift、名称长度、非预乘
+        writer.put_bits(2, 0);
+        writer.put_bits(2, 0);
+        writer.put_bits(1, 0);
+    } else {
+        writer.put_bits(1, 1);
+    }
+    // 不使用 XYB
+    writer.put_bits(1, 0);
+
+    match transfer_function {
+        None => writer.put_bits(1, 1),
+        Some(transfer_function) => {
+            writer.put_bits(1, 0);
+            writer.put_bits(1, 0);
+            writer.put_enum(JXL_COLOUR_SPACE_RGB);
+            writer.put_enum(JXL_WHITE_POINT_D65);
+            writer.put_enum(JXL_PRIMARIES_BT2100);
+            writer.put_bits(1, 0);
+            writer.put_enum(match transfer_function {
+                HdrTransferFunction::Pq => JXL_TRANSFER_FUNCTION_PQ,
+                HdrTransferFunction::Hlg => JXL_TRANSFER_FUNCTION_HLG,
+            });
+            writer.put_enum(JXL_RENDERING_INTENT_RELATIVE);
+
+            // ToneMapping，intensity_target 为信号最大值对应的亮度
+            let intensity_target = match transfer_function {
+                HdrTransferFunction::Pq => PQ_MAX_NITS,
+                HdrTransferFunction::Hlg => HLG_NOMINAL_PEAK_NITS,
+            };
+            writer.put_bits(1, 0);
+            writer.put_bits(16, f16::from_f32(intensity_target).to_bits() as u32);
+            writer.put_bits(16, 0);
+            writer.put_bits(1, 0);
+            writer.put_bits(16, 0);
+        }
+    }
+    // 没有扩展，默认的 CustomTransformData
+    writer.put_bits(2, 0);
+    writer.put_bits(1, 1);
+
+    writer.finish()
+}
+
+impl HdrImage {
+    /**
+     * pixels 为小端序的 Rgba16F 数据，sdr_white_nits 为显示器的 SDR 白色亮度
+     */
+    pub fn new(
+        width: u32,
+        height: u32,
+        pixels: Vec<u8>,
+        sdr_white_nits: f32,
+    ) -> Result<Self, String> {
+        if pixels.len() != width as usize * height as usize * RGBA16F_PIXEL_BYTE_COUNT {
+            return Err(format!(
+                "[HdrImage::new] Invalid pixel buffer size: {} for {}x{}",
+                pixels.len(),
+                width,
+                height
+            ));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            pixels,
+            sdr_white_nits: if sdr_white_nits > 0.0 {
+                sdr_white_nits
+            } else {
+                SCRGB_REFERENCE_WHITE_NITS
+            },
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn sdr_white_nits(&self) -> f32 {
+        self.sdr_white_nits
+    }
+
+    /**
+     * RGBA16F 像素数据
+     */
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// 读取一个像素的 scRGB 值
+    #[inline]
+    fn read_pixel(pixel: &[u8]) -> [f32; 4] {
+        std::array::from_fn(|channel| {
+            f16::from_bits(u16::from_le_bytes([
+                pixel[channel * 2],
+                pixel[channel * 2 + 1],
+            ]))
+            .to_f32()
+        })
+    }
+
+    /**
+     * 按像素并行转换，convert 接收 scRGB 像素，写入 channel_count 个输出值
+     */
+    fn convert_pixels<T: Copy + Default + Send + Sync>(
+        &self,
+        channel_count: usize,
+        convert: impl Fn([f32; 4], &mut [T]) + Send + Sync,
+    ) -> Vec<T> {
+        let mut output =
+            vec![T::default(); self.width as usize * self.height as usize * channel_count];
+
+        output
+            .par_chunks_exact_mut(channel_count)
+            .zip(self.pixels.par_chunks_exact(RGBA16F_PIXEL_BYTE_COUNT))
+            .for_each(|(target, source)| convert(Self::read_pixel(source), target));
+
+        output
+    }
+
+    /**
+     * 通过色调映射转换为 8 位 sRGB 图片，用于写入剪贴板等 SDR 场景
+     */
+    pub fn to_sdr_image(
+        &self,
+        algorithm: CorrectHdrColorAlgorithm,
+        tone_mapping: HdrToneMappingOptions,
+    ) -> Result<DynamicImage, String> {
+        let tone_mapper = HdrToneMapper::new(algorithm, tone_mapping, self.sdr_white_nits);
+        let pixels = self.convert_pixels::<u8>(RGBA_CHANNEL_COUNT, |pixel, target| {
+            let rgb = tone_mapper.map([pixel[0], pixel[1], pixel[2]]);
+            for channel in 0..3 {
+                target[channel] = (linear_to_srgb(rgb[channel]) * 255.0) as u8;
+            }
+            target[3] = (pixel[3].clamp(0.0, 1.0) * 255.0) as u8;
+        });
+
+        match RgbaImage::from_raw(self.width, self.height, pixels) {
+            Some(image) => Ok(DynamicImage::ImageRgba8(image)),
+            None => Err(String::from(
+                "[HdrImage::to_sdr_image] Failed to create rgba8 image",
+            )),
+        }
+    }
+
+    /**
+     * 转换为 BT.2020 色域的 16 位信号值（RGBA），alpha 保持线性
+     */
+    pub fn to_bt2020_rgba16(&self, transfer_function: HdrTransferFunction) -> Vec<u16> {
+        let hlg_scale = HDR_REFERENCE_WHITE_NITS / self.sdr_white_nits;
+
+        self.convert_pixels::<u16>(RGBA_CHANNEL_COUNT, |pixel, target| {
+            let rgb_nits = bt709_to_bt2020([pixel[0], pixel[1], pixel[2]])
+                .map(|value| value.max(0.0) * SCRGB_REFERENCE_WHITE_NITS);
+
+            let signal = match transfer_function {
+                HdrTransferFunction::Pq => rgb_nits.map(pq_inverse_eotf),
+                HdrTransferFunction::Hlg => {
+                    hlg_encode_display_light(rgb_nits.map(|nits| nits * hlg_scale))
+                }
+            };
+
+            for channel in 0..3 {
+                target[channel] = to_u16_sample(signal[channel]);
+            }
+            target[3] = to_u16_sample(pixel[3]);
+        })
+    }
+
+    /**
+     * 编码为 16 位 PNG，通过 cICP 块标记 BT.2020 色域和 PQ / HLG 传递函数
+     */
+    pub fn encode_png16(&self, transfer_function: HdrTransferFunction) -> Result<Vec<u8>, String> {
+        let samples = self.to_bt2020_rgba16(transfer_function);
+        // PNG 使用大端序
+        let image_data = samples
+            .iter()
+            .flat_map(|sample| sample.to_be_bytes())
+            .collect::<Vec<u8>>();
+
+        let mut buf = Vec::new();
+        let mut encoder = png::Encoder::new(&mut buf, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Sixteen);
+        encoder.set_compression(png::Compression::Balanced);
+
+        let mut writer = match encoder.write_header() {
+            Ok(writer) => writer,
+            Err(e) => {
+                return Err(format!(
+                    "[HdrImage::encode_png16] Failed to write header: {}",
+                    e
+                ));
+            }
+        };
+
+        // cICP 必须位于 IDAT 之前
+        let cicp = [
+            CICP_COLOR_PRIMARIES_BT2020,
+            match transfer_function {
+                HdrTransferFunction::Pq => CICP_TRANSFER_FUNCTION_PQ,
+                HdrTransferFunction::Hlg => CICP_TRANSFER_FUNCTION_HLG,
+            },
+            CICP_MATRIX_COEFFICIENTS_RGB,
+            // 全范围
+            1,
+        ];
+        if let Err(e) = writer.write_chunk(png::chunk::cICP, &cicp) {
+            return Err(format!(
+                "[HdrImage::encode_png16] Failed to write cICP chunk: {}",
+                e
+            ));
+        }
+
+        if let Err(e) = writer.write_image_data(&image_data) {
+            return Err(format!(
+                "[HdrImage::encode_png16] Failed to write image data: {}",
+                e
+            ));
+        }
+
+        if let Err(e) = writer.finish() {
+            return Err(format!("[HdrImage::encode_png16] Failed to finish: {}", e));
+        }
+
+        Ok(buf)
+    }
+
+    /**
+     * 编码为 16 位 JPEG XL，颜色编码为 BT.2100 PQ / HLG，像素和 16 位 PNG 相同
+     *
+     * JPEG XL 编码器只能标记 sRGB，编码后替换码流开头的图像头
+     */
+    pub fn encode_jxl16(
+        &self,
+        transfer_function: HdrTransferFunction,
+        effort: u8,
+    ) -> Result<Vec<u8>, String> {
+        let samples = self.to_bt2020_rgba16(transfer_function);
+        // 16 位数据按本机字节序传给编码器
+        let image_data = samples
+            .iter()
+            .flat_map(|sample| sample.to_ne_bytes())
+            .collect::<Vec<u8>>();
+
+        let encoder = JxlSimpleEncoder::new(
+            &image_data,
+            EncoderOptions::new(
+                self.width as usize,
+                self.height as usize,
+                ColorSpace::RGBA,
+                BitDepth::Sixteen,
+            )
+            .set_effort(effort.clamp(1, 9)),
+        );
+
+        let buf = match encoder.encode() {
+            Ok(buf) => buf,
+            Err(e) => {
+                return Err(format!(
+                    "[HdrImage::encode_jxl16] Failed to encode image: {:?}",
+                    e
+                ));
+            }
+        };
+
+        // 图像头之后按字节对齐，帧数据可以直接拼接
+        let srgb_header = jxl_image_header(self.width, self.height, None);
+        let Some(frame_data) = buf.strip_prefix(srgb_header.as_slice()) else {
+            return Err(String::from(
+                "[HdrImage::encode_jxl16] Unexpected image header",
+            ));
+        };
+
+        let mut jxl_data = jxl_image_header(self.width, self.height, Some(transfer_function));
+        jxl_data.extend_from_slice(frame_data);
+
+        Ok(jxl_data)
+    }
+
+    /**
+     * 编码为 OpenEXR，原样保存 scRGB 浮点数据（1.0 对应 80 尼特）
+     */
+    pub fn encode_exr(&self) -> Result<Vec<u8>, String> {
+        let pixels = self.convert_pixels::<f32>(RGBA_CHANNEL_COUNT, |pixel, target| {
+            target.copy_from_slice(&pixel);
+        });
+
+        let image = match Rgba32FImage::from_raw(self.width, self.height, pixels) {
+            Some(image) => DynamicImage::ImageRgba32F(image),
+            None => {
+                return Err(String::from(
+                    "[HdrImage::encode_exr] Failed to create rgba32f image",
+                ));
+            }
+        };
+
+        let mut buf = Cursor::new(Vec::new());
+        match image.write_to(&mut buf, ImageFormat::OpenExr) {
+            Ok(_) => Ok(buf.into_inner()),
+            Err(e) => Err(format!(
+                "[HdrImage::encode_exr] Failed to encode image: {}",
+                e
+            )),
+        }
+    }
+}
+
+/**
+ * 保存 HDR 图片，按扩展名选择格式：
+ * png 为 16 位 PNG，jxl 为 16 位 JPEG XL，exr 为 OpenEXR
+ *
+ * 其他格式使用用户设置的色调映射算法转换为 SDR 图片后保存
+ */
+pub async fn save_hdr_image_to_file(
+    image: &HdrImage,
+    file_path: PathBuf,
+    transfer_function: HdrTransferFunction,
+    correct_hdr_color_algorithm: CorrectHdrColorAlgorithm,
+    hdr_tone_mapping: HdrToneMappingOptions,
+    encode_options: &crate::EncodeOptions,
+    capture_metadata: Option<&CaptureMetadata>,
+) -> Result<(), String> {
+    let extension = file_path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let image_data = match extension.as_str() {
+        "png" => {
+            let image_data = image.encode_png16(transfer_function)?;
+            match capture_metadata {
+                Some(capture_metadata) => {
+                    embed_capture_metadata(image_data, crate::ImageEncoder::Png, capture_metadata)?
+                }
+                None => image_data,
+            }
+        }
+        "jxl" => {
+            if encode_options.lossless == Some(false) {
+                return Err(String::from(
+                    "[save_hdr_image_to_file] Lossy JPEG XL is not supported",
+                ));
+            }
+
+            image.encode_jxl16(transfer_function, encode_options.effort.unwrap_or(4))?
+        }
+        "exr" => image.encode_exr()?,
+        _ => {
+            log::warn!(
+                "[save_hdr_image_to_file] HDR is not supported for {}, saving as SDR",
+                file_path.display()
+            );
+
+            let sdr_image = image.to_sdr_image(correct_hdr_color_algorithm, hdr_tone_mapping)?;
+
+            return crate::save_image_to_file(
+                &sdr_image,
+                file_path,
+                encode_options,
+                None,
+                capture_metadata,
+            )
+            .await;
+        }
+    };
+
+    if let Some(parent_dir) = file_path.parent()
+        && let Err(e) = fs::create_dir_all(parent_dir).await
+    {
+        return Err(format!(
+            "[save_hdr_image_to_file] Failed to create directory {}: {}",
+            parent_dir.display(),
+            e
+        ));
+    }
+
+    match fs::write(&file_path, image_data).await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!(
+            "[save_hdr_image_to_file] Failed to save image to file: {} {}",
+            e,
+            file_path.display(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 生成所有像素都相同的 Rgba16F 图片
+    fn solid_image(width: u32, height: u32, rgba: [f32; 4], sdr_white_nits: f32) -> HdrImage {
+        let pixel = rgba
+            .iter()
+            .flat_map(|value| f16::from_f32(*value).to_bits().to_le_bytes())
+            .collect::<Vec<u8>>();
+        let pixels = pixel.repeat(width as usize * height as usize);
+
+        HdrImage::new(width, height, pixels, sdr_white_nits).unwrap()
+    }
+
+    fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "actual: {}, expected: {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_new_rejects_invalid_buffer() {
+        assert!(HdrImage::new(2, 2, vec![0; 8], 80.0).is_err());
+    }
+
+    #[test]
+    fn test_pq_reference_values() {
+        assert_close(pq_inverse_eotf(0.0), 0.0, 1e-6);
+        assert_close(pq_inverse_eotf(10000.0), 1.0, 1e-5);
+        // BT.2408 中 100 尼特约为 0.508，203 尼特约为 0.58
+        assert_close(pq_inverse_eotf(100.0), 0.508, 0.002);
+        assert_close(pq_inverse_eotf(203.0), 0.58, 0.002);
+        assert_close(pq_inverse_eotf(1000.0), 0.752, 0.002);
+    }
+
+    #[test]
+    fn test_hlg_reference_values() {
+        assert_close(hlg_oetf(0.0), 0.0, 1e-6);
+        assert_close(hlg_oetf(1.0 / 12.0), 0.5, 1e-5);
+        assert_close(hlg_oetf(1.0), 1.0, 1e-5);
+    }
+
+    #[test]
+    fn test_hlg_reference_white() {
+        // SDR 白色映射到 203 尼特，BT.2408 中 HLG 参考白约为 75% 信号
+        let image = solid_image(1, 1, [2.5, 2.5, 2.5, 1.0], 200.0);
+        let samples = image.to_bt2020_rgba16(HdrTransferFunction::Hlg);
+
+        for sample in &samples[..3] {
+            assert_close(*sample as f32 / u16::MAX as f32, 0.75, 0.01);
+        }
+        assert_eq!(samples[3], u16::MAX);
+    }
+
+    #[test]
+    fn test_bt2020_keeps_white_neutral() {
+        let image = solid_image(1, 1, [1.0, 1.0, 1.0, 1.0], 80.0);
+        let samples = image.to_bt2020_rgba16(HdrTransferFunction::Pq);
+
+        let expected = to_u16_sample(pq_inverse_eotf(80.0));
+        for sample in &samples[..3] {
+            assert!(sample.abs_diff(expected) <= 2);
+        }
+    }
+
+    #[test]
+    fn test_pq_keeps_highlights() {
+        // SDR 白色为 200 尼特时，2.5 为 SDR 白色，12.5 为 1000 尼特的高光
+        let white = solid_image(1, 1, [2.5, 2.5, 2.5, 1.0], 200.0)
+            .to_bt2020_rgba16(HdrTransferFunction::Pq);
+        let highlight = solid_image(1, 1, [12.5, 12.5, 12.5, 1.0], 200.0)
+            .to_bt2020_rgba16(HdrTransferFunction::Pq);
+
+        assert!(highlight[0] > white[0]);
+        assert_close(highlight[0] as f32 / u16::MAX as f32, 0.752, 0.003);
+    }
+
+    #[test]
+    fn test_sdr_image_clips_highlights() {
+        let image = solid_image(2, 2, [12.5, 0.0, 2.5, 0.5], 200.0);
+        let sdr_image = image
+            .to_sdr_image(
+                CorrectHdrColorAlgorithm::Linear,
+                HdrToneMappingOptions::default(),
+            )
+            .unwrap()
+            .to_rgba8();
+
+        for pixel in sdr_image.pixels() {
+            assert_eq!(pixel.0[0], 255);
+            assert_eq!(pixel.0[1], 0);
+            assert!(pixel.0[2] >= 254);
+            assert_eq!(pixel.0[3], 127);
+        }
+    }
+
+    #[test]
+    fn test_negative_values_are_clamped() {
+        // scRGB 中超出 BT.709 色域的颜色为负值
+        let image = solid_image(1, 1, [-0.5, 1.0, -0.1, 1.0], 80.0);
+        let samples = image.to_bt2020_rgba16(HdrTransferFunction::Pq);
+
+        assert!(samples[1] > 0);
+        assert_eq!(
+            image
+                .to_sdr_image(
+                    CorrectHdrColorAlgorithm::Linear,
+                    HdrToneMappingOptions::default(),
+                )
+                .unwrap()
+                .to_rgba8()
+                .get_pixel(0, 0)
+                .0[0],
+            0
+        );
+    }
+
+    #[test]
+    fn test_encode_png16_writes_cicp() {
+        let image = solid_image(4, 3, [2.5, 1.0, 0.5, 1.0], 200.0);
+        let png_data = image.encode_png16(HdrTransferFunction::Pq).unwrap();
+
+        let decoder = png::Decoder::new(Cursor::new(&png_data));
+        let mut reader = decoder.read_info().unwrap();
+        let info = reader.info();
+        assert_eq!(info.bit_depth, png::BitDepth::Sixteen);
+        assert_eq!(
+            info.coding_independent_code_points,
+            Some(png::CodingIndependentCodePoints {
+                color_primaries: CICP_COLOR_PRIMARIES_BT2020,
+                transfer_function: CICP_TRANSFER_FUNCTION_PQ,
+                matrix_coefficients: CICP_MATRIX_COEFFICIENTS_RGB,
+                is_video_full_range_image: true,
+            })
+        );
+
+        let mut buf = vec![0; reader.output_buffer_size().unwrap()];
+        reader.next_frame(&mut buf).unwrap();
+        let samples = image.to_bt2020_rgba16(HdrTransferFunction::Pq);
+        assert_eq!(u16::from_be_bytes([buf[0], buf[1]]), samples[0]);
+    }
+
+    #[test]
+    fn test_encode_jxl16_round_trip() {
+        // 每个像素的颜色不同，检查解码后的像素位置和数值
+        let pixels = (0..5 * 3)
+            .flat_map(|index| {
+                let value = index as f32 * 0.75;
+                [value, 1.0, 12.5 - value, 1.0 - index as f32 / 30.0]
+            })
+            .flat_map(|value| f16::from_f32(value).to_bits().to_le_bytes())
+            .collect::<Vec<u8>>();
+        let image = HdrImage::new(5, 3, pixels, 200.0).unwrap();
+
+        for (transfer_function, cicp_transfer_function, hdr_type, intensity_target) in [
+            (
+                HdrTransferFunction::Pq,
+                CICP_TRANSFER_FUNCTION_PQ,
+                jxl_oxide::HdrType::Pq,
+                PQ_MAX_NITS,
+            ),
+            (
+                HdrTransferFunction::Hlg,
+                CICP_TRANSFER_FUNCTION_HLG,
+                jxl_oxide::HdrType::Hlg,
+                HLG_NOMINAL_PEAK_NITS,
+            ),
+        ] {
+            let jxl_data = image.encode_jxl16(transfer_function, 4).unwrap();
+            let jxl_image = jxl_oxide::JxlImage::builder()
+                .read(Cursor::new(jxl_data))
+                .unwrap();
+
+            assert_eq!((jxl_image.width(), jxl_image.height()), (5, 3));
+            assert_eq!(
+                jxl_image.rendered_cicp(),
+                Some([
+                    CICP_COLOR_PRIMARIES_BT2020,
+                    cicp_transfer_function,
+                    CICP_MATRIX_COEFFICIENTS_RGB,
+                    1,
+                ])
+            );
+            assert_eq!(jxl_image.hdr_type(), Some(hdr_type));
+            assert_eq!(
+                jxl_image
+                    .image_header()
+                    .metadata
+                    .tone_mapping
+                    .intensity_target,
+                intensity_target
+            );
+
+            let frame_buffer = jxl_image.render_frame(0).unwrap().image_all_channels();
+            assert_eq!(frame_buffer.channels(), RGBA_CHANNEL_COUNT);
+
+            // 无损编码，解码后的像素和 16 位 PNG 相同
+            let samples = image.to_bt2020_rgba16(transfer_function);
+            assert_eq!(frame_buffer.buf().len(), samples.len());
+            for (decoded, sample) in frame_buffer.buf().iter().zip(samples) {
+                assert_close(*decoded, sample as f32 / u16::MAX as f32, 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_exr_keeps_float_values() {
+        let image = solid_image(3, 2, [12.5, 0.25, -0.125, 1.0], 200.0);
+        let exr_data = image.encode_exr().unwrap();
+
+        let decoded = image::load_from_memory_with_format(&exr_data, ImageFormat::OpenExr)
+            .unwrap()
+            .to_rgba32f();
+        let pixel = decoded.get_pixel(2, 1).0;
+        assert_close(pixel[0], 12.5, 1e-3);
+        assert_close(pixel[1], 0.25, 1e-3);
+        assert_close(pixel[2], -0.125, 1e-3);
+    }
+}
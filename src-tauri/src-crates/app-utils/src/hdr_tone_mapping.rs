@@ -0,0 +1,297 @@
+use serde::{Deserialize, Serialize};
+
+use crate::hdr_image::{SCRGB_REFERENCE_WHITE_NITS, pq_eotf, pq_inverse_eotf};
+use crate::monitor_info::CorrectHdrColorAlgorithm;
+
+/// 未指定峰值亮度时使用的 HDR 内容峰值亮度（尼特）
+const DEFAULT_PEAK_NITS: f32 = 1000.0;
+
+/// Hable（Uncharted 2）曲线参数
+const HABLE_SHOULDER_STRENGTH: f32 = 0.15;
+const HABLE_LINEAR_STRENGTH: f32 = 0.50;
+const HABLE_LINEAR_ANGLE: f32 = 0.10;
+const HABLE_TOE_STRENGTH: f32 = 0.20;
+const HABLE_TOE_NUMERATOR: f32 = 0.02;
+const HABLE_TOE_DENOMINATOR: f32 = 0.30;
+const HABLE_EXPOSURE_BIAS: f32 = 2.0;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Copy, PartialEq, Default)]
+#[serde(default)]
+pub struct HdrToneMappingOptions {
+    /// 映射到白色的内容峰值亮度（尼特），为空时为 1000 尼特
+    pub peak_nits: Option<f32>,
+    /// 映射到 SDR 白色的亮度（尼特），为空时使用显示器的 SDR 白色亮度
+    pub paper_white_nits: Option<f32>,
+}
+
+/**
+ * 将 scRGB 线性值映射到 [0, 1] 的 SDR 线性值
+ *
+ * 在 rayon 的逐像素转换中使用，所有参数在创建时预先计算
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct HdrToneMapper {
+    algorithm: CorrectHdrColorAlgorithm,
+    /// scRGB 值转换为相对纸白亮度的缩放
+    input_scale: f32,
+    /// 峰值亮度相对纸白亮度的倍数
+    relative_peak: f32,
+    /// Reinhard 中 1 / relative_peak²
+    reinhard_inverse_white_squared: f32,
+    /// ACES 和 Hable 中峰值亮度的曲线值，用于归一化
+    curve_white: f32,
+    paper_white_nits: f32,
+    /// BT.2390 中纸白和峰值亮度的 PQ 信号值
+    pq_paper_white: f32,
+    pq_peak: f32,
+}
+
+#[inline]
+fn aces_filmic(x: f32) -> f32 {
+    // Krzysztof Narkowicz 的 ACES 拟合曲线
+    (x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14)
+}
+
+#[inline]
+fn hable(x: f32) -> f32 {
+    const A: f32 = HABLE_SHOULDER_STRENGTH;
+    const B: f32 = HABLE_LINEAR_STRENGTH;
+    const C: f32 = HABLE_LINEAR_ANGLE;
+    const D: f32 = HABLE_TOE_STRENGTH;
+    const E: f32 = HABLE_TOE_NUMERATOR;
+    const F: f32 = HABLE_TOE_DENOMINATOR;
+
+    ((x * (A * x + C * B) + D * E) / (x * (A * x + B) + D * F)) - E / F
+}
+
+/**
+ * BT.2390 EETF，source 和 target_max 均为归一化到源峰值的 PQ 信号值
+ */
+#[inline]
+fn bt2390_eetf(source: f32, target_max: f32) -> f32 {
+    let knee_start = 1.5 * target_max - 0.5;
+    // 峰值不高于纸白时没有需要压缩的高光，同时避免下面除以 0
+    if knee_start >= 1.0 {
+        return source.min(target_max);
+    }
+    if source < knee_start {
+        return source;
+    }
+
+    // 高光部分使用 Hermite 样条压缩到 target_max
+    let t = (source - knee_start) / (1.0 - knee_start);
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    (2.0 * t3 - 3.0 * t2 + 1.0) * knee_start
+        + (t3 - 2.0 * t2 + t) * (1.0 - knee_start)
+        + (-2.0 * t3 + 3.0 * t2) * target_max
+}
+
+impl HdrToneMapper {
+    /**
+     * sdr_white_nits 为显示器的 SDR 白色亮度，未指定纸白亮度时使用
+     */
+    pub fn new(
+        algorithm: CorrectHdrColorAlgorithm,
+        options: HdrToneMappingOptions,
+        sdr_white_nits: f32,
+    ) -> Self {
+        let paper_white_nits = options
+            .paper_white_nits
+            .filter(|nits| *nits > 0.0)
+            .unwrap_or(sdr_white_nits)
+            .max(1.0);
+        let peak_nits = options
+            .peak_nits
+            .unwrap_or(DEFAULT_PEAK_NITS)
+            .max(paper_white_nits);
+        let relative_peak = peak_nits / paper_white_nits;
+
+        let curve_white = match algorithm {
+            CorrectHdrColorAlgorithm::AcesFilmic => aces_filmic(relative_peak),
+            CorrectHdrColorAlgorithm::Hable => hable(relative_peak * HABLE_EXPOSURE_BIAS),
+            _ => 1.0,
+        };
+
+        Self {
+            algorithm,
+            input_scale: SCRGB_REFERENCE_WHITE_NITS / paper_white_nits,
+            relative_peak,
+            reinhard_inverse_white_squared: 1.0 / (relative_peak * relative_peak),
+            curve_white,
+            paper_white_nits,
+            pq_paper_white: pq_inverse_eotf(paper_white_nits),
+            pq_peak: pq_inverse_eotf(peak_nits),
+        }
+    }
+
+    /**
+     * 按最大通道压缩亮度，保持色相
+     */
+    #[inline]
+    fn map_by_max_channel(rgb: [f32; 3], map: impl Fn(f32) -> f32) -> [f32; 3] {
+        let max_channel = rgb[0].max(rgb[1]).max(rgb[2]);
+        if max_channel <= 0.0 {
+            return [0.0; 3];
+        }
+
+        let scale = map(max_channel) / max_channel;
+        rgb.map(|value| value * scale)
+    }
+
+    /**
+     * 将 scRGB 线性值（1.0 对应 80 尼特）映射到 SDR 线性值，1.0 为 SDR 白色
+     */
+    #[inline]
+    pub fn map(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let rgb = rgb.map(|value| (value * self.input_scale).max(0.0));
+
+        match self.algorithm {
+            CorrectHdrColorAlgorithm::None | CorrectHdrColorAlgorithm::Linear => rgb,
+            CorrectHdrColorAlgorithm::Reinhard => Self::map_by_max_channel(rgb, |value| {
+                (value * (1.0 + value * self.reinhard_inverse_white_squared) / (1.0 + value))
+                    .min(1.0)
+            }),
+            CorrectHdrColorAlgorithm::AcesFilmic => {
+                rgb.map(|value| aces_filmic(value.min(self.relative_peak)) / self.curve_white)
+            }
+            CorrectHdrColorAlgorithm::Hable => rgb.map(|value| {
+                hable(value.min(self.relative_peak) * HABLE_EXPOSURE_BIAS) / self.curve_white
+            }),
+            CorrectHdrColorAlgorithm::Bt2390 => Self::map_by_max_channel(rgb, |value| {
+                // 在 PQ 域中把 [0, 峰值] 压缩到 [0, 纸白]
+                let source = pq_inverse_eotf(value * self.paper_white_nits) / self.pq_peak;
+                let target = bt2390_eetf(source.min(1.0), self.pq_paper_white / self.pq_peak);
+
+                pq_eotf(target * self.pq_peak) / self.paper_white_nits
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TONE_MAPPING_ALGORITHMS: [CorrectHdrColorAlgorithm; 4] = [
+        CorrectHdrColorAlgorithm::Reinhard,
+        CorrectHdrColorAlgorithm::AcesFilmic,
+        CorrectHdrColorAlgorithm::Hable,
+        CorrectHdrColorAlgorithm::Bt2390,
+    ];
+
+    fn options(peak_nits: f32, paper_white_nits: f32) -> HdrToneMappingOptions {
+        HdrToneMappingOptions {
+            peak_nits: Some(peak_nits),
+            paper_white_nits: Some(paper_white_nits),
+        }
+    }
+
+    #[test]
+    fn test_linear_keeps_paper_white() {
+        let mapper = HdrToneMapper::new(
+            CorrectHdrColorAlgorithm::Linear,
+            options(1000.0, 200.0),
+            80.0,
+        );
+
+        let white = mapper.map([2.5, 2.5, 2.5]);
+        assert!(white.iter().all(|value| (value - 1.0).abs() < 1e-4));
+    }
+
+    #[test]
+    fn test_peak_not_above_paper_white() {
+        for algorithm in TONE_MAPPING_ALGORITHMS {
+            for (peak_nits, paper_white_nits) in [(200.0, 200.0), (100.0, 200.0)] {
+                let mapper =
+                    HdrToneMapper::new(algorithm, options(peak_nits, paper_white_nits), 80.0);
+
+                for value in [0.5, 2.5, 12.5] {
+                    let mapped = mapper.map([value, value, value]);
+                    assert!(
+                        mapped
+                            .iter()
+                            .all(|value| value.is_finite() && (0.0..=1.0 + 1e-4).contains(value)),
+                        "{:?} {} {}: {:?}",
+                        algorithm,
+                        peak_nits,
+                        value,
+                        mapped
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_peak_maps_to_white() {
+        for algorithm in TONE_MAPPING_ALGORITHMS {
+            let mapper = HdrToneMapper::new(algorithm, options(1000.0, 200.0), 80.0);
+            // 1000 尼特
+            let peak = mapper.map([12.5, 12.5, 12.5]);
+
+            assert!(
+                peak.iter().all(|value| (value - 1.0).abs() < 0.01),
+                "{:?}: {:?}",
+                algorithm,
+                peak
+            );
+        }
+    }
+
+    #[test]
+    fn test_monotonic_and_in_range() {
+        for algorithm in TONE_MAPPING_ALGORITHMS {
+            let mapper = HdrToneMapper::new(algorithm, HdrToneMappingOptions::default(), 200.0);
+
+            let mut previous = 0.0;
+            for step in 0..=200 {
+                // 0 到 2000 尼特
+                let value = step as f32 * 10.0 / SCRGB_REFERENCE_WHITE_NITS;
+                let mapped = mapper.map([value, value, value])[0];
+
+                assert!(
+                    (0.0..=1.0 + 1e-4).contains(&mapped),
+                    "{:?}: {}",
+                    algorithm,
+                    mapped
+                );
+                assert!(
+                    mapped + 1e-5 >= previous,
+                    "{:?}: {} < {}",
+                    algorithm,
+                    mapped,
+                    previous
+                );
+                previous = mapped;
+            }
+        }
+    }
+
+    #[test]
+    fn test_bt2390_keeps_dark_tones() {
+        let mapper = HdrToneMapper::new(
+            CorrectHdrColorAlgorithm::Bt2390,
+            options(1000.0, 200.0),
+            80.0,
+        );
+
+        // 纸白 10% 的亮度低于拐点，保持不变
+        let dark = mapper.map([0.25, 0.25, 0.25]);
+        assert!((dark[0] - 0.1).abs() < 1e-3, "{:?}", dark);
+    }
+
+    #[test]
+    fn test_max_channel_keeps_hue() {
+        let mapper = HdrToneMapper::new(
+            CorrectHdrColorAlgorithm::Reinhard,
+            options(1000.0, 200.0),
+            80.0,
+        );
+
+        let color = mapper.map([10.0, 5.0, 2.5]);
+        assert!((color[0] / color[1] - 2.0).abs() < 1e-3);
+        assert!((color[1] / color[2] - 2.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,680 @@
+use image::codecs::avif::AvifEncoder;
+use image::{DynamicImage, GenericImageView};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use zune_core::bit_depth::BitDepth;
+use zune_core::colorspace::ColorSpace;
+use zune_core::options::EncoderOptions;
+use zune_jpegxl::JxlSimpleEncoder;
+
+use crate::capture_metadata::{CaptureMetadata, embed_capture_metadata};
+use crate::hdr_image::HdrTransferFunction;
+
+/// 估算文件大小时最多编码的像素数量
+const ESTIMATE_SAMPLE_PIXEL_COUNT: u64 = 1024 * 1024;
+/// 估算文件大小时均匀抽取的条带数量
+const ESTIMATE_SAMPLE_STRIP_COUNT: u32 = 4;
+
+const WEBP_RIFF_HEADER_LEN: usize = 12;
+const WEBP_CHUNK_HEADER_LEN: usize = 8;
+const WEBP_VP8X_PAYLOAD_LEN: usize = 10;
+const WEBP_VP8X_ICC_FLAG: u8 = 0x20;
+const WEBP_VP8X_ALPHA_FLAG: u8 = 0x10;
+const WEBP_VP8X_EXIF_FLAG: u8 = 0x08;
+const WEBP_VP8X_XMP_FLAG: u8 = 0x04;
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum ImageEncoder {
+    Webp,
+    Png,
+    Avif,
+    Jpeg,
+    Jxl,
+}
+
+impl ImageEncoder {
+    /**
+     * 根据文件扩展名获取编码器，不支持的格式返回 None
+     */
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "webp" => Some(Self::Webp),
+            "png" => Some(Self::Png),
+            "avif" => Some(Self::Avif),
+            "jpg" | "jpeg" => Some(Self::Jpeg),
+            "jxl" => Some(Self::Jxl),
+            _ => None,
+        }
+    }
+
+    /**
+     * 根据 MIME 类型获取编码器，不支持的格式返回 None
+     */
+    pub fn from_mime_type(mime_type: &str) -> Option<Self> {
+        match mime_type {
+            "image/webp" => Some(Self::Webp),
+            "image/png" => Some(Self::Png),
+            "image/avif" => Some(Self::Avif),
+            "image/jpeg" => Some(Self::Jpeg),
+            "image/jpeg-xl" | "image/jxl" => Some(Self::Jxl),
+            _ => None,
+        }
+    }
+
+    /**
+     * 是否支持嵌入 ICC 配置文件
+     *
+     * AVIF 和 JPEG XL 的编码器不支持写入 ICC 配置文件
+     */
+    pub fn supports_icc_profile(&self) -> bool {
+        match self {
+            Self::Webp | Self::Png | Self::Jpeg => true,
+            Self::Avif | Self::Jxl => false,
+        }
+    }
+
+    /// 未指定 lossless 时各格式的默认值
+    fn default_lossless(&self) -> bool {
+        match self {
+            Self::Webp | Self::Png | Self::Jxl => true,
+            Self::Avif | Self::Jpeg => false,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum ChromaSubsampling {
+    /// 不进行色度抽样，文字边缘最清晰
+    Yuv444,
+    Yuv422,
+    Yuv420,
+}
+
+/**
+ * 图片编码参数，所有保存和复制图片的路径共用
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct EncodeOptions {
+    /// 有损编码的质量（1 - 100），JPEG、WebP、AVIF 有效
+    pub quality: u8,
+    /// 是否无损编码，为空时使用各格式的默认值（WebP、PNG、JPEG XL 无损，JPEG、AVIF 有损）
+    /// JPEG XL 编码器只支持无损编码，设置为 false 时返回错误
+    pub lossless: Option<bool>,
+    /// 编码耗时和压缩率的权衡（0 - 10），越大越慢、文件越小，为空时使用各格式的默认值
+    /// 对应 WebP 的 method、AVIF 的 speed 和 JPEG XL 的 effort
+    pub effort: Option<u8>,
+    /// 色度抽样，JPEG 有效
+    pub chroma_subsampling: ChromaSubsampling,
+    /// PNG 压缩等级（0 - 9），0 为不压缩，1 为最快
+    pub png_compression_level: u8,
+    /// 保存 PNG 后在后台进行无损优化的等级（0 - 6），为空时不优化
+    pub png_optimize_level: Option<u8>,
+    /// 捕获到 HDR 图像时保留浮点数据，保存为 16 位 PNG（使用该传递函数）、JPEG XL 或 EXR
+    /// 为空时转换为 8 位 SDR 图像
+    pub hdr_transfer_function: Option<HdrTransferFunction>,
+}
+
+impl Default for EncodeOptions {
+    /// 与之前固定的编码参数保持一致，JPEG 不进行色度抽样
+    fn default() -> Self {
+        Self {
+            quality: 80,
+            lossless: None,
+            effort: None,
+            chroma_subsampling: ChromaSubsampling::Yuv444,
+            png_compression_level: 1,
+            png_optimize_level: None,
+            hdr_transfer_function: None,
+        }
+    }
+}
+
+impl EncodeOptions {
+    fn quality(&self) -> u8 {
+        self.quality.clamp(1, 100)
+    }
+
+    fn is_lossless(&self, encoder: ImageEncoder) -> bool {
+        self.lossless.unwrap_or_else(|| encoder.default_lossless())
+    }
+
+    /// 将 effort 映射到 [min, max]，为空时返回 default
+    fn effort_in_range(&self, min: u8, max: u8, default: u8) -> u8 {
+        match self.effort {
+            Some(effort) => min + ((effort.min(10) as u32 * (max - min) as u32 + 5) / 10) as u8,
+            None => default,
+        }
+    }
+
+    /**
+     * 设置 PNG 编码器的压缩等级和过滤方式，流式写入 PNG 时也使用该设置
+     */
+    pub fn apply_png_compression<W: std::io::Write>(&self, encoder: &mut png::Encoder<'_, W>) {
+        encoder.set_deflate_compression(match self.png_compression_level {
+            0 => png::DeflateCompression::NoCompression,
+            1 => png::DeflateCompression::FdeflateUltraFast,
+            level => png::DeflateCompression::Level(level.min(9)),
+        });
+        encoder.set_filter(if self.png_compression_level <= 1 {
+            png::Filter::Paeth
+        } else {
+            png::Filter::Adaptive
+        });
+    }
+}
+
+/**
+ * 编码时写入图片文件的元数据
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ImageMetadata<'a> {
+    /// ICC 配置文件，格式不支持时忽略
+    pub icc_profile: Option<&'a [u8]>,
+    /// 截图的来源信息，格式不支持时忽略
+    pub capture_metadata: Option<&'a CaptureMetadata>,
+}
+
+fn encode_jpeg(
+    image: &DynamicImage,
+    options: &EncodeOptions,
+    metadata: &ImageMetadata,
+) -> Result<Vec<u8>, String> {
+    let (width, height) = image.dimensions();
+    if width > u16::MAX as u32 || height > u16::MAX as u32 {
+        return Err(format!(
+            "[encode_jpeg] Image is too large for JPEG: {}x{}",
+            width, height
+        ));
+    }
+
+    let mut buf = Vec::with_capacity(image.as_bytes().len() / 8);
+    let mut encoder = jpeg_encoder::Encoder::new(&mut buf, options.quality());
+    encoder.set_sampling_factor(match options.chroma_subsampling {
+        ChromaSubsampling::Yuv444 => jpeg_encoder::SamplingFactor::R_4_4_4,
+        ChromaSubsampling::Yuv422 => jpeg_encoder::SamplingFactor::R_4_2_2,
+        ChromaSubsampling::Yuv420 => jpeg_encoder::SamplingFactor::R_4_2_0,
+    });
+    if let Some(icc_profile) = metadata.icc_profile
+        && let Err(e) = encoder.add_icc_profile(icc_profile)
+    {
+        return Err(format!("[encode_jpeg] Failed to add ICC profile: {}", e));
+    }
+
+    // jpg 不支持透明通道，直接忽略 alpha
+    let (image_data, color_type) = match image {
+        DynamicImage::ImageRgb8(image) => (image.as_raw().as_slice(), jpeg_encoder::ColorType::Rgb),
+        DynamicImage::ImageRgba8(image) => {
+            (image.as_raw().as_slice(), jpeg_encoder::ColorType::Rgba)
+        }
+        DynamicImage::ImageLuma8(image) => {
+            (image.as_raw().as_slice(), jpeg_encoder::ColorType::Luma)
+        }
+        _ => {
+            return encode_jpeg(&DynamicImage::ImageRgb8(image.to_rgb8()), options, metadata);
+        }
+    };
+
+    match encoder.encode(image_data, width as u16, height as u16, color_type) {
+        Ok(_) => Ok(buf),
+        Err(e) => Err(format!("[encode_jpeg] Failed to encode image: {}", e)),
+    }
+}
+
+/**
+ * WebP 扩展格式中的元数据块
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct WebpMetadataChunks<'a> {
+    pub icc_profile: Option<&'a [u8]>,
+    pub exif: Option<&'a [u8]>,
+    pub xmp: Option<&'a [u8]>,
+}
+
+fn push_webp_chunk(buf: &mut Vec<u8>, fourcc: &[u8; 4], payload: &[u8]) {
+    buf.extend_from_slice(fourcc);
+    buf.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    buf.extend_from_slice(payload);
+    // 块的长度需要是偶数
+    if payload.len() % 2 == 1 {
+        buf.push(0);
+    }
+}
+
+/**
+ * 在 WebP 文件中插入元数据块，ICCP 块在图像数据之前，EXIF 和 XMP 块在图像数据之后
+ *
+ * 简单格式（VP8 / VP8L）的文件需要先转换为扩展格式（VP8X）
+ */
+pub(crate) fn add_webp_metadata_chunks(
+    data: &[u8],
+    chunks: &WebpMetadataChunks,
+) -> Result<Vec<u8>, String> {
+    let invalid_data_error = || String::from("[add_webp_metadata_chunks] Invalid WebP data");
+
+    if data.len() < WEBP_RIFF_HEADER_LEN + WEBP_CHUNK_HEADER_LEN
+        || &data[0..4] != b"RIFF"
+        || &data[8..12] != b"WEBP"
+    {
+        return Err(invalid_data_error());
+    }
+
+    let first_chunk = &data[WEBP_RIFF_HEADER_LEN..];
+    let payload = &first_chunk[WEBP_CHUNK_HEADER_LEN..];
+    let mut vp8x_payload = [0u8; WEBP_VP8X_PAYLOAD_LEN];
+    let (image_chunks, canvas_size) = match &first_chunk[0..4] {
+        b"VP8X" => {
+            let vp8x = payload
+                .get(..WEBP_VP8X_PAYLOAD_LEN)
+                .ok_or_else(invalid_data_error)?;
+            vp8x_payload.copy_from_slice(vp8x);
+
+            (
+                &first_chunk[WEBP_CHUNK_HEADER_LEN + WEBP_VP8X_PAYLOAD_LEN..],
+                None,
+            )
+        }
+        b"VP8L" => {
+            // 签名 0x2f 后依次为 14 位宽度、14 位高度和 1 位透明标记
+            let header = payload.get(1..5).ok_or_else(invalid_data_error)?;
+            let bits = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+            if (bits >> 28) & 1 == 1 {
+                vp8x_payload[0] |= WEBP_VP8X_ALPHA_FLAG;
+            }
+
+            (
+                first_chunk,
+                Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1)),
+            )
+        }
+        b"VP8 " => {
+            // 3 字节帧标记和 3 字节起始码后依次为 14 位宽度和 14 位高度
+            let header = payload.get(6..10).ok_or_else(invalid_data_error)?;
+            let width = u16::from_le_bytes([header[0], header[1]]) & 0x3fff;
+            let height = u16::from_le_bytes([header[2], header[3]]) & 0x3fff;
+
+            (first_chunk, Some((width as u32, height as u32)))
+        }
+        _ => return Err(invalid_data_error()),
+    };
+
+    if let Some((width, height)) = canvas_size {
+        vp8x_payload[4..7].copy_from_slice(&(width - 1).to_le_bytes()[..3]);
+        vp8x_payload[7..10].copy_from_slice(&(height - 1).to_le_bytes()[..3]);
+    }
+    if chunks.icc_profile.is_some() {
+        vp8x_payload[0] |= WEBP_VP8X_ICC_FLAG;
+    }
+    if chunks.exif.is_some() {
+        vp8x_payload[0] |= WEBP_VP8X_EXIF_FLAG;
+    }
+    if chunks.xmp.is_some() {
+        vp8x_payload[0] |= WEBP_VP8X_XMP_FLAG;
+    }
+
+    let metadata_len = [chunks.icc_profile, chunks.exif, chunks.xmp]
+        .iter()
+        .flatten()
+        .map(|chunk| chunk.len() + WEBP_CHUNK_HEADER_LEN + 1)
+        .sum::<usize>();
+    let mut buf = Vec::with_capacity(data.len() + metadata_len + 32);
+    buf.extend_from_slice(b"RIFF\0\0\0\0WEBP");
+    push_webp_chunk(&mut buf, b"VP8X", &vp8x_payload);
+    if let Some(icc_profile) = chunks.icc_profile {
+        push_webp_chunk(&mut buf, b"ICCP", icc_profile);
+    }
+    buf.extend_from_slice(image_chunks);
+    if let Some(exif) = chunks.exif {
+        push_webp_chunk(&mut buf, b"EXIF", exif);
+    }
+    if let Some(xmp) = chunks.xmp {
+        push_webp_chunk(&mut buf, b"XMP ", xmp);
+    }
+
+    let riff_size = (buf.len() - 8) as u32;
+    buf[4..8].copy_from_slice(&riff_size.to_le_bytes());
+
+    Ok(buf)
+}
+
+fn encode_webp(
+    image: &DynamicImage,
+    options: &EncodeOptions,
+    metadata: &ImageMetadata,
+) -> Result<Vec<u8>, String> {
+    let rgba_image;
+    let encoder = match image {
+        DynamicImage::ImageRgb8(image) => {
+            webp::Encoder::from_rgb(image.as_raw(), image.width(), image.height())
+        }
+        DynamicImage::ImageRgba8(image) => {
+            webp::Encoder::from_rgba(image.as_raw(), image.width(), image.height())
+        }
+        _ => {
+            rgba_image = image.to_rgba8();
+            webp::Encoder::from_rgba(rgba_image.as_raw(), image.width(), image.height())
+        }
+    };
+
+    let mut config = match webp::WebPConfig::new() {
+        Ok(config) => config,
+        Err(_) => return Err(String::from("[encode_webp] Failed to create config")),
+    };
+    let lossless = options.is_lossless(ImageEncoder::Webp);
+    config.lossless = if lossless { 1 } else { 0 };
+    config.alpha_compression = if lossless { 0 } else { 1 };
+    // 无损编码时 quality 表示压缩力度
+    config.quality = if lossless {
+        options.effort_in_range(0, 100, 0) as f32
+    } else {
+        options.quality() as f32
+    };
+    config.method = options.effort_in_range(0, 6, if lossless { 0 } else { 4 }) as i32;
+
+    let memory = match encoder.encode_advanced(&config) {
+        Ok(memory) => memory,
+        Err(e) => return Err(format!("[encode_webp] Failed to encode image: {:?}", e)),
+    };
+
+    match metadata.icc_profile {
+        Some(icc_profile) => add_webp_metadata_chunks(
+            &memory,
+            &WebpMetadataChunks {
+                icc_profile: Some(icc_profile),
+                ..WebpMetadataChunks::default()
+            },
+        ),
+        None => Ok(memory.to_vec()),
+    }
+}
+
+fn encode_png(
+    image: &DynamicImage,
+    options: &EncodeOptions,
+    metadata: &ImageMetadata,
+) -> Result<Vec<u8>, String> {
+    let (width, height) = image.dimensions();
+    let converted_image;
+    let (image_data, color_type) = match image {
+        DynamicImage::ImageRgb8(image) => (image.as_raw().as_slice(), png::ColorType::Rgb),
+        DynamicImage::ImageRgba8(image) => (image.as_raw().as_slice(), png::ColorType::Rgba),
+        DynamicImage::ImageLuma8(image) => (image.as_raw().as_slice(), png::ColorType::Grayscale),
+        DynamicImage::ImageLumaA8(image) => {
+            (image.as_raw().as_slice(), png::ColorType::GrayscaleAlpha)
+        }
+        _ => {
+            converted_image = image.to_rgba8();
+            (converted_image.as_raw().as_slice(), png::ColorType::Rgba)
+        }
+    };
+
+    let mut info = png::Info::with_size(width, height);
+    info.icc_profile = metadata.icc_profile.map(Cow::Borrowed);
+
+    let mut buf = Vec::with_capacity(image_data.len() / 8);
+    let mut encoder = match png::Encoder::with_info(&mut buf, info) {
+        Ok(encoder) => encoder,
+        Err(e) => return Err(format!("[encode_png] Failed to create encoder: {}", e)),
+    };
+    encoder.set_color(color_type);
+    encoder.set_depth(png::BitDepth::Eight);
+    options.apply_png_compression(&mut encoder);
+
+    let mut writer = match encoder.write_header() {
+        Ok(writer) => writer,
+        Err(e) => return Err(format!("[encode_png] Failed to write header: {}", e)),
+    };
+    if let Err(e) = writer.write_image_data(image_data) {
+        return Err(format!("[encode_png] Failed to encode image: {}", e));
+    }
+    if let Err(e) = writer.finish() {
+        return Err(format!("[encode_png] Failed to finish image: {}", e));
+    }
+
+    Ok(buf)
+}
+
+fn encode_avif(image: &DynamicImage, options: &EncodeOptions) -> Result<Vec<u8>, String> {
+    // AVIF 编码器没有真正的无损模式，使用最高质量代替
+    let quality = if options.is_lossless(ImageEncoder::Avif) {
+        100
+    } else {
+        options.quality()
+    };
+    let speed = 10 - options.effort_in_range(0, 9, 0);
+
+    let mut buf = Vec::with_capacity(image.as_bytes().len() / 8);
+    match image.write_with_encoder(AvifEncoder::new_with_speed_quality(
+        &mut buf, speed, quality,
+    )) {
+        Ok(_) => Ok(buf),
+        Err(e) => Err(format!("[encode_avif] Failed to encode image: {}", e)),
+    }
+}
+
+fn encode_jxl(image: &DynamicImage, options: &EncodeOptions) -> Result<Vec<u8>, String> {
+    if !options.is_lossless(ImageEncoder::Jxl) {
+        return Err(String::from("[encode_jxl] Lossy JPEG XL is not supported"));
+    }
+
+    let has_alpha = image.color().has_alpha();
+    let (width, height) = image.dimensions();
+    let image_data = if has_alpha {
+        DynamicImage::ImageRgba8(image.to_rgba8())
+    } else {
+        DynamicImage::ImageRgb8(image.to_rgb8())
+    };
+    let encoder = JxlSimpleEncoder::new(
+        image_data.as_bytes(),
+        EncoderOptions::new(
+            width as usize,
+            height as usize,
+            if has_alpha {
+                ColorSpace::RGBA
+            } else {
+                ColorSpace::RGB
+            },
+            BitDepth::Eight,
+        )
+        .set_effort(options.effort_in_range(1, 9, 4)),
+    );
+
+    match encoder.encode() {
+        Ok(buf) => Ok(buf),
+        Err(e) => Err(format!("[encode_jxl] Failed to encode image: {:?}", e)),
+    }
+}
+
+/**
+ * 按编码参数将图片编码为指定格式
+ */
+pub fn encode_image(
+    image: &DynamicImage,
+    encoder: ImageEncoder,
+    options: &EncodeOptions,
+) -> Result<Vec<u8>, String> {
+    encode_image_with_metadata(image, encoder, options, &ImageMetadata::default())
+}
+
+/**
+ * 按编码参数将图片编码为指定格式，并写入元数据
+ */
+pub fn encode_image_with_metadata(
+    image: &DynamicImage,
+    encoder: ImageEncoder,
+    options: &EncodeOptions,
+    metadata: &ImageMetadata,
+) -> Result<Vec<u8>, String> {
+    let image_data = match encoder {
+        ImageEncoder::Jpeg => encode_jpeg(image, options, metadata),
+        ImageEncoder::Webp => encode_webp(image, options, metadata),
+        ImageEncoder::Png => encode_png(image, options, metadata),
+        ImageEncoder::Avif => encode_avif(image, options),
+        ImageEncoder::Jxl => encode_jxl(image, options),
+    }?;
+
+    match metadata.capture_metadata {
+        Some(capture_metadata) => embed_capture_metadata(image_data, encoder, capture_metadata),
+        None => Ok(image_data),
+    }
+}
+
+/**
+ * 估算文件大小时需要编码的条带（起始行、行数）
+ *
+ * 小图返回整张图片，大图均匀抽取若干条带，每个条带取所在区间的中间部分
+ */
+pub fn estimate_sample_strips(width: u32, height: u32) -> Vec<(u32, u32)> {
+    let pixel_count = width as u64 * height as u64;
+    if pixel_count <= ESTIMATE_SAMPLE_PIXEL_COUNT || width == 0 {
+        return vec![(0, height)];
+    }
+
+    let sample_height = (ESTIMATE_SAMPLE_PIXEL_COUNT / width as u64)
+        .max(ESTIMATE_SAMPLE_STRIP_COUNT as u64) as u32
+        / ESTIMATE_SAMPLE_STRIP_COUNT;
+    let strip_step = height / ESTIMATE_SAMPLE_STRIP_COUNT;
+
+    (0..ESTIMATE_SAMPLE_STRIP_COUNT)
+        .map(|strip_index| {
+            let strip_y = strip_index * strip_step + strip_step.saturating_sub(sample_height) / 2;
+            (strip_y, sample_height.min(height - strip_y))
+        })
+        .collect()
+}
+
+/**
+ * 编码 estimate_sample_strips 抽取的条带，按高度比例推算整张图片编码后的大小（字节）
+ */
+pub fn estimate_encoded_size_from_strips(
+    strips: &[DynamicImage],
+    height: u32,
+    encoder: ImageEncoder,
+    options: &EncodeOptions,
+) -> Result<u64, String> {
+    let mut sample_size = 0;
+    let mut sampled_height = 0;
+    for strip_image in strips {
+        sample_size += encode_image(strip_image, encoder, options)?.len() as u64;
+        sampled_height += strip_image.height();
+    }
+
+    Ok(sample_size * height as u64 / sampled_height.max(1) as u64)
+}
+
+/**
+ * 估算图片按编码参数编码后的文件大小（字节）
+ *
+ * 大图均匀抽取若干条带编码，按面积比例推算总大小
+ */
+pub fn estimate_encoded_size(
+    image: &DynamicImage,
+    encoder: ImageEncoder,
+    options: &EncodeOptions,
+) -> Result<u64, String> {
+    let (width, height) = image.dimensions();
+    let strips = estimate_sample_strips(width, height)
+        .into_iter()
+        .map(|(strip_y, strip_height)| image.crop_imm(0, strip_y, width, strip_height))
+        .collect::<Vec<_>>();
+
+    estimate_encoded_size_from_strips(&strips, height, encoder, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::ImageDecoder;
+    use std::io::Cursor;
+
+    /// 只用于测试嵌入和读取，不需要是有效的配置文件
+    const TEST_ICC_PROFILE: &[u8] = b"test icc profile data";
+
+    fn test_image(has_alpha: bool) -> DynamicImage {
+        let image = image::RgbaImage::from_fn(33, 17, |x, y| {
+            image::Rgba([(x * 7) as u8, (y * 13) as u8, 128, 200])
+        });
+
+        if has_alpha {
+            DynamicImage::ImageRgba8(image)
+        } else {
+            DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(image).to_rgb8())
+        }
+    }
+
+    fn encode_with_icc_profile(
+        image: &DynamicImage,
+        encoder: ImageEncoder,
+        lossless: bool,
+    ) -> Vec<u8> {
+        encode_image_with_metadata(
+            image,
+            encoder,
+            &EncodeOptions {
+                lossless: Some(lossless),
+                ..EncodeOptions::default()
+            },
+            &ImageMetadata {
+                icc_profile: Some(TEST_ICC_PROFILE),
+                ..ImageMetadata::default()
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_png_and_jpeg_embed_icc_profile() {
+        let image = test_image(false);
+
+        let png_data = encode_with_icc_profile(&image, ImageEncoder::Png, true);
+        let mut png_decoder =
+            image::codecs::png::PngDecoder::new(Cursor::new(png_data.as_slice())).unwrap();
+        assert_eq!(
+            png_decoder.icc_profile().unwrap().as_deref(),
+            Some(TEST_ICC_PROFILE)
+        );
+
+        let jpeg_data = encode_with_icc_profile(&image, ImageEncoder::Jpeg, false);
+        let mut jpeg_decoder =
+            image::codecs::jpeg::JpegDecoder::new(Cursor::new(jpeg_data.as_slice())).unwrap();
+        assert_eq!(
+            jpeg_decoder.icc_profile().unwrap().as_deref(),
+            Some(TEST_ICC_PROFILE)
+        );
+    }
+
+    #[test]
+    fn test_webp_embed_icc_profile() {
+        // 分别覆盖 VP8L、VP8 和带透明通道的 VP8X
+        for (has_alpha, lossless) in [(false, true), (false, false), (true, false), (true, true)] {
+            let image = test_image(has_alpha);
+            let webp_data = encode_with_icc_profile(&image, ImageEncoder::Webp, lossless);
+
+            let mut decoder =
+                image::codecs::webp::WebPDecoder::new(Cursor::new(webp_data.as_slice())).unwrap();
+            assert_eq!(
+                decoder.icc_profile().unwrap().as_deref(),
+                Some(TEST_ICC_PROFILE)
+            );
+            assert_eq!(decoder.dimensions(), (33, 17));
+            assert_eq!(decoder.color_type().has_alpha(), has_alpha);
+
+            let decoded = DynamicImage::from_decoder(decoder).unwrap();
+            assert_eq!(decoded.width(), 33);
+        }
+    }
+
+    #[test]
+    fn test_jxl_rejects_lossy() {
+        let image = test_image(true);
+
+        let jxl_data = encode_image(&image, ImageEncoder::Jxl, &EncodeOptions::default()).unwrap();
+        assert!(jxl_data.starts_with(&[0xFF, 0x0A]));
+
+        let lossy_options = EncodeOptions {
+            lossless: Some(false),
+            ..EncodeOptions::default()
+        };
+        assert!(encode_image(&image, ImageEncoder::Jxl, &lossy_options).is_err());
+    }
+}
@@ -1,32 +1,39 @@
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use std::ffi::OsStr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::http::HeaderValue;
 use tokio::fs;
 
 use base64::prelude::*;
 use device_query::{DeviceQuery, DeviceState, MouseState};
-use image::codecs::avif::AvifEncoder;
-use image::codecs::jpeg::JpegEncoder;
-use image::codecs::png::{CompressionType, FilterType, PngEncoder};
-use image::codecs::webp::WebPEncoder;
 use image::{DynamicImage, GenericImageView};
 use snow_shot_app_shared::ElementRect;
 use tauri::AppHandle;
 use xcap::Monitor;
-use zune_core::bit_depth::BitDepth;
-use zune_core::colorspace::ColorSpace;
-use zune_core::options::EncoderOptions;
-use zune_jpegxl::JxlSimpleEncoder;
 
+use crate::capture_metadata::CaptureMetadata;
+use crate::color_profile::ColorProfile;
 use crate::monitor_info::{ColorFormat, MonitorList};
 
+pub use crate::image_encoder::{
+    ChromaSubsampling, EncodeOptions, ImageEncoder, ImageMetadata, encode_image,
+    encode_image_with_metadata, estimate_encoded_size, estimate_encoded_size_from_strips,
+    estimate_sample_strips,
+};
+
 #[cfg(target_os = "windows")]
 pub mod monitor_hdr_info;
 #[cfg(target_os = "windows")]
 pub mod windows_capture_image;
 
+pub mod capture_metadata;
+pub mod color_profile;
+pub mod file_name_template;
+pub mod hdr_image;
+pub mod hdr_tone_mapping;
+pub mod image_encoder;
 pub mod monitor_info;
+pub mod png_optimizer;
 
 pub fn get_device_state() -> Result<DeviceState, String> {
     #[cfg(target_os = "macos")]
@@ -75,9 +82,18 @@ pub fn get_target_monitor() -> Result<(i32, i32, Monitor), String> {
     Ok((mouse_x, mouse_y, monitor))
 }
 
+/**
+ * 按文件扩展名编码并保存图片
+ *
+ * color_profile 为图像的 ICC 配置文件，格式不支持嵌入时转换为 sRGB 后保存
+ * capture_metadata 为截图的来源信息，格式不支持时不写入
+ */
 pub async fn save_image_to_file(
     image: &image::DynamicImage,
     file_path: PathBuf,
+    encode_options: &EncodeOptions,
+    color_profile: Option<&ColorProfile>,
+    capture_metadata: Option<&CaptureMetadata>,
 ) -> Result<(), String> {
     // 确保文件路径的父目录存在
     if let Some(parent_dir) = file_path.parent() {
@@ -109,66 +125,105 @@ pub async fn save_image_to_file(
         }
     };
 
-    if extension == "jxl" {
-        let has_alpha = image.color().has_alpha();
-        let (width, height) = image.dimensions();
-        let image_data = if has_alpha {
-            DynamicImage::ImageRgba8(image.to_rgba8())
-        } else {
-            DynamicImage::ImageRgb8(image.to_rgb8())
-        };
-        let encoder = JxlSimpleEncoder::new(
-            image_data.as_bytes(),
-            EncoderOptions::new(
-                width as usize,
-                height as usize,
-                if has_alpha {
-                    ColorSpace::RGBA
-                } else {
-                    ColorSpace::RGB
-                },
-                BitDepth::Eight,
-            ),
-        );
-        let encoder_result = match encoder.encode() {
-            Ok(encoder_result) => encoder_result,
-            Err(_) => {
-                return Err(format!(
-                    "[save_image_to_file] Failed to encode image: {}",
-                    file_path.display()
-                ));
-            }
-        };
+    let encoder = ImageEncoder::from_extension(&extension.to_string_lossy());
 
-        return match fs::write(file_path.clone(), encoder_result).await {
-            Ok(_) => Ok(()),
-            Err(e) => Err(format!(
-                "[save_image_to_file] Failed to save image to file: {} {}",
-                e,
-                file_path.display(),
-            )),
-        };
-    } else {
-        // jpg 是 RGB 格式，所以需要转换为 RGB 格式
-        let image = if image.color().has_alpha() && extension == "jpg" {
-            &DynamicImage::ImageRgb8(image.to_rgb8())
-        } else {
-            image
-        };
+    let converted_image;
+    let (image, icc_profile) = match color_profile {
+        Some(color_profile) if encoder.is_some_and(|encoder| encoder.supports_icc_profile()) => {
+            (image, Some(color_profile.icc_data()))
+        }
+        Some(color_profile) => {
+            converted_image = color_profile.convert_to_srgb(image)?;
+            (&converted_image, None)
+        }
+        None => (image, None),
+    };
 
-        match image.save(file_path.clone()) {
-            Ok(_) => (),
-            Err(e) => {
-                return Err(format!(
+    let encoder = match encoder {
+        Some(encoder) => encoder,
+        None => {
+            // 其他格式使用 image 的默认参数
+            return match image.save(file_path.clone()) {
+                Ok(_) => Ok(()),
+                Err(e) => Err(format!(
                     "[save_image_to_file] Failed to save image to file: {} {}",
                     e,
                     file_path.display(),
-                ));
-            }
+                )),
+            };
+        }
+    };
+
+    let image_data = encode_image_with_metadata(
+        image,
+        encoder,
+        encode_options,
+        &ImageMetadata {
+            icc_profile,
+            capture_metadata,
+        },
+    )?;
+
+    match fs::write(file_path.clone(), image_data).await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!(
+            "[save_image_to_file] Failed to save image to file: {} {}",
+            e,
+            file_path.display(),
+        )),
+    }
+}
+
+/**
+ * 转义 XML 文本和属性值，并去掉 XML 1.0 不允许的控制字符
+ */
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
         }
     }
 
-    return Ok(());
+    escaped
+}
+
+/**
+ * 获取 ffmpeg 插件目录下的可执行文件路径，非 Windows 平台会为文件添加可执行权限
+ */
+pub fn get_ffmpeg_path(ffmpeg_plugin_dir: &Path) -> PathBuf {
+    #[cfg(target_os = "windows")]
+    {
+        ffmpeg_plugin_dir.join("ffmpeg.exe")
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let ffmpeg_path = ffmpeg_plugin_dir.join("ffmpeg");
+
+        // 为 ffmpeg 文件添加可执行权限
+        if let Ok(metadata) = std::fs::metadata(&ffmpeg_path) {
+            let mut permissions = metadata.permissions();
+            permissions.set_mode(0o755); // 设置可执行权限 (rwxr-xr-x)
+
+            if let Err(e) = std::fs::set_permissions(&ffmpeg_path, permissions) {
+                log::error!(
+                    "[get_ffmpeg_path] Failed to set executable permissions for ffmpeg: {}",
+                    e
+                );
+            }
+        }
+
+        ffmpeg_path
+    }
 }
 
 pub fn get_mouse_position(
@@ -542,47 +597,6 @@ pub fn bgra_to_rgba(bgra_data: &[u8]) -> Vec<u8> {
     rgba_data
 }
 
-pub enum ImageEncoder {
-    Webp,
-    Png,
-    Avif,
-    Jpeg,
-}
-
-pub fn encode_image(image: &image::DynamicImage, encoder: ImageEncoder) -> Vec<u8> {
-    // 编码为指定格式
-    let mut buf = Vec::with_capacity(image.as_bytes().len() / 8);
-
-    match encoder {
-        ImageEncoder::Jpeg => {
-            image
-                .write_with_encoder(JpegEncoder::new_with_quality(&mut buf, 80))
-                .unwrap();
-        }
-        ImageEncoder::Webp => {
-            image
-                .write_with_encoder(WebPEncoder::new_lossless(&mut buf))
-                .unwrap();
-        }
-        ImageEncoder::Png => {
-            image
-                .write_with_encoder(PngEncoder::new_with_quality(
-                    &mut buf,
-                    CompressionType::Fast,
-                    FilterType::Paeth,
-                ))
-                .unwrap();
-        }
-        ImageEncoder::Avif => {
-            image
-                .write_with_encoder(AvifEncoder::new_with_speed_quality(&mut buf, 10, 80))
-                .unwrap();
-        }
-    }
-
-    return buf;
-}
-
 /// 将一个图像绘制到另一个图像上
 ///
 /// # Arguments
@@ -6,6 +6,10 @@ use serde::{Deserialize, Serialize};
 use snow_shot_app_shared::ElementRect;
 use xcap::Monitor;
 
+use crate::color_profile::{self, ColorProfile, ColorProfileMode};
+use crate::hdr_image::HdrImage;
+use crate::hdr_tone_mapping::HdrToneMappingOptions;
+
 #[cfg(target_os = "windows")]
 use crate::monitor_hdr_info::{self, MonitorHdrInfo};
 #[cfg(target_os = "windows")]
@@ -38,7 +42,10 @@ pub struct MonitorRect {
 pub struct CaptureOption {
     pub color_format: ColorFormat,
     pub correct_hdr_color_algorithm: CorrectHdrColorAlgorithm,
+    pub hdr_tone_mapping: HdrToneMappingOptions,
     pub correct_color_filter: bool,
+    /// 为 ConvertToSrgb 时按显示器的 ICC 配置文件将每个显示器的图像转换为 sRGB
+    pub color_profile_mode: ColorProfileMode,
 }
 
 impl MonitorInfo {
@@ -160,11 +167,60 @@ impl MonitorInfo {
         Ok(device_name)
     }
 
+    /**
+     * 获取捕获图像的 ICC 配置文件，没有配置文件或图像已经是 sRGB 时返回 None
+     */
+    pub fn get_color_profile(
+        &self,
+        #[allow(unused_variables)] capture_option: &CaptureOption,
+    ) -> Option<ColorProfile> {
+        // HDR 图像由 scRGB 数据转换得到，已经是 sRGB
+        #[cfg(target_os = "windows")]
+        if self.monitor_hdr_info.hdr_enabled
+            && capture_option.correct_hdr_color_algorithm != CorrectHdrColorAlgorithm::None
+        {
+            return None;
+        }
+
+        match color_profile::get_monitor_color_profile(&self.monitor) {
+            Ok(color_profile) => color_profile,
+            Err(e) => {
+                log::warn!("[MonitorInfo::get_color_profile] {}", e);
+                None
+            }
+        }
+    }
+
+    /// @param hdr_image 已经捕获的该显示器的 HDR 图像，存在时直接转换，不再重复捕获
     pub fn capture(
         &self,
         crop_area: Option<ElementRect>,
         exclude_window: Option<&tauri::Window>,
         capture_option: CaptureOption,
+        hdr_image: Option<&HdrImage>,
+    ) -> Option<image::DynamicImage> {
+        let image = self.capture_image(crop_area, exclude_window, capture_option, hdr_image)?;
+
+        // 拼接多个显示器的图像前转换，每个显示器使用各自的配置文件
+        if capture_option.color_profile_mode != ColorProfileMode::ConvertToSrgb {
+            return Some(image);
+        }
+
+        let (image, _) = color_profile::apply_color_profile_mode(
+            image,
+            self.get_color_profile(&capture_option),
+            ColorProfileMode::ConvertToSrgb,
+        );
+
+        Some(image)
+    }
+
+    fn capture_image(
+        &self,
+        crop_area: Option<ElementRect>,
+        exclude_window: Option<&tauri::Window>,
+        capture_option: CaptureOption,
+        #[allow(unused_variables)] hdr_image: Option<&HdrImage>,
     ) -> Option<image::DynamicImage> {
         #[cfg(target_os = "macos")]
         {
@@ -184,12 +240,27 @@ impl MonitorInfo {
             if self.monitor_hdr_info.hdr_enabled
                 && capture_option.correct_hdr_color_algorithm != CorrectHdrColorAlgorithm::None
             {
-                capture_hdr_image = match windows_capture_image::capture_monitor_image(
-                    &self,
-                    None,
-                    crop_area,
-                    capture_option.color_format,
-                ) {
+                let capture_result = match hdr_image {
+                    // 已经捕获的是整个显示器，只能用于不裁剪的情况
+                    Some(hdr_image) if crop_area.is_none() => {
+                        windows_capture_image::convert_monitor_hdr_image(
+                            hdr_image,
+                            capture_option.color_format,
+                            capture_option.correct_hdr_color_algorithm,
+                            capture_option.hdr_tone_mapping,
+                        )
+                    }
+                    _ => windows_capture_image::capture_monitor_image(
+                        &self,
+                        None,
+                        crop_area,
+                        capture_option.color_format,
+                        capture_option.correct_hdr_color_algorithm,
+                        capture_option.hdr_tone_mapping,
+                    ),
+                };
+
+                capture_hdr_image = match capture_result {
                     Ok(image) => Some(image),
                     Err(e) => {
                         log::error!(
@@ -220,7 +291,16 @@ pub struct MonitorList(Vec<MonitorInfo>);
 #[derive(Serialize, Deserialize, Clone, Debug, Copy, PartialEq)]
 pub enum CorrectHdrColorAlgorithm {
     None,
+    /// 按 SDR 白色亮度线性缩放，高光被裁剪
     Linear,
+    /// 扩展 Reinhard，峰值亮度映射到白色
+    Reinhard,
+    /// ACES 电影色调曲线
+    AcesFilmic,
+    /// Hable（Uncharted 2）电影色调曲线
+    Hable,
+    /// BT.2390 EETF，在 PQ 域中压缩高光，纸白以下基本保持不变
+    Bt2390,
 }
 
 impl MonitorList {
@@ -342,13 +422,20 @@ impl MonitorList {
     /// 捕获所有显示器，拼接为一个完整的图像
     ///
     /// @param crop_region 显示器的裁剪区域
+    /// @param hdr_monitor_image 已经捕获 HDR 图像的显示器区域和图像
     async fn capture_future(
         &self,
         crop_region: Option<ElementRect>,
         exclude_window: Option<&tauri::Window>,
         capture_option: CaptureOption,
+        hdr_monitor_image: Option<(ElementRect, &HdrImage)>,
     ) -> Result<image::DynamicImage, String> {
         let monitors = &self.0;
+        let get_hdr_image = |monitor: &MonitorInfo| {
+            hdr_monitor_image
+                .filter(|(monitor_rect, _)| *monitor_rect == monitor.rect)
+                .map(|(_, hdr_image)| hdr_image)
+        };
 
         // 特殊情况，只有一个显示器，直接返回
         if monitors.len() == 1 {
@@ -361,6 +448,7 @@ impl MonitorList {
                 },
                 exclude_window,
                 capture_option,
+                get_hdr_image(first_monitor),
             );
 
             // 有些捕获失败的显示器，返回一个空图像，这里需要特殊处理
@@ -406,7 +494,12 @@ impl MonitorList {
                     None
                 };
 
-                let capture_image = monitor.capture(monitor_crop_region, exclude_window, capture_option);
+                let capture_image = monitor.capture(
+                    monitor_crop_region,
+                    exclude_window,
+                    capture_option,
+                    get_hdr_image(monitor),
+                );
 
                 match capture_image {
                     Some(image) => Some((image, monitor_crop_region)),
@@ -791,6 +884,7 @@ impl MonitorList {
         crop_region: Option<ElementRect>,
         exclude_window: Option<&tauri::Window>,
         capture_option: CaptureOption,
+        hdr_monitor_image: Option<(ElementRect, &HdrImage)>,
     ) -> Result<image::DynamicImage, String> {
         let enable_exclude_window = {
             #[cfg(target_os = "windows")]
@@ -827,7 +921,12 @@ impl MonitorList {
         }
 
         let result = tokio::try_join!(
-            self.capture_future(crop_region, exclude_window, capture_option,),
+            self.capture_future(
+                crop_region,
+                exclude_window,
+                capture_option,
+                hdr_monitor_image
+            ),
             Self::get_mag_color_effect_inverse(capture_option.correct_color_filter)
         );
 
@@ -874,17 +973,36 @@ impl MonitorList {
         exclude_window: Option<&tauri::Window>,
         capture_option: CaptureOption,
     ) -> Result<image::DynamicImage, String> {
-        self.capture_core(None, exclude_window, capture_option)
+        self.capture_core(None, exclude_window, capture_option, None)
             .await
     }
 
+    /**
+     * 捕获所有显示器，已经捕获 HDR 图像的显示器直接由 HDR 图像转换，避免重复捕获
+     */
+    pub async fn capture_with_hdr_image(
+        &self,
+        exclude_window: Option<&tauri::Window>,
+        capture_option: CaptureOption,
+        hdr_monitor_rect: ElementRect,
+        hdr_image: &HdrImage,
+    ) -> Result<image::DynamicImage, String> {
+        self.capture_core(
+            None,
+            exclude_window,
+            capture_option,
+            Some((hdr_monitor_rect, hdr_image)),
+        )
+        .await
+    }
+
     pub async fn capture_region(
         &self,
         region: ElementRect,
         exclude_window: Option<&tauri::Window>,
         capture_option: CaptureOption,
     ) -> Result<image::DynamicImage, String> {
-        self.capture_core(Some(region), exclude_window, capture_option)
+        self.capture_core(Some(region), exclude_window, capture_option, None)
             .await
     }
 
@@ -970,7 +1088,9 @@ mod tests {
                 CaptureOption {
                     color_format: ColorFormat::Rgb8,
                     correct_hdr_color_algorithm: CorrectHdrColorAlgorithm::None,
+                    hdr_tone_mapping: HdrToneMappingOptions::default(),
                     correct_color_filter: false,
+                    color_profile_mode: ColorProfileMode::Ignore,
                 },
             )
             .await
@@ -1069,7 +1189,9 @@ mod tests {
                 CaptureOption {
                     color_format: ColorFormat::Rgb8,
                     correct_hdr_color_algorithm: CorrectHdrColorAlgorithm::None,
+                    hdr_tone_mapping: HdrToneMappingOptions::default(),
                     correct_color_filter: false,
+                    color_profile_mode: ColorProfileMode::Ignore,
                 },
             )
             .await
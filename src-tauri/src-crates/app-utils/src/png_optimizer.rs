@@ -0,0 +1,260 @@
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter};
+
+use crate::image_encoder::{EncodeOptions, ImageEncoder};
+
+/// 优化完成后通知前端的事件
+pub const PNG_OPTIMIZE_FINISHED_EVENT: &str = "png-optimize-finished";
+
+/// 单张图片优化的最长耗时，超时后使用已经找到的最优结果
+const PNG_OPTIMIZE_TIMEOUT: Duration = Duration::from_secs(30);
+const PNG_OPTIMIZE_MAX_LEVEL: u8 = 6;
+
+/// 优化时保留的辅助块：色彩和显示相关的块，以及 embed_capture_metadata 写入的文本、XMP 和 eXIf 块
+const PNG_KEEP_CHUNKS: [[u8; 4]; 13] = [
+    *b"cICP", *b"iCCP", *b"sRGB", *b"gAMA", *b"cHRM", *b"pHYs", *b"acTL", *b"fcTL", *b"fdAT",
+    *b"tEXt", *b"zTXt", *b"iTXt", *b"eXIf",
+];
+
+/// 优化很耗 CPU，同一时间只优化一张图片，避免影响截图
+static PNG_OPTIMIZE_LOCK: LazyLock<tokio::sync::Mutex<()>> =
+    LazyLock::new(|| tokio::sync::Mutex::new(()));
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PngOptimizeResult {
+    pub file_path: PathBuf,
+    /// 优化前的文件大小（字节）
+    pub original_size: u64,
+    /// 优化后的文件大小（字节），没有变小时与 original_size 相同
+    pub optimized_size: u64,
+    /// 节省的文件大小（字节）
+    pub saved_size: u64,
+}
+
+fn get_file_state(file_path: &Path) -> Result<(u64, Option<SystemTime>), String> {
+    match fs::metadata(file_path) {
+        Ok(metadata) => Ok((metadata.len(), metadata.modified().ok())),
+        Err(e) => Err(format!(
+            "[get_file_state] Failed to read file metadata: {} {}",
+            e,
+            file_path.display()
+        )),
+    }
+}
+
+/**
+ * 将优化后的数据写入同目录下的临时文件，再重命名覆盖原文件
+ */
+fn replace_file_atomically(file_path: &Path, data: &[u8]) -> Result<(), String> {
+    let file_name = file_path.file_name().unwrap_or_default().to_string_lossy();
+    let temp_file_path = file_path.with_file_name(format!(".{}.optimizing", file_name));
+
+    let write_result = fs::File::create(&temp_file_path).and_then(|mut file| {
+        file.write_all(data)?;
+        file.sync_all()
+    });
+    if let Err(e) = write_result {
+        let _ = fs::remove_file(&temp_file_path);
+        return Err(format!(
+            "[replace_file_atomically] Failed to write temp file: {} {}",
+            e,
+            temp_file_path.display()
+        ));
+    }
+
+    if let Err(e) = fs::rename(&temp_file_path, file_path) {
+        let _ = fs::remove_file(&temp_file_path);
+        return Err(format!(
+            "[replace_file_atomically] Failed to replace file: {} {}",
+            e,
+            file_path.display()
+        ));
+    }
+
+    Ok(())
+}
+
+/**
+ * 无损优化 PNG 数据，保留 PNG_KEEP_CHUNKS 中的辅助块
+ */
+fn optimize_png_data(data: &[u8], level: u8) -> Result<Vec<u8>, oxipng::PngError> {
+    let mut options = oxipng::Options::from_preset(level.min(PNG_OPTIMIZE_MAX_LEVEL));
+    options.strip = oxipng::StripChunks::Keep(PNG_KEEP_CHUNKS.into_iter().collect());
+    options.timeout = Some(PNG_OPTIMIZE_TIMEOUT);
+
+    oxipng::optimize_from_memory(data, &options)
+}
+
+/**
+ * 无损优化 PNG 文件
+ *
+ * 颜色较少时转换为调色板，并尝试不同的过滤器和更高的压缩等级
+ * 只有文件变小时才会替换原文件
+ */
+pub fn optimize_png_file(file_path: &Path, level: u8) -> Result<PngOptimizeResult, String> {
+    let original_state = get_file_state(file_path)?;
+    let original_data = match fs::read(file_path) {
+        Ok(data) => data,
+        Err(e) => {
+            return Err(format!(
+                "[optimize_png_file] Failed to read file: {} {}",
+                e,
+                file_path.display()
+            ));
+        }
+    };
+    let original_size = original_data.len() as u64;
+
+    let optimized_data = match optimize_png_data(&original_data, level) {
+        Ok(data) => data,
+        Err(e) => {
+            return Err(format!(
+                "[optimize_png_file] Failed to optimize png: {} {}",
+                e,
+                file_path.display()
+            ));
+        }
+    };
+
+    let optimized_size = optimized_data.len() as u64;
+    if optimized_size >= original_size {
+        return Ok(PngOptimizeResult {
+            file_path: file_path.to_path_buf(),
+            original_size,
+            optimized_size: original_size,
+            saved_size: 0,
+        });
+    }
+
+    // 优化期间文件被其他操作覆盖时放弃本次结果
+    if get_file_state(file_path)? != original_state {
+        return Err(format!(
+            "[optimize_png_file] File changed during optimization: {}",
+            file_path.display()
+        ));
+    }
+
+    replace_file_atomically(file_path, &optimized_data)?;
+
+    Ok(PngOptimizeResult {
+        file_path: file_path.to_path_buf(),
+        original_size,
+        optimized_size,
+        saved_size: original_size - optimized_size,
+    })
+}
+
+/**
+ * 在后台优化保存的 PNG 文件，完成后通过 PNG_OPTIMIZE_FINISHED_EVENT 通知前端
+ *
+ * 未启用优化或者文件不是 PNG 时不做处理
+ */
+pub fn spawn_png_optimization(
+    app_handle: &AppHandle,
+    file_path: PathBuf,
+    encode_options: &EncodeOptions,
+) {
+    let level = match encode_options.png_optimize_level {
+        Some(level) => level,
+        None => return,
+    };
+
+    let is_png = file_path
+        .extension()
+        .and_then(|extension| ImageEncoder::from_extension(&extension.to_string_lossy()))
+        == Some(ImageEncoder::Png);
+    if !is_png {
+        return;
+    }
+
+    let app_handle = app_handle.clone();
+
+    tokio::spawn(async move {
+        let _guard = PNG_OPTIMIZE_LOCK.lock().await;
+
+        let result =
+            match tokio::task::spawn_blocking(move || optimize_png_file(&file_path, level)).await {
+                Ok(result) => result,
+                Err(e) => Err(format!(
+                    "[spawn_png_optimization] Failed to join optimization task: {}",
+                    e
+                )),
+            };
+
+        match result {
+            Ok(result) => {
+                log::info!(
+                    "[spawn_png_optimization] Optimized {}: {} -> {}",
+                    result.file_path.display(),
+                    result.original_size,
+                    result.optimized_size
+                );
+
+                if let Err(e) = app_handle.emit(PNG_OPTIMIZE_FINISHED_EVENT, result) {
+                    log::error!("[spawn_png_optimization] Failed to emit event: {}", e);
+                }
+            }
+            Err(e) => {
+                log::warn!("{}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture_metadata::{CaptureMetadata, CaptureMode, embed_capture_metadata};
+    use crate::image_encoder::encode_image;
+    use image::DynamicImage;
+
+    fn png_chunk_types(data: &[u8]) -> Vec<[u8; 4]> {
+        let mut chunk_types = Vec::new();
+        let mut offset = 8;
+        while offset + 8 <= data.len() {
+            let chunk_len = u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap());
+            chunk_types.push(data[offset + 4..offset + 8].try_into().unwrap());
+            offset += 12 + chunk_len as usize;
+        }
+        chunk_types
+    }
+
+    #[test]
+    fn test_optimize_png_keeps_capture_metadata() {
+        // 颜色较少的图片，优化时会转换为调色板
+        let image = DynamicImage::ImageRgba8(image::RgbaImage::from_fn(64, 48, |x, y| {
+            image::Rgba([(x / 16 * 60) as u8, (y / 16 * 60) as u8, 128, 255])
+        }));
+        let png_data = encode_image(&image, ImageEncoder::Png, &EncodeOptions::default()).unwrap();
+        let metadata = CaptureMetadata {
+            capture_time: Some(1_709_251_199_000),
+            window_title: Some(String::from("测试窗口")),
+            process_name: Some(String::from("snow-shot.exe")),
+            capture_mode: Some(CaptureMode::Window),
+            ..CaptureMetadata::default()
+        };
+        let png_data = embed_capture_metadata(png_data, ImageEncoder::Png, &metadata).unwrap();
+
+        let optimized_data = optimize_png_data(&png_data, 2).unwrap();
+        assert!(optimized_data.len() < png_data.len());
+
+        let chunk_types = png_chunk_types(&optimized_data);
+        for chunk_type in [b"tEXt", b"iTXt", b"eXIf"] {
+            assert!(
+                chunk_types.contains(chunk_type),
+                "missing chunk: {}",
+                String::from_utf8_lossy(chunk_type)
+            );
+        }
+        let optimized_xmp = String::from_utf8_lossy(&optimized_data);
+        assert!(optimized_xmp.contains(&metadata.to_xmp()));
+
+        let optimized_image = image::load_from_memory(&optimized_data).unwrap();
+        assert_eq!(optimized_image.to_rgba8(), image.to_rgba8());
+    }
+}
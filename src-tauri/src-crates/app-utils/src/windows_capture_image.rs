@@ -13,7 +13,9 @@ use windows_capture::settings::{
     SecondaryWindowSettings, Settings,
 };
 
-use crate::monitor_info::{ColorFormat, MonitorInfo};
+use crate::hdr_image::{HdrImage, SCRGB_REFERENCE_WHITE_NITS};
+use crate::hdr_tone_mapping::{HdrToneMapper, HdrToneMappingOptions};
+use crate::monitor_info::{ColorFormat, CorrectHdrColorAlgorithm, MonitorInfo};
 
 /// 全局标志：标记系统是否支持 DrawBorderSettings::WithoutBorder
 /// 默认值为 true，当遇到 BorderConfigUnsupported 错误时会设置为 false
@@ -148,30 +150,35 @@ fn linear_to_srgb_byte(linear: f32) -> u8 {
     }
 }
 
+#[inline]
+fn read_rgba16f_channel(rgba16f_image: *const u8, pixel_index: usize, channel: usize) -> f32 {
+    unsafe {
+        f16::from_bits(u16::from_le(
+            *(rgba16f_image.add(pixel_index * 8 + channel * 2) as *const u16),
+        ))
+        .to_f32()
+    }
+}
+
+#[inline]
+fn read_rgba16f_rgb(rgba16f_image: *const u8, pixel_index: usize) -> [f32; 3] {
+    [
+        read_rgba16f_channel(rgba16f_image, pixel_index, 0),
+        read_rgba16f_channel(rgba16f_image, pixel_index, 1),
+        read_rgba16f_channel(rgba16f_image, pixel_index, 2),
+    ]
+}
+
 #[inline]
 pub fn write_rgba16f_linear_to_rgb8(
     rgba16f_image: *const u8,
     rgb8_image: *mut u8,
-    hdr_scale: f32,
+    tone_mapper: &HdrToneMapper,
     pixel_index: usize,
 ) {
-    unsafe {
-        let red_f = f16::from_bits(u16::from_le(
-            *(rgba16f_image.add(pixel_index * 8) as *const u16),
-        ))
-        .to_f32()
-            * hdr_scale;
-        let green_f = f16::from_bits(u16::from_le(
-            *(rgba16f_image.add(pixel_index * 8 + 2) as *const u16),
-        ))
-        .to_f32()
-            * hdr_scale;
-        let blue_f = f16::from_bits(u16::from_le(
-            *(rgba16f_image.add(pixel_index * 8 + 4) as *const u16),
-        ))
-        .to_f32()
-            * hdr_scale;
+    let [red_f, green_f, blue_f] = tone_mapper.map(read_rgba16f_rgb(rgba16f_image, pixel_index));
 
+    unsafe {
         // 使用快速饱和转换
         rgb8_image
             .add(pixel_index * 3)
@@ -189,30 +196,13 @@ pub fn write_rgba16f_linear_to_rgb8(
 pub fn write_rgba16f_linear_to_rgba8(
     rgba16f_image: *const u8,
     rgba8_image: *mut u8,
-    hdr_scale: f32,
+    tone_mapper: &HdrToneMapper,
     pixel_index: usize,
 ) {
-    unsafe {
-        let red_f = f16::from_bits(u16::from_le(
-            *(rgba16f_image.add(pixel_index * 8) as *const u16),
-        ))
-        .to_f32()
-            * hdr_scale;
-        let green_f = f16::from_bits(u16::from_le(
-            *(rgba16f_image.add(pixel_index * 8 + 2) as *const u16),
-        ))
-        .to_f32()
-            * hdr_scale;
-        let blue_f = f16::from_bits(u16::from_le(
-            *(rgba16f_image.add(pixel_index * 8 + 4) as *const u16),
-        ))
-        .to_f32()
-            * hdr_scale;
-        let alpha_f = f16::from_bits(u16::from_le(
-            *(rgba16f_image.add(pixel_index * 8 + 6) as *const u16),
-        ))
-        .to_f32();
+    let [red_f, green_f, blue_f] = tone_mapper.map(read_rgba16f_rgb(rgba16f_image, pixel_index));
+    let alpha_f = read_rgba16f_channel(rgba16f_image, pixel_index, 3);
 
+    unsafe {
         // 使用快速饱和转换
         rgba8_image
             .add(pixel_index * 4)
@@ -230,22 +220,25 @@ pub fn write_rgba16f_linear_to_rgba8(
     }
 }
 
+/// 接收捕获的 Rgba16F 图像数据
+fn receive_captured_image(
+    receiver: std::sync::mpsc::Receiver<(Vec<u8>, usize, usize)>,
+) -> Result<(Vec<u8>, usize, usize), String> {
+    match receiver.recv() {
+        Ok(image) => Ok(image),
+        Err(e) => Err(format!(
+            "[windows_capture_image::receive_captured_image] failed to receive image: {:?}",
+            e
+        )),
+    }
+}
+
 /// 处理捕获的图像数据
 fn process_captured_image(
-    receiver: std::sync::mpsc::Receiver<(Vec<u8>, usize, usize)>,
-    monitor: &MonitorInfo,
+    (rgba16f_image, image_width, image_height): (&[u8], usize, usize),
     color_format: ColorFormat,
+    tone_mapper: HdrToneMapper,
 ) -> Result<image::DynamicImage, String> {
-    let (rgba16f_image, image_width, image_height) = match receiver.recv() {
-        Ok(image) => image,
-        Err(e) => {
-            return Err(format!(
-                "[windows_capture_image::process_captured_image] failed to receive image: {:?}",
-                e
-            ));
-        }
-    };
-
     let pixel_len = match color_format {
         ColorFormat::Rgb8 => 3,
         ColorFormat::Rgba8 => 4,
@@ -258,8 +251,6 @@ fn process_captured_image(
         image_pixels
     };
 
-    let hdr_scale = 1000.0 / (monitor.monitor_hdr_info.sdr_white_level as f32);
-
     let image_pixels_ptr = image_pixels.as_mut_ptr() as usize;
     let rgba16f_image_ptr = rgba16f_image.as_ptr() as usize;
     match color_format {
@@ -270,7 +261,7 @@ fn process_captured_image(
                     write_rgba16f_linear_to_rgb8(
                         rgba16f_image_ptr as *const u8,
                         image_pixels_ptr as *mut u8,
-                        hdr_scale,
+                        &tone_mapper,
                         i,
                     );
                 });
@@ -289,7 +280,7 @@ fn process_captured_image(
                     write_rgba16f_linear_to_rgba8(
                         rgba16f_image_ptr as *const u8,
                         image_pixels_ptr as *mut u8,
-                        hdr_scale,
+                        &tone_mapper,
                         i,
                     );
                 });
@@ -310,7 +301,80 @@ pub fn capture_monitor_image(
     window: Option<HWND>,
     crop_area: Option<ElementRect>,
     color_format: ColorFormat,
+    correct_hdr_color_algorithm: CorrectHdrColorAlgorithm,
+    hdr_tone_mapping: HdrToneMappingOptions,
+) -> Result<image::DynamicImage, String> {
+    let (rgba16f_image, image_width, image_height) =
+        capture_monitor_rgba16f(monitor, window, crop_area)?;
+
+    let tone_mapper = HdrToneMapper::new(
+        correct_hdr_color_algorithm,
+        hdr_tone_mapping,
+        get_sdr_white_nits(monitor),
+    );
+
+    process_captured_image(
+        (&rgba16f_image, image_width, image_height),
+        color_format,
+        tone_mapper,
+    )
+}
+
+/**
+ * 将已经捕获的 HDR 图像转换为 8 位图像，结果和 capture_monitor_image 一致
+ */
+pub fn convert_monitor_hdr_image(
+    hdr_image: &HdrImage,
+    color_format: ColorFormat,
+    correct_hdr_color_algorithm: CorrectHdrColorAlgorithm,
+    hdr_tone_mapping: HdrToneMappingOptions,
 ) -> Result<image::DynamicImage, String> {
+    let tone_mapper = HdrToneMapper::new(
+        correct_hdr_color_algorithm,
+        hdr_tone_mapping,
+        hdr_image.sdr_white_nits(),
+    );
+
+    process_captured_image(
+        (
+            hdr_image.pixels(),
+            hdr_image.width() as usize,
+            hdr_image.height() as usize,
+        ),
+        color_format,
+        tone_mapper,
+    )
+}
+
+/// sdr_white_level 以 1000 对应 80 尼特
+fn get_sdr_white_nits(monitor: &MonitorInfo) -> f32 {
+    monitor.monitor_hdr_info.sdr_white_level as f32 / 1000.0 * SCRGB_REFERENCE_WHITE_NITS
+}
+
+/**
+ * 捕获 HDR 图像并保留浮点数据，不转换为 8 位图像
+ */
+pub fn capture_monitor_hdr_image(
+    monitor: &MonitorInfo,
+    window: Option<HWND>,
+    crop_area: Option<ElementRect>,
+) -> Result<HdrImage, String> {
+    let (rgba16f_image, image_width, image_height) =
+        capture_monitor_rgba16f(monitor, window, crop_area)?;
+
+    HdrImage::new(
+        image_width as u32,
+        image_height as u32,
+        rgba16f_image,
+        get_sdr_white_nits(monitor),
+    )
+}
+
+fn capture_monitor_rgba16f(
+    monitor: &MonitorInfo,
+    window: Option<HWND>,
+    crop_area: Option<ElementRect>,
+) -> Result<(Vec<u8>, usize, usize), String> {
     // 检查系统是否支持 HDR 图像捕获
     if !SUPPORT_HDR_IMAGE.load(Ordering::Relaxed) {
         return Err(format!(
@@ -375,8 +439,8 @@ pub fn capture_monitor_image(
 
     match start_result {
         Ok(_capturer) => {
-            // 启动成功，处理捕获的图像
-            process_captured_image(receiver, monitor, color_format)
+            // 启动成功，接收捕获的图像
+            receive_captured_image(receiver)
         }
         Err(e) => match e {
             GraphicsCaptureApiError::GraphicsCaptureApiError(
@@ -432,8 +496,8 @@ pub fn capture_monitor_image(
                 // 重试启动捕获器
                 match start_result {
                     Ok(_capturer) => {
-                        // 重试成功，处理捕获的图像
-                        process_captured_image(retry_receiver, monitor, color_format)
+                        // 重试成功，接收捕获的图像
+                        receive_captured_image(retry_receiver)
                     }
                     Err(retry_e) => {
                         // 重试失败，标记系统不支持 HDR 图像捕获
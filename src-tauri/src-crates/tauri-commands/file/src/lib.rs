@@ -1,13 +1,82 @@
 use base64::prelude::*;
-use image::DynamicImage;
+use snow_shot_app_utils::capture_metadata::{self, CaptureMetadata};
+use snow_shot_app_utils::file_name_template::{
+    self, DailyCounter, FileNameTemplateContext, ResolvedFilePath,
+};
+use snow_shot_app_utils::{EncodeOptions, ImageEncoder, ImageMetadata};
+use std::borrow::Cow;
 use std::path::PathBuf;
 use tokio::fs;
-use zune_core::bit_depth::BitDepth;
-use zune_core::colorspace::ColorSpace;
-use zune_core::options::EncoderOptions;
-use zune_jpegxl::JxlSimpleEncoder;
 
-pub async fn save_file(request: tauri::ipc::Request<'_>) -> Result<(), String> {
+/**
+ * 读取请求头中以 base64 编码的 JSON 编码参数，没有时返回 None
+ */
+fn get_request_encode_options(
+    request: &tauri::ipc::Request<'_>,
+) -> Result<Option<EncodeOptions>, String> {
+    let header = match request.headers().get("x-encode-options") {
+        Some(header) => header,
+        None => return Ok(None),
+    };
+
+    let encode_options = match header
+        .to_str()
+        .ok()
+        .and_then(|header| BASE64_STANDARD.decode(header).ok())
+    {
+        Some(encode_options) => encode_options,
+        None => return Err(String::from("[get_request_encode_options] Invalid header")),
+    };
+
+    match serde_json::from_slice(&encode_options) {
+        Ok(encode_options) => Ok(Some(encode_options)),
+        Err(e) => Err(format!(
+            "[get_request_encode_options] Invalid encode options: {}",
+            e
+        )),
+    }
+}
+
+/**
+ * 读取请求头中以 base64 编码的 JSON 截图元数据，没有时返回 None
+ */
+fn get_request_capture_metadata(
+    request: &tauri::ipc::Request<'_>,
+) -> Result<Option<CaptureMetadata>, String> {
+    let header = match request.headers().get("x-capture-metadata") {
+        Some(header) => header,
+        None => return Ok(None),
+    };
+
+    let capture_metadata = match header
+        .to_str()
+        .ok()
+        .and_then(|header| BASE64_STANDARD.decode(header).ok())
+    {
+        Some(capture_metadata) => capture_metadata,
+        None => {
+            return Err(String::from(
+                "[get_request_capture_metadata] Invalid header",
+            ));
+        }
+    };
+
+    match serde_json::from_slice(&capture_metadata) {
+        Ok(capture_metadata) => Ok(Some(capture_metadata)),
+        Err(e) => Err(format!(
+            "[get_request_capture_metadata] Invalid capture metadata: {}",
+            e
+        )),
+    }
+}
+
+/**
+ * 保存文件，PNG 文件按编码参数在后台进行无损优化
+ */
+pub async fn save_file(
+    app_handle: tauri::AppHandle,
+    request: tauri::ipc::Request<'_>,
+) -> Result<(), String> {
     let file_data = match request.body() {
         tauri::ipc::InvokeBody::Raw(data) => data,
         _ => return Err(String::from("[save_file] Invalid request body")),
@@ -40,68 +109,130 @@ pub async fn save_file(request: tauri::ipc::Request<'_>) -> Result<(), String> {
         None => return Err(String::from("[save_file] Missing file type")),
     };
 
-    // 如果是 avif 则重写解码写入
-    if file_type == "image/avif" {
-        let image = match image::load_from_memory_with_format(
-            file_data.as_slice(),
-            image::ImageFormat::WebP,
-        ) {
-            Ok(image) => image,
-            Err(_) => return Err(String::from("[save_file] Invalid image")),
-        };
+    let encode_options = get_request_encode_options(&request)?;
+    let capture_metadata = get_request_capture_metadata(&request)?;
 
-        return match image.save_with_format(file_path, image::ImageFormat::Avif) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(format!(
-                "[save_file] Failed to save image to file: {}",
-                e.to_string()
-            )),
-        };
-    } else if file_type == "image/jpeg-xl" {
-        let image = match image::load_from_memory_with_format(
-            file_data.as_slice(),
-            image::ImageFormat::WebP,
-        ) {
+    // avif 和 jxl 由前端以 WebP 格式传入后重新编码，指定了编码参数时其他格式也重新编码
+    let encoder = match ImageEncoder::from_mime_type(&file_type) {
+        Some(encoder @ (ImageEncoder::Avif | ImageEncoder::Jxl)) => Some(encoder),
+        Some(encoder) if encode_options.is_some() => Some(encoder),
+        _ => None,
+    };
+    let encode_options = encode_options.unwrap_or_default();
+    let file_data = if let Some(encoder) = encoder {
+        let image = match image::load_from_memory(file_data.as_slice()) {
             Ok(image) => image,
             Err(_) => return Err(String::from("[save_file] Invalid image")),
         };
-        let has_alpha = image.color().has_alpha();
-        let image_data = if has_alpha {
-            DynamicImage::ImageRgba8(image.to_rgba8())
-        } else {
-            DynamicImage::ImageRgb8(image.to_rgb8())
-        };
-        let encoder = JxlSimpleEncoder::new(
-            image_data.as_bytes(),
-            EncoderOptions::new(
-                image.width() as usize,
-                image.height() as usize,
-                if has_alpha {
-                    ColorSpace::RGBA
-                } else {
-                    ColorSpace::RGB
-                },
-                BitDepth::Eight,
-            ),
-        );
-        let encoder_result = match encoder.encode() {
-            Ok(encoder_result) => encoder_result,
-            Err(_) => return Err(String::from("[save_file] Failed to encode image")),
-        };
-        return match fs::write(file_path, encoder_result).await {
-            Ok(_) => Ok(()),
-            Err(e) => Err(format!(
-                "[save_file] Failed to save image to file: {}",
-                e.to_string()
-            )),
-        };
-    }
 
-    match fs::write(file_path, file_data).await {
-        Ok(_) => Ok(()),
-        Err(e) => Err(format!(
+        Cow::Owned(snow_shot_app_utils::encode_image_with_metadata(
+            &image,
+            encoder,
+            &encode_options,
+            &ImageMetadata {
+                capture_metadata: capture_metadata.as_ref(),
+                ..ImageMetadata::default()
+            },
+        )?)
+    } else {
+        // 不重新编码时直接在图片数据中插入元数据
+        match (ImageEncoder::from_mime_type(&file_type), capture_metadata) {
+            (Some(encoder), Some(capture_metadata)) => {
+                match capture_metadata::embed_capture_metadata(
+                    file_data.clone(),
+                    encoder,
+                    &capture_metadata,
+                ) {
+                    Ok(file_data) => Cow::Owned(file_data),
+                    Err(e) => {
+                        log::warn!("[save_file] Failed to embed capture metadata: {}", e);
+                        Cow::Borrowed(file_data.as_slice())
+                    }
+                }
+            }
+            _ => Cow::Borrowed(file_data.as_slice()),
+        }
+    };
+
+    if let Err(e) = fs::write(&file_path, file_data).await {
+        return Err(format!(
             "[save_file] Failed to save image to file: {}",
             e.to_string()
+        ));
+    }
+
+    snow_shot_app_utils::png_optimizer::spawn_png_optimization(
+        &app_handle,
+        file_path,
+        &encode_options,
+    );
+
+    Ok(())
+}
+
+/**
+ * 估算图片按编码参数编码后的文件大小（字节），用于预览保存后的文件大小
+ *
+ * 请求体为任意可解码的图片数据，x-file-type 为目标格式
+ */
+pub async fn estimate_image_file_size(request: tauri::ipc::Request<'_>) -> Result<u64, String> {
+    let file_data = match request.body() {
+        tauri::ipc::InvokeBody::Raw(data) => data,
+        _ => {
+            return Err(String::from(
+                "[estimate_image_file_size] Invalid request body",
+            ));
+        }
+    };
+
+    let file_type = match request.headers().get("x-file-type") {
+        Some(header) => {
+            let file_type = match header.to_str() {
+                Ok(header) => BASE64_STANDARD
+                    .decode(header)
+                    .map_err(|e| e.to_string())
+                    .and_then(|file_type| String::from_utf8(file_type).map_err(|e| e.to_string())),
+                Err(e) => Err(e.to_string()),
+            };
+
+            match file_type {
+                Ok(file_type) => file_type,
+                Err(e) => {
+                    return Err(format!(
+                        "[estimate_image_file_size] Invalid file type: {}",
+                        e
+                    ));
+                }
+            }
+        }
+        None => return Err(String::from("[estimate_image_file_size] Missing file type")),
+    };
+
+    let encoder = match ImageEncoder::from_mime_type(&file_type) {
+        Some(encoder) => encoder,
+        None => {
+            return Err(format!(
+                "[estimate_image_file_size] Unsupported file type: {}",
+                file_type
+            ));
+        }
+    };
+    let encode_options = get_request_encode_options(&request)?.unwrap_or_default();
+
+    let image = match image::load_from_memory(file_data.as_slice()) {
+        Ok(image) => image,
+        Err(_) => return Err(String::from("[estimate_image_file_size] Invalid image")),
+    };
+
+    match tokio::task::spawn_blocking(move || {
+        snow_shot_app_utils::estimate_encoded_size(&image, encoder, &encode_options)
+    })
+    .await
+    {
+        Ok(estimate_result) => estimate_result,
+        Err(e) => Err(format!(
+            "[estimate_image_file_size] Failed to estimate file size: {}",
+            e
         )),
     }
 }
@@ -159,3 +290,26 @@ pub async fn remove_file(file_path: PathBuf) -> Result<(), String> {
         )),
     }
 }
+
+pub async fn resolve_file_path_template(
+    app: tauri::AppHandle,
+    path_template: String,
+    context: FileNameTemplateContext,
+) -> Result<ResolvedFilePath, String> {
+    file_name_template::resolve_file_path_template(&app, &path_template, &context)
+}
+
+pub async fn commit_daily_counter(
+    app: tauri::AppHandle,
+    daily_counter: DailyCounter,
+) -> Result<(), String> {
+    file_name_template::commit_daily_counter(&app, &daily_counter)
+}
+
+pub async fn preview_file_name_template(
+    app: tauri::AppHandle,
+    template: String,
+    context: FileNameTemplateContext,
+) -> Result<String, String> {
+    file_name_template::preview_file_name_template(&app, &template, &context)
+}
@@ -0,0 +1,170 @@
+use image::{DynamicImage, ImageFormat, Luma};
+use qrcode::QrCode;
+use rxing::{Exceptions, RXingResult};
+use serde::{Deserialize, Serialize};
+use snow_shot_app_shared::ElementRect;
+use std::io::Cursor;
+
+/// 生成二维码的边长范围（像素）
+const QR_CODE_MIN_SIZE: u32 = 64;
+const QR_CODE_MAX_SIZE: u32 = 4096;
+
+/**
+ * 支持识别的条码格式，其他格式的识别结果会被丢弃
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarcodeFormat {
+    QrCode,
+    DataMatrix,
+    Ean13,
+    Ean8,
+    UpcA,
+    UpcE,
+    Code128,
+}
+
+impl BarcodeFormat {
+    fn from_rxing(format: &rxing::BarcodeFormat) -> Option<Self> {
+        match format {
+            rxing::BarcodeFormat::QR_CODE => Some(BarcodeFormat::QrCode),
+            rxing::BarcodeFormat::DATA_MATRIX => Some(BarcodeFormat::DataMatrix),
+            rxing::BarcodeFormat::EAN_13 => Some(BarcodeFormat::Ean13),
+            rxing::BarcodeFormat::EAN_8 => Some(BarcodeFormat::Ean8),
+            rxing::BarcodeFormat::UPC_A => Some(BarcodeFormat::UpcA),
+            rxing::BarcodeFormat::UPC_E => Some(BarcodeFormat::UpcE),
+            rxing::BarcodeFormat::CODE_128 => Some(BarcodeFormat::Code128),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct BarcodePoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Barcode {
+    pub format: BarcodeFormat,
+    pub text: String,
+    /// rxing 识别时使用的定位点，不是条码的轮廓
+    ///
+    /// 二维码为三个定位图案（和校正图案）的中心，DataMatrix 为 L 形边框的角点，
+    /// 一维条码为扫描线的两个端点
+    pub points: Vec<BarcodePoint>,
+    /// 定位点的外接矩形，比条码实际区域小，一维条码的高度可能为 0
+    pub rect: ElementRect,
+}
+
+impl Barcode {
+    fn from_rxing(result: &RXingResult) -> Option<Self> {
+        let format = BarcodeFormat::from_rxing(result.getBarcodeFormat())?;
+
+        let points = result
+            .getPoints()
+            .iter()
+            .map(|point| BarcodePoint {
+                x: point.x,
+                y: point.y,
+            })
+            .collect::<Vec<_>>();
+        if points.is_empty() {
+            return None;
+        }
+
+        let mut rect = ElementRect {
+            min_x: i32::MAX,
+            min_y: i32::MAX,
+            max_x: i32::MIN,
+            max_y: i32::MIN,
+        };
+        for point in &points {
+            rect.min_x = rect.min_x.min(point.x.floor() as i32);
+            rect.min_y = rect.min_y.min(point.y.floor() as i32);
+            rect.max_x = rect.max_x.max(point.x.ceil() as i32);
+            rect.max_y = rect.max_y.max(point.y.ceil() as i32);
+        }
+
+        Some(Barcode {
+            format,
+            text: result.getText().to_string(),
+            points,
+            rect,
+        })
+    }
+}
+
+/**
+ * 识别图片中的二维码和条码
+ */
+pub fn detect_barcodes(image: &DynamicImage) -> Result<Vec<Barcode>, String> {
+    let luma = image.to_luma8();
+    let (width, height) = luma.dimensions();
+
+    let results = match rxing::helpers::detect_multiple_in_luma(luma.into_raw(), width, height) {
+        Ok(results) => results,
+        // 图片中没有条码
+        Err(Exceptions::NotFoundException(_)) => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(format!(
+                "[detect_barcodes] Failed to detect barcodes: {}",
+                e
+            ));
+        }
+    };
+
+    let mut barcodes = results
+        .iter()
+        .filter_map(Barcode::from_rxing)
+        .collect::<Vec<_>>();
+    // 按从上到下、从左到右的顺序排列
+    barcodes.sort_by_key(|barcode| (barcode.rect.min_y, barcode.rect.min_x));
+    barcodes.dedup_by(|a, b| a.format == b.format && a.text == b.text && a.rect == b.rect);
+
+    Ok(barcodes)
+}
+
+/**
+ * 将文本编码为二维码，返回 PNG 图片
+ */
+pub fn generate_qr_code(text: &str, size: u32) -> Result<Vec<u8>, String> {
+    let code = match QrCode::new(text.as_bytes()) {
+        Ok(code) => code,
+        Err(e) => {
+            return Err(format!("[generate_qr_code] Failed to encode text: {}", e));
+        }
+    };
+
+    let size = size.clamp(QR_CODE_MIN_SIZE, QR_CODE_MAX_SIZE);
+    let image = code.render::<Luma<u8>>().min_dimensions(size, size).build();
+
+    let mut png_data = Vec::new();
+    if let Err(e) = image.write_to(&mut Cursor::new(&mut png_data), ImageFormat::Png) {
+        return Err(format!("[generate_qr_code] Failed to encode image: {}", e));
+    }
+
+    Ok(png_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_and_detect_qr_code() {
+        let text = "https://example.com/?a=1&b=2";
+        let png_data = generate_qr_code(text, 256).unwrap();
+        let image = image::load_from_memory(&png_data).unwrap();
+        assert!(image.width() >= 256);
+
+        let barcodes = detect_barcodes(&image).unwrap();
+        assert_eq!(barcodes.len(), 1);
+        assert_eq!(barcodes[0].format, BarcodeFormat::QrCode);
+        assert_eq!(barcodes[0].text, text);
+        assert!(barcodes[0].rect.max_x > barcodes[0].rect.min_x);
+
+        let blank = DynamicImage::new_luma8(64, 64);
+        assert!(detect_barcodes(&blank).unwrap().is_empty());
+    }
+}
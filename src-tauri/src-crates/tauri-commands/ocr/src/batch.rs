@@ -0,0 +1,394 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// 批量识别时每处理完一张图片通知前端的事件
+pub const OCR_BATCH_PROGRESS_EVENT: &str = "ocr-batch-progress";
+
+/// 批量识别支持的图片扩展名
+const IMAGE_EXTENSIONS: [&str; 7] = ["png", "jpg", "jpeg", "webp", "bmp", "tif", "tiff"];
+
+const SIDECAR_TEXT_EXTENSION: &str = "ocr.txt";
+const SIDECAR_JSON_EXTENSION: &str = "ocr.json";
+
+/// 识别参数的哈希只保留前 16 位，和图片的哈希一起组成缓存的文件名
+const CACHE_PARAMS_KEY_LENGTH: usize = 16;
+
+/// 识别结果缓存的总大小上限，超过后删除最早写入的缓存
+pub const CACHE_MAX_SIZE: u64 = 256 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct OcrBatchOptions {
+    /// 递归识别子文件夹中的图片
+    pub recursive: bool,
+    /// 在图片旁写入 .ocr.txt 和 .ocr.json 文件，如 a.png.ocr.txt
+    pub write_sidecar: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct OcrBatchProgress {
+    pub batch_id: String,
+    pub file_path: PathBuf,
+    /// 已处理的图片数量，包含识别失败的图片
+    pub finished: usize,
+    pub total: usize,
+    /// 识别结果是否来自缓存
+    pub from_cache: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct OcrBatchFailure {
+    pub file_path: PathBuf,
+    pub error: String,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct OcrBatchResult {
+    pub total: usize,
+    /// 重新识别的图片数量
+    pub detected: usize,
+    /// 使用缓存结果的图片数量
+    pub from_cache: usize,
+    pub failed: Vec<OcrBatchFailure>,
+    /// 任务是否被取消，取消后剩余的图片不再识别
+    pub cancelled: bool,
+}
+
+pub fn is_image_file(file_path: &Path) -> bool {
+    let is_hidden = file_path
+        .file_name()
+        .is_some_and(|file_name| file_name.to_string_lossy().starts_with('.'));
+    if is_hidden {
+        return false;
+    }
+
+    file_path.extension().is_some_and(|extension| {
+        let extension = extension.to_string_lossy().to_ascii_lowercase();
+        IMAGE_EXTENSIONS.contains(&extension.as_str())
+    })
+}
+
+fn collect_dir_image_files(dir: &Path, recursive: bool, files: &mut Vec<PathBuf>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::warn!(
+                "[collect_dir_image_files] Failed to read dir: {} {}",
+                e,
+                dir.display()
+            );
+            return;
+        }
+    };
+
+    let mut dir_files = Vec::new();
+    let mut sub_dirs = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => sub_dirs.push(path),
+            Ok(file_type) if file_type.is_file() && is_image_file(&path) => dir_files.push(path),
+            _ => {}
+        }
+    }
+
+    dir_files.sort();
+    files.extend(dir_files);
+
+    if recursive {
+        sub_dirs.sort();
+        for sub_dir in sub_dirs {
+            collect_dir_image_files(&sub_dir, recursive, files);
+        }
+    }
+}
+
+/**
+ * 展开文件夹并过滤出图片文件，重复的路径只保留一次
+ */
+pub fn collect_image_files(paths: &[PathBuf], recursive: bool) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            collect_dir_image_files(path, recursive, &mut files);
+        } else if is_image_file(path) {
+            files.push(path.clone());
+        }
+    }
+
+    let mut visited = HashSet::new();
+    files.retain(|file| visited.insert(file.clone()));
+
+    files
+}
+
+/**
+ * 图片旁的文本和 JSON 文件的路径
+ *
+ * 扩展名追加在完整的文件名之后，a.png 和 a.jpg 的结果不会互相覆盖
+ */
+pub fn sidecar_paths(file_path: &Path) -> (PathBuf, PathBuf) {
+    let sidecar_path = |extension: &str| {
+        let mut path = file_path.as_os_str().to_os_string();
+        path.push(".");
+        path.push(extension);
+        PathBuf::from(path)
+    };
+
+    (
+        sidecar_path(SIDECAR_TEXT_EXTENSION),
+        sidecar_path(SIDECAR_JSON_EXTENSION),
+    )
+}
+
+pub fn hash_image(data: &[u8]) -> String {
+    blake3::hash(data).to_hex().to_string()
+}
+
+/**
+ * 以图片内容的哈希为键缓存识别结果，重复识别同一张图片时直接读取
+ */
+pub struct OcrResultCache {
+    dir: PathBuf,
+}
+
+impl OcrResultCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /**
+     * 识别参数不同时结果也不同，缓存的键由图片的哈希和识别参数的哈希组成
+     */
+    pub fn key(image_hash: &str, params: &impl Serialize) -> Result<String, String> {
+        let params = match serde_json::to_vec(params) {
+            Ok(params) => params,
+            Err(e) => {
+                return Err(format!(
+                    "[OcrResultCache::key] Failed to serialize params: {}",
+                    e
+                ));
+            }
+        };
+        let params_hash = blake3::hash(&params).to_hex();
+
+        Ok(format!(
+            "{}_{}",
+            image_hash,
+            &params_hash[..CACHE_PARAMS_KEY_LENGTH]
+        ))
+    }
+
+    fn file_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let data = tokio::fs::read(self.file_path(key)).await.ok()?;
+
+        match serde_json::from_slice(&data) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                log::warn!("[OcrResultCache::get] Invalid cache {}: {}", key, e);
+                None
+            }
+        }
+    }
+
+    pub async fn put<T: Serialize>(&self, key: &str, value: &T) -> Result<(), String> {
+        let data = match serde_json::to_vec(value) {
+            Ok(data) => data,
+            Err(e) => {
+                return Err(format!(
+                    "[OcrResultCache::put] Failed to serialize value: {}",
+                    e
+                ));
+            }
+        };
+
+        if let Err(e) = tokio::fs::create_dir_all(&self.dir).await {
+            return Err(format!(
+                "[OcrResultCache::put] Failed to create cache dir: {} {}",
+                e,
+                self.dir.display()
+            ));
+        }
+
+        // 先写入临时文件再重命名，避免读取到写了一半的缓存
+        let file_path = self.file_path(key);
+        let temp_file_path = file_path.with_extension("json.writing");
+        if let Err(e) = tokio::fs::write(&temp_file_path, data).await {
+            return Err(format!(
+                "[OcrResultCache::put] Failed to write cache: {} {}",
+                e,
+                temp_file_path.display()
+            ));
+        }
+        if let Err(e) = tokio::fs::rename(&temp_file_path, &file_path).await {
+            let _ = tokio::fs::remove_file(&temp_file_path).await;
+            return Err(format!(
+                "[OcrResultCache::put] Failed to replace cache: {} {}",
+                e,
+                file_path.display()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /**
+     * 缓存的总大小超过 max_size 时，按写入时间从早到晚删除缓存，返回删除的文件数量
+     */
+    pub async fn prune(&self, max_size: u64) -> Result<usize, String> {
+        let dir = self.dir.clone();
+        match tokio::task::spawn_blocking(move || prune_cache_dir(&dir, max_size)).await {
+            Ok(result) => result,
+            Err(e) => Err(format!(
+                "[OcrResultCache::prune] Failed to join task: {}",
+                e
+            )),
+        }
+    }
+}
+
+fn prune_cache_dir(dir: &Path, max_size: u64) -> Result<usize, String> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => {
+            return Err(format!(
+                "[OcrResultCache::prune] Failed to read cache dir: {} {}",
+                e,
+                dir.display()
+            ));
+        }
+    };
+
+    let mut files = entries
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if !metadata.is_file() {
+                return None;
+            }
+
+            let modified = metadata.modified().ok()?;
+            Some((modified, metadata.len(), entry.path()))
+        })
+        .collect::<Vec<_>>();
+
+    let mut total_size = files.iter().map(|(_, size, _)| size).sum::<u64>();
+    if total_size <= max_size {
+        return Ok(0);
+    }
+
+    files.sort();
+
+    let mut removed_count = 0;
+    for (_, size, file_path) in files {
+        if total_size <= max_size {
+            break;
+        }
+
+        match std::fs::remove_file(&file_path) {
+            Ok(_) => {
+                total_size -= size;
+                removed_count += 1;
+            }
+            Err(e) => log::warn!(
+                "[OcrResultCache::prune] Failed to remove cache: {} {}",
+                e,
+                file_path.display()
+            ),
+        }
+    }
+
+    Ok(removed_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_image_files() {
+        let dir = std::env::temp_dir().join(format!("ocr_batch_test_{}", std::process::id()));
+        let sub_dir = dir.join("sub");
+        std::fs::create_dir_all(&sub_dir).unwrap();
+        for file in [
+            "b.png",
+            "a.JPG",
+            "note.txt",
+            ".a.png.optimizing",
+            "a.ocr.json",
+        ] {
+            std::fs::write(dir.join(file), b"").unwrap();
+        }
+        std::fs::write(sub_dir.join("c.webp"), b"").unwrap();
+
+        let files = collect_image_files(&[dir.clone(), dir.join("b.png")], false);
+        assert_eq!(files, vec![dir.join("a.JPG"), dir.join("b.png")]);
+
+        let files = collect_image_files(std::slice::from_ref(&dir), true);
+        assert_eq!(
+            files,
+            vec![dir.join("a.JPG"), dir.join("b.png"), sub_dir.join("c.webp")]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let image_hash = hash_image(b"image");
+        let key = OcrResultCache::key(&image_hash, &(true, 1.0)).unwrap();
+
+        assert!(key.starts_with(&image_hash));
+        assert_eq!(key, OcrResultCache::key(&image_hash, &(true, 1.0)).unwrap());
+        assert_ne!(
+            key,
+            OcrResultCache::key(&image_hash, &(false, 1.0)).unwrap()
+        );
+        assert_eq!(
+            sidecar_paths(Path::new("dir/a.png")),
+            (
+                PathBuf::from("dir/a.png.ocr.txt"),
+                PathBuf::from("dir/a.png.ocr.json")
+            )
+        );
+        assert_ne!(
+            sidecar_paths(Path::new("dir/a.png")),
+            sidecar_paths(Path::new("dir/a.jpg"))
+        );
+    }
+
+    #[test]
+    fn test_prune_cache_dir() {
+        let dir = std::env::temp_dir().join(format!("ocr_cache_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let now = std::time::SystemTime::now();
+        for (i, file) in ["c.json", "a.json", "b.json"].iter().enumerate() {
+            let file_path = dir.join(file);
+            std::fs::write(&file_path, [0u8; 10]).unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(&file_path)
+                .unwrap()
+                .set_modified(now - std::time::Duration::from_secs(100 - i as u64))
+                .unwrap();
+        }
+
+        assert_eq!(prune_cache_dir(&dir, 30).unwrap(), 0);
+        assert_eq!(prune_cache_dir(&dir, 15).unwrap(), 2);
+        assert!(dir.join("b.json").exists());
+        assert!(!dir.join("a.json").exists());
+        assert!(!dir.join("c.json").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(prune_cache_dir(&dir, 15).unwrap(), 0);
+    }
+}
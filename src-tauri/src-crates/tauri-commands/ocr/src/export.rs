@@ -0,0 +1,759 @@
+use crate::layout::{OcrLayout, analyze_layout};
+use flate2::{Compression, write::ZlibEncoder};
+use image::RgbImage;
+use paddle_ocr_rs::ocr_result::TextBlock;
+use pdf_writer::types::{CidFontType, FontFlags, SystemInfo, TextRenderingMode, UnicodeCmap};
+use pdf_writer::{Content, Filter, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+use serde::{Deserialize, Serialize};
+use snow_shot_app_shared::ElementRect;
+use snow_shot_app_utils::escape_xml;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+
+/**
+ * 带有文本层的文档格式
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OcrDocumentFormat {
+    /// 图片上覆盖不可见文本层的 PDF，可以选中和搜索文本
+    SearchablePdf,
+    Hocr,
+    /// ALTO v4 XML
+    Alto,
+}
+
+impl OcrDocumentFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OcrDocumentFormat::SearchablePdf => "pdf",
+            OcrDocumentFormat::Hocr => "hocr",
+            OcrDocumentFormat::Alto => "xml",
+        }
+    }
+}
+
+const SOFTWARE_NAME: &str = "Snow Shot";
+
+pub(crate) fn block_rect(text_block: &TextBlock) -> ElementRect {
+    let mut rect = ElementRect {
+        min_x: i32::MAX,
+        min_y: i32::MAX,
+        max_x: i32::MIN,
+        max_y: i32::MIN,
+    };
+    for point in &text_block.box_points {
+        rect.min_x = rect.min_x.min(point.x as i32);
+        rect.min_y = rect.min_y.min(point.y as i32);
+        rect.max_x = rect.max_x.max(point.x as i32);
+        rect.max_y = rect.max_y.max(point.y as i32);
+    }
+
+    rect
+}
+
+/**
+ * 将文本块按空白拆分为单词，按字符数估算每个单词的位置
+ */
+fn split_words(text_block: &TextBlock) -> Vec<(&str, ElementRect)> {
+    let rect = block_rect(text_block);
+    let words = text_block.text.split_whitespace().collect::<Vec<_>>();
+    let total_chars = text_block.text.trim().chars().count().max(1) as f32;
+    let vertical = (rect.max_y - rect.min_y) > (rect.max_x - rect.min_x);
+    let (start, length) = if vertical {
+        (rect.min_y as f32, (rect.max_y - rect.min_y) as f32)
+    } else {
+        (rect.min_x as f32, (rect.max_x - rect.min_x) as f32)
+    };
+
+    let mut offset = 0;
+    let trimmed = text_block.text.trim();
+    words
+        .into_iter()
+        .map(|word| {
+            let word_offset = trimmed[offset..].find(word).unwrap_or(0) + offset;
+            offset = word_offset + word.len();
+
+            let char_start = trimmed[..word_offset].chars().count() as f32;
+            let char_end = char_start + word.chars().count() as f32;
+            let word_start = (start + length * char_start / total_chars).round() as i32;
+            let word_end = (start + length * char_end / total_chars).round() as i32;
+
+            let word_rect = if vertical {
+                ElementRect {
+                    min_y: word_start,
+                    max_y: word_end,
+                    ..rect
+                }
+            } else {
+                ElementRect {
+                    min_x: word_start,
+                    max_x: word_end,
+                    ..rect
+                }
+            };
+
+            (word, word_rect)
+        })
+        .collect()
+}
+
+fn layout_or_analyze<'a>(
+    text_blocks: &[TextBlock],
+    layout: Option<&'a OcrLayout>,
+) -> Cow<'a, OcrLayout> {
+    match layout {
+        Some(layout) => Cow::Borrowed(layout),
+        None => Cow::Owned(analyze_layout(text_blocks)),
+    }
+}
+
+fn hocr_bbox(rect: &ElementRect) -> String {
+    format!(
+        "bbox {} {} {} {}",
+        rect.min_x, rect.min_y, rect.max_x, rect.max_y
+    )
+}
+
+/**
+ * 生成 hOCR，栏、段落、行和单词分别对应 ocr_carea、ocr_par、ocr_line 和 ocrx_word
+ */
+pub fn render_hocr(
+    text_blocks: &[TextBlock],
+    layout: Option<&OcrLayout>,
+    image_width: u32,
+    image_height: u32,
+    image_file_name: &str,
+) -> String {
+    let layout = layout_or_analyze(text_blocks, layout);
+
+    let mut hocr = String::new();
+    hocr.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    hocr.push_str("<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">\n");
+    hocr.push_str("<html xmlns=\"http://www.w3.org/1999/xhtml\">\n<head>\n");
+    hocr.push_str(&format!("<title>{}</title>\n", escape_xml(image_file_name)));
+    hocr.push_str("<meta http-equiv=\"Content-Type\" content=\"text/html;charset=utf-8\" />\n");
+    hocr.push_str(&format!(
+        "<meta name=\"ocr-system\" content=\"{}\" />\n",
+        SOFTWARE_NAME
+    ));
+    hocr.push_str("<meta name=\"ocr-capabilities\" content=\"ocr_page ocr_carea ocr_par ocr_line ocrx_word\" />\n");
+    hocr.push_str("</head>\n<body>\n");
+    hocr.push_str(&format!(
+        "<div class=\"ocr_page\" id=\"page_1\" title=\"image &quot;{}&quot;; bbox 0 0 {} {}; ppageno 0\">\n",
+        escape_xml(image_file_name),
+        image_width,
+        image_height
+    ));
+
+    let mut line_id = 0;
+    let mut word_id = 0;
+    let mut paragraph_id = 0;
+    for (column_index, column) in layout.columns.iter().enumerate() {
+        hocr.push_str(&format!(
+            " <div class=\"ocr_carea\" id=\"block_1_{}\" title=\"{}\">\n",
+            column_index + 1,
+            hocr_bbox(&column.rect)
+        ));
+
+        for paragraph in &column.paragraphs {
+            paragraph_id += 1;
+            hocr.push_str(&format!(
+                "  <p class=\"ocr_par\" id=\"par_1_{}\" title=\"{}\">\n",
+                paragraph_id,
+                hocr_bbox(&paragraph.rect)
+            ));
+
+            let line_class = if paragraph.heading_level.is_some() {
+                "ocr_header"
+            } else {
+                "ocr_line"
+            };
+            for line in &paragraph.lines {
+                line_id += 1;
+                hocr.push_str(&format!(
+                    "   <span class=\"{}\" id=\"line_1_{}\" title=\"{}\">",
+                    line_class,
+                    line_id,
+                    hocr_bbox(&line.rect)
+                ));
+
+                let mut first_word = true;
+                for text_block in line
+                    .block_indices
+                    .iter()
+                    .filter_map(|&index| text_blocks.get(index))
+                {
+                    let confidence = (text_block.text_score * 100.0).round().clamp(0.0, 100.0);
+                    for (word, rect) in split_words(text_block) {
+                        word_id += 1;
+                        if !first_word {
+                            hocr.push(' ');
+                        }
+                        first_word = false;
+
+                        hocr.push_str(&format!(
+                            "<span class=\"ocrx_word\" id=\"word_1_{}\" title=\"{}; x_wconf {}\">{}</span>",
+                            word_id,
+                            hocr_bbox(&rect),
+                            confidence,
+                            escape_xml(word)
+                        ));
+                    }
+                }
+
+                hocr.push_str("</span>\n");
+            }
+
+            hocr.push_str("  </p>\n");
+        }
+
+        hocr.push_str(" </div>\n");
+    }
+
+    hocr.push_str("</div>\n</body>\n</html>\n");
+    hocr
+}
+
+fn alto_position(rect: &ElementRect) -> String {
+    format!(
+        "HPOS=\"{}\" VPOS=\"{}\" WIDTH=\"{}\" HEIGHT=\"{}\"",
+        rect.min_x,
+        rect.min_y,
+        rect.max_x - rect.min_x,
+        rect.max_y - rect.min_y
+    )
+}
+
+/**
+ * 生成 ALTO v4 XML，每个段落对应一个 TextBlock，坐标单位为像素
+ */
+pub fn render_alto(
+    text_blocks: &[TextBlock],
+    layout: Option<&OcrLayout>,
+    image_width: u32,
+    image_height: u32,
+    image_file_name: &str,
+) -> String {
+    let layout = layout_or_analyze(text_blocks, layout);
+
+    let mut alto = String::new();
+    alto.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    alto.push_str("<alto xmlns=\"http://www.loc.gov/standards/alto/ns-v4#\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" xsi:schemaLocation=\"http://www.loc.gov/standards/alto/ns-v4# http://www.loc.gov/standards/alto/v4/alto-4-2.xsd\">\n");
+    alto.push_str(" <Description>\n  <MeasurementUnit>pixel</MeasurementUnit>\n");
+    alto.push_str(&format!(
+        "  <sourceImageInformation>\n   <fileName>{}</fileName>\n  </sourceImageInformation>\n",
+        escape_xml(image_file_name)
+    ));
+    alto.push_str(&format!(
+        "  <OCRProcessing ID=\"OCR_0\">\n   <ocrProcessingStep>\n    <processingSoftware>\n     <softwareName>{}</softwareName>\n    </processingSoftware>\n   </ocrProcessingStep>\n  </OCRProcessing>\n",
+        SOFTWARE_NAME
+    ));
+    alto.push_str(" </Description>\n <Layout>\n");
+    alto.push_str(&format!(
+        "  <Page ID=\"page_0\" PHYSICAL_IMG_NR=\"1\" WIDTH=\"{}\" HEIGHT=\"{}\">\n",
+        image_width, image_height
+    ));
+    alto.push_str(&format!(
+        "   <PrintSpace HPOS=\"0\" VPOS=\"0\" WIDTH=\"{}\" HEIGHT=\"{}\">\n",
+        image_width, image_height
+    ));
+
+    let mut line_id = 0;
+    let mut string_id = 0;
+    for (block_id, paragraph) in layout
+        .columns
+        .iter()
+        .flat_map(|column| column.paragraphs.iter())
+        .enumerate()
+    {
+        alto.push_str(&format!(
+            "    <TextBlock ID=\"block_{}\" {}>\n",
+            block_id,
+            alto_position(&paragraph.rect)
+        ));
+
+        for line in &paragraph.lines {
+            alto.push_str(&format!(
+                "     <TextLine ID=\"line_{}\" {}>\n",
+                line_id,
+                alto_position(&line.rect)
+            ));
+            line_id += 1;
+
+            let mut first_word = true;
+            for text_block in line
+                .block_indices
+                .iter()
+                .filter_map(|&index| text_blocks.get(index))
+            {
+                let confidence = text_block.text_score.clamp(0.0, 1.0);
+                for (word, rect) in split_words(text_block) {
+                    if !first_word {
+                        alto.push_str("      <SP/>\n");
+                    }
+                    first_word = false;
+
+                    alto.push_str(&format!(
+                        "      <String ID=\"string_{}\" {} WC=\"{:.2}\" CONTENT=\"{}\"/>\n",
+                        string_id,
+                        alto_position(&rect),
+                        confidence,
+                        escape_xml(word)
+                    ));
+                    string_id += 1;
+                }
+            }
+
+            alto.push_str("     </TextLine>\n");
+        }
+
+        alto.push_str("    </TextBlock>\n");
+    }
+
+    alto.push_str("   </PrintSpace>\n  </Page>\n </Layout>\n</alto>\n");
+    alto
+}
+
+fn deflate(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(data)
+        .and_then(|_| encoder.finish())
+        .map_err(|e| format!("[deflate] Failed to compress data: {}", e))
+}
+
+/// 代理区的码位不是有效字符，用作基本多文种平面以外字符的 CID
+const PDF_SUPPLEMENTARY_CID_START: u16 = 0xD800;
+const PDF_SUPPLEMENTARY_CID_END: u16 = 0xDFFF;
+
+/**
+ * 将文本编码为 CID，并记录用到的 CID 和字符的对应关系
+ *
+ * 基本多文种平面的字符以码位作为 CID，其他字符依次分配代理区的 CID，
+ * 在 ToUnicode 中以 UTF-16 代理对映射回原字符，代理区用完后使用替换字符
+ */
+#[derive(Default)]
+struct PdfTextEncoder {
+    cid_chars: BTreeMap<u16, char>,
+    supplementary_cids: HashMap<char, u16>,
+}
+
+impl PdfTextEncoder {
+    fn cid(&mut self, c: char) -> u16 {
+        let (cid, c) = match u16::try_from(c as u32) {
+            Ok(code) => (code, c),
+            Err(_) => match self.supplementary_cids.get(&c) {
+                Some(&cid) => (cid, c),
+                None => {
+                    let next_cid =
+                        PDF_SUPPLEMENTARY_CID_START as usize + self.supplementary_cids.len();
+                    if next_cid <= PDF_SUPPLEMENTARY_CID_END as usize {
+                        self.supplementary_cids.insert(c, next_cid as u16);
+                        (next_cid as u16, c)
+                    } else {
+                        (0xFFFD, char::REPLACEMENT_CHARACTER)
+                    }
+                }
+            },
+        };
+        self.cid_chars.entry(cid).or_insert(c);
+
+        cid
+    }
+
+    /// 每个字符编码为 2 字节的 CID（Identity-H）
+    fn encode(&mut self, text: &str) -> Vec<u8> {
+        text.chars()
+            .flat_map(|c| self.cid(c).to_be_bytes())
+            .collect()
+    }
+
+    fn to_unicode_cmap(&self) -> Vec<u8> {
+        let mut cmap = UnicodeCmap::new(
+            Name(b"Adobe-Identity-UCS"),
+            SystemInfo {
+                registry: Str(b"Adobe"),
+                ordering: Str(b"UCS"),
+                supplement: 0,
+            },
+        );
+        for (&cid, &c) in &self.cid_chars {
+            cmap.pair(cid, c);
+        }
+
+        cmap.finish()
+    }
+}
+
+/// 页面的最大边长（点）
+const PDF_MAX_PAGE_SIZE: f32 = 14400.0;
+/// 字体的默认字宽（千分之一 em）
+const PDF_GLYPH_WIDTH: f32 = 500.0;
+
+const PDF_IMAGE_NAME: Name = Name(b"Im0");
+const PDF_FONT_NAME: Name = Name(b"F0");
+const PDF_GLYPHLESS_FONT_NAME: Name = Name(b"GlyphLessFont");
+
+/**
+ * 写入不可见文本的绘制指令，文本的基线和宽度与文本框对齐
+ */
+fn write_pdf_text(
+    content: &mut Content,
+    text_encoder: &mut PdfTextEncoder,
+    text_block: &TextBlock,
+    point_scale: f32,
+    page_height: f32,
+) {
+    let text = text_block.text.trim();
+    let char_count = text.chars().count();
+    if char_count == 0 || text_block.box_points.len() < 4 {
+        return;
+    }
+
+    let point = |index: usize| {
+        let point = &text_block.box_points[index];
+        (
+            point.x as f32 * point_scale,
+            page_height - point.y as f32 * point_scale,
+        )
+    };
+    let distance = |a: (f32, f32), b: (f32, f32)| (b.0 - a.0).hypot(b.1 - a.1);
+    let (top_left, top_right, bottom_left) = (point(0), point(1), point(3));
+
+    // 竖排的文本沿左边从上到下书写
+    let (start, end, thickness) = if char_count > 1
+        && distance(top_left, bottom_left) > distance(top_left, top_right) * 1.5
+    {
+        (top_left, bottom_left, distance(top_left, top_right))
+    } else {
+        (bottom_left, point(2), distance(top_left, bottom_left))
+    };
+
+    let length = distance(start, end);
+    if length <= 0.0 || thickness <= 0.0 {
+        return;
+    }
+
+    let (cos, sin) = ((end.0 - start.0) / length, (end.1 - start.1) / length);
+    let font_size = thickness;
+    // 每个字符对应一个 CID，宽度都为 PDF_GLYPH_WIDTH
+    let natural_width = char_count as f32 * PDF_GLYPH_WIDTH / 1000.0 * font_size;
+    let horizontal_scale = length / natural_width * 100.0;
+    // 基线位于文本框底部向上 1/5 处
+    let descent = thickness * 0.2;
+    let origin = (start.0 - sin * descent, start.1 + cos * descent);
+
+    content.set_font(PDF_FONT_NAME, font_size);
+    content.set_horizontal_scaling(horizontal_scale);
+    content.set_text_matrix([cos, sin, -sin, cos, origin.0, origin.1]);
+    content.show(Str(&text_encoder.encode(text)));
+}
+
+/**
+ * 生成可搜索的 PDF，图片作为页面背景，文本以不可见的方式覆盖在对应位置
+ * dpi 用于换算页面尺寸，通常为 96 乘以显示器的缩放比例
+ */
+pub fn render_searchable_pdf(
+    image: &RgbImage,
+    text_blocks: &[TextBlock],
+    dpi: f32,
+) -> Result<Vec<u8>, String> {
+    let dpi = if dpi > 0.0 { dpi } else { 96.0 };
+    // 页面尺寸不能超过 PDF 阅读器普遍支持的 200 英寸，较长的滚动截图按比例缩小页面
+    let max_side = image.width().max(image.height()).max(1) as f32;
+    let point_scale = (72.0 / dpi).min(PDF_MAX_PAGE_SIZE / max_side);
+    let page_width = image.width() as f32 * point_scale;
+    let page_height = image.height() as f32 * point_scale;
+
+    let mut content = Content::new();
+    content.save_state();
+    content.transform([page_width, 0.0, 0.0, page_height, 0.0, 0.0]);
+    content.x_object(PDF_IMAGE_NAME);
+    content.restore_state();
+    content.begin_text();
+    content.set_text_rendering_mode(TextRenderingMode::Invisible);
+    let mut text_encoder = PdfTextEncoder::default();
+    for text_block in text_blocks {
+        write_pdf_text(
+            &mut content,
+            &mut text_encoder,
+            text_block,
+            point_scale,
+            page_height,
+        );
+    }
+    content.end_text();
+
+    let mut pdf = Pdf::new();
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let page_id = Ref::new(3);
+    let content_id = Ref::new(4);
+    let image_id = Ref::new(5);
+    let font_id = Ref::new(6);
+    let cid_font_id = Ref::new(7);
+    let font_descriptor_id = Ref::new(8);
+    let to_unicode_id = Ref::new(9);
+    let info_id = Ref::new(10);
+
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id).kids([page_id]).count(1);
+
+    let mut page = pdf.page(page_id);
+    page.media_box(Rect::new(0.0, 0.0, page_width, page_height));
+    page.parent(page_tree_id);
+    page.contents(content_id);
+    let mut resources = page.resources();
+    resources.x_objects().pair(PDF_IMAGE_NAME, image_id);
+    resources.fonts().pair(PDF_FONT_NAME, font_id);
+    resources.finish();
+    page.finish();
+
+    let content_data = deflate(&content.finish())?;
+    pdf.stream(content_id, &content_data)
+        .filter(Filter::FlateDecode);
+
+    let image_data = deflate(image.as_raw())?;
+    let mut image_xobject = pdf.image_xobject(image_id, &image_data);
+    image_xobject.filter(Filter::FlateDecode);
+    image_xobject.width(image.width() as i32);
+    image_xobject.height(image.height() as i32);
+    image_xobject.color_space().device_rgb();
+    image_xobject.bits_per_component(8);
+    image_xobject.finish();
+
+    // 文本不可见，字体不嵌入字形，仅通过 ToUnicode 提供文本内容
+    pdf.type0_font(font_id)
+        .base_font(PDF_GLYPHLESS_FONT_NAME)
+        .encoding_predefined(Name(b"Identity-H"))
+        .descendant_font(cid_font_id)
+        .to_unicode(to_unicode_id);
+    pdf.cid_font(cid_font_id)
+        .subtype(CidFontType::Type2)
+        .base_font(PDF_GLYPHLESS_FONT_NAME)
+        .system_info(SystemInfo {
+            registry: Str(b"Adobe"),
+            ordering: Str(b"Identity"),
+            supplement: 0,
+        })
+        .font_descriptor(font_descriptor_id)
+        .default_width(PDF_GLYPH_WIDTH)
+        .cid_to_gid_map_predefined(Name(b"Identity"));
+    pdf.font_descriptor(font_descriptor_id)
+        .name(PDF_GLYPHLESS_FONT_NAME)
+        .flags(FontFlags::FIXED_PITCH | FontFlags::SYMBOLIC)
+        .bbox(Rect::new(0.0, -200.0, PDF_GLYPH_WIDTH, 800.0))
+        .italic_angle(0.0)
+        .ascent(800.0)
+        .descent(-200.0)
+        .cap_height(800.0)
+        .stem_v(80.0);
+
+    let to_unicode_data = deflate(&text_encoder.to_unicode_cmap())?;
+    pdf.stream(to_unicode_id, &to_unicode_data)
+        .filter(Filter::FlateDecode);
+
+    pdf.document_info(info_id).producer(TextStr(SOFTWARE_NAME));
+
+    Ok(pdf.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::text_block;
+    use flate2::read::ZlibDecoder;
+    use std::io::Read;
+
+    const IMAGE_FILE_NAME: &str = "shot \"1\" & 2.png";
+
+    /// 同一段落中的两行，第一行包含需要转义的字符
+    fn paragraph_blocks() -> Vec<TextBlock> {
+        vec![
+            text_block(0, 0, 100, 20, "a<b & c"),
+            text_block(0, 25, 100, 45, "second line"),
+        ]
+    }
+
+    /// 按顺序查找各个片段，确认片段依次出现
+    fn assert_in_order(text: &str, parts: &[&str]) {
+        let mut offset = 0;
+        for part in parts {
+            match text[offset..].find(part) {
+                Some(position) => offset += position + part.len(),
+                None => panic!("{} not found after offset {} in:\n{}", part, offset, text),
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_hocr() {
+        let hocr = render_hocr(&paragraph_blocks(), None, 200, 100, IMAGE_FILE_NAME);
+
+        assert!(hocr.contains("<title>shot &quot;1&quot; &amp; 2.png</title>"));
+        assert!(hocr.contains("bbox 0 0 200 100; ppageno 0"));
+        assert_in_order(
+            &hocr,
+            &[
+                "<div class=\"ocr_carea\" id=\"block_1_1\" title=\"bbox 0 0 100 45\">",
+                "<p class=\"ocr_par\" id=\"par_1_1\" title=\"bbox 0 0 100 45\">",
+                "<span class=\"ocr_line\" id=\"line_1_1\" title=\"bbox 0 0 100 20\">",
+                "<span class=\"ocrx_word\" id=\"word_1_1\" title=\"bbox 0 0 43 20; x_wconf 100\">a&lt;b</span>",
+                " <span class=\"ocrx_word\" id=\"word_1_2\" title=\"bbox 57 0 71 20; x_wconf 100\">&amp;</span>",
+                " <span class=\"ocrx_word\" id=\"word_1_3\" title=\"bbox 86 0 100 20; x_wconf 100\">c</span>",
+                "</span>\n",
+                "<span class=\"ocr_line\" id=\"line_1_2\" title=\"bbox 0 25 100 45\">",
+                "<span class=\"ocrx_word\" id=\"word_1_4\" title=\"bbox 0 25 55 45; x_wconf 100\">second</span>",
+                " <span class=\"ocrx_word\" id=\"word_1_5\" title=\"bbox 64 25 100 45; x_wconf 100\">line</span>",
+                "</span>\n",
+                "</p>\n",
+                " </div>\n",
+                "</div>\n</body>\n</html>\n",
+            ],
+        );
+
+        for (open_tag, close_tag) in [("<div", "</div>"), ("<p ", "</p>"), ("<span", "</span>")] {
+            assert_eq!(
+                hocr.matches(open_tag).count(),
+                hocr.matches(close_tag).count()
+            );
+        }
+        assert_eq!(hocr.matches("class=\"ocrx_word\"").count(), 5);
+    }
+
+    #[test]
+    fn test_render_alto() {
+        let alto = render_alto(&paragraph_blocks(), None, 200, 100, IMAGE_FILE_NAME);
+
+        assert!(alto.contains("<fileName>shot &quot;1&quot; &amp; 2.png</fileName>"));
+        assert_in_order(
+            &alto,
+            &[
+                "<Page ID=\"page_0\" PHYSICAL_IMG_NR=\"1\" WIDTH=\"200\" HEIGHT=\"100\">",
+                "<PrintSpace HPOS=\"0\" VPOS=\"0\" WIDTH=\"200\" HEIGHT=\"100\">",
+                "<TextBlock ID=\"block_0\" HPOS=\"0\" VPOS=\"0\" WIDTH=\"100\" HEIGHT=\"45\">",
+                "<TextLine ID=\"line_0\" HPOS=\"0\" VPOS=\"0\" WIDTH=\"100\" HEIGHT=\"20\">",
+                "<String ID=\"string_0\" HPOS=\"0\" VPOS=\"0\" WIDTH=\"43\" HEIGHT=\"20\" WC=\"1.00\" CONTENT=\"a&lt;b\"/>",
+                "<SP/>",
+                "<String ID=\"string_1\" HPOS=\"57\" VPOS=\"0\" WIDTH=\"14\" HEIGHT=\"20\" WC=\"1.00\" CONTENT=\"&amp;\"/>",
+                "<SP/>",
+                "<String ID=\"string_2\" HPOS=\"86\" VPOS=\"0\" WIDTH=\"14\" HEIGHT=\"20\" WC=\"1.00\" CONTENT=\"c\"/>",
+                "</TextLine>",
+                "<TextLine ID=\"line_1\" HPOS=\"0\" VPOS=\"25\" WIDTH=\"100\" HEIGHT=\"20\">",
+                "<String ID=\"string_3\" HPOS=\"0\" VPOS=\"25\" WIDTH=\"55\" HEIGHT=\"20\" WC=\"1.00\" CONTENT=\"second\"/>",
+                "<SP/>",
+                "<String ID=\"string_4\" HPOS=\"64\" VPOS=\"25\" WIDTH=\"36\" HEIGHT=\"20\" WC=\"1.00\" CONTENT=\"line\"/>",
+                "</TextLine>",
+                "</TextBlock>",
+                "</PrintSpace>\n  </Page>\n </Layout>\n</alto>\n",
+            ],
+        );
+        assert_eq!(alto.matches("<TextLine ").count(), 2);
+        assert_eq!(alto.matches("<SP/>").count(), 3);
+    }
+
+    /// 读取 PDF 中指定对象的流，并解压数据
+    fn pdf_stream(pdf: &[u8], id: i32) -> Vec<u8> {
+        let find = |needle: &[u8], from: usize| {
+            from + pdf[from..]
+                .windows(needle.len())
+                .position(|window| window == needle)
+                .unwrap()
+        };
+
+        let object_start = find(format!("\n{} 0 obj", id).as_bytes(), 0);
+        let data_start = find(b"stream\n", object_start) + b"stream\n".len();
+        let data_end = find(b"\nendstream", data_start);
+
+        let mut data = Vec::new();
+        ZlibDecoder::new(&pdf[data_start..data_end])
+            .read_to_end(&mut data)
+            .unwrap();
+        data
+    }
+
+    /// 解析 ToUnicode 中单个 CID 到文本的映射
+    fn parse_to_unicode(cmap: &str) -> HashMap<u16, String> {
+        cmap.lines()
+            .filter_map(|line| {
+                let (cid, text) = line.strip_prefix('<')?.split_once("> <")?;
+                let text = text.strip_suffix('>')?;
+                let units = (0..text.len())
+                    .step_by(4)
+                    .map(|i| u16::from_str_radix(&text[i..i + 4], 16).unwrap())
+                    .collect::<Vec<_>>();
+
+                Some((
+                    u16::from_str_radix(cid, 16).unwrap(),
+                    String::from_utf16(&units).unwrap(),
+                ))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_render_searchable_pdf() {
+        let image = RgbImage::new(200, 100);
+        let text_blocks = vec![
+            text_block(10, 10, 90, 30, "中文识别"),
+            text_block(10, 40, 90, 60, "扫描文档"),
+        ];
+        let pdf = render_searchable_pdf(&image, &text_blocks, 72.0).unwrap();
+
+        let pdf_text = String::from_utf8_lossy(&pdf);
+        assert!(pdf_text.contains("/MediaBox [0 0 200 100]"));
+        assert!(pdf_text.contains("/Encoding /Identity-H"));
+        assert!(pdf_text.contains("/ToUnicode 9 0 R"));
+
+        // 图片铺满页面，文本使用不可见模式绘制，字号为文本框高度
+        let content = String::from_utf8(pdf_stream(&pdf, 4)).unwrap();
+        assert_in_order(
+            &content,
+            &[
+                "/Im0 Do",
+                "BT",
+                "3 Tr",
+                "/F0 20 Tf",
+                "Tz",
+                "Tm",
+                "Tj",
+                "/F0 20 Tf",
+                "Tj",
+                "ET",
+            ],
+        );
+
+        // 通过 ToUnicode 将显示的 CID 还原为原文本
+        let to_unicode = parse_to_unicode(&String::from_utf8(pdf_stream(&pdf, 9)).unwrap());
+        let shown_text = content
+            .lines()
+            .filter_map(|line| line.strip_suffix("> Tj")?.strip_prefix('<'))
+            .map(|hex| {
+                (0..hex.len())
+                    .step_by(4)
+                    .map(|i| to_unicode[&u16::from_str_radix(&hex[i..i + 4], 16).unwrap()].as_str())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(shown_text, vec!["中文识别", "扫描文档"]);
+    }
+
+    #[test]
+    fn test_pdf_text_encoder() {
+        let mut text_encoder = PdfTextEncoder::default();
+
+        assert_eq!(
+            text_encoder.encode("A中😀😀"),
+            [0x00, 0x41, 0x4E, 0x2D, 0xD8, 0x00, 0xD8, 0x00]
+        );
+        assert_eq!(text_encoder.encode("𠀀"), [0xD8, 0x01]);
+
+        let cmap = String::from_utf8(text_encoder.to_unicode_cmap()).unwrap();
+        assert!(cmap.contains("4 beginbfchar\n"));
+        assert!(cmap.contains("<0041> <0041>\n"));
+        assert!(cmap.contains("<4E2D> <4E2D>\n"));
+        assert!(cmap.contains("<D800> <D83DDE00>\n"));
+        assert!(cmap.contains("<D801> <D840DC00>\n"));
+    }
+}
@@ -0,0 +1,856 @@
+use paddle_ocr_rs::ocr_result::TextBlock;
+use serde::{Deserialize, Serialize};
+use snow_shot_app_shared::ElementRect;
+
+/**
+ * 文本的书写方向
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OcrWritingMode {
+    /// 横排，从左到右
+    HorizontalLtr,
+    /// 横排，从右到左，如阿拉伯语、希伯来语
+    HorizontalRtl,
+    /// 竖排，从上到下，行从右到左，如竖排的中文、日文
+    VerticalRl,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OcrLayoutLine {
+    pub rect: ElementRect,
+    /// 行内文本块在 text_blocks 中的索引，按阅读顺序排列
+    pub block_indices: Vec<usize>,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OcrLayoutParagraph {
+    pub rect: ElementRect,
+    /// 标题级别，字号明显大于正文的段落视为标题
+    pub heading_level: Option<u8>,
+    pub lines: Vec<OcrLayoutLine>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OcrLayoutColumn {
+    pub rect: ElementRect,
+    pub paragraphs: Vec<OcrLayoutParagraph>,
+}
+
+/**
+ * 按阅读顺序排列的版面结构
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OcrLayout {
+    pub writing_mode: OcrWritingMode,
+    pub columns: Vec<OcrLayoutColumn>,
+    /// 纯文本，段落之间以空行分隔
+    pub text: String,
+    pub markdown: String,
+}
+
+/// 同一行的文本块在行方向上的最大间距（相对行高）
+const LINE_MAX_GAP_RATIO: f32 = 1.2;
+/// 同一行的文本块在换行方向上的最小重叠（相对较小的行高）
+const LINE_MIN_OVERLAP_RATIO: f32 = 0.5;
+/// 分栏的最小间距（相对行高的中位数）
+const COLUMN_MIN_GAP_RATIO: f32 = 1.0;
+/// 段落之间的最小行距（相对行高）
+const PARAGRAPH_MIN_GAP_RATIO: f32 = 0.8;
+/// 相邻两行的行高之比超过该值时视为不同段落
+const PARAGRAPH_MAX_THICKNESS_RATIO: f32 = 1.4;
+
+/**
+ * 以阅读方向为坐标轴的矩形
+ * inline 为行内文字的前进方向，block 为换行的方向
+ */
+#[derive(Debug, Clone, Copy)]
+struct FlowRect {
+    inline_start: f32,
+    inline_end: f32,
+    block_start: f32,
+    block_end: f32,
+}
+
+impl FlowRect {
+    fn thickness(&self) -> f32 {
+        self.block_end - self.block_start
+    }
+
+    fn width(&self) -> f32 {
+        self.inline_end - self.inline_start
+    }
+
+    fn block_center(&self) -> f32 {
+        (self.block_start + self.block_end) * 0.5
+    }
+
+    fn union(&self, other: &FlowRect) -> FlowRect {
+        FlowRect {
+            inline_start: self.inline_start.min(other.inline_start),
+            inline_end: self.inline_end.max(other.inline_end),
+            block_start: self.block_start.min(other.block_start),
+            block_end: self.block_end.max(other.block_end),
+        }
+    }
+
+    fn block_overlap(&self, other: &FlowRect) -> f32 {
+        self.block_end.min(other.block_end) - self.block_start.max(other.block_start)
+    }
+
+    fn inline_overlap(&self, other: &FlowRect) -> f32 {
+        self.inline_end.min(other.inline_end) - self.inline_start.max(other.inline_start)
+    }
+}
+
+pub(crate) struct LayoutBlock {
+    /// 在 text_blocks 中的索引
+    pub(crate) index: usize,
+    pub(crate) text: String,
+    points: [(f32, f32); 4],
+    pub(crate) rect: ElementRect,
+    flow: FlowRect,
+}
+
+impl LayoutBlock {
+    pub(crate) fn new(index: usize, points: [(f32, f32); 4], text: String) -> Self {
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+        for (x, y) in points {
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+
+        Self {
+            index,
+            text,
+            points,
+            rect: ElementRect {
+                min_x: min_x.floor() as i32,
+                min_y: min_y.floor() as i32,
+                max_x: max_x.ceil() as i32,
+                max_y: max_y.ceil() as i32,
+            },
+            flow: FlowRect {
+                inline_start: 0.0,
+                inline_end: 0.0,
+                block_start: 0.0,
+                block_end: 0.0,
+            },
+        }
+    }
+
+    fn width(&self) -> f32 {
+        (self.rect.max_x - self.rect.min_x) as f32
+    }
+
+    fn height(&self) -> f32 {
+        (self.rect.max_y - self.rect.min_y) as f32
+    }
+
+    /// 最长边的方向，竖排时相对 y 轴，横排时相对 x 轴
+    fn skew_angle(&self, vertical: bool) -> Option<f32> {
+        let edge = |from: usize, to: usize| {
+            let (x0, y0) = self.points[from];
+            let (x1, y1) = self.points[to];
+            (x1 - x0, y1 - y0)
+        };
+        let (ax, ay) = edge(0, 1);
+        let (bx, by) = edge(1, 2);
+        let a_len = ax.hypot(ay);
+        let b_len = bx.hypot(by);
+
+        // 接近正方形的文本块无法判断方向
+        let (dx, dy) = if a_len >= b_len * 2.0 {
+            (ax, ay)
+        } else if b_len >= a_len * 2.0 {
+            (bx, by)
+        } else {
+            return None;
+        };
+
+        let mut angle = if vertical {
+            (-dx).atan2(dy)
+        } else {
+            dy.atan2(dx)
+        };
+        // 边的方向不区分首尾
+        if angle > std::f32::consts::FRAC_PI_2 {
+            angle -= std::f32::consts::PI;
+        } else if angle <= -std::f32::consts::FRAC_PI_2 {
+            angle += std::f32::consts::PI;
+        }
+
+        if angle.abs() < std::f32::consts::FRAC_PI_4 {
+            Some(angle)
+        } else {
+            None
+        }
+    }
+}
+
+struct LayoutLine {
+    blocks: Vec<usize>,
+    flow: FlowRect,
+}
+
+fn is_rtl_char(c: char) -> bool {
+    matches!(c as u32, 0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF)
+}
+
+/// 不使用空格分词的文字（中文、日文），韩文使用空格分词
+pub(crate) fn is_cjk_char(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x3000..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF | 0xFF00..=0xFFEF
+    )
+}
+
+fn detect_writing_mode(blocks: &[LayoutBlock]) -> OcrWritingMode {
+    let mut vertical_count = 0;
+    let mut horizontal_count = 0;
+    let mut rtl_chars = 0;
+    let mut ltr_chars = 0;
+
+    for block in blocks {
+        let char_count = block.text.chars().count();
+        if char_count > 1 {
+            // 与前端的判断保持一致，高度明显大于宽度时视为竖排
+            if block.height() > block.width() * 1.5 {
+                vertical_count += 1;
+            } else if block.width() > block.height() * 1.5 {
+                horizontal_count += 1;
+            }
+        }
+
+        for c in block.text.chars() {
+            if is_rtl_char(c) {
+                rtl_chars += 1;
+            } else if c.is_alphabetic() {
+                ltr_chars += 1;
+            }
+        }
+    }
+
+    if vertical_count > horizontal_count {
+        OcrWritingMode::VerticalRl
+    } else if rtl_chars > ltr_chars {
+        OcrWritingMode::HorizontalRtl
+    } else {
+        OcrWritingMode::HorizontalLtr
+    }
+}
+
+fn median(values: &mut [f32]) -> Option<f32> {
+    if values.is_empty() {
+        return None;
+    }
+
+    values.sort_by(|a, b| a.total_cmp(b));
+    Some(values[values.len() / 2])
+}
+
+/**
+ * 计算文本块在阅读方向坐标系中的位置，倾斜的图片先按文本块的倾斜角度的中位数校正
+ */
+fn compute_flow_rects(blocks: &mut [LayoutBlock], writing_mode: OcrWritingMode) {
+    let vertical = writing_mode == OcrWritingMode::VerticalRl;
+    let mut angles = blocks
+        .iter()
+        .filter_map(|block| block.skew_angle(vertical))
+        .collect::<Vec<_>>();
+    let skew = median(&mut angles).unwrap_or(0.0);
+    let (sin, cos) = (-skew).sin_cos();
+
+    for block in blocks.iter_mut() {
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+        for (x, y) in block.points {
+            let (x, y) = (x * cos - y * sin, x * sin + y * cos);
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+
+        block.flow = match writing_mode {
+            OcrWritingMode::HorizontalLtr => FlowRect {
+                inline_start: min_x,
+                inline_end: max_x,
+                block_start: min_y,
+                block_end: max_y,
+            },
+            OcrWritingMode::HorizontalRtl => FlowRect {
+                inline_start: -max_x,
+                inline_end: -min_x,
+                block_start: min_y,
+                block_end: max_y,
+            },
+            OcrWritingMode::VerticalRl => FlowRect {
+                inline_start: min_y,
+                inline_end: max_y,
+                block_start: -max_x,
+                block_end: -min_x,
+            },
+        };
+    }
+}
+
+fn can_join_line(a: &FlowRect, b: &FlowRect) -> bool {
+    let min_thickness = a.thickness().min(b.thickness()).max(1.0);
+    let max_thickness = a.thickness().max(b.thickness()).max(1.0);
+
+    if a.block_overlap(b) < min_thickness * LINE_MIN_OVERLAP_RATIO {
+        return false;
+    }
+
+    let gap = (b.inline_start - a.inline_end).max(a.inline_start - b.inline_end);
+    gap <= max_thickness * LINE_MAX_GAP_RATIO
+}
+
+/**
+ * 将文本块合并为行
+ */
+fn group_lines(blocks: &[LayoutBlock]) -> Vec<LayoutLine> {
+    let mut order = (0..blocks.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| {
+        blocks[a]
+            .flow
+            .block_center()
+            .total_cmp(&blocks[b].flow.block_center())
+            .then(
+                blocks[a]
+                    .flow
+                    .inline_start
+                    .total_cmp(&blocks[b].flow.inline_start),
+            )
+    });
+
+    let mut lines: Vec<LayoutLine> = Vec::new();
+    for index in order {
+        let flow = blocks[index].flow;
+        let best_line = lines
+            .iter_mut()
+            .filter(|line| can_join_line(&line.flow, &flow))
+            .max_by(|a, b| {
+                a.flow
+                    .block_overlap(&flow)
+                    .total_cmp(&b.flow.block_overlap(&flow))
+            });
+
+        match best_line {
+            Some(line) => {
+                line.blocks.push(index);
+                line.flow = line.flow.union(&flow);
+            }
+            None => lines.push(LayoutLine {
+                blocks: vec![index],
+                flow,
+            }),
+        }
+    }
+
+    // 行的范围扩大后，之前无法合并的行可能可以合并
+    let mut merged = true;
+    while merged {
+        merged = false;
+        'outer: for i in 0..lines.len() {
+            for j in (i + 1)..lines.len() {
+                if can_join_line(&lines[i].flow, &lines[j].flow) {
+                    let line = lines.remove(j);
+                    lines[i].blocks.extend(line.blocks);
+                    lines[i].flow = lines[i].flow.union(&line.flow);
+                    merged = true;
+                    break 'outer;
+                }
+            }
+        }
+    }
+
+    for line in lines.iter_mut() {
+        line.blocks.sort_by(|&a, &b| {
+            blocks[a]
+                .flow
+                .inline_start
+                .total_cmp(&blocks[b].flow.inline_start)
+        });
+    }
+
+    lines
+}
+
+fn union_flow(lines: &[LayoutLine], indices: &[usize]) -> FlowRect {
+    indices
+        .iter()
+        .skip(1)
+        .fold(lines[indices[0]].flow, |rect, &index| {
+            rect.union(&lines[index].flow)
+        })
+}
+
+/**
+ * 沿行方向寻找贯穿所有行的空白，将行分为多栏
+ */
+fn inline_cut(lines: &[LayoutLine], indices: &[usize], min_gap: f32) -> Option<Vec<Vec<usize>>> {
+    let mut sorted = indices.to_vec();
+    sorted.sort_by(|&a, &b| {
+        lines[a]
+            .flow
+            .inline_start
+            .total_cmp(&lines[b].flow.inline_start)
+    });
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut current_end = f32::MIN;
+    for index in sorted {
+        let flow = &lines[index].flow;
+        match groups.last_mut() {
+            Some(group) if flow.inline_start - current_end < min_gap => group.push(index),
+            _ => groups.push(vec![index]),
+        }
+        current_end = current_end.max(flow.inline_end);
+    }
+
+    if groups.len() > 1 { Some(groups) } else { None }
+}
+
+/**
+ * 沿换行方向寻找空白，将行分为多个横向的区域
+ */
+fn block_cut(lines: &[LayoutLine], indices: &[usize]) -> Vec<Vec<usize>> {
+    let mut sorted = indices.to_vec();
+    sorted.sort_by(|&a, &b| {
+        lines[a]
+            .flow
+            .block_start
+            .total_cmp(&lines[b].flow.block_start)
+    });
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut current_end = f32::MIN;
+    for index in sorted {
+        let flow = &lines[index].flow;
+        match groups.last_mut() {
+            Some(group) if flow.block_start < current_end => group.push(index),
+            _ => groups.push(vec![index]),
+        }
+        current_end = current_end.max(flow.block_end);
+    }
+
+    groups
+}
+
+/**
+ * 递归切分版面（XY-cut），返回按阅读顺序排列的栏，每栏中的行按换行方向排列
+ */
+fn split_regions(lines: &[LayoutLine], indices: &[usize], min_gap: f32) -> Vec<Vec<usize>> {
+    if indices.len() <= 1 {
+        return vec![indices.to_vec()];
+    }
+
+    if let Some(columns) = inline_cut(lines, indices, min_gap) {
+        return columns
+            .iter()
+            .flat_map(|column| split_regions(lines, column, min_gap))
+            .collect();
+    }
+
+    let bands = block_cut(lines, indices);
+    if bands.len() == 1 {
+        return bands;
+    }
+
+    // 相邻的区域合并后仍能分栏时属于同一个多栏区域，避免多栏的内容被逐行交错读取
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for band in bands {
+        if let Some(group) = groups.last_mut() {
+            let candidate = group.iter().chain(band.iter()).copied().collect::<Vec<_>>();
+            if inline_cut(lines, &candidate, min_gap).is_some() {
+                *group = candidate;
+                continue;
+            }
+        }
+        groups.push(band);
+    }
+
+    groups
+        .iter()
+        .flat_map(|group| split_regions(lines, group, min_gap))
+        .collect()
+}
+
+/**
+ * 切分后连续且上下对齐的区域属于同一栏
+ */
+fn merge_regions(lines: &[LayoutLine], regions: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+    let mut columns: Vec<Vec<usize>> = Vec::new();
+    for region in regions {
+        if let Some(column) = columns.last_mut() {
+            let column_flow = union_flow(lines, column);
+            let region_flow = union_flow(lines, &region);
+            let min_width = column_flow.width().min(region_flow.width()).max(1.0);
+            if column_flow.inline_overlap(&region_flow) > min_width * 0.3
+                && region_flow.block_start >= column_flow.block_end
+            {
+                column.extend(region);
+                continue;
+            }
+        }
+        columns.push(region);
+    }
+
+    columns
+}
+
+pub(crate) fn join_text<'a>(parts: impl IntoIterator<Item = &'a str>) -> String {
+    let mut text = String::new();
+    for part in parts {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+
+        if let (Some(last), Some(first)) = (text.chars().last(), part.chars().next())
+            && !(is_cjk_char(last) && is_cjk_char(first))
+        {
+            text.push(' ');
+        }
+        text.push_str(part);
+    }
+
+    text
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|') {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+
+    // 行首的标记会被解析为标题、列表等
+    if result.starts_with(['#', '-', '+']) {
+        result.insert(0, '\\');
+    } else {
+        let digits = result.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits > 0 && result[digits..].starts_with(['.', ')']) {
+            result.insert(digits, '\\');
+        }
+    }
+
+    result
+}
+
+fn union_rect(rects: impl IntoIterator<Item = ElementRect>) -> ElementRect {
+    rects
+        .into_iter()
+        .reduce(|a, b| ElementRect {
+            min_x: a.min_x.min(b.min_x),
+            min_y: a.min_y.min(b.min_y),
+            max_x: a.max_x.max(b.max_x),
+            max_y: a.max_y.max(b.max_y),
+        })
+        .unwrap_or(ElementRect {
+            min_x: 0,
+            min_y: 0,
+            max_x: 0,
+            max_y: 0,
+        })
+}
+
+fn build_paragraphs(
+    blocks: &[LayoutBlock],
+    lines: &[LayoutLine],
+    column: &[usize],
+    body_thickness: f32,
+) -> Vec<OcrLayoutParagraph> {
+    let mut paragraph_lines: Vec<Vec<usize>> = Vec::new();
+    for (position, &index) in column.iter().enumerate() {
+        let starts_paragraph = match position.checked_sub(1).map(|prev| &lines[column[prev]]) {
+            Some(prev) => {
+                let current = &lines[index];
+                let prev_thickness = prev.flow.thickness().max(1.0);
+                let current_thickness = current.flow.thickness().max(1.0);
+                let gap = current.flow.block_start - prev.flow.block_end;
+
+                gap > (prev_thickness + current_thickness) * 0.5 * PARAGRAPH_MIN_GAP_RATIO
+                    || prev_thickness.max(current_thickness) / prev_thickness.min(current_thickness)
+                        > PARAGRAPH_MAX_THICKNESS_RATIO
+            }
+            None => true,
+        };
+
+        match paragraph_lines.last_mut() {
+            Some(paragraph) if !starts_paragraph => paragraph.push(index),
+            _ => paragraph_lines.push(vec![index]),
+        }
+    }
+
+    paragraph_lines
+        .into_iter()
+        .map(|paragraph| {
+            let thickness = paragraph
+                .iter()
+                .map(|&index| lines[index].flow.thickness())
+                .sum::<f32>()
+                / paragraph.len() as f32;
+            let heading_level = if paragraph.len() <= 2 && body_thickness > 0.0 {
+                let ratio = thickness / body_thickness;
+                if ratio >= 1.8 {
+                    Some(1)
+                } else if ratio >= PARAGRAPH_MAX_THICKNESS_RATIO {
+                    Some(2)
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+
+            let layout_lines = paragraph
+                .iter()
+                .map(|&index| {
+                    let line = &lines[index];
+                    OcrLayoutLine {
+                        rect: union_rect(line.blocks.iter().map(|&block| blocks[block].rect)),
+                        block_indices: line
+                            .blocks
+                            .iter()
+                            .map(|&block| blocks[block].index)
+                            .collect(),
+                        text: join_text(
+                            line.blocks.iter().map(|&block| blocks[block].text.as_str()),
+                        ),
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            OcrLayoutParagraph {
+                rect: union_rect(layout_lines.iter().map(|line| line.rect)),
+                heading_level,
+                lines: layout_lines,
+            }
+        })
+        .collect()
+}
+
+fn render_text(columns: &[OcrLayoutColumn]) -> String {
+    columns
+        .iter()
+        .flat_map(|column| column.paragraphs.iter())
+        .map(|paragraph| {
+            paragraph
+                .lines
+                .iter()
+                .map(|line| line.text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn render_markdown(columns: &[OcrLayoutColumn]) -> String {
+    columns
+        .iter()
+        .flat_map(|column| column.paragraphs.iter())
+        .map(|paragraph| {
+            // Markdown 中段落内的换行会被忽略，按行内的规则直接拼接
+            let text = escape_markdown(&join_text(
+                paragraph.lines.iter().map(|line| line.text.as_str()),
+            ));
+            match paragraph.heading_level {
+                Some(level) => format!("{} {}", "#".repeat(level as usize), text),
+                None => text,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn build_layout(mut blocks: Vec<LayoutBlock>) -> OcrLayout {
+    let writing_mode = detect_writing_mode(&blocks);
+    compute_flow_rects(&mut blocks, writing_mode);
+
+    let lines = group_lines(&blocks);
+    let mut thickness_list = lines
+        .iter()
+        .map(|line| line.flow.thickness())
+        .collect::<Vec<_>>();
+    let body_thickness = median(&mut thickness_list).unwrap_or(0.0);
+
+    let columns = if lines.is_empty() {
+        vec![]
+    } else {
+        let indices = (0..lines.len()).collect::<Vec<_>>();
+        let regions = split_regions(
+            &lines,
+            &indices,
+            (body_thickness * COLUMN_MIN_GAP_RATIO).max(1.0),
+        );
+        merge_regions(&lines, regions)
+    };
+
+    let columns = columns
+        .into_iter()
+        .map(|mut column| {
+            column.sort_by(|&a, &b| {
+                lines[a]
+                    .flow
+                    .block_start
+                    .total_cmp(&lines[b].flow.block_start)
+            });
+            let paragraphs = build_paragraphs(&blocks, &lines, &column, body_thickness);
+
+            OcrLayoutColumn {
+                rect: union_rect(paragraphs.iter().map(|paragraph| paragraph.rect)),
+                paragraphs,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    OcrLayout {
+        writing_mode,
+        text: render_text(&columns),
+        markdown: render_markdown(&columns),
+        columns,
+    }
+}
+
+/**
+ * 转换 OCR 结果中的文本块，忽略顶点数量不足的文本块
+ */
+pub(crate) fn layout_blocks(text_blocks: &[TextBlock]) -> Vec<LayoutBlock> {
+    text_blocks
+        .iter()
+        .enumerate()
+        .filter(|(_, text_block)| text_block.box_points.len() >= 4)
+        .map(|(index, text_block)| {
+            let point = |index: usize| {
+                let point = &text_block.box_points[index];
+                (point.x as f32, point.y as f32)
+            };
+
+            LayoutBlock::new(
+                index,
+                [point(0), point(1), point(2), point(3)],
+                text_block.text.clone(),
+            )
+        })
+        .collect()
+}
+
+/**
+ * 分析文本块的版面，将文本块合并为行、段落和栏，并按阅读顺序排列
+ */
+pub fn analyze_layout(text_blocks: &[TextBlock]) -> OcrLayout {
+    build_layout(layout_blocks(text_blocks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::layout_block;
+
+    fn reading_order(layout: &OcrLayout) -> Vec<usize> {
+        layout
+            .columns
+            .iter()
+            .flat_map(|column| column.paragraphs.iter())
+            .flat_map(|paragraph| paragraph.lines.iter())
+            .flat_map(|line| line.block_indices.iter().copied())
+            .collect()
+    }
+
+    #[test]
+    fn test_two_columns_with_header() {
+        let layout = build_layout(vec![
+            layout_block(0, 300.0, 40.0, 500.0, 60.0, "right one"),
+            layout_block(1, 10.0, 40.0, 200.0, 60.0, "left one"),
+            layout_block(2, 10.0, 0.0, 500.0, 20.0, "header"),
+            layout_block(3, 300.0, 65.0, 500.0, 85.0, "right two"),
+            layout_block(4, 10.0, 65.0, 200.0, 85.0, "left two"),
+        ]);
+
+        assert_eq!(layout.writing_mode, OcrWritingMode::HorizontalLtr);
+        assert_eq!(reading_order(&layout), vec![2, 1, 4, 0, 3]);
+        assert_eq!(layout.columns.len(), 2);
+        assert_eq!(
+            layout.text,
+            "header\n\nleft one\nleft two\n\nright one\nright two"
+        );
+    }
+
+    #[test]
+    fn test_lines_paragraphs_and_headings() {
+        let layout = build_layout(vec![
+            layout_block(0, 0.0, 0.0, 300.0, 40.0, "Title"),
+            layout_block(1, 110.0, 60.0, 200.0, 80.0, "line"),
+            layout_block(2, 0.0, 60.0, 100.0, 80.0, "first"),
+            layout_block(3, 0.0, 85.0, 200.0, 105.0, "second line"),
+            layout_block(4, 0.0, 130.0, 200.0, 150.0, "1. next"),
+        ]);
+
+        assert_eq!(reading_order(&layout), vec![0, 2, 1, 3, 4]);
+        let paragraphs = &layout.columns[0].paragraphs;
+        assert_eq!(paragraphs.len(), 3);
+        assert_eq!(paragraphs[0].heading_level, Some(1));
+        assert_eq!(paragraphs[1].lines[0].text, "first line");
+        assert_eq!(
+            layout.markdown,
+            "# Title\n\nfirst line second line\n\n1\\. next"
+        );
+    }
+
+    #[test]
+    fn test_vertical_cjk() {
+        let layout = build_layout(vec![
+            layout_block(0, 0.0, 0.0, 20.0, 100.0, "第二列"),
+            layout_block(1, 30.0, 0.0, 50.0, 100.0, "第一列"),
+            layout_block(2, 30.0, 105.0, 50.0, 160.0, "续写"),
+        ]);
+
+        assert_eq!(layout.writing_mode, OcrWritingMode::VerticalRl);
+        assert_eq!(reading_order(&layout), vec![1, 2, 0]);
+        assert_eq!(layout.text, "第一列续写\n第二列");
+    }
+
+    #[test]
+    fn test_right_to_left() {
+        let layout = build_layout(vec![
+            layout_block(0, 0.0, 0.0, 80.0, 20.0, "عالم"),
+            layout_block(1, 100.0, 0.0, 180.0, 20.0, "مرحبا"),
+        ]);
+
+        assert_eq!(layout.writing_mode, OcrWritingMode::HorizontalRtl);
+        assert_eq!(reading_order(&layout), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_skewed_lines() {
+        // 整体顺时针旋转约 3 度的两行文本
+        let rotate = |x: f32, y: f32| {
+            let (sin, cos) = 3.0f32.to_radians().sin_cos();
+            (x * cos - y * sin, x * sin + y * cos)
+        };
+        let skewed = |index: usize, min_y: f32, text: &str| {
+            LayoutBlock::new(
+                index,
+                [
+                    rotate(0.0, min_y),
+                    rotate(400.0, min_y),
+                    rotate(400.0, min_y + 20.0),
+                    rotate(0.0, min_y + 20.0),
+                ],
+                text.to_string(),
+            )
+        };
+
+        let layout = build_layout(vec![skewed(0, 30.0, "second"), skewed(1, 0.0, "first")]);
+
+        assert_eq!(reading_order(&layout), vec![1, 0]);
+        assert_eq!(layout.columns[0].paragraphs[0].lines.len(), 2);
+    }
+}
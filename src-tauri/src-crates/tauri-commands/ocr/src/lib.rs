@@ -1,25 +1,74 @@
+pub mod barcode;
+pub mod batch;
+pub mod export;
+pub mod layout;
+pub mod options;
+pub mod search;
+pub mod table;
+#[cfg(test)]
+mod test_utils;
+pub mod watch;
+
+use barcode::Barcode;
+use batch::{OcrBatchFailure, OcrBatchOptions, OcrBatchProgress, OcrBatchResult, OcrResultCache};
+use export::OcrDocumentFormat;
+use layout::OcrLayout;
 use log;
+use options::OcrOptions;
+use paddle_ocr_rs::ocr_lite::OcrLite;
 use paddle_ocr_rs::ocr_result::TextBlock;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
+use regex::Regex;
+use search::{
+    CaptureSearchDocument, CaptureSearchHit, CaptureSearchIndex, CaptureSearchSyncResult,
+};
 use serde::Deserialize;
 use serde::Serialize;
-use snow_shot_app_services::ocr_service::{OcrModel, OcrService};
+use serde::de::IntoDeserializer;
+use snow_shot_app_services::ocr_service::{
+    OcrModel, OcrModelPlugin, OcrRequestPriority, OcrService, OcrSessionGuard,
+    OcrSessionPoolOptions,
+};
+use snow_shot_app_shared::ElementRect;
+use snow_shot_app_utils::EncodeOptions;
+use snow_shot_app_utils::capture_metadata::{
+    CaptureMetadata, CaptureMetadataOptions, CaptureMode, current_timestamp_millis,
+};
+use snow_shot_app_utils::color_profile::ColorProfileMode;
+use snow_shot_app_utils::file_name_template::{self, FileNameTemplateContext};
+use snow_shot_app_utils::hdr_tone_mapping::HdrToneMappingOptions;
+use snow_shot_app_utils::monitor_info::{
+    CaptureOption, ColorFormat, CorrectHdrColorAlgorithm, MonitorList,
+};
+use snow_shot_plugin_service::plugin_service::PluginService;
+use std::collections::HashSet;
 use std::io::Cursor;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+use table::{OcrTable, OcrTableExportFormat};
+use tauri::{Emitter, Manager};
 use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use watch::{RegionFingerprint, RegionWatchHit, RegionWatchOptions};
 
 pub async fn ocr_init(
     orc_plugin_path: PathBuf,
-    ocr_service: tauri::State<'_, Mutex<OcrService>>,
+    ocr_service: tauri::State<'_, OcrService>,
     model: OcrModel,
     hot_start: bool,
     ocr_model_write_to_memory: bool,
+    pool_options: Option<OcrSessionPoolOptions>,
 ) -> Result<(), String> {
-    let mut ocr_service = ocr_service.lock().await;
-
     ocr_service
-        .init_models(orc_plugin_path, model, hot_start, ocr_model_write_to_memory)
+        .init_models(
+            orc_plugin_path,
+            model,
+            hot_start,
+            ocr_model_write_to_memory,
+            pool_options.unwrap_or_default(),
+        )
         .await?;
 
     Ok(())
@@ -29,6 +78,30 @@ pub async fn ocr_init(
 pub struct OcrDetectResult {
     pub text_blocks: Vec<TextBlock>,
     pub scale_factor: f32,
+    /// 按阅读顺序排列的版面结构
+    #[serde(default)]
+    pub layout: Option<OcrLayout>,
+    /// 识别的表格结构，未开启或未识别到表格时为空
+    #[serde(default)]
+    pub table: Option<OcrTable>,
+    /// 识别的二维码和条码，未开启识别时为空
+    #[serde(default)]
+    pub barcodes: Option<Vec<Barcode>>,
+}
+
+impl OcrDetectResult {
+    /// 识别结果的纯文本，有版面结构时按阅读顺序排列
+    pub fn text(&self) -> String {
+        match &self.layout {
+            Some(layout) => layout.text.clone(),
+            None => self
+                .text_blocks
+                .iter()
+                .map(|text_block| text_block.text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
 }
 
 fn convert_rgba_to_rgb(image: &[u8]) -> Vec<u8> {
@@ -55,21 +128,122 @@ fn convert_rgba_to_rgb(image: &[u8]) -> Vec<u8> {
     rgb_data
 }
 
+/**
+ * 加载识别请求指定的语言包，未指定时使用初始化时的模型
+ */
+async fn prepare_ocr_model(
+    ocr_service: &OcrService,
+    plugin_service: &PluginService,
+    model: Option<OcrModel>,
+) -> Result<OcrModel, String> {
+    match model {
+        Some(model) => {
+            let orc_plugin_path = plugin_service
+                .get_plugin_dir_path(model.pack().plugin_id.to_string())
+                .await?;
+
+            ocr_service.prepare_model(orc_plugin_path, model).await?;
+
+            Ok(model)
+        }
+        None => ocr_service
+            .default_model()
+            .ok_or_else(|| "[prepare_ocr_model] Models are not initialized".to_string()),
+    }
+}
+
+/**
+ * 交互识别，request_id 不为空时可以通过 ocr_cancel 取消排队中的请求
+ */
 pub async fn ocr_detect_core(
-    ocr_service: tauri::State<'_, Mutex<OcrService>>,
+    ocr_service: tauri::State<'_, OcrService>,
+    plugin_service: tauri::State<'_, Arc<PluginService>>,
     image: image::DynamicImage,
     scale_factor: f32,
     detect_angle: bool,
+    model: Option<OcrModel>,
+    options: Option<OcrOptions>,
+    request_id: Option<String>,
 ) -> Result<OcrDetectResult, String> {
-    let mut ocr_service = ocr_service.lock().await;
+    // 在等待之前登记请求，加载语言包期间也可以取消
+    let cancel_token = request_id
+        .as_deref()
+        .map(|request_id| ocr_service.register_request(request_id));
+    let session = async {
+        let model = prepare_ocr_model(&ocr_service, &plugin_service, model).await?;
+
+        ocr_service
+            .acquire_session(
+                model,
+                OcrRequestPriority::Interactive,
+                cancel_token.as_ref(),
+            )
+            .await
+    }
+    .await;
+    if let Some(request_id) = &request_id {
+        ocr_service.finish_request(request_id);
+    }
+    let mut session = session?;
+
+    detect_image_blocking(
+        &mut session,
+        image,
+        scale_factor,
+        detect_angle,
+        options.unwrap_or_default(),
+    )
+    .await
+}
+
+/**
+ * 在阻塞线程中使用 session 识别图片，识别期间不占用异步运行时的线程
+ */
+async fn detect_image_blocking(
+    session: &mut OcrSessionGuard<'_>,
+    image: image::DynamicImage,
+    scale_factor: f32,
+    detect_angle: bool,
+    options: OcrOptions,
+) -> Result<OcrDetectResult, String> {
+    session
+        .run_blocking(move |ocr_core| {
+            detect_image(ocr_core, image, scale_factor, detect_angle, &options)
+        })
+        .await?
+}
+
+fn detect_image(
+    ocr_core: &mut OcrLite,
+    image: image::DynamicImage,
+    scale_factor: f32,
+    detect_angle: bool,
+    options: &OcrOptions,
+) -> Result<OcrDetectResult, String> {
+    let params = options.params();
     let mut scale_factor = scale_factor;
     let mut image = image;
+    let mut resize_factor = 1.0;
+
+    // 在放大前的图片上识别条码，坐标和文本框一致
+    // 条码识别失败时不影响文本识别的结果
+    let barcodes = if options.detect_barcode {
+        match barcode::detect_barcodes(&image) {
+            Ok(barcodes) => Some(barcodes),
+            Err(e) => {
+                log::error!("[detect_image] Failed to detect barcodes: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
 
-    // 分辨率过小的图片识别可能有问题，当 scale_factor 低于 1.5 时，放大图片使有效缩放达到 1.5
-    let target_scale_factor = 1.5;
+    // 分辨率过小的图片识别可能有问题，当 scale_factor 低于 min_scale_factor 时，放大图片使有效缩放达到 min_scale_factor
+    let target_scale_factor = params.min_scale_factor;
     if scale_factor < target_scale_factor && scale_factor > 0.0 {
+        resize_factor = target_scale_factor / scale_factor;
         scale_factor = target_scale_factor;
-        let resize_factor = target_scale_factor / scale_factor;
         image = image.resize(
             (image.width() as f32 * resize_factor) as u32,
             (image.height() as f32 * resize_factor) as u32,
@@ -85,31 +259,56 @@ pub async fn ocr_detect_core(
             let rgb_data = convert_rgba_to_rgb(image.as_raw());
             image::RgbImage::from_raw(image.width(), image.height(), rgb_data).unwrap()
         }
-        _ => return Err("[ocr_detect_core] Invalid image".to_string()),
+        _ => return Err("[detect_image] Invalid image".to_string()),
     };
-    let ocr_result = ocr_service.get_session().await?.detect_angle_rollback(
+    let ocr_result = ocr_core.detect_angle_rollback(
         &image_buffer,
-        50,
+        params.padding,
         max_size,
-        0.5,
-        0.3,
-        1.6,
+        params.box_score_thresh,
+        params.box_thresh,
+        params.unclip_ratio,
         detect_angle,
         false,
-        0.9, // 屏幕截取的文字质量通常较高，且非横向排版的情况较少，尽量减少角度的影响
+        params.angle_rollback_threshold,
     );
 
     match ocr_result {
-        Ok(ocr_result) => Ok(OcrDetectResult {
-            text_blocks: ocr_result.text_blocks,
-            scale_factor,
-        }),
-        Err(e) => return Err(format!("[ocr_detect_core] Failed to detect text: {}", e)),
+        Ok(mut ocr_result) => {
+            // 文本框的坐标还原到放大前的图片
+            if resize_factor != 1.0 {
+                ocr_result
+                    .text_blocks
+                    .iter_mut()
+                    .flat_map(|text_block| text_block.box_points.iter_mut())
+                    .for_each(|point| {
+                        point.x = (point.x as f32 / resize_factor).round() as u32;
+                        point.y = (point.y as f32 / resize_factor).round() as u32;
+                    });
+            }
+
+            let layout = layout::analyze_layout(&ocr_result.text_blocks);
+            let table = if options.recognize_table {
+                table::recognize_table(&image_buffer, resize_factor, &ocr_result.text_blocks)
+            } else {
+                None
+            };
+
+            Ok(OcrDetectResult {
+                text_blocks: ocr_result.text_blocks,
+                scale_factor,
+                layout: Some(layout),
+                table,
+                barcodes,
+            })
+        }
+        Err(e) => return Err(format!("[detect_image] Failed to detect text: {}", e)),
     }
 }
 
 pub async fn ocr_detect(
-    ocr_service: tauri::State<'_, Mutex<OcrService>>,
+    ocr_service: tauri::State<'_, OcrService>,
+    plugin_service: tauri::State<'_, Arc<PluginService>>,
     request: tauri::ipc::Request<'_>,
 ) -> Result<OcrDetectResult, String> {
     log::info!("[ocr_detect] start detect");
@@ -119,12 +318,12 @@ pub async fn ocr_detect(
         _ => return Err("[ocr_detect] Invalid request body".to_string()),
     };
 
-    let mut image = match image::load(Cursor::new(image_data), image::ImageFormat::Png) {
+    let image = match image::load(Cursor::new(image_data), image::ImageFormat::Png) {
         Ok(image) => image,
         Err(_) => return Err("[ocr_detect] Invalid image".to_string()),
     };
 
-    let mut scale_factor: f32 = match request.headers().get("x-scale-factor") {
+    let scale_factor: f32 = match request.headers().get("x-scale-factor") {
         Some(header) => match header.to_str() {
             Ok(scale_factor) => scale_factor.parse::<f32>().unwrap(),
             Err(_) => return Err("[ocr_detect] Invalid scale factor".to_string()),
@@ -132,18 +331,6 @@ pub async fn ocr_detect(
         None => return Err("[ocr_detect] Missing scale factor".to_string()),
     };
 
-    // 分辨率过小的图片识别可能有问题，当 scale_factor 低于 1.5 时，放大图片使有效缩放达到 1.5
-    let target_scale_factor = 1.5;
-    if scale_factor < target_scale_factor && scale_factor > 0.0 {
-        scale_factor = target_scale_factor;
-        let resize_factor = target_scale_factor / scale_factor;
-        image = image.resize(
-            (image.width() as f32 * resize_factor) as u32,
-            (image.height() as f32 * resize_factor) as u32,
-            image::imageops::FilterType::Lanczos3,
-        );
-    }
-
     let detect_angle = match request.headers().get("x-detect-angle") {
         Some(header) => match header.to_str() {
             Ok(detect_angle) => detect_angle.parse::<bool>().unwrap(),
@@ -152,16 +339,61 @@ pub async fn ocr_detect(
         None => return Err("[ocr_detect] Missing detect angle".to_string()),
     };
 
-    ocr_detect_core(ocr_service, image, scale_factor, detect_angle).await
+    // 识别使用的语言包，为空时使用初始化时的模型
+    let model = match request.headers().get("x-ocr-model") {
+        Some(header) => match header.to_str() {
+            Ok(model) => Some(OcrModel::deserialize(model.into_deserializer()).map_err(
+                |e: serde::de::value::Error| format!("[ocr_detect] Invalid ocr model: {}", e),
+            )?),
+            Err(_) => return Err("[ocr_detect] Invalid ocr model".to_string()),
+        },
+        None => None,
+    };
+
+    let options = match request.headers().get("x-ocr-options") {
+        Some(header) => match header.to_str() {
+            Ok(options) => Some(
+                serde_json::from_str::<OcrOptions>(options)
+                    .map_err(|e| format!("[ocr_detect] Invalid ocr options: {}", e))?,
+            ),
+            Err(_) => return Err("[ocr_detect] Invalid ocr options".to_string()),
+        },
+        None => None,
+    };
+
+    // 请求 ID，用于取消排队中的请求
+    let request_id = match request.headers().get("x-request-id") {
+        Some(header) => match header.to_str() {
+            Ok(request_id) => Some(request_id.to_string()),
+            Err(_) => return Err("[ocr_detect] Invalid request id".to_string()),
+        },
+        None => None,
+    };
+
+    ocr_detect_core(
+        ocr_service,
+        plugin_service,
+        image,
+        scale_factor,
+        detect_angle,
+        model,
+        options,
+        request_id,
+    )
+    .await
 }
 
 #[cfg(target_os = "windows")]
 pub async fn ocr_detect_with_shared_buffer(
-    ocr_service: tauri::State<'_, Mutex<OcrService>>,
+    ocr_service: tauri::State<'_, OcrService>,
+    plugin_service: tauri::State<'_, Arc<PluginService>>,
     shared_buffer_service: tauri::State<'_, std::sync::Arc<snow_shot_webview::SharedBufferService>>,
     channel_id: String,
     scale_factor: f32,
     detect_angle: bool,
+    model: Option<OcrModel>,
+    options: Option<OcrOptions>,
+    request_id: Option<String>,
 ) -> Result<OcrDetectResult, String> {
     log::info!("[ocr_detect_with_shared_buffer] start detect");
 
@@ -188,6 +420,7 @@ pub async fn ocr_detect_with_shared_buffer(
 
     ocr_detect_core(
         ocr_service,
+        plugin_service,
         image::DynamicImage::ImageRgba8(
             match image::RgbaImage::from_raw(image_width, image_height, image_data) {
                 Some(image) => image,
@@ -196,14 +429,1015 @@ pub async fn ocr_detect_with_shared_buffer(
         ),
         scale_factor,
         detect_angle,
+        model,
+        options,
+        request_id,
+    )
+    .await
+}
+
+/// 导出文档时分块识别的最小高度
+const EXPORT_TILE_MIN_HEIGHT: u32 = 2048;
+/// 相邻分块之间重叠的高度
+const EXPORT_TILE_OVERLAP: u32 = 128;
+
+/**
+ * 识别图片文件中的文本，导出为可搜索的 PDF、hOCR 或 ALTO
+ * 较长的图片（如滚动截图）分块识别后合并
+ */
+pub async fn ocr_export_document(
+    ocr_service: tauri::State<'_, OcrService>,
+    plugin_service: tauri::State<'_, Arc<PluginService>>,
+    image_path: PathBuf,
+    output_path: PathBuf,
+    format: OcrDocumentFormat,
+    scale_factor: f32,
+    detect_angle: bool,
+    model: Option<OcrModel>,
+    options: Option<OcrOptions>,
+) -> Result<(), String> {
+    let image = match image::open(&image_path) {
+        Ok(image) => image.into_rgb8(),
+        Err(e) => {
+            return Err(format!("[ocr_export_document] Failed to open image: {}", e));
+        }
+    };
+    let options = options.unwrap_or_default();
+
+    let model = prepare_ocr_model(&ocr_service, &plugin_service, model).await?;
+
+    let mut text_blocks = Vec::new();
+    {
+        let tile_height = (image.width() * 2).max(EXPORT_TILE_MIN_HEIGHT);
+        let mut tile_top = 0;
+        loop {
+            let tile_bottom = (tile_top + tile_height).min(image.height());
+            let is_last_tile = tile_bottom == image.height();
+            let tile = image::imageops::crop_imm(
+                &image,
+                0,
+                tile_top,
+                image.width(),
+                tile_bottom - tile_top,
+            )
+            .to_image();
+
+            // 每个分块单独从池中取 session，导出期间其他识别请求可以穿插执行
+            let result = detect_image_blocking(
+                &mut ocr_service
+                    .acquire_session(model, OcrRequestPriority::Background, None)
+                    .await?,
+                image::DynamicImage::ImageRgb8(tile),
+                scale_factor,
+                detect_angle,
+                options,
+            )
+            .await?;
+
+            // 重叠区域中的文本块以重叠区域的中线为界，只保留一次
+            let keep_top = if tile_top == 0 {
+                f32::MIN
+            } else {
+                (tile_top + EXPORT_TILE_OVERLAP / 2) as f32
+            };
+            let keep_bottom = if is_last_tile {
+                f32::MAX
+            } else {
+                (tile_bottom - EXPORT_TILE_OVERLAP / 2) as f32
+            };
+            for mut text_block in result.text_blocks {
+                text_block
+                    .box_points
+                    .iter_mut()
+                    .for_each(|point| point.y += tile_top);
+
+                let center_y = text_block
+                    .box_points
+                    .iter()
+                    .map(|point| point.y as f32)
+                    .sum::<f32>()
+                    / text_block.box_points.len().max(1) as f32;
+                if center_y >= keep_top && center_y < keep_bottom {
+                    text_blocks.push(text_block);
+                }
+            }
+
+            if is_last_tile {
+                break;
+            }
+            tile_top = tile_bottom - EXPORT_TILE_OVERLAP;
+        }
+    }
+
+    let image_file_name = image_path
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let data = match format {
+        OcrDocumentFormat::SearchablePdf => {
+            export::render_searchable_pdf(&image, &text_blocks, 96.0 * scale_factor)?
+        }
+        OcrDocumentFormat::Hocr => export::render_hocr(
+            &text_blocks,
+            None,
+            image.width(),
+            image.height(),
+            &image_file_name,
+        )
+        .into_bytes(),
+        OcrDocumentFormat::Alto => export::render_alto(
+            &text_blocks,
+            None,
+            image.width(),
+            image.height(),
+            &image_file_name,
+        )
+        .into_bytes(),
+    };
+
+    match tokio::fs::write(&output_path, data).await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("[ocr_export_document] Failed to write file: {}", e)),
+    }
+}
+
+/// 识别结果的缓存位于应用缓存目录下的该文件夹
+const OCR_CACHE_DIR_NAME: &str = "ocr_cache";
+
+fn get_ocr_result_cache(app_handle: &tauri::AppHandle) -> Result<OcrResultCache, String> {
+    match app_handle.path().app_cache_dir() {
+        Ok(app_cache_dir) => Ok(OcrResultCache::new(app_cache_dir.join(OCR_CACHE_DIR_NAME))),
+        Err(e) => Err(format!(
+            "[get_ocr_result_cache] Failed to get app cache dir: {}",
+            e
+        )),
+    }
+}
+
+/**
+ * 识别图片文件，结果按图片内容和识别参数缓存，返回识别结果和结果是否来自缓存
+ *
+ * 以后台任务的优先级识别，只在识别单张图片时占用 session，批量识别期间交互识别可以穿插执行
+ * cancel_token 被取消时停止排队
+ */
+pub async fn ocr_detect_file(
+    ocr_service: &OcrService,
+    plugin_service: &PluginService,
+    cache: &OcrResultCache,
+    file_path: &Path,
+    scale_factor: f32,
+    detect_angle: bool,
+    model: Option<OcrModel>,
+    options: &OcrOptions,
+    cancel_token: Option<&CancellationToken>,
+) -> Result<(OcrDetectResult, bool), String> {
+    let data = match tokio::fs::read(file_path).await {
+        Ok(data) => data,
+        Err(e) => {
+            return Err(format!(
+                "[ocr_detect_file] Failed to read file: {} {}",
+                e,
+                file_path.display()
+            ));
+        }
+    };
+
+    let model = prepare_ocr_model(ocr_service, plugin_service, model).await?;
+    let cache_key = OcrResultCache::key(
+        &batch::hash_image(&data),
+        &(model, scale_factor, detect_angle, options),
+    )?;
+    if let Some(result) = cache.get::<OcrDetectResult>(&cache_key).await {
+        return Ok((result, true));
+    }
+
+    let image = match image::load_from_memory(&data) {
+        Ok(image) => image,
+        Err(e) => {
+            return Err(format!(
+                "[ocr_detect_file] Failed to decode image: {} {}",
+                e,
+                file_path.display()
+            ));
+        }
+    };
+
+    let result = detect_image_blocking(
+        &mut ocr_service
+            .acquire_session(model, OcrRequestPriority::Background, cancel_token)
+            .await?,
+        image,
+        scale_factor,
+        detect_angle,
+        *options,
+    )
+    .await?;
+
+    if let Err(e) = cache.put(&cache_key, &result).await {
+        log::warn!("{}", e);
+    }
+
+    Ok((result, false))
+}
+
+/**
+ * 在图片旁写入识别结果的纯文本和 JSON
+ */
+async fn write_ocr_sidecar(file_path: &Path, result: &OcrDetectResult) -> Result<(), String> {
+    let (text_file_path, json_file_path) = batch::sidecar_paths(file_path);
+
+    let json = match serde_json::to_vec_pretty(result) {
+        Ok(json) => json,
+        Err(e) => {
+            return Err(format!(
+                "[write_ocr_sidecar] Failed to serialize result: {}",
+                e
+            ));
+        }
+    };
+
+    for (sidecar_path, data) in [
+        (text_file_path, result.text().into_bytes()),
+        (json_file_path, json),
+    ] {
+        if let Err(e) = tokio::fs::write(&sidecar_path, data).await {
+            return Err(format!(
+                "[write_ocr_sidecar] Failed to write file: {} {}",
+                e,
+                sidecar_path.display()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/**
+ * 批量识别图片文件和文件夹中的图片，每处理完一张图片发送 OCR_BATCH_PROGRESS_EVENT
+ *
+ * 单张图片识别失败不会中断任务，失败的图片记录在返回结果中
+ */
+pub async fn ocr_batch_detect(
+    app_handle: tauri::AppHandle,
+    ocr_service: tauri::State<'_, OcrService>,
+    plugin_service: tauri::State<'_, Arc<PluginService>>,
+    batch_id: String,
+    paths: Vec<PathBuf>,
+    scale_factor: f32,
+    detect_angle: bool,
+    model: Option<OcrModel>,
+    options: Option<OcrOptions>,
+    batch_options: Option<OcrBatchOptions>,
+) -> Result<OcrBatchResult, String> {
+    let options = options.unwrap_or_default();
+    let batch_options = batch_options.unwrap_or_default();
+    let cache = get_ocr_result_cache(&app_handle)?;
+
+    // 在等待之前登记任务，收集文件期间也可以取消
+    let cancel_token = ocr_service.register_request(&batch_id);
+
+    let recursive = batch_options.recursive;
+    let files =
+        match tokio::task::spawn_blocking(move || batch::collect_image_files(&paths, recursive))
+            .await
+        {
+            Ok(files) => files,
+            Err(e) => {
+                ocr_service.finish_request(&batch_id);
+                return Err(format!(
+                    "[ocr_batch_detect] Failed to join collect task: {}",
+                    e
+                ));
+            }
+        };
+
+    log::info!(
+        "[ocr_batch_detect] batch {} start, {} files",
+        batch_id,
+        files.len()
+    );
+
+    let mut batch_result = OcrBatchResult {
+        total: files.len(),
+        cancelled: cancel_token.is_cancelled(),
+        ..Default::default()
+    };
+    for (index, file_path) in files.into_iter().enumerate() {
+        if cancel_token.is_cancelled() {
+            batch_result.cancelled = true;
+            break;
+        }
+
+        let result = match ocr_detect_file(
+            &ocr_service,
+            &plugin_service,
+            &cache,
+            &file_path,
+            scale_factor,
+            detect_angle,
+            model,
+            &options,
+            Some(&cancel_token),
+        )
+        .await
+        {
+            Ok((detect_result, from_cache)) if batch_options.write_sidecar => {
+                write_ocr_sidecar(&file_path, &detect_result)
+                    .await
+                    .map(|_| from_cache)
+            }
+            Ok((_, from_cache)) => Ok(from_cache),
+            Err(e) => Err(e),
+        };
+
+        // 排队时被取消的图片不计入失败
+        if result.is_err() && cancel_token.is_cancelled() {
+            batch_result.cancelled = true;
+            break;
+        }
+
+        let progress = OcrBatchProgress {
+            batch_id: batch_id.clone(),
+            file_path: file_path.clone(),
+            finished: index + 1,
+            total: batch_result.total,
+            from_cache: result == Ok(true),
+            error: result.clone().err(),
+        };
+
+        match result {
+            Ok(true) => batch_result.from_cache += 1,
+            Ok(false) => batch_result.detected += 1,
+            Err(error) => {
+                log::warn!("[ocr_batch_detect] {}", error);
+                batch_result
+                    .failed
+                    .push(OcrBatchFailure { file_path, error });
+            }
+        }
+
+        if let Err(e) = app_handle.emit(batch::OCR_BATCH_PROGRESS_EVENT, progress) {
+            log::error!("[ocr_batch_detect] Failed to emit event: {}", e);
+        }
+    }
+
+    ocr_service.finish_request(&batch_id);
+
+    if let Err(e) = cache.prune(batch::CACHE_MAX_SIZE).await {
+        log::warn!("{}", e);
+    }
+
+    Ok(batch_result)
+}
+
+/**
+ * 取消批量任务，正在识别的图片完成后生效，排队中的图片立即停止
+ */
+pub async fn ocr_batch_cancel(
+    ocr_service: tauri::State<'_, OcrService>,
+    batch_id: String,
+) -> Result<(), String> {
+    ocr_service.cancel_request(&batch_id);
+
+    Ok(())
+}
+
+/// 截图历史的搜索索引保存在应用数据目录下
+const CAPTURE_SEARCH_INDEX_FILE_NAME: &str = "capture_search_index.json";
+
+/// 同一时间只同步一次索引，避免重复识别同一张截图
+static CAPTURE_SEARCH_SYNC_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+async fn load_capture_search_index(
+    app_handle: &tauri::AppHandle,
+    search_index: &mut CaptureSearchIndex,
+) -> Result<(), String> {
+    if search_index.is_loaded() {
+        return Ok(());
+    }
+
+    let app_data_dir = match app_handle.path().app_data_dir() {
+        Ok(app_data_dir) => app_data_dir,
+        Err(e) => {
+            return Err(format!(
+                "[load_capture_search_index] Failed to get app data dir: {}",
+                e
+            ));
+        }
+    };
+
+    search_index
+        .load(app_data_dir.join(CAPTURE_SEARCH_INDEX_FILE_NAME))
+        .await;
+
+    Ok(())
+}
+
+/**
+ * 使用截图历史的列表同步搜索索引
+ *
+ * 删除列表中不存在的截图，识别新增和变化的截图，每识别完一张截图发送 OCR_BATCH_PROGRESS_EVENT
+ * only_if_indexed 为 true 时，还没有建立过索引则不做处理，用于截图历史变化后的自动同步
+ */
+pub async fn capture_search_sync(
+    app_handle: tauri::AppHandle,
+    ocr_service: tauri::State<'_, OcrService>,
+    plugin_service: tauri::State<'_, Arc<PluginService>>,
+    search_index: tauri::State<'_, Mutex<CaptureSearchIndex>>,
+    batch_id: String,
+    documents: Vec<CaptureSearchDocument>,
+    only_if_indexed: bool,
+    scale_factor: f32,
+    detect_angle: bool,
+    model: Option<OcrModel>,
+    options: Option<OcrOptions>,
+) -> Result<CaptureSearchSyncResult, String> {
+    // 在等待之前登记任务，等待其他同步完成期间也可以取消
+    let cancel_token = ocr_service.register_request(&batch_id);
+    let sync_result = async {
+        let _sync_guard = CAPTURE_SEARCH_SYNC_LOCK.lock().await;
+
+        let options = options.unwrap_or_default();
+        let cache = get_ocr_result_cache(&app_handle)?;
+
+        let mut sync_result = CaptureSearchSyncResult {
+            total: documents.len(),
+            ..Default::default()
+        };
+
+        // 只在比较和写入索引时持有锁，识别期间仍然可以搜索
+        let mut pending_documents = Vec::new();
+        {
+            let mut search_index = search_index.lock().await;
+            load_capture_search_index(&app_handle, &mut search_index).await?;
+
+            if only_if_indexed && search_index.is_empty() {
+                return Ok(sync_result);
+            }
+
+            let ids = documents
+                .iter()
+                .map(|document| document.id.as_str())
+                .collect::<HashSet<_>>();
+            sync_result.removed = search_index.retain(&ids);
+
+            for document in documents {
+                match search::file_modified_ts(&document.file_path).await {
+                    Ok(modified_ts) => {
+                        if search_index.needs_update(&document, modified_ts) {
+                            pending_documents.push((document, modified_ts));
+                        }
+                    }
+                    Err(error) => sync_result.failed.push(OcrBatchFailure {
+                        file_path: document.file_path,
+                        error,
+                    }),
+                }
+            }
+        }
+
+        log::info!(
+            "[capture_search_sync] {} captures, {} removed, {} pending",
+            sync_result.total,
+            sync_result.removed,
+            pending_documents.len()
+        );
+
+        let pending_count = pending_documents.len();
+        for (index, (document, modified_ts)) in pending_documents.into_iter().enumerate() {
+            if cancel_token.is_cancelled() {
+                sync_result.cancelled = true;
+                break;
+            }
+
+            let file_path = document.file_path.clone();
+            let result = ocr_detect_file(
+                &ocr_service,
+                &plugin_service,
+                &cache,
+                &file_path,
+                scale_factor,
+                detect_angle,
+                model,
+                &options,
+                Some(&cancel_token),
+            )
+            .await;
+
+            if result.is_err() && cancel_token.is_cancelled() {
+                sync_result.cancelled = true;
+                break;
+            }
+
+            let progress = OcrBatchProgress {
+                batch_id: batch_id.clone(),
+                file_path: file_path.clone(),
+                finished: index + 1,
+                total: pending_count,
+                from_cache: matches!(result, Ok((_, true))),
+                error: result.as_ref().err().cloned(),
+            };
+
+            match result {
+                Ok((detect_result, _)) => {
+                    search_index.lock().await.insert(
+                        document,
+                        modified_ts,
+                        &detect_result.text_blocks,
+                    );
+                    sync_result.indexed += 1;
+                }
+                Err(error) => {
+                    log::warn!("[capture_search_sync] {}", error);
+                    sync_result
+                        .failed
+                        .push(OcrBatchFailure { file_path, error });
+                }
+            }
+
+            if let Err(e) = app_handle.emit(batch::OCR_BATCH_PROGRESS_EVENT, progress) {
+                log::error!("[capture_search_sync] Failed to emit event: {}", e);
+            }
+        }
+
+        if sync_result.indexed > 0 || sync_result.removed > 0 {
+            search_index.lock().await.save().await?;
+        }
+
+        // 截图历史清理后，已删除截图的识别结果只会留在缓存中
+        if let Err(e) = cache.prune(batch::CACHE_MAX_SIZE).await {
+            log::warn!("{}", e);
+        }
+
+        Ok::<_, String>(sync_result)
+    }
+    .await;
+    ocr_service.finish_request(&batch_id);
+
+    sync_result
+}
+
+/**
+ * 搜索截图历史，返回按相关度排列的截图和包含关键词的文本块
+ */
+pub async fn capture_search_query(
+    app_handle: tauri::AppHandle,
+    search_index: tauri::State<'_, Mutex<CaptureSearchIndex>>,
+    query: String,
+    limit: usize,
+) -> Result<Vec<CaptureSearchHit>, String> {
+    let mut search_index = search_index.lock().await;
+    load_capture_search_index(&app_handle, &mut search_index).await?;
+
+    Ok(search_index.query(&query, limit))
+}
+
+pub async fn ocr_export_table(
+    table: OcrTable,
+    format: OcrTableExportFormat,
+) -> Result<String, String> {
+    Ok(table.export(format))
+}
+
+/**
+ * 识别图片中的二维码和条码，请求体为 PNG 图片
+ */
+pub async fn barcode_detect(request: tauri::ipc::Request<'_>) -> Result<Vec<Barcode>, String> {
+    let image_data = match request.body() {
+        tauri::ipc::InvokeBody::Raw(data) => data.clone(),
+        _ => return Err("[barcode_detect] Invalid request body".to_string()),
+    };
+
+    let barcodes = tokio::task::spawn_blocking(move || {
+        let image = match image::load(Cursor::new(image_data), image::ImageFormat::Png) {
+            Ok(image) => image,
+            Err(_) => return Err("[barcode_detect] Invalid image".to_string()),
+        };
+
+        barcode::detect_barcodes(&image)
+    })
+    .await
+    .map_err(|e| format!("[barcode_detect] Failed to join task: {}", e))??;
+
+    Ok(barcodes)
+}
+
+/**
+ * 将文本编码为二维码，返回 PNG 图片
+ */
+pub async fn barcode_generate_qr_code(
+    text: String,
+    size: u32,
+) -> Result<tauri::ipc::Response, String> {
+    let png_data = barcode::generate_qr_code(&text, size)?;
+
+    Ok(tauri::ipc::Response::new(png_data))
+}
+
+/**
+ * 监控任务启动后不再变化的参数
+ */
+struct RegionWatchTask {
+    watch_id: String,
+    region: ElementRect,
+    scale_factor: f32,
+    options: RegionWatchOptions,
+    /// 匹配识别文字的正则表达式和识别使用的模型
+    text_matcher: Option<(Regex, OcrModel)>,
+    encode_options: EncodeOptions,
+    capture_metadata_options: CaptureMetadataOptions,
+}
+
+/**
+ * 监控过程中需要在两次截图间保留的状态
+ */
+#[derive(Default)]
+struct RegionWatchState {
+    /// 上一次比较的画面
+    baseline: Option<RegionFingerprint>,
+    /// 上一次识别时匹配的文字
+    matched_text: Option<String>,
+    last_hit: Option<Instant>,
+    /// 本次截图中识别文字的耗时
+    ocr_time: Duration,
+}
+
+/**
+ * 截取一次监控区域，触发时返回触发信息和截图
+ *
+ * 第一帧只作为比较的基准，不会触发
+ */
+async fn region_watch_tick(
+    ocr_service: &OcrService,
+    monitor_list: &MonitorList,
+    task: &RegionWatchTask,
+    state: &mut RegionWatchState,
+    cancel_token: &CancellationToken,
+) -> Result<Option<(RegionWatchHit, image::DynamicImage)>, String> {
+    // 只比较画面的变化，不需要校正颜色
+    let image = monitor_list
+        .capture_region(
+            task.region,
+            None,
+            CaptureOption {
+                color_format: ColorFormat::Rgba8,
+                correct_hdr_color_algorithm: CorrectHdrColorAlgorithm::None,
+                hdr_tone_mapping: HdrToneMappingOptions::default(),
+                correct_color_filter: false,
+                color_profile_mode: ColorProfileMode::default(),
+            },
+        )
+        .await?;
+
+    let fingerprint = RegionFingerprint::new(&image);
+    let change_ratio = state
+        .baseline
+        .as_ref()
+        .map(|baseline| baseline.difference(&fingerprint));
+    if change_ratio.is_some_and(|change_ratio| change_ratio < task.options.change_threshold) {
+        return Ok(None);
+    }
+    // 冷却期间不更新基准，冷却结束后画面仍有变化时再触发
+    if state
+        .last_hit
+        .is_some_and(|last_hit| last_hit.elapsed() < task.options.cooldown())
+    {
+        return Ok(None);
+    }
+    state.baseline = Some(fingerprint);
+
+    let mut hit = RegionWatchHit {
+        watch_id: task.watch_id.clone(),
+        region: task.region,
+        change_ratio: change_ratio.unwrap_or_default(),
+        text: None,
+        matched_text: None,
+        capture_time: current_timestamp_millis(),
+        capture_path: None,
+        capture_history_file_name: None,
+        notify: task.options.notify,
+    };
+
+    let Some((text_regex, model)) = &task.text_matcher else {
+        return Ok(change_ratio.map(|_| (hit, image)));
+    };
+
+    // 以后台任务的优先级识别，不阻塞交互识别
+    let ocr_start = Instant::now();
+    let detect_result = detect_image_blocking(
+        &mut ocr_service
+            .acquire_session(*model, OcrRequestPriority::Background, Some(cancel_token))
+            .await?,
+        image.clone(),
+        task.scale_factor,
+        false,
+        OcrOptions::default(),
+    )
+    .await;
+    state.ocr_time = ocr_start.elapsed();
+    let text = detect_result?.text();
+    let matched_text = text_regex
+        .find(&text)
+        .map(|matched_text| matched_text.as_str().to_string());
+
+    // 只在匹配的文字出现或变化时触发，文字保持不变时不重复触发
+    let is_new_match = matched_text.is_some() && matched_text != state.matched_text;
+    state.matched_text = matched_text.clone();
+    if change_ratio.is_none() || !is_new_match {
+        return Ok(None);
+    }
+
+    hit.text = Some(text);
+    hit.matched_text = matched_text;
+
+    Ok(Some((hit, image)))
+}
+
+/**
+ * 按截图的保存设置保存触发时的截图，文件路径支持文件名模板
+ */
+async fn save_region_watch_capture(
+    app_handle: &tauri::AppHandle,
+    task: &RegionWatchTask,
+    file_path: &str,
+    image: &image::DynamicImage,
+    capture_time: u64,
+) -> Result<PathBuf, String> {
+    let resolved_file_path = file_name_template::resolve_file_path_template(
+        app_handle,
+        file_path,
+        &FileNameTemplateContext {
+            capture_time: Some(capture_time),
+            capture_mode: Some(CaptureMode::Region),
+            ..FileNameTemplateContext::default()
+        },
+    )?;
+
+    let capture_metadata = task.capture_metadata_options.apply(CaptureMetadata::new(
+        app_handle,
+        CaptureMode::Region,
+        capture_time,
+    ));
+
+    let file_path = resolved_file_path.file_path.clone();
+    snow_shot_app_utils::save_image_to_file(
+        image,
+        file_path.clone(),
+        &task.encode_options,
+        None,
+        capture_metadata.as_ref(),
     )
+    .await?;
+    resolved_file_path.commit(app_handle);
+
+    snow_shot_app_utils::png_optimizer::spawn_png_optimization(
+        app_handle,
+        file_path.clone(),
+        &task.encode_options,
+    );
+
+    Ok(file_path)
+}
+
+/**
+ * 将触发时的截图写入截图历史文件夹，返回文件名
+ */
+async fn save_region_watch_history(
+    capture_history_dir: &Path,
+    image: image::DynamicImage,
+    capture_time: u64,
+) -> Result<String, String> {
+    if let Err(e) = tokio::fs::create_dir_all(capture_history_dir).await {
+        return Err(format!(
+            "[save_region_watch_history] Failed to create dir: {} {}",
+            e,
+            capture_history_dir.display()
+        ));
+    }
+
+    let file_name = format!("{}.png", capture_time);
+    let file_path = capture_history_dir.join(&file_name);
+    match tokio::task::spawn_blocking(move || {
+        image.save_with_format(&file_path, image::ImageFormat::Png)
+    })
     .await
+    {
+        Ok(Ok(())) => Ok(file_name),
+        Ok(Err(e)) => Err(format!(
+            "[save_region_watch_history] Failed to save image: {} {}",
+            e, file_name
+        )),
+        Err(e) => Err(format!(
+            "[save_region_watch_history] Failed to join task: {}",
+            e
+        )),
+    }
+}
+
+async fn run_region_watch(
+    app_handle: &tauri::AppHandle,
+    task: &RegionWatchTask,
+    cancel_token: &CancellationToken,
+) {
+    let ocr_service = app_handle.state::<OcrService>();
+    // 监控期间不跟随显示器布局的变化
+    let monitor_list = MonitorList::get_by_region(task.region, true);
+
+    let mut state = RegionWatchState::default();
+    let mut delay = Duration::ZERO;
+    loop {
+        tokio::select! {
+            _ = cancel_token.cancelled() => break,
+            _ = tokio::time::sleep(delay) => {}
+        }
+
+        let work_start = Instant::now();
+        state.ocr_time = Duration::ZERO;
+        match region_watch_tick(&ocr_service, &monitor_list, task, &mut state, cancel_token).await {
+            Ok(Some((mut hit, image))) => {
+                state.last_hit = Some(Instant::now());
+
+                if let Some(file_path) = &task.options.file_path {
+                    match save_region_watch_capture(
+                        app_handle,
+                        task,
+                        file_path,
+                        &image,
+                        hit.capture_time,
+                    )
+                    .await
+                    {
+                        Ok(capture_path) => hit.capture_path = Some(capture_path),
+                        Err(e) => log::warn!("{}", e),
+                    }
+                }
+
+                if let Some(capture_history_dir) = &task.options.capture_history_dir {
+                    match save_region_watch_history(capture_history_dir, image, hit.capture_time)
+                        .await
+                    {
+                        Ok(file_name) => hit.capture_history_file_name = Some(file_name),
+                        Err(e) => log::warn!("{}", e),
+                    }
+                }
+
+                if let Err(e) = app_handle.emit(watch::REGION_WATCH_HIT_EVENT, hit) {
+                    log::error!("[run_region_watch] Failed to emit event: {}", e);
+                }
+
+                if task.options.stop_on_hit {
+                    break;
+                }
+            }
+            Ok(None) => {}
+            // 截图或识别失败时跳过这一帧，下一次继续监控
+            Err(e) if !cancel_token.is_cancelled() => {
+                log::warn!("[run_region_watch] {}", e);
+            }
+            Err(_) => break,
+        }
+
+        // 识别文字时每个 session 使用多个线程，按线程数估算占用的 CPU 时间
+        let work = work_start.elapsed();
+        let cpu_time =
+            work + state.ocr_time * ocr_service.threads_per_session().saturating_sub(1) as u32;
+        delay = watch::next_delay(
+            task.options.interval(),
+            work,
+            cpu_time,
+            task.options.cpu_budget,
+        );
+    }
 }
 
-pub async fn ocr_release(ocr_service: tauri::State<'_, Mutex<OcrService>>) -> Result<(), String> {
-    let mut ocr_service = ocr_service.lock().await;
+fn emit_region_watch_state(app_handle: &tauri::AppHandle) {
+    if let Err(e) = app_handle.emit(watch::REGION_WATCH_STATE_EVENT, watch::watch_ids()) {
+        log::error!("[emit_region_watch_state] Failed to emit event: {}", e);
+    }
+}
+
+/**
+ * 在后台监控屏幕区域，画面变化或变化后的文字匹配 text_pattern 时发送 REGION_WATCH_HIT_EVENT
+ *
+ * 监控任务开始和结束时发送 REGION_WATCH_STATE_EVENT
+ */
+pub async fn region_watch_start(
+    app_handle: tauri::AppHandle,
+    #[allow(unused_variables)] window: tauri::Window,
+    ocr_service: tauri::State<'_, OcrService>,
+    watch_id: String,
+    min_x: i32,
+    min_y: i32,
+    max_x: i32,
+    max_y: i32,
+    scale_factor: f32,
+    options: Option<RegionWatchOptions>,
+    encode_options: Option<EncodeOptions>,
+    capture_metadata_options: Option<CaptureMetadataOptions>,
+) -> Result<(), String> {
+    let options = options.unwrap_or_default();
+
+    let text_matcher = match options.text_pattern() {
+        Some(text_pattern) => {
+            let text_regex = match Regex::new(text_pattern) {
+                Ok(text_regex) => text_regex,
+                Err(e) => {
+                    return Err(format!("[region_watch_start] Invalid text pattern: {}", e));
+                }
+            };
+            let model = ocr_service
+                .default_model()
+                .ok_or_else(|| "[region_watch_start] Models are not initialized".to_string())?;
+
+            Some((text_regex, model))
+        }
+        None => None,
+    };
+
+    #[cfg(target_os = "macos")]
+    let rect_scale;
+    #[cfg(not(target_os = "macos"))]
+    let rect_scale = 1.0f64;
+
+    // macOS 下截图区域是基于逻辑像素
+    #[cfg(target_os = "macos")]
+    {
+        rect_scale = (1.0 / window.scale_factor().unwrap_or(1.0)) as f64;
+    }
+
+    let region = ElementRect {
+        min_x: (min_x as f64 * rect_scale).round() as i32,
+        min_y: (min_y as f64 * rect_scale).round() as i32,
+        max_x: (max_x as f64 * rect_scale).round() as i32,
+        max_y: (max_y as f64 * rect_scale).round() as i32,
+    };
+    if region.max_x <= region.min_x || region.max_y <= region.min_y {
+        return Err("[region_watch_start] Invalid region".to_string());
+    }
+
+    let cancel_token = watch::register_watch(&watch_id)?;
+    emit_region_watch_state(&app_handle);
+
+    log::info!("[region_watch_start] watch {} start", watch_id);
 
+    let task = RegionWatchTask {
+        watch_id,
+        region,
+        scale_factor,
+        options,
+        text_matcher,
+        encode_options: encode_options.unwrap_or_default(),
+        capture_metadata_options: capture_metadata_options.unwrap_or_default(),
+    };
+    tokio::spawn(async move {
+        run_region_watch(&app_handle, &task, &cancel_token).await;
+
+        watch::finish_watch(&task.watch_id);
+        emit_region_watch_state(&app_handle);
+
+        log::info!("[region_watch_start] watch {} stop", task.watch_id);
+    });
+
+    Ok(())
+}
+
+/**
+ * 停止区域监控，watch_id 为空时停止所有监控
+ */
+pub async fn region_watch_stop(watch_id: Option<String>) -> Result<(), String> {
+    watch::stop_watch(watch_id.as_deref());
+
+    Ok(())
+}
+
+/**
+ * 正在运行的区域监控
+ */
+pub async fn region_watch_list() -> Result<Vec<String>, String> {
+    Ok(watch::watch_ids())
+}
+
+/**
+ * 取消排队中的交互识别请求
+ */
+pub async fn ocr_cancel(
+    ocr_service: tauri::State<'_, OcrService>,
+    request_id: String,
+) -> Result<(), String> {
+    ocr_service.cancel_request(&request_id);
+
+    Ok(())
+}
+
+pub async fn ocr_release(ocr_service: tauri::State<'_, OcrService>) -> Result<(), String> {
     ocr_service.release_session().await?;
 
     Ok(())
 }
+
+/**
+ * 获取提供 OCR 模型的插件和插件包含的模型文件
+ */
+pub async fn ocr_get_model_plugins() -> Result<Vec<OcrModelPlugin>, String> {
+    Ok(OcrModel::plugins())
+}
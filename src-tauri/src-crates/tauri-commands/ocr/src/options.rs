@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+/**
+ * 文本识别参数的预设
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OcrPreset {
+    /// 屏幕截取的文字，质量通常较高且非横向排版的情况较少
+    #[default]
+    ScreenText,
+    /// 扫描或拍摄的文档，可能存在倾斜和旋转
+    Document,
+    /// 行距较小的表格，收紧文本框避免相邻单元格合并
+    DenseTable,
+}
+
+/**
+ * 文本识别参数，未设置的参数使用预设的值
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(default)]
+pub struct OcrOptions {
+    pub preset: OcrPreset,
+    /// 图片四周的留白（像素）
+    pub padding: Option<u32>,
+    /// 文本框的最低得分，低于该值的文本框会被丢弃
+    pub box_score_thresh: Option<f32>,
+    /// 文本区域概率图的二值化阈值
+    pub box_thresh: Option<f32>,
+    /// 文本框的扩张比例
+    pub unclip_ratio: Option<f32>,
+    /// 文字方向分类的置信度低于该值时不旋转文本
+    pub angle_rollback_threshold: Option<f32>,
+    /// 有效缩放低于该值时放大图片，为 0 时不放大
+    pub min_scale_factor: Option<f32>,
+    /// 识别表格结构
+    pub recognize_table: bool,
+    /// 识别二维码和条码
+    pub detect_barcode: bool,
+}
+
+/**
+ * 合并预设后的文本识别参数
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OcrDetectParams {
+    pub padding: u32,
+    pub box_score_thresh: f32,
+    pub box_thresh: f32,
+    pub unclip_ratio: f32,
+    pub angle_rollback_threshold: f32,
+    pub min_scale_factor: f32,
+}
+
+impl OcrPreset {
+    pub fn params(&self) -> OcrDetectParams {
+        match self {
+            OcrPreset::ScreenText => OcrDetectParams {
+                padding: 50,
+                box_score_thresh: 0.5,
+                box_thresh: 0.3,
+                unclip_ratio: 1.6,
+                angle_rollback_threshold: 0.9,
+                // 分辨率过小的图片识别可能有问题
+                min_scale_factor: 1.5,
+            },
+            OcrPreset::Document => OcrDetectParams {
+                padding: 50,
+                box_score_thresh: 0.5,
+                box_thresh: 0.3,
+                unclip_ratio: 1.8,
+                angle_rollback_threshold: 0.5,
+                min_scale_factor: 1.0,
+            },
+            OcrPreset::DenseTable => OcrDetectParams {
+                padding: 10,
+                box_score_thresh: 0.4,
+                box_thresh: 0.25,
+                unclip_ratio: 1.3,
+                angle_rollback_threshold: 0.9,
+                // 表格中的文字通常较小
+                min_scale_factor: 2.0,
+            },
+        }
+    }
+}
+
+impl OcrOptions {
+    pub fn params(&self) -> OcrDetectParams {
+        let preset = self.preset.params();
+
+        OcrDetectParams {
+            padding: self.padding.unwrap_or(preset.padding),
+            box_score_thresh: self.box_score_thresh.unwrap_or(preset.box_score_thresh),
+            box_thresh: self.box_thresh.unwrap_or(preset.box_thresh),
+            unclip_ratio: self.unclip_ratio.unwrap_or(preset.unclip_ratio),
+            angle_rollback_threshold: self
+                .angle_rollback_threshold
+                .unwrap_or(preset.angle_rollback_threshold),
+            min_scale_factor: self.min_scale_factor.unwrap_or(preset.min_scale_factor),
+        }
+    }
+}
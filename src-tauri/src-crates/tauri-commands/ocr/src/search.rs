@@ -0,0 +1,788 @@
+use crate::batch::OcrBatchFailure;
+use crate::export::block_rect;
+use crate::layout::is_cjk_char;
+use paddle_ocr_rs::ocr_result::TextBlock;
+use serde::{Deserialize, Serialize};
+use snow_shot_app_shared::ElementRect;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+/// 索引文件的格式版本，分词方式变化后需要重建索引
+const SEARCH_INDEX_VERSION: u32 = 1;
+
+/// 变更日志的扩展名，与索引文件位于同一目录
+const SEARCH_INDEX_LOG_EXTENSION: &str = "log";
+/// 变更日志的条数超过截图数量且不少于该值时，重写索引文件并清空日志
+const SEARCH_INDEX_MIN_COMPACT_ENTRIES: usize = 64;
+
+/// BM25 的词频饱和参数
+const BM25_K1: f32 = 1.2;
+/// BM25 的文档长度归一化参数
+const BM25_B: f32 = 0.75;
+
+/// 窗口标题和进程名称比截图中的文字更能代表截图，提高权重
+const TITLE_FIELD_WEIGHT: f32 = 3.0;
+const TAG_FIELD_WEIGHT: f32 = 1.5;
+const TEXT_FIELD_WEIGHT: f32 = 1.0;
+
+/**
+ * 需要建立索引的截图
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CaptureSearchDocument {
+    /// 截图历史的 ID
+    pub id: String,
+    /// 识别文字使用的图片
+    pub file_path: PathBuf,
+    /// 截图时间，Unix 时间戳（毫秒）
+    pub create_ts: i64,
+    #[serde(default)]
+    pub window_title: Option<String>,
+    #[serde(default)]
+    pub process_name: Option<String>,
+    /// 其他可以搜索的描述，例如截图来源
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CaptureSearchHighlight {
+    /// 文本块在图片中的位置
+    pub rect: ElementRect,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CaptureSearchHit {
+    pub id: String,
+    pub file_path: PathBuf,
+    pub create_ts: i64,
+    pub window_title: Option<String>,
+    pub process_name: Option<String>,
+    pub score: f32,
+    /// 包含关键词的文本块，按文本块在图片中的顺序排列
+    pub highlights: Vec<CaptureSearchHighlight>,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct CaptureSearchSyncResult {
+    /// 截图历史中的截图数量
+    pub total: usize,
+    /// 新建或更新索引的截图数量
+    pub indexed: usize,
+    /// 从索引中删除的截图数量
+    pub removed: usize,
+    pub failed: Vec<OcrBatchFailure>,
+    /// 任务是否被取消，已经识别的截图仍然保存在索引中
+    pub cancelled: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct IndexedDocument {
+    document: CaptureSearchDocument,
+    /// 建立索引时图片的修改时间（毫秒），图片被覆盖后重新识别
+    modified_ts: u64,
+    blocks: Vec<CaptureSearchHighlight>,
+    /// 按字段权重累加的词频
+    #[serde(skip)]
+    term_freqs: HashMap<String, f32>,
+    #[serde(skip)]
+    length: f32,
+}
+
+impl IndexedDocument {
+    fn new(
+        document: CaptureSearchDocument,
+        modified_ts: u64,
+        blocks: Vec<CaptureSearchHighlight>,
+    ) -> Self {
+        let mut indexed_document = Self {
+            document,
+            modified_ts,
+            blocks,
+            term_freqs: HashMap::new(),
+            length: 0.0,
+        };
+        indexed_document.compute_term_freqs();
+
+        indexed_document
+    }
+
+    fn compute_term_freqs(&mut self) {
+        let document = &self.document;
+        let titles = document
+            .window_title
+            .iter()
+            .chain(document.process_name.iter())
+            .map(|text| (text.as_str(), TITLE_FIELD_WEIGHT));
+        let tags = document
+            .tags
+            .iter()
+            .map(|text| (text.as_str(), TAG_FIELD_WEIGHT));
+        let texts = self
+            .blocks
+            .iter()
+            .map(|block| (block.text.as_str(), TEXT_FIELD_WEIGHT));
+
+        let mut term_freqs = HashMap::new();
+        let mut length = 0.0;
+        for (text, weight) in titles.chain(tags).chain(texts) {
+            for term in tokenize(text) {
+                *term_freqs.entry(term).or_insert(0.0) += weight;
+                length += weight;
+            }
+        }
+
+        self.term_freqs = term_freqs;
+        self.length = length;
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SearchIndexFile {
+    version: u32,
+    documents: Vec<IndexedDocument>,
+}
+
+/**
+ * 变更日志的一行，加载索引文件后按顺序重放
+ */
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum SearchIndexLogEntry {
+    Upsert { document: IndexedDocument },
+    Remove { id: String },
+}
+
+/**
+ * 将文本拆分为索引的词
+ *
+ * 字母和数字按单词拆分，中文和日文没有空格分词，同时使用单字和相邻的两个字
+ */
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut word = String::new();
+    let mut prev_cjk_char: Option<char> = None;
+
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                terms.push(std::mem::take(&mut word));
+            }
+            prev_cjk_char = None;
+            continue;
+        }
+
+        if is_cjk_char(c) {
+            if !word.is_empty() {
+                terms.push(std::mem::take(&mut word));
+            }
+            if let Some(prev_cjk_char) = prev_cjk_char {
+                terms.push(String::from_iter([prev_cjk_char, c]));
+            }
+            terms.push(c.to_string());
+            prev_cjk_char = Some(c);
+        } else {
+            word.push(c);
+            prev_cjk_char = None;
+        }
+    }
+    if !word.is_empty() {
+        terms.push(word);
+    }
+
+    terms
+}
+
+/**
+ * 截图历史的全文索引，索引识别的文字、窗口标题和截图的描述
+ *
+ * 没有使用 tantivy 或 SQLite FTS：中文需要按单字和相邻两字分词，搜索结果需要返回文本块的位置，
+ * 截图历史通常只有几千张，倒排表放在内存中即可
+ *
+ * 索引保存为 JSON 文件和追加写入的变更日志，同步时只追加变化的截图，
+ * 日志条数超过截图数量后再整体重写索引文件，加载时在内存中重建倒排表
+ */
+#[derive(Default)]
+pub struct CaptureSearchIndex {
+    index_path: Option<PathBuf>,
+    documents: HashMap<String, IndexedDocument>,
+    /// 词到包含该词的截图 ID
+    postings: HashMap<String, HashSet<String>>,
+    total_length: f32,
+    /// 还没有写入变更日志的修改
+    pending_entries: Vec<SearchIndexLogEntry>,
+    /// 变更日志中已有的条数
+    log_entry_count: usize,
+    /// 索引文件过期或损坏，下次保存时重写
+    needs_compaction: bool,
+}
+
+impl CaptureSearchIndex {
+    pub fn is_loaded(&self) -> bool {
+        self.index_path.is_some()
+    }
+
+    fn log_path(index_path: &Path) -> PathBuf {
+        index_path.with_extension(SEARCH_INDEX_LOG_EXTENSION)
+    }
+
+    /**
+     * 读取索引文件并重放变更日志，文件不存在或者格式过期时使用空的索引
+     */
+    pub async fn load(&mut self, index_path: PathBuf) {
+        *self = Self::default();
+
+        let mut is_valid = true;
+        match tokio::fs::read(&index_path).await {
+            Ok(data) => match serde_json::from_slice::<SearchIndexFile>(&data) {
+                Ok(index_file) if index_file.version == SEARCH_INDEX_VERSION => {
+                    for mut document in index_file.documents {
+                        document.compute_term_freqs();
+                        self.insert_document(document);
+                    }
+                }
+                Ok(_) => {
+                    log::info!("[CaptureSearchIndex::load] Index version changed, rebuild index");
+                    is_valid = false;
+                }
+                Err(e) => {
+                    log::warn!("[CaptureSearchIndex::load] Invalid index file: {}", e);
+                    is_valid = false;
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                log::warn!(
+                    "[CaptureSearchIndex::load] Failed to read index file: {}",
+                    e
+                );
+                is_valid = false;
+            }
+        }
+
+        // 索引文件无效时丢弃日志，下次保存时重写索引文件并清空日志
+        if is_valid {
+            self.replay_log(&Self::log_path(&index_path)).await;
+        } else {
+            self.needs_compaction = true;
+        }
+
+        self.index_path = Some(index_path);
+    }
+
+    async fn replay_log(&mut self, log_path: &Path) {
+        let data = match tokio::fs::read(log_path).await {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+            Err(e) => {
+                log::warn!(
+                    "[CaptureSearchIndex::replay_log] Failed to read index log: {}",
+                    e
+                );
+                self.needs_compaction = true;
+                return;
+            }
+        };
+
+        for line in data.split(|byte| *byte == b'\n') {
+            if line.is_empty() {
+                continue;
+            }
+
+            // 写入中断时最后一行可能不完整，之后的内容无法确定顺序，全部丢弃
+            match serde_json::from_slice::<SearchIndexLogEntry>(line) {
+                Ok(SearchIndexLogEntry::Upsert { mut document }) => {
+                    document.compute_term_freqs();
+                    self.insert_document(document);
+                }
+                Ok(SearchIndexLogEntry::Remove { id }) => {
+                    self.remove_document(&id);
+                }
+                Err(e) => {
+                    log::warn!(
+                        "[CaptureSearchIndex::replay_log] Invalid index log entry: {}",
+                        e
+                    );
+                    self.needs_compaction = true;
+                    break;
+                }
+            }
+            self.log_entry_count += 1;
+        }
+    }
+
+    /**
+     * 保存索引的修改
+     *
+     * 通常只在变更日志末尾追加修改，日志过长或者索引文件需要重建时重写索引文件
+     */
+    pub async fn save(&mut self) -> Result<(), String> {
+        let Some(index_path) = self.index_path.clone() else {
+            return Err(String::from(
+                "[CaptureSearchIndex::save] Index is not loaded",
+            ));
+        };
+
+        if let Some(parent) = index_path.parent()
+            && let Err(e) = tokio::fs::create_dir_all(parent).await
+        {
+            return Err(format!(
+                "[CaptureSearchIndex::save] Failed to create dir: {} {}",
+                e,
+                parent.display()
+            ));
+        }
+
+        let log_entry_count = self.log_entry_count + self.pending_entries.len();
+        if self.needs_compaction
+            || log_entry_count >= self.documents.len().max(SEARCH_INDEX_MIN_COMPACT_ENTRIES)
+        {
+            self.compact(&index_path).await?;
+        } else if !self.pending_entries.is_empty() {
+            self.append_log(&index_path).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn append_log(&mut self, index_path: &Path) -> Result<(), String> {
+        let mut data = Vec::new();
+        for entry in &self.pending_entries {
+            if let Err(e) = serde_json::to_writer(&mut data, entry) {
+                return Err(format!(
+                    "[CaptureSearchIndex::append_log] Failed to serialize index log: {}",
+                    e
+                ));
+            }
+            data.push(b'\n');
+        }
+
+        let log_path = Self::log_path(index_path);
+        let write_result = async {
+            let mut file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&log_path)
+                .await?;
+            file.write_all(&data).await?;
+            file.sync_all().await
+        }
+        .await;
+        if let Err(e) = write_result {
+            return Err(format!(
+                "[CaptureSearchIndex::append_log] Failed to write index log: {} {}",
+                e,
+                log_path.display()
+            ));
+        }
+
+        self.log_entry_count += self.pending_entries.len();
+        self.pending_entries.clear();
+
+        Ok(())
+    }
+
+    /**
+     * 重写索引文件并删除变更日志
+     */
+    async fn compact(&mut self, index_path: &Path) -> Result<(), String> {
+        let index_file = SearchIndexFile {
+            version: SEARCH_INDEX_VERSION,
+            documents: self.documents.values().cloned().collect(),
+        };
+        let data = match serde_json::to_vec(&index_file) {
+            Ok(data) => data,
+            Err(e) => {
+                return Err(format!(
+                    "[CaptureSearchIndex::compact] Failed to serialize index: {}",
+                    e
+                ));
+            }
+        };
+
+        // 先写入临时文件再重命名，避免写入中断后索引损坏
+        let temp_index_path = index_path.with_extension("writing");
+        if let Err(e) = tokio::fs::write(&temp_index_path, data).await {
+            return Err(format!(
+                "[CaptureSearchIndex::compact] Failed to write index: {} {}",
+                e,
+                temp_index_path.display()
+            ));
+        }
+        if let Err(e) = tokio::fs::rename(&temp_index_path, index_path).await {
+            let _ = tokio::fs::remove_file(&temp_index_path).await;
+            return Err(format!(
+                "[CaptureSearchIndex::compact] Failed to replace index: {} {}",
+                e,
+                index_path.display()
+            ));
+        }
+
+        // 索引文件已经包含所有修改，删除失败时重放日志的结果不变
+        let log_path = Self::log_path(index_path);
+        match tokio::fs::remove_file(&log_path).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                log::warn!(
+                    "[CaptureSearchIndex::compact] Failed to remove index log: {} {}",
+                    e,
+                    log_path.display()
+                );
+            }
+        }
+
+        self.pending_entries.clear();
+        self.log_entry_count = 0;
+        self.needs_compaction = false;
+
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /**
+     * 截图未建立索引，或者建立索引后图片和描述发生了变化
+     */
+    pub fn needs_update(&self, document: &CaptureSearchDocument, modified_ts: u64) -> bool {
+        match self.documents.get(&document.id) {
+            Some(indexed_document) => {
+                indexed_document.modified_ts != modified_ts
+                    || indexed_document.document != *document
+            }
+            None => true,
+        }
+    }
+
+    fn insert_document(&mut self, document: IndexedDocument) {
+        let id = document.document.id.clone();
+        self.remove_document(&id);
+
+        for term in document.term_freqs.keys() {
+            self.postings
+                .entry(term.clone())
+                .or_default()
+                .insert(id.clone());
+        }
+        self.total_length += document.length;
+        self.documents.insert(id, document);
+    }
+
+    /**
+     * 添加或更新截图的索引
+     */
+    pub fn insert(
+        &mut self,
+        document: CaptureSearchDocument,
+        modified_ts: u64,
+        text_blocks: &[TextBlock],
+    ) {
+        let blocks = text_blocks
+            .iter()
+            .filter(|text_block| !text_block.text.trim().is_empty())
+            .map(|text_block| CaptureSearchHighlight {
+                rect: block_rect(text_block),
+                text: text_block.text.clone(),
+            })
+            .collect();
+
+        let document = IndexedDocument::new(document, modified_ts, blocks);
+        self.pending_entries.push(SearchIndexLogEntry::Upsert {
+            document: document.clone(),
+        });
+        self.insert_document(document);
+    }
+
+    pub fn remove(&mut self, id: &str) -> bool {
+        if !self.remove_document(id) {
+            return false;
+        }
+
+        self.pending_entries
+            .push(SearchIndexLogEntry::Remove { id: id.to_string() });
+        true
+    }
+
+    fn remove_document(&mut self, id: &str) -> bool {
+        let Some(document) = self.documents.remove(id) else {
+            return false;
+        };
+
+        for term in document.term_freqs.keys() {
+            if let Some(ids) = self.postings.get_mut(term) {
+                ids.remove(id);
+                if ids.is_empty() {
+                    self.postings.remove(term);
+                }
+            }
+        }
+        self.total_length -= document.length;
+        if self.documents.is_empty() {
+            self.total_length = 0.0;
+        }
+
+        true
+    }
+
+    /**
+     * 删除不在列表中的截图，返回删除的数量
+     */
+    pub fn retain(&mut self, ids: &HashSet<&str>) -> usize {
+        let removed_ids = self
+            .documents
+            .keys()
+            .filter(|id| !ids.contains(id.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        for id in &removed_ids {
+            self.remove(id);
+        }
+
+        removed_ids.len()
+    }
+
+    /**
+     * 搜索包含所有关键词的截图，按 BM25 得分从高到低排列
+     */
+    pub fn query(&self, query: &str, limit: usize) -> Vec<CaptureSearchHit> {
+        let mut terms = tokenize(query);
+        terms.sort();
+        terms.dedup();
+        if terms.is_empty() || self.documents.is_empty() {
+            return Vec::new();
+        }
+
+        let mut term_ids = Vec::with_capacity(terms.len());
+        for term in &terms {
+            match self.postings.get(term) {
+                Some(ids) => term_ids.push((term, ids)),
+                None => return Vec::new(),
+            }
+        }
+        // 从包含文档最少的词开始求交集
+        term_ids.sort_by_key(|(_, ids)| ids.len());
+
+        let document_count = self.documents.len() as f32;
+        let average_length = (self.total_length / document_count).max(f32::EPSILON);
+
+        let mut hits = term_ids[0]
+            .1
+            .iter()
+            .filter(|id| term_ids[1..].iter().all(|(_, ids)| ids.contains(*id)))
+            .filter_map(|id| self.documents.get(id))
+            .map(|document| {
+                let length_norm = 1.0 - BM25_B + BM25_B * document.length / average_length;
+                let score = term_ids
+                    .iter()
+                    .map(|(term, ids)| {
+                        let document_freq = ids.len() as f32;
+                        let idf = (1.0
+                            + (document_count - document_freq + 0.5) / (document_freq + 0.5))
+                            .ln();
+                        let term_freq = document.term_freqs.get(*term).copied().unwrap_or(0.0);
+
+                        idf * term_freq * (BM25_K1 + 1.0) / (term_freq + BM25_K1 * length_norm)
+                    })
+                    .sum::<f32>();
+
+                (document, score)
+            })
+            .collect::<Vec<_>>();
+
+        hits.sort_by(|(a, a_score), (b, b_score)| {
+            b_score
+                .total_cmp(a_score)
+                .then(b.document.create_ts.cmp(&a.document.create_ts))
+        });
+
+        hits.into_iter()
+            .take(limit)
+            .map(|(document, score)| CaptureSearchHit {
+                id: document.document.id.clone(),
+                file_path: document.document.file_path.clone(),
+                create_ts: document.document.create_ts,
+                window_title: document.document.window_title.clone(),
+                process_name: document.document.process_name.clone(),
+                score,
+                highlights: document
+                    .blocks
+                    .iter()
+                    .filter(|block| {
+                        let block_terms = tokenize(&block.text);
+                        terms.iter().any(|term| block_terms.contains(term))
+                    })
+                    .cloned()
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+/**
+ * 图片的修改时间（毫秒），用于判断索引是否需要更新
+ */
+pub async fn file_modified_ts(file_path: &Path) -> Result<u64, String> {
+    let metadata = match tokio::fs::metadata(file_path).await {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            return Err(format!(
+                "[file_modified_ts] Failed to read file metadata: {} {}",
+                e,
+                file_path.display()
+            ));
+        }
+    };
+
+    Ok(metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    fn text_block(text: &str, y: u32) -> TextBlock {
+        test_utils::text_block(10, y, 200, y + 20, text)
+    }
+
+    fn document(id: &str, window_title: Option<&str>) -> CaptureSearchDocument {
+        CaptureSearchDocument {
+            id: id.to_string(),
+            file_path: PathBuf::from(format!("{}.png", id)),
+            create_ts: id.len() as i64,
+            window_title: window_title.map(str::to_string),
+            process_name: None,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("Build FAILED: e2e-42"),
+            ["build", "failed", "e2e", "42"]
+        );
+        assert_eq!(
+            tokenize("截图历史Snow"),
+            ["截", "截图", "图", "图历", "历", "历史", "史", "snow"]
+        );
+        assert_eq!(tokenize("。，"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_query_ranking_and_highlights() {
+        let mut index = CaptureSearchIndex::default();
+        index.insert(
+            document("a", None),
+            1,
+            &[
+                text_block("deploy pipeline", 0),
+                text_block("build failed", 40),
+            ],
+        );
+        index.insert(
+            document("b", Some("Build Monitor")),
+            1,
+            &[text_block("build failed again", 0)],
+        );
+        index.insert(document("c", None), 1, &[text_block("查看构建日志", 0)]);
+
+        let hits = index.query("build FAILED", 10);
+        assert_eq!(
+            hits.iter().map(|hit| hit.id.as_str()).collect::<Vec<_>>(),
+            ["b", "a"]
+        );
+        assert_eq!(hits[1].highlights.len(), 1);
+        assert_eq!(hits[1].highlights[0].text, "build failed");
+        assert_eq!(hits[1].highlights[0].rect.min_y, 40);
+
+        let hits = index.query("构建", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "c");
+
+        assert!(index.query("missing", 10).is_empty());
+    }
+
+    #[test]
+    fn test_incremental_update() {
+        let mut index = CaptureSearchIndex::default();
+        index.insert(document("a", None), 1, &[text_block("old text", 0)]);
+        index.insert(document("b", None), 1, &[text_block("other", 0)]);
+
+        assert!(!index.needs_update(&document("a", None), 1));
+        assert!(index.needs_update(&document("a", None), 2));
+        assert!(index.needs_update(&document("a", Some("title")), 1));
+
+        index.insert(document("a", None), 2, &[text_block("new text", 0)]);
+        assert!(index.query("old", 10).is_empty());
+        assert_eq!(index.query("new", 10).len(), 1);
+
+        assert_eq!(index.retain(&HashSet::from(["b"])), 1);
+        assert_eq!(index.len(), 1);
+        assert!(index.query("text", 10).is_empty());
+        assert!(!index.postings.contains_key("text"));
+    }
+
+    #[tokio::test]
+    async fn test_save_appends_log_and_compacts() {
+        let index_dir = std::env::temp_dir().join(format!(
+            "snow_shot_capture_search_test_{}",
+            std::process::id()
+        ));
+        let index_path = index_dir.join("capture_search_index.json");
+        let log_path = CaptureSearchIndex::log_path(&index_path);
+        let _ = std::fs::remove_dir_all(&index_dir);
+
+        let mut index = CaptureSearchIndex::default();
+        index.load(index_path.clone()).await;
+        index.insert(document("a", None), 1, &[text_block("old text", 0)]);
+        index.save().await.unwrap();
+        assert!(!index_path.exists());
+        assert!(log_path.exists());
+
+        index.insert(document("b", None), 1, &[text_block("other", 0)]);
+        index.insert(document("a", None), 2, &[text_block("new text", 0)]);
+        index.remove("b");
+        index.save().await.unwrap();
+        assert_eq!(index.log_entry_count, 4);
+
+        let mut loaded_index = CaptureSearchIndex::default();
+        loaded_index.load(index_path.clone()).await;
+        assert_eq!(loaded_index.len(), 1);
+        assert!(loaded_index.query("old", 10).is_empty());
+        assert_eq!(loaded_index.query("new", 10).len(), 1);
+        assert!(!loaded_index.needs_update(&document("a", None), 2));
+
+        // 日志条数达到阈值后重写索引文件
+        for index_id in 0..SEARCH_INDEX_MIN_COMPACT_ENTRIES {
+            loaded_index.insert(
+                document(&format!("c{}", index_id), None),
+                1,
+                &[text_block("more", 0)],
+            );
+        }
+        loaded_index.save().await.unwrap();
+        assert!(index_path.exists());
+        assert!(!log_path.exists());
+
+        let mut compacted_index = CaptureSearchIndex::default();
+        compacted_index.load(index_path).await;
+        assert_eq!(compacted_index.len(), SEARCH_INDEX_MIN_COMPACT_ENTRIES + 1);
+        assert_eq!(compacted_index.query("new", 10).len(), 1);
+
+        let _ = std::fs::remove_dir_all(&index_dir);
+    }
+}
@@ -0,0 +1,909 @@
+use crate::layout::{LayoutBlock, join_text, layout_blocks};
+use image::{GrayImage, RgbImage};
+use paddle_ocr_rs::ocr_result::TextBlock;
+use serde::{Deserialize, Serialize};
+use snow_shot_app_shared::ElementRect;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OcrTableCell {
+    pub row: usize,
+    pub column: usize,
+    pub row_span: usize,
+    pub column_span: usize,
+    pub rect: ElementRect,
+    pub text: String,
+    /// 单元格内文本块在 text_blocks 中的索引
+    pub block_indices: Vec<usize>,
+}
+
+/**
+ * 从 OCR 结果重建的表格
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OcrTable {
+    pub rect: ElementRect,
+    pub row_count: usize,
+    pub column_count: usize,
+    /// 是否根据图片中的表格线划分单元格，为 false 时根据文本块的对齐推断
+    pub has_ruling_lines: bool,
+    /// 按行、列排列，被合并的单元格不包含在内
+    pub cells: Vec<OcrTableCell>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OcrTableExportFormat {
+    Csv,
+    Tsv,
+    Markdown,
+    Html,
+}
+
+/// 与背景的亮度差超过该值的像素视为表格线
+const RULING_MIN_CONTRAST: i16 = 48;
+/// 表格线的最短长度（像素）
+const RULING_MIN_LENGTH: u32 = 24;
+/// 表格线的最大粗细（像素），更粗的区域通常是背景色块
+const RULING_MAX_THICKNESS: u32 = 8;
+/// 表格线中允许的最大断开距离（像素），兼容虚线和抗锯齿
+const RULING_MAX_GAP: u32 = 2;
+/// 位置相差不超过该值的表格线视为同一条边界
+const BOUNDARY_TOLERANCE: f32 = 4.0;
+
+#[derive(Debug, Clone, Copy)]
+struct RulingLine {
+    /// 水平线为 y，竖直线为 x
+    position: f32,
+    start: f32,
+    end: f32,
+}
+
+impl RulingLine {
+    fn covers(&self, position: f32, start: f32, end: f32) -> bool {
+        (self.position - position).abs() <= BOUNDARY_TOLERANCE
+            && self.end.min(end) - self.start.max(start) >= (end - start) * 0.5
+    }
+
+    fn crosses(&self, other: &RulingLine) -> bool {
+        other.position >= self.start - BOUNDARY_TOLERANCE
+            && other.position <= self.end + BOUNDARY_TOLERANCE
+            && self.position >= other.start - BOUNDARY_TOLERANCE
+            && self.position <= other.end + BOUNDARY_TOLERANCE
+    }
+}
+
+/// 亮度直方图的中位数作为背景亮度
+fn background_luma(image: &GrayImage) -> u8 {
+    let mut histogram = [0usize; 256];
+    for pixel in image.pixels() {
+        histogram[pixel.0[0] as usize] += 1;
+    }
+
+    let half = (image.width() as usize * image.height() as usize) / 2;
+    let mut count = 0;
+    for (luma, &value) in histogram.iter().enumerate() {
+        count += value;
+        if count > half {
+            return luma as u8;
+        }
+    }
+
+    255
+}
+
+/**
+ * 检测水平或竖直的表格线，同时适用于浅色和深色背景
+ */
+fn detect_ruling_lines(image: &GrayImage, horizontal: bool, image_scale: f32) -> Vec<RulingLine> {
+    let (length, count) = if horizontal {
+        (image.width(), image.height())
+    } else {
+        (image.height(), image.width())
+    };
+    if length == 0 || count == 0 {
+        return vec![];
+    }
+
+    let background = background_luma(image) as i16;
+    let is_ink = |along: u32, position: u32| {
+        let (x, y) = if horizontal {
+            (along, position)
+        } else {
+            (position, along)
+        };
+        (image.get_pixel(x, y).0[0] as i16 - background).abs() > RULING_MIN_CONTRAST
+    };
+    let min_length = (length / 20).max(RULING_MIN_LENGTH).min(length);
+
+    // (start, end, first_position, last_position)
+    let mut active: Vec<(u32, u32, u32, u32)> = Vec::new();
+    let mut finished: Vec<(u32, u32, u32, u32)> = Vec::new();
+    for position in 0..count {
+        let mut runs = Vec::new();
+        let mut run_start = None;
+        let mut last_ink = 0;
+        for along in 0..length {
+            if is_ink(along, position) {
+                if run_start.is_none() {
+                    run_start = Some(along);
+                }
+                last_ink = along;
+            } else if let Some(start) = run_start
+                && along - last_ink > RULING_MAX_GAP
+            {
+                runs.push((start, last_ink + 1));
+                run_start = None;
+            }
+        }
+        if let Some(start) = run_start {
+            runs.push((start, last_ink + 1));
+        }
+
+        let mut next_active = Vec::new();
+        for (start, end) in runs
+            .into_iter()
+            .filter(|(start, end)| end - start >= min_length)
+        {
+            let continued = active.iter().position(|&(active_start, active_end, _, _)| {
+                let overlap = end.min(active_end) as i64 - start.max(active_start) as i64;
+                overlap * 2 >= (end - start).min(active_end - active_start) as i64
+            });
+
+            match continued {
+                Some(index) => {
+                    let (active_start, active_end, first, _) = active.remove(index);
+                    next_active.push((
+                        active_start.min(start),
+                        active_end.max(end),
+                        first,
+                        position,
+                    ));
+                }
+                None => next_active.push((start, end, position, position)),
+            }
+        }
+
+        finished.append(&mut active);
+        active = next_active;
+    }
+    finished.append(&mut active);
+
+    finished
+        .into_iter()
+        .filter(|&(_, _, first, last)| last - first < RULING_MAX_THICKNESS)
+        .map(|(start, end, first, last)| RulingLine {
+            position: (first + last + 1) as f32 * 0.5 / image_scale,
+            start: start as f32 / image_scale,
+            end: end as f32 / image_scale,
+        })
+        .collect()
+}
+
+/// 合并相近的位置
+fn cluster_positions(mut positions: Vec<f32>) -> Vec<f32> {
+    positions.sort_by(|a, b| a.total_cmp(b));
+
+    let mut clusters: Vec<(f32, usize)> = Vec::new();
+    for position in positions {
+        match clusters.last_mut() {
+            Some((sum, count)) if position - *sum / *count as f32 <= BOUNDARY_TOLERANCE => {
+                *sum += position;
+                *count += 1;
+            }
+            _ => clusters.push((position, 1)),
+        }
+    }
+
+    clusters
+        .into_iter()
+        .map(|(sum, count)| sum / count as f32)
+        .collect()
+}
+
+fn find_interval(boundaries: &[f32], value: f32) -> Option<usize> {
+    boundaries
+        .windows(2)
+        .position(|window| value >= window[0] && value < window[1])
+}
+
+fn union_rect(rects: impl IntoIterator<Item = ElementRect>) -> Option<ElementRect> {
+    rects.into_iter().reduce(|a, b| ElementRect {
+        min_x: a.min_x.min(b.min_x),
+        min_y: a.min_y.min(b.min_y),
+        max_x: a.max_x.max(b.max_x),
+        max_y: a.max_y.max(b.max_y),
+    })
+}
+
+fn center(rect: &ElementRect) -> (f32, f32) {
+    (
+        (rect.min_x + rect.max_x) as f32 * 0.5,
+        (rect.min_y + rect.max_y) as f32 * 0.5,
+    )
+}
+
+/**
+ * 单元格内的文本按行拼接，行之间以换行分隔
+ */
+fn cell_text(blocks: &[LayoutBlock], indices: &mut [usize]) -> String {
+    indices.sort_by_key(|&index| (blocks[index].rect.min_y, blocks[index].rect.min_x));
+
+    let mut lines: Vec<(i32, Vec<&str>)> = Vec::new();
+    for &index in indices.iter() {
+        let block = &blocks[index];
+        let (_, center_y) = center(&block.rect);
+        match lines.last_mut() {
+            Some((bottom, line)) if center_y < *bottom as f32 => {
+                line.push(block.text.as_str());
+                *bottom = (*bottom).max(block.rect.max_y);
+            }
+            _ => lines.push((block.rect.max_y, vec![block.text.as_str()])),
+        }
+    }
+
+    lines
+        .into_iter()
+        .map(|(_, line)| join_text(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+struct CellGroup {
+    row: usize,
+    column: usize,
+    row_span: usize,
+    column_span: usize,
+    rect: Option<ElementRect>,
+    blocks: Vec<usize>,
+}
+
+fn build_table(
+    blocks: &[LayoutBlock],
+    mut groups: Vec<CellGroup>,
+    row_count: usize,
+    column_count: usize,
+    has_ruling_lines: bool,
+) -> Option<OcrTable> {
+    groups.sort_by_key(|group| (group.row, group.column));
+
+    let cells = groups
+        .into_iter()
+        .map(|mut group| OcrTableCell {
+            row: group.row,
+            column: group.column,
+            row_span: group.row_span,
+            column_span: group.column_span,
+            rect: group
+                .rect
+                .or_else(|| union_rect(group.blocks.iter().map(|&index| blocks[index].rect)))
+                .unwrap_or(ElementRect {
+                    min_x: 0,
+                    min_y: 0,
+                    max_x: 0,
+                    max_y: 0,
+                }),
+            text: cell_text(blocks, &mut group.blocks),
+            block_indices: group
+                .blocks
+                .iter()
+                .map(|&index| blocks[index].index)
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+
+    // 至少两行包含两个以上有文本的单元格才视为表格
+    let filled_rows = (0..row_count)
+        .filter(|&row| {
+            cells
+                .iter()
+                .filter(|cell| cell.row == row && !cell.text.is_empty())
+                .count()
+                >= 2
+        })
+        .count();
+    if filled_rows < 2 || column_count < 2 {
+        return None;
+    }
+
+    Some(OcrTable {
+        rect: union_rect(cells.iter().map(|cell| cell.rect))?,
+        row_count,
+        column_count,
+        has_ruling_lines,
+        cells,
+    })
+}
+
+/**
+ * 根据表格线划分单元格，缺少的边界视为合并单元格
+ */
+fn recognize_ruled_table(
+    blocks: &[LayoutBlock],
+    horizontal_lines: Vec<RulingLine>,
+    vertical_lines: Vec<RulingLine>,
+) -> Option<OcrTable> {
+    // 只保留与至少两条其他方向的线相交的线，排除下划线、分隔线等
+    let vertical_lines = vertical_lines
+        .iter()
+        .filter(|line| horizontal_lines.iter().filter(|h| line.crosses(h)).count() >= 2)
+        .copied()
+        .collect::<Vec<_>>();
+    let horizontal_lines = horizontal_lines
+        .iter()
+        .filter(|line| vertical_lines.iter().filter(|v| line.crosses(v)).count() >= 2)
+        .copied()
+        .collect::<Vec<_>>();
+
+    let rows = cluster_positions(horizontal_lines.iter().map(|line| line.position).collect());
+    let columns = cluster_positions(vertical_lines.iter().map(|line| line.position).collect());
+    if rows.len() < 2 || columns.len() < 2 {
+        return None;
+    }
+
+    let row_count = rows.len() - 1;
+    let column_count = columns.len() - 1;
+    let cell_index = |row: usize, column: usize| row * column_count + column;
+
+    let mut parents = (0..row_count * column_count).collect::<Vec<_>>();
+    fn find(parents: &mut [usize], index: usize) -> usize {
+        let mut root = index;
+        while parents[root] != root {
+            root = parents[root];
+        }
+        parents[index] = root;
+        root
+    }
+
+    for row in 0..row_count {
+        for column in 0..column_count {
+            let has_right_edge = column + 1 == column_count
+                || vertical_lines
+                    .iter()
+                    .any(|line| line.covers(columns[column + 1], rows[row], rows[row + 1]));
+            if !has_right_edge {
+                let a = find(&mut parents, cell_index(row, column));
+                let b = find(&mut parents, cell_index(row, column + 1));
+                parents[b] = a;
+            }
+
+            let has_bottom_edge = row + 1 == row_count
+                || horizontal_lines
+                    .iter()
+                    .any(|line| line.covers(rows[row + 1], columns[column], columns[column + 1]));
+            if !has_bottom_edge {
+                let a = find(&mut parents, cell_index(row, column));
+                let b = find(&mut parents, cell_index(row + 1, column));
+                parents[b] = a;
+            }
+        }
+    }
+
+    let mut groups: Vec<(usize, CellGroup)> = Vec::new();
+    for row in 0..row_count {
+        for column in 0..column_count {
+            let root = find(&mut parents, cell_index(row, column));
+            match groups
+                .iter_mut()
+                .find(|(group_root, _)| *group_root == root)
+            {
+                Some((_, group)) => {
+                    group.row_span = group.row_span.max(row + 1 - group.row);
+                    group.column_span = group.column_span.max(column + 1 - group.column);
+                }
+                None => groups.push((
+                    root,
+                    CellGroup {
+                        row,
+                        column,
+                        row_span: 1,
+                        column_span: 1,
+                        rect: None,
+                        blocks: vec![],
+                    },
+                )),
+            }
+        }
+    }
+
+    for (_, group) in groups.iter_mut() {
+        group.rect = Some(ElementRect {
+            min_x: columns[group.column].round() as i32,
+            min_y: rows[group.row].round() as i32,
+            max_x: columns[group.column + group.column_span].round() as i32,
+            max_y: rows[group.row + group.row_span].round() as i32,
+        });
+    }
+
+    for (index, block) in blocks.iter().enumerate() {
+        let (center_x, center_y) = center(&block.rect);
+        let (Some(row), Some(column)) = (
+            find_interval(&rows, center_y),
+            find_interval(&columns, center_x),
+        ) else {
+            continue;
+        };
+
+        let root = find(&mut parents, cell_index(row, column));
+        if let Some((_, group)) = groups
+            .iter_mut()
+            .find(|(group_root, _)| *group_root == root)
+        {
+            group.blocks.push(index);
+        }
+    }
+
+    build_table(
+        blocks,
+        groups.into_iter().map(|(_, group)| group).collect(),
+        row_count,
+        column_count,
+        true,
+    )
+}
+
+/**
+ * 没有表格线时，根据文本块的对齐推断行和列，跨越多列的文本块视为合并单元格
+ */
+fn recognize_borderless_table(blocks: &[LayoutBlock]) -> Option<OcrTable> {
+    let mut order = (0..blocks.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| {
+        center(&blocks[a].rect)
+            .1
+            .total_cmp(&center(&blocks[b].rect).1)
+    });
+
+    // (min_y, max_y, blocks)
+    let mut rows: Vec<(i32, i32, Vec<usize>)> = Vec::new();
+    for index in order {
+        let rect = &blocks[index].rect;
+        match rows.last_mut() {
+            Some((min_y, max_y, row))
+                if (rect.max_y.min(*max_y) - rect.min_y.max(*min_y)) * 2
+                    >= (rect.max_y - rect.min_y).min(*max_y - *min_y) =>
+            {
+                *min_y = (*min_y).min(rect.min_y);
+                *max_y = (*max_y).max(rect.max_y);
+                row.push(index);
+            }
+            _ => rows.push((rect.min_y, rect.max_y, vec![index])),
+        }
+    }
+
+    // 以最常见的单元格数量的行作为参考，避免跨列的文本块影响列的划分
+    let mut count_frequency: Vec<(usize, usize)> = Vec::new();
+    for (_, _, row) in rows.iter().filter(|(_, _, row)| row.len() >= 2) {
+        match count_frequency
+            .iter_mut()
+            .find(|(count, _)| *count == row.len())
+        {
+            Some((_, frequency)) => *frequency += 1,
+            None => count_frequency.push((row.len(), 1)),
+        }
+    }
+    let (reference_count, _) = count_frequency
+        .into_iter()
+        .max_by_key(|&(count, frequency)| (frequency, count))?;
+
+    let mut intervals = rows
+        .iter()
+        .filter(|(_, _, row)| row.len() == reference_count)
+        .flat_map(|(_, _, row)| {
+            row.iter()
+                .map(|&index| (blocks[index].rect.min_x, blocks[index].rect.max_x))
+        })
+        .collect::<Vec<_>>();
+    intervals.sort();
+    let mut columns: Vec<(i32, i32)> = Vec::new();
+    for (start, end) in intervals {
+        match columns.last_mut() {
+            Some((_, column_end)) if start <= *column_end => *column_end = (*column_end).max(end),
+            _ => columns.push((start, end)),
+        }
+    }
+    if columns.len() < 2 {
+        return None;
+    }
+
+    let mut groups: Vec<CellGroup> = Vec::new();
+    for (row_index, (_, _, row)) in rows.iter().enumerate() {
+        let mut row_groups: Vec<CellGroup> = Vec::new();
+        for &index in row {
+            let rect = &blocks[index].rect;
+            let start = columns
+                .iter()
+                .position(|&(_, end)| end > rect.min_x)
+                .unwrap_or(columns.len() - 1);
+            let end = columns
+                .iter()
+                .rposition(|&(start, _)| start < rect.max_x)
+                .unwrap_or(0)
+                .max(start);
+
+            match row_groups.iter_mut().find(|group| group.column == start) {
+                Some(group) => {
+                    group.column_span = group.column_span.max(end + 1 - start);
+                    group.blocks.push(index);
+                }
+                None => row_groups.push(CellGroup {
+                    row: row_index,
+                    column: start,
+                    row_span: 1,
+                    column_span: end + 1 - start,
+                    rect: None,
+                    blocks: vec![index],
+                }),
+            }
+        }
+
+        // 合并的单元格不能覆盖同一行的其他单元格
+        row_groups.sort_by_key(|group| group.column);
+        let next_columns = row_groups
+            .iter()
+            .skip(1)
+            .map(|group| group.column)
+            .chain(std::iter::once(columns.len()))
+            .collect::<Vec<_>>();
+        for (group, next_column) in row_groups.iter_mut().zip(next_columns) {
+            group.column_span = group.column_span.min(next_column - group.column);
+        }
+
+        groups.extend(row_groups);
+    }
+
+    build_table(blocks, groups, rows.len(), columns.len(), false)
+}
+
+fn recognize_table_from_blocks(
+    image: &GrayImage,
+    image_scale: f32,
+    blocks: &[LayoutBlock],
+) -> Option<OcrTable> {
+    if blocks.is_empty() {
+        return None;
+    }
+
+    let horizontal_lines = detect_ruling_lines(image, true, image_scale);
+    let vertical_lines = detect_ruling_lines(image, false, image_scale);
+
+    recognize_ruled_table(blocks, horizontal_lines, vertical_lines)
+        .or_else(|| recognize_borderless_table(blocks))
+}
+
+/**
+ * 根据文本块和图片中的表格线重建表格
+ * image_scale 为图片相对文本块坐标的缩放
+ */
+pub fn recognize_table(
+    image: &RgbImage,
+    image_scale: f32,
+    text_blocks: &[TextBlock],
+) -> Option<OcrTable> {
+    let gray_image = image::imageops::grayscale(image);
+
+    recognize_table_from_blocks(&gray_image, image_scale, &layout_blocks(text_blocks))
+}
+
+fn escape_csv(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn escape_markdown_cell(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "<br>")
+}
+
+impl OcrTable {
+    /// 展开为二维表格，合并单元格的文本放在左上角的单元格
+    fn to_grid(&self) -> Vec<Vec<&str>> {
+        let mut grid = vec![vec![""; self.column_count]; self.row_count];
+        for cell in &self.cells {
+            if let Some(value) = grid
+                .get_mut(cell.row)
+                .and_then(|row| row.get_mut(cell.column))
+            {
+                *value = cell.text.as_str();
+            }
+        }
+
+        grid
+    }
+
+    pub fn to_csv(&self) -> String {
+        self.to_grid()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|text| escape_csv(text))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn to_tsv(&self) -> String {
+        self.to_grid()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|text| text.replace(['\t', '\r', '\n'], " "))
+                    .collect::<Vec<_>>()
+                    .join("\t")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Markdown 表格不支持合并单元格，第一行作为表头
+    pub fn to_markdown(&self) -> String {
+        let grid = self.to_grid();
+        let render_row = |row: &Vec<&str>| {
+            format!(
+                "| {} |",
+                row.iter()
+                    .map(|text| escape_markdown_cell(text))
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            )
+        };
+
+        let mut lines = Vec::with_capacity(grid.len() + 1);
+        for (index, row) in grid.iter().enumerate() {
+            lines.push(render_row(row));
+            if index == 0 {
+                lines.push(format!("|{}", " --- |".repeat(self.column_count)));
+            }
+        }
+
+        lines.join("\n")
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<table>\n");
+        for row in 0..self.row_count {
+            html.push_str("  <tr>");
+            for cell in self.cells.iter().filter(|cell| cell.row == row) {
+                html.push_str("<td");
+                if cell.row_span > 1 {
+                    html.push_str(&format!(" rowspan=\"{}\"", cell.row_span));
+                }
+                if cell.column_span > 1 {
+                    html.push_str(&format!(" colspan=\"{}\"", cell.column_span));
+                }
+                html.push('>');
+                html.push_str(&escape_html(&cell.text));
+                html.push_str("</td>");
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>");
+
+        html
+    }
+
+    pub fn export(&self, format: OcrTableExportFormat) -> String {
+        match format {
+            OcrTableExportFormat::Csv => self.to_csv(),
+            OcrTableExportFormat::Tsv => self.to_tsv(),
+            OcrTableExportFormat::Markdown => self.to_markdown(),
+            OcrTableExportFormat::Html => self.to_html(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::layout_block;
+    use image::Luma;
+
+    const INK: u8 = 20;
+    const PAPER: u8 = 250;
+
+    /// 绘制表格线，skip_vertical 和 skip_horizontal 为不绘制的线段 (线的索引, 单元格的索引)
+    fn render_ruled_table(
+        rows: &[u32],
+        columns: &[u32],
+        skip_vertical: &[(usize, usize)],
+        skip_horizontal: &[(usize, usize)],
+        ink: u8,
+        paper: u8,
+    ) -> GrayImage {
+        let width = columns.last().unwrap() + 20;
+        let height = rows.last().unwrap() + 20;
+        let mut image = GrayImage::from_pixel(width, height, Luma([paper]));
+
+        for (row_index, &y) in rows.iter().enumerate() {
+            for column_index in 0..columns.len() - 1 {
+                if skip_horizontal.contains(&(row_index, column_index)) {
+                    continue;
+                }
+                for x in columns[column_index]..=columns[column_index + 1] {
+                    image.put_pixel(x, y, Luma([ink]));
+                }
+            }
+        }
+        for (column_index, &x) in columns.iter().enumerate() {
+            for row_index in 0..rows.len() - 1 {
+                if skip_vertical.contains(&(column_index, row_index)) {
+                    continue;
+                }
+                for y in rows[row_index]..=rows[row_index + 1] {
+                    image.put_pixel(x, y, Luma([ink]));
+                }
+            }
+        }
+
+        image
+    }
+
+    fn grid_blocks(rows: &[u32], columns: &[u32], texts: &[&[&str]]) -> Vec<LayoutBlock> {
+        let mut blocks = Vec::new();
+        for (row, row_texts) in texts.iter().enumerate() {
+            for (column, text) in row_texts.iter().enumerate() {
+                if text.is_empty() {
+                    continue;
+                }
+                blocks.push(layout_block(
+                    blocks.len(),
+                    columns[column] as f32 + 6.0,
+                    rows[row] as f32 + 8.0,
+                    columns[column] as f32 + 6.0 + text.len() as f32 * 8.0,
+                    rows[row] as f32 + 24.0,
+                    text,
+                ));
+            }
+        }
+
+        blocks
+    }
+
+    #[test]
+    fn test_ruled_table() {
+        let rows = [10, 42, 74, 106];
+        let columns = [10, 110, 210, 310];
+        let image = render_ruled_table(&rows, &columns, &[], &[], INK, PAPER);
+        let blocks = grid_blocks(
+            &rows,
+            &columns,
+            &[
+                &["Name", "Qty", "Price"],
+                &["Apple", "3", "1.5"],
+                &["Pear", "", "2,0"],
+            ],
+        );
+
+        let table = recognize_table_from_blocks(&image, 1.0, &blocks).unwrap();
+
+        assert!(table.has_ruling_lines);
+        assert_eq!((table.row_count, table.column_count), (3, 3));
+        assert_eq!(table.cells.len(), 9);
+        assert_eq!(table.to_csv(), "Name,Qty,Price\nApple,3,1.5\nPear,,\"2,0\"");
+        assert_eq!(
+            table.to_tsv(),
+            "Name\tQty\tPrice\nApple\t3\t1.5\nPear\t\t2,0"
+        );
+        assert_eq!(
+            table.to_markdown(),
+            "| Name | Qty | Price |\n| --- | --- | --- |\n| Apple | 3 | 1.5 |\n| Pear |  | 2,0 |"
+        );
+    }
+
+    #[test]
+    fn test_ruled_table_with_merged_cells() {
+        let rows = [10, 42, 74, 106];
+        let columns = [10, 110, 210, 310];
+        // 第一行的前两列合并，第一列的后两行合并
+        let image = render_ruled_table(&rows, &columns, &[(1, 0)], &[(2, 0)], INK, PAPER);
+        let blocks = grid_blocks(
+            &rows,
+            &columns,
+            &[
+                &["Fruit", "", "Price"],
+                &["Apple", "Red", "1"],
+                &["", "Green", "2"],
+            ],
+        );
+
+        let table = recognize_table_from_blocks(&image, 1.0, &blocks).unwrap();
+
+        assert_eq!(table.cells.len(), 7);
+        assert_eq!(
+            table.to_html(),
+            "<table>\n  <tr><td colspan=\"2\">Fruit</td><td>Price</td></tr>\n  <tr><td rowspan=\"2\">Apple</td><td>Red</td><td>1</td></tr>\n  <tr><td>Green</td><td>2</td></tr>\n</table>"
+        );
+        assert_eq!(table.to_csv(), "Fruit,,Price\nApple,Red,1\n,Green,2");
+    }
+
+    #[test]
+    fn test_ruled_table_on_dark_background_with_image_scale() {
+        let rows = [20, 84, 148];
+        let columns = [20, 220, 420];
+        let image = render_ruled_table(&rows, &columns, &[], &[], 200, 30);
+        // 文本块的坐标是图片的一半
+        let blocks = vec![
+            layout_block(0, 15.0, 15.0, 50.0, 30.0, "a"),
+            layout_block(1, 115.0, 15.0, 150.0, 30.0, "b"),
+            layout_block(2, 15.0, 47.0, 50.0, 62.0, "c"),
+            layout_block(3, 115.0, 47.0, 150.0, 62.0, "d"),
+        ];
+
+        let table = recognize_table_from_blocks(&image, 2.0, &blocks).unwrap();
+
+        assert!(table.has_ruling_lines);
+        assert_eq!(table.to_tsv(), "a\tb\nc\td");
+        assert_eq!(
+            table.cells[0].rect,
+            ElementRect {
+                min_x: 10,
+                min_y: 10,
+                max_x: 110,
+                max_y: 42,
+            }
+        );
+    }
+
+    #[test]
+    fn test_borderless_table() {
+        let image = GrayImage::from_pixel(400, 200, Luma([PAPER]));
+        let blocks = vec![
+            layout_block(0, 10.0, 10.0, 300.0, 26.0, "Quarterly report"),
+            layout_block(1, 10.0, 40.0, 80.0, 56.0, "Region"),
+            layout_block(2, 150.0, 40.0, 220.0, 56.0, "Sales"),
+            layout_block(3, 10.0, 70.0, 80.0, 86.0, "North"),
+            layout_block(4, 150.0, 70.0, 200.0, 86.0, "120"),
+            layout_block(5, 10.0, 100.0, 70.0, 116.0, "South"),
+            layout_block(6, 150.0, 100.0, 190.0, 116.0, "98|x"),
+        ];
+
+        let table = recognize_table_from_blocks(&image, 1.0, &blocks).unwrap();
+
+        assert!(!table.has_ruling_lines);
+        assert_eq!((table.row_count, table.column_count), (4, 2));
+        assert_eq!(table.cells[0].column_span, 2);
+        assert_eq!(
+            table.to_markdown(),
+            "| Quarterly report |  |\n| --- | --- |\n| Region | Sales |\n| North | 120 |\n| South | 98\\|x |"
+        );
+    }
+
+    #[test]
+    fn test_multiline_cell() {
+        let rows = [10, 60, 110];
+        let columns = [10, 150, 290];
+        let image = render_ruled_table(&rows, &columns, &[], &[], INK, PAPER);
+        let mut blocks = grid_blocks(&rows, &columns, &[&["Key", "Value"], &["a", "x"]]);
+        blocks.push(layout_block(4, 16.0, 36.0, 60.0, 52.0, "line"));
+
+        let table = recognize_table_from_blocks(&image, 1.0, &blocks).unwrap();
+
+        assert_eq!(table.cells[0].text, "Key\nline");
+        assert_eq!(table.to_csv(), "\"Key\nline\",Value\na,x");
+        assert_eq!(table.to_tsv(), "Key line\tValue\na\tx");
+    }
+
+    #[test]
+    fn test_paragraph_is_not_table() {
+        let image = GrayImage::from_pixel(400, 200, Luma([PAPER]));
+        let blocks = vec![
+            layout_block(0, 10.0, 10.0, 300.0, 26.0, "first line of text"),
+            layout_block(1, 10.0, 30.0, 280.0, 46.0, "second line of text"),
+            layout_block(2, 10.0, 50.0, 200.0, 66.0, "end"),
+        ];
+
+        assert!(recognize_table_from_blocks(&image, 1.0, &blocks).is_none());
+    }
+}
@@ -0,0 +1,44 @@
+use crate::layout::LayoutBlock;
+use paddle_ocr_rs::ocr_result::{Point, TextBlock};
+
+/**
+ * 创建测试用的水平文本块
+ */
+pub(crate) fn text_block(min_x: u32, min_y: u32, max_x: u32, max_y: u32, text: &str) -> TextBlock {
+    TextBlock {
+        box_points: vec![
+            Point { x: min_x, y: min_y },
+            Point { x: max_x, y: min_y },
+            Point { x: max_x, y: max_y },
+            Point { x: min_x, y: max_y },
+        ],
+        box_score: 1.0,
+        angle_index: 0,
+        angle_score: 1.0,
+        text: text.to_string(),
+        text_score: 1.0,
+    }
+}
+
+/**
+ * 创建测试用的水平版面文本块
+ */
+pub(crate) fn layout_block(
+    index: usize,
+    min_x: f32,
+    min_y: f32,
+    max_x: f32,
+    max_y: f32,
+    text: &str,
+) -> LayoutBlock {
+    LayoutBlock::new(
+        index,
+        [
+            (min_x, min_y),
+            (max_x, min_y),
+            (max_x, max_y),
+            (min_x, max_y),
+        ],
+        text.to_string(),
+    )
+}
@@ -0,0 +1,268 @@
+use image::DynamicImage;
+use image::imageops::FilterType;
+use serde::{Deserialize, Serialize};
+use snow_shot_app_shared::ElementRect;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// 监控区域触发时通知前端的事件
+pub const REGION_WATCH_HIT_EVENT: &str = "region-watch-hit";
+/// 监控任务开始或结束时通知前端的事件，内容为正在运行的任务 ID
+pub const REGION_WATCH_STATE_EVENT: &str = "region-watch-state";
+
+/// 画面指纹长边的格子数，截图缩小到该尺寸后比较
+const FINGERPRINT_SIZE: u32 = 64;
+/// 格子的亮度差超过该值时视为变化，过滤光标闪烁和抗锯齿带来的抖动
+const FINGERPRINT_PIXEL_TOLERANCE: u8 = 8;
+
+/// 截图间隔的下限（毫秒）
+const MIN_INTERVAL_MS: u64 = 200;
+/// CPU 预算的下限，避免间隔被无限延长
+const MIN_CPU_BUDGET: f32 = 0.01;
+
+/// 正在运行的监控任务
+static REGION_WATCHES: LazyLock<Mutex<HashMap<String, CancellationToken>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct RegionWatchOptions {
+    /// 两次截图的间隔（毫秒）
+    pub interval_ms: u64,
+    /// 监控占用的 CPU 时间比例上限，截图和识别耗时较长时自动延长间隔
+    pub cpu_budget: f32,
+    /// 变化的格子比例超过该值时视为画面变化
+    pub change_threshold: f32,
+    /// 画面变化后识别文字，匹配该正则表达式时才触发，为空时画面变化即触发
+    pub text_pattern: Option<String>,
+    /// 两次触发的最短间隔（毫秒）
+    pub cooldown_ms: u64,
+    /// 触发时由前端发送系统通知
+    pub notify: bool,
+    /// 触发时将截图保存到该路径，支持文件名模板
+    pub file_path: Option<String>,
+    /// 触发时将截图写入该截图历史文件夹
+    pub capture_history_dir: Option<PathBuf>,
+    /// 触发一次后停止监控
+    pub stop_on_hit: bool,
+}
+
+impl Default for RegionWatchOptions {
+    fn default() -> Self {
+        Self {
+            interval_ms: 1000,
+            cpu_budget: 0.1,
+            change_threshold: 0.02,
+            text_pattern: None,
+            cooldown_ms: 5000,
+            notify: true,
+            file_path: None,
+            capture_history_dir: None,
+            stop_on_hit: false,
+        }
+    }
+}
+
+impl RegionWatchOptions {
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval_ms.max(MIN_INTERVAL_MS))
+    }
+
+    pub fn cooldown(&self) -> Duration {
+        Duration::from_millis(self.cooldown_ms)
+    }
+
+    pub fn text_pattern(&self) -> Option<&str> {
+        self.text_pattern
+            .as_deref()
+            .filter(|text_pattern| !text_pattern.is_empty())
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct RegionWatchHit {
+    pub watch_id: String,
+    pub region: ElementRect,
+    /// 与上一次比较的画面相比，变化的格子比例
+    pub change_ratio: f32,
+    /// 识别到的文字，未设置 text_pattern 时为空
+    pub text: Option<String>,
+    /// 匹配正则表达式的文字
+    pub matched_text: Option<String>,
+    /// 触发时的截图时间（毫秒时间戳）
+    pub capture_time: u64,
+    /// 保存的截图路径
+    pub capture_path: Option<PathBuf>,
+    /// 写入截图历史的文件名，由前端添加截图历史记录
+    pub capture_history_file_name: Option<String>,
+    pub notify: bool,
+}
+
+/**
+ * 缩小后的灰度画面，用于快速比较两帧的差异
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionFingerprint {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl RegionFingerprint {
+    pub fn new(image: &DynamicImage) -> Self {
+        let (width, height) = (image.width().max(1), image.height().max(1));
+        let scale = FINGERPRINT_SIZE as f32 / width.max(height) as f32;
+        let width = ((width as f32 * scale).round() as u32).max(1);
+        let height = ((height as f32 * scale).round() as u32).max(1);
+
+        // Triangle 缩小时按缩放比例扩大采样范围，结果接近区域平均值
+        let luma = image
+            .resize_exact(width, height, FilterType::Triangle)
+            .to_luma8();
+
+        Self {
+            width,
+            height,
+            pixels: luma.into_raw(),
+        }
+    }
+
+    /**
+     * 变化的格子比例，尺寸不同时视为完全变化
+     */
+    pub fn difference(&self, other: &Self) -> f32 {
+        if self.width != other.width || self.height != other.height {
+            return 1.0;
+        }
+
+        let changed = self
+            .pixels
+            .iter()
+            .zip(other.pixels.iter())
+            .filter(|(a, b)| a.abs_diff(**b) > FINGERPRINT_PIXEL_TOLERANCE)
+            .count();
+
+        changed as f32 / self.pixels.len() as f32
+    }
+}
+
+/**
+ * 下一次截图前的等待时间
+ *
+ * work 为截图和识别的耗时，cpu_time 为占用的 CPU 时间，多线程识别时大于 work
+ * 保证 cpu_time / (work + delay) 不超过 cpu_budget，且不短于 interval
+ */
+pub fn next_delay(
+    interval: Duration,
+    work: Duration,
+    cpu_time: Duration,
+    cpu_budget: f32,
+) -> Duration {
+    let cpu_budget = cpu_budget.clamp(MIN_CPU_BUDGET, 1.0);
+    let budget_delay = cpu_time.mul_f32(1.0 / cpu_budget).saturating_sub(work);
+
+    interval.max(budget_delay)
+}
+
+pub fn register_watch(watch_id: &str) -> Result<CancellationToken, String> {
+    let mut watches = REGION_WATCHES.lock().unwrap_or_else(|e| e.into_inner());
+    if watches.contains_key(watch_id) {
+        return Err(format!(
+            "[register_watch] Watch already exists: {}",
+            watch_id
+        ));
+    }
+
+    let cancel_token = CancellationToken::new();
+    watches.insert(watch_id.to_string(), cancel_token.clone());
+
+    Ok(cancel_token)
+}
+
+/**
+ * 停止监控任务，watch_id 为空时停止所有任务
+ */
+pub fn stop_watch(watch_id: Option<&str>) {
+    let watches = REGION_WATCHES.lock().unwrap_or_else(|e| e.into_inner());
+    match watch_id {
+        Some(watch_id) => {
+            if let Some(cancel_token) = watches.get(watch_id) {
+                cancel_token.cancel();
+            }
+        }
+        None => watches.values().for_each(CancellationToken::cancel),
+    }
+}
+
+pub fn finish_watch(watch_id: &str) {
+    REGION_WATCHES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(watch_id);
+}
+
+pub fn watch_ids() -> Vec<String> {
+    let mut watch_ids = REGION_WATCHES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    watch_ids.sort();
+
+    watch_ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn test_fingerprint_difference() {
+        let mut image = RgbaImage::from_pixel(640, 320, Rgba([255, 255, 255, 255]));
+        let base = RegionFingerprint::new(&DynamicImage::ImageRgba8(image.clone()));
+        assert_eq!(base.width, FINGERPRINT_SIZE);
+        assert_eq!(base.height, FINGERPRINT_SIZE / 2);
+
+        // 单个像素的变化被忽略
+        image.put_pixel(10, 10, Rgba([0, 0, 0, 255]));
+        let noise = RegionFingerprint::new(&DynamicImage::ImageRgba8(image.clone()));
+        assert_eq!(base.difference(&noise), 0.0);
+
+        // 左半边变黑
+        for x in 0..320 {
+            for y in 0..320 {
+                image.put_pixel(x, y, Rgba([0, 0, 0, 255]));
+            }
+        }
+        let changed = RegionFingerprint::new(&DynamicImage::ImageRgba8(image));
+        let ratio = base.difference(&changed);
+        assert!(ratio > 0.45 && ratio < 0.55, "ratio: {}", ratio);
+
+        let resized = RegionFingerprint::new(&DynamicImage::new_rgba8(100, 100));
+        assert_eq!(base.difference(&resized), 1.0);
+    }
+
+    #[test]
+    fn test_next_delay() {
+        let interval = Duration::from_millis(1000);
+        let next_delay = |work: u64, cpu_time: u64, cpu_budget: f32| {
+            next_delay(
+                interval,
+                Duration::from_millis(work),
+                Duration::from_millis(cpu_time),
+                cpu_budget,
+            )
+        };
+
+        assert_eq!(next_delay(10, 10, 0.1), interval);
+        assert_eq!(next_delay(500, 500, 0.1), Duration::from_millis(4500));
+        assert_eq!(next_delay(500, 500, 0.0), Duration::from_millis(49500));
+        // 4 个线程识别时按 4 倍的 CPU 时间计算
+        assert_eq!(next_delay(500, 2000, 0.1), Duration::from_millis(19500));
+    }
+}
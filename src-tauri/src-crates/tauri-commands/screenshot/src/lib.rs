@@ -3,9 +3,17 @@ use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterato
 use serde::Serialize;
 use snow_shot_app_os::ui_automation::UIElements;
 use snow_shot_app_shared::ElementRect;
+use snow_shot_app_utils::capture_metadata::{
+    CaptureMetadata, CaptureMetadataOptions, CaptureMode, current_timestamp_millis,
+};
+use snow_shot_app_utils::color_profile::{self, ColorProfile, ColorProfileMode};
+use snow_shot_app_utils::file_name_template::{self, FileNameTemplateContext};
+use snow_shot_app_utils::hdr_image::HdrImage;
+use snow_shot_app_utils::hdr_tone_mapping::HdrToneMappingOptions;
 use snow_shot_app_utils::monitor_info::{
     CaptureOption, ColorFormat, CorrectHdrColorAlgorithm, MonitorList,
 };
+use snow_shot_app_utils::{EncodeOptions, ImageEncoder};
 use snow_shot_global_state::WebViewSharedBufferState;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -36,11 +44,12 @@ pub async fn capture_current_monitor(
     let image_buffer = snow_shot_app_utils::encode_image(
         &image_buffer,
         match encoder.as_str() {
-            "webp" => snow_shot_app_utils::ImageEncoder::Webp,
-            "png" => snow_shot_app_utils::ImageEncoder::Png,
-            _ => snow_shot_app_utils::ImageEncoder::Webp,
+            "webp" => ImageEncoder::Webp,
+            "png" => ImageEncoder::Png,
+            _ => ImageEncoder::Webp,
         },
-    );
+        &EncodeOptions::default(),
+    )?;
 
     Ok(Response::new(image_buffer))
 }
@@ -55,7 +64,9 @@ pub async fn capture_all_monitors(
     >,
     enable_multiple_monitor: bool,
     correct_hdr_color_algorithm: CorrectHdrColorAlgorithm,
+    hdr_tone_mapping: Option<HdrToneMappingOptions>,
     correct_color_filter: bool,
+    color_profile_mode: Option<ColorProfileMode>,
 ) -> Result<Response, String> {
     #[cfg(target_os = "macos")]
     {
@@ -70,13 +81,18 @@ pub async fn capture_all_monitors(
             CaptureOption {
                 color_format: ColorFormat::Rgb8,
                 correct_hdr_color_algorithm,
+                hdr_tone_mapping: hdr_tone_mapping.unwrap_or_default(),
                 correct_color_filter,
+                color_profile_mode: color_profile_mode.unwrap_or_default().without_embed(),
             },
         )
         .await?;
 
-        let image_buffer =
-            snow_shot_app_utils::encode_image(&image, snow_shot_app_utils::ImageEncoder::Png);
+        let image_buffer = snow_shot_app_utils::encode_image(
+            &image,
+            ImageEncoder::Png,
+            &EncodeOptions::default(),
+        )?;
 
         Ok(Response::new(image_buffer))
     }
@@ -94,7 +110,9 @@ pub async fn capture_all_monitors(
             CaptureOption {
                 color_format: ColorFormat::Rgba8,
                 correct_hdr_color_algorithm,
+                hdr_tone_mapping: hdr_tone_mapping.unwrap_or_default(),
                 correct_color_filter,
+                color_profile_mode: color_profile_mode.unwrap_or_default().without_embed(),
             },
         )
         .await?;
@@ -127,8 +145,11 @@ pub async fn capture_all_monitors(
             // 通过 SharedBuffer 传输的特殊标记
             Ok(Response::new(vec![1]))
         } else {
-            let image_buffer =
-                snow_shot_app_utils::encode_image(&image, snow_shot_app_utils::ImageEncoder::Png);
+            let image_buffer = snow_shot_app_utils::encode_image(
+                &image,
+                ImageEncoder::Png,
+                &EncodeOptions::default(),
+            )?;
 
             Ok(Response::new(image_buffer))
         }
@@ -136,18 +157,50 @@ pub async fn capture_all_monitors(
 }
 
 pub async fn save_and_copy_image<F>(
+    app_handle: &tauri::AppHandle,
     write_image_to_clipboard: F,
     image: image::DynamicImage,
     file_path: PathBuf,
     copy_to_clipboard: bool,
+    encode_options: EncodeOptions,
+    hdr_image: Option<HdrImage>,
+    correct_hdr_color_algorithm: CorrectHdrColorAlgorithm,
+    hdr_tone_mapping: HdrToneMappingOptions,
+    color_profile: Option<ColorProfile>,
+    capture_metadata: Option<CaptureMetadata>,
 ) -> Result<(), String>
 where
     F: Fn(&image::DynamicImage) -> Result<(), String> + Send + 'static,
 {
     let image = Arc::new(image);
     // 并行执行保存文件和写入剪贴板
-    let save_file_future =
-        snow_shot_app_utils::save_image_to_file(&image, PathBuf::from(file_path));
+    let save_file_future = async {
+        // 保留了 HDR 数据时保存为高位深图片
+        match (&hdr_image, encode_options.hdr_transfer_function) {
+            (Some(hdr_image), Some(transfer_function)) => {
+                snow_shot_app_utils::hdr_image::save_hdr_image_to_file(
+                    hdr_image,
+                    file_path.clone(),
+                    transfer_function,
+                    correct_hdr_color_algorithm,
+                    hdr_tone_mapping,
+                    &encode_options,
+                    capture_metadata.as_ref(),
+                )
+                .await
+            }
+            _ => {
+                snow_shot_app_utils::save_image_to_file(
+                    &image,
+                    file_path.clone(),
+                    &encode_options,
+                    color_profile.as_ref(),
+                    capture_metadata.as_ref(),
+                )
+                .await
+            }
+        }
+    };
     let clipboard_future = if copy_to_clipboard {
         let image_clone = Arc::clone(&image);
         Some(tokio::task::spawn_blocking(
@@ -175,15 +228,24 @@ where
         save_file_future.await?;
     }
 
+    snow_shot_app_utils::png_optimizer::spawn_png_optimization(
+        app_handle,
+        file_path,
+        &encode_options,
+    );
+
     Ok(())
 }
 
+/**
+ * 获取窗口所在的显示器，显示器未开启 HDR 时返回 None
+ */
 #[cfg(target_os = "windows")]
-pub fn capture_window_hdr_image(window: &xcap::Window) -> Option<image::DynamicImage> {
+fn get_window_hdr_monitor(
+    window: &xcap::Window,
+) -> Option<snow_shot_app_utils::monitor_info::MonitorInfo> {
     use snow_shot_app_utils::monitor_hdr_info::get_all_monitors_sdr_info;
     use snow_shot_app_utils::monitor_info::MonitorInfo;
-    use snow_shot_app_utils::windows_capture_image;
-    use windows::Win32::Foundation::HWND;
 
     // 获取 Windows 所属的显示
     let monitor = match window.current_monitor() {
@@ -195,7 +257,7 @@ pub fn capture_window_hdr_image(window: &xcap::Window) -> Option<image::DynamicI
         Ok(hdr_infos) => hdr_infos,
         Err(e) => {
             log::error!(
-                "[capture_window_hdr_image] Failed to get all monitors SDR info: {}",
+                "[get_window_hdr_monitor] Failed to get all monitors SDR info: {}",
                 e
             );
             return None;
@@ -215,11 +277,27 @@ pub fn capture_window_hdr_image(window: &xcap::Window) -> Option<image::DynamicI
         return None;
     }
 
+    Some(MonitorInfo::new(&monitor, Some(hdr_info.clone())))
+}
+
+#[cfg(target_os = "windows")]
+pub fn capture_window_hdr_image(
+    window: &xcap::Window,
+    correct_hdr_color_algorithm: CorrectHdrColorAlgorithm,
+    hdr_tone_mapping: HdrToneMappingOptions,
+) -> Option<image::DynamicImage> {
+    use snow_shot_app_utils::windows_capture_image;
+    use windows::Win32::Foundation::HWND;
+
+    let monitor_info = get_window_hdr_monitor(window)?;
+
     return match windows_capture_image::capture_monitor_image(
-        &MonitorInfo::new(&monitor, Some(hdr_info.clone())),
+        &monitor_info,
         Some(HWND(window.hwnd().unwrap())),
         None,
         ColorFormat::Rgba8,
+        correct_hdr_color_algorithm,
+        hdr_tone_mapping,
     ) {
         Ok(image) => Some(image),
         Err(error) => {
@@ -232,20 +310,78 @@ pub fn capture_window_hdr_image(window: &xcap::Window) -> Option<image::DynamicI
     };
 }
 
+/**
+ * 捕获窗口的 HDR 图像并保留浮点数据
+ */
+#[cfg(target_os = "windows")]
+pub fn capture_window_hdr_float_image(window: &xcap::Window) -> Option<HdrImage> {
+    use snow_shot_app_utils::windows_capture_image;
+    use windows::Win32::Foundation::HWND;
+
+    let monitor_info = get_window_hdr_monitor(window)?;
+
+    match windows_capture_image::capture_monitor_hdr_image(
+        &monitor_info,
+        Some(HWND(window.hwnd().unwrap())),
+        None,
+    ) {
+        Ok(image) => Some(image),
+        Err(error) => {
+            log::error!(
+                "[capture_window_hdr_float_image] Failed to capture HDR window image: {}",
+                error
+            );
+            None
+        }
+    }
+}
+
+/**
+ * 获取显示器在所有显示器中的序号，从 1 开始
+ */
+fn get_monitor_index(monitor: &xcap::Monitor) -> Option<usize> {
+    let monitor_id = monitor.id().ok()?;
+
+    xcap::Monitor::all()
+        .ok()?
+        .iter()
+        .position(|monitor| monitor.id().ok() == Some(monitor_id))
+        .map(|index| index + 1)
+}
+
 pub async fn capture_focused_window<F>(
+    app_handle: tauri::AppHandle,
     write_image_to_clipboard: F,
     file_path: String,
     copy_to_clipboard: bool,
-    focus_window_app_name_variable_name: String,
-    #[allow(unused_variables)] correct_hdr_color_algorithm: CorrectHdrColorAlgorithm,
-) -> Result<(), String>
+    correct_hdr_color_algorithm: CorrectHdrColorAlgorithm,
+    hdr_tone_mapping: Option<HdrToneMappingOptions>,
+    color_profile_mode: Option<ColorProfileMode>,
+    capture_metadata_options: Option<CaptureMetadataOptions>,
+    file_name_template_context: Option<FileNameTemplateContext>,
+    encode_options: Option<EncodeOptions>,
+) -> Result<PathBuf, String>
 where
     F: Fn(&image::DynamicImage) -> Result<(), String> + Send + 'static,
 {
+    let encode_options = encode_options.unwrap_or_default();
+
+    // 截图时间以前端触发截图的时间为准，没有时使用开始截图的时间
+    let mut file_name_template_context = file_name_template_context.unwrap_or_default();
+    let capture_time = *file_name_template_context
+        .capture_time
+        .get_or_insert_with(current_timestamp_millis);
+
     let image;
+    // 保留浮点数据的 HDR 图像，只在 Windows 上可用
+    let hdr_float_image: Option<HdrImage>;
+    // 图像所在的显示器，用于获取色彩配置文件，图像由 HDR 数据转换得到时为空
+    let color_profile_monitor: Option<xcap::Monitor>;
 
     // 截取窗口的应用名称
     let focused_window_app_name;
+    // 截取窗口的标题
+    let focused_window_title: Option<String>;
 
     #[cfg(target_os = "windows")]
     {
@@ -254,18 +390,42 @@ where
         let focused_window = xcap::Window::new(xcap::ImplWindow::new(hwnd));
 
         focused_window_app_name = focused_window.app_name().unwrap_or_default();
+        focused_window_title = focused_window.title().ok();
 
-        let hdr_image = if correct_hdr_color_algorithm != CorrectHdrColorAlgorithm::None {
-            capture_window_hdr_image(&focused_window)
+        hdr_float_image = if correct_hdr_color_algorithm != CorrectHdrColorAlgorithm::None
+            && encode_options.hdr_transfer_function.is_some()
+        {
+            capture_window_hdr_float_image(&focused_window)
         } else {
             None
         };
 
-        image = match hdr_image {
-            Some(image) => image,
+        let hdr_image = match &hdr_float_image {
+            // 写入剪贴板的图像由 HDR 数据转换得到，不再重复捕获
+            Some(hdr_float_image) => hdr_float_image
+                .to_sdr_image(
+                    correct_hdr_color_algorithm,
+                    hdr_tone_mapping.unwrap_or_default(),
+                )
+                .ok(),
+            None if correct_hdr_color_algorithm != CorrectHdrColorAlgorithm::None => {
+                capture_window_hdr_image(
+                    &focused_window,
+                    correct_hdr_color_algorithm,
+                    hdr_tone_mapping.unwrap_or_default(),
+                )
+            }
+            None => None,
+        };
+
+        (image, color_profile_monitor) = match hdr_image {
+            Some(image) => (image, None),
             None => {
                 match focused_window.capture_image() {
-                    Ok(image) => DynamicImage::ImageRgba8(image),
+                    Ok(image) => (
+                        DynamicImage::ImageRgba8(image),
+                        focused_window.current_monitor().ok(),
+                    ),
                     Err(_) => {
                         log::warn!("[capture_focused_window] Failed to capture focused window");
                         // 改成捕获当前显示器
@@ -273,7 +433,7 @@ where
                         let (_, _, monitor) = snow_shot_app_utils::get_target_monitor()?;
 
                         match monitor.capture_image() {
-                            Ok(image) => DynamicImage::ImageRgba8(image),
+                            Ok(image) => (DynamicImage::ImageRgba8(image), Some(monitor)),
                             Err(_) => {
                                 return Err(String::from(
                                     "[capture_focused_window] Failed to capture image",
@@ -288,6 +448,9 @@ where
 
     #[cfg(target_os = "linux")]
     {
+        hdr_float_image = None;
+        focused_window_title = None;
+
         let (_, _, monitor) = snow_shot_app_utils::get_target_monitor();
 
         image = match monitor.capture_image() {
@@ -298,22 +461,21 @@ where
                 ));
             }
         };
+        color_profile_monitor = Some(monitor);
     }
 
     #[cfg(target_os = "macos")]
     {
+        hdr_float_image = None;
+
         let window_list = xcap::Window::all().unwrap_or_default();
-        let window = window_list.iter().find(|w| {
-            w.is_focused().unwrap_or(false)
-                // 排除某些托盘应用，托盘应用会捕获到托盘图标
-                && w.y().unwrap_or(0) != 0
-                && !w.title().unwrap_or_default().starts_with("Item-")
-        });
+        let window = window_list.iter().find(|w| is_focused_app_window(w));
 
         focused_window_app_name = match window {
             Some(window) => window.app_name().unwrap_or_default(),
             None => "".to_string(),
         };
+        focused_window_title = window.and_then(|window| window.title().ok());
 
         let window_image = match window {
             Some(window) => match window.capture_image() {
@@ -323,8 +485,11 @@ where
             None => None,
         };
 
-        image = match window_image {
-            Some(image) => DynamicImage::ImageRgba8(image),
+        (image, color_profile_monitor) = match window_image {
+            Some(image) => (
+                DynamicImage::ImageRgba8(image),
+                window.and_then(|window| window.current_monitor().ok()),
+            ),
             None => {
                 log::warn!("[capture_focused_window] Failed to capture focused window");
                 // 改成捕获当前显示器
@@ -332,7 +497,7 @@ where
                 let (_, _, monitor) = snow_shot_app_utils::get_target_monitor()?;
 
                 match monitor.capture_image() {
-                    Ok(image) => DynamicImage::ImageRgba8(image),
+                    Ok(image) => (DynamicImage::ImageRgba8(image), Some(monitor)),
                     Err(_) => {
                         return Err(String::from(
                             "[capture_focused_window] Failed to capture image",
@@ -343,25 +508,122 @@ where
         };
     }
 
-    let focused_window_app_name = if focused_window_app_name == "" {
-        "unknown".to_string()
-    } else {
-        focused_window_app_name
+    let color_profile_mode = color_profile_mode.unwrap_or_default();
+    let color_profile = match (&color_profile_monitor, color_profile_mode) {
+        (Some(monitor), ColorProfileMode::ConvertToSrgb | ColorProfileMode::Embed) => {
+            match color_profile::get_monitor_color_profile(monitor) {
+                Ok(color_profile) => color_profile,
+                Err(e) => {
+                    log::warn!("[capture_focused_window] {}", e);
+                    None
+                }
+            }
+        }
+        _ => None,
     };
+    let (image, color_profile) =
+        color_profile::apply_color_profile_mode(image, color_profile, color_profile_mode);
 
-    // 简单处理下 FOCUS_WINDOW_APP_NAME 的变量占位
-    let file_path = PathBuf::from(file_path.replace(
-        focus_window_app_name_variable_name.as_str(),
-        &focused_window_app_name,
-    ));
+    let focused_window_title = focused_window_title.filter(|title| !title.is_empty());
+    let focused_window_app_name = Some(focused_window_app_name).filter(|name| !name.is_empty());
+
+    let resolved_file_path = file_name_template::resolve_file_path_template(
+        &app_handle,
+        &file_path,
+        &FileNameTemplateContext {
+            window_title: focused_window_title.clone(),
+            process_name: focused_window_app_name.clone(),
+            monitor_index: color_profile_monitor.as_ref().and_then(get_monitor_index),
+            capture_mode: Some(CaptureMode::Window),
+            ..file_name_template_context
+        },
+    )?;
+
+    let capture_metadata = capture_metadata_options
+        .unwrap_or_default()
+        .apply(CaptureMetadata {
+            window_title: focused_window_title,
+            process_name: focused_window_app_name,
+            monitor_name: color_profile_monitor
+                .as_ref()
+                .and_then(|monitor| monitor.name().ok()),
+            ..CaptureMetadata::new(&app_handle, CaptureMode::Window, capture_time)
+        });
 
     save_and_copy_image(
+        &app_handle,
         write_image_to_clipboard,
         image,
-        file_path,
+        resolved_file_path.file_path.clone(),
         copy_to_clipboard,
+        encode_options,
+        hdr_float_image,
+        correct_hdr_color_algorithm,
+        hdr_tone_mapping.unwrap_or_default(),
+        color_profile,
+        capture_metadata,
     )
-    .await
+    .await?;
+    resolved_file_path.commit(&app_handle);
+
+    Ok(resolved_file_path.file_path)
+}
+
+#[cfg(target_os = "macos")]
+fn is_focused_app_window(window: &xcap::Window) -> bool {
+    window.is_focused().unwrap_or(false)
+        // 排除某些托盘应用，托盘应用会捕获到托盘图标
+        && window.y().unwrap_or(0) != 0
+        && !window.title().unwrap_or_default().starts_with("Item-")
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct FocusedWindowInfo {
+    pub window_title: Option<String>,
+    pub process_name: Option<String>,
+}
+
+/**
+ * 获取焦点窗口的标题和进程名称，需要在截图窗口显示前调用
+ */
+pub async fn get_focused_window_info() -> Result<FocusedWindowInfo, String> {
+    let focused_window_info;
+
+    #[cfg(target_os = "windows")]
+    {
+        let hwnd = snow_shot_app_os::utils::get_focused_window();
+        let focused_window = xcap::Window::new(xcap::ImplWindow::new(hwnd));
+
+        focused_window_info = FocusedWindowInfo {
+            window_title: focused_window.title().ok(),
+            process_name: focused_window.app_name().ok(),
+        };
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let window_list = xcap::Window::all().unwrap_or_default();
+        let window = window_list.iter().find(|w| is_focused_app_window(w));
+
+        focused_window_info = FocusedWindowInfo {
+            window_title: window.and_then(|window| window.title().ok()),
+            process_name: window.and_then(|window| window.app_name().ok()),
+        };
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        focused_window_info = FocusedWindowInfo::default();
+    }
+
+    Ok(FocusedWindowInfo {
+        window_title: focused_window_info
+            .window_title
+            .filter(|window_title| !window_title.is_empty()),
+        process_name: focused_window_info
+            .process_name
+            .filter(|process_name| !process_name.is_empty()),
+    })
 }
 
 pub async fn init_ui_elements(ui_elements: tauri::State<'_, Mutex<UIElements>>) -> Result<(), ()> {
@@ -668,6 +930,8 @@ pub async fn set_draw_window_style(window: tauri::Window) {
 #[derive(Serialize, Clone)]
 pub struct CaptureFullScreenResult {
     monitor_rect: ElementRect,
+    /// 截图实际保存的路径，重名时会追加序号
+    file_path: PathBuf,
 }
 
 /**
@@ -681,11 +945,22 @@ pub async fn capture_full_screen<F>(
     copy_to_clipboard: bool,
     capture_history_file_path: String,
     correct_hdr_color_algorithm: CorrectHdrColorAlgorithm,
+    hdr_tone_mapping: Option<HdrToneMappingOptions>,
     correct_color_filter: bool,
+    color_profile_mode: Option<ColorProfileMode>,
+    capture_metadata_options: Option<CaptureMetadataOptions>,
+    file_name_template_context: Option<FileNameTemplateContext>,
+    encode_options: Option<EncodeOptions>,
 ) -> Result<CaptureFullScreenResult, String>
 where
     F: Fn(&image::DynamicImage) -> Result<(), String> + Send + 'static,
 {
+    // 截图时间以前端触发截图的时间为准，没有时使用开始截图的时间
+    let mut file_name_template_context = file_name_template_context.unwrap_or_default();
+    let capture_time = *file_name_template_context
+        .capture_time
+        .get_or_insert_with(current_timestamp_millis);
+
     // 激活的显示器
     let (mouse_x, mouse_y) = snow_shot_app_utils::get_mouse_position(&app_handle)?;
     let active_monitor = MonitorList::get_by_region(
@@ -706,16 +981,51 @@ where
     )?;
 
     // 截取所有显示器的截图
-    let all_monitors_image = monitor_list
-        .capture(
-            None,
-            CaptureOption {
-                color_format: ColorFormat::Rgb8,
-                correct_hdr_color_algorithm,
-                correct_color_filter,
-            },
-        )
-        .await?;
+    let color_profile_mode = color_profile_mode.unwrap_or_default();
+    let capture_option = CaptureOption {
+        color_format: ColorFormat::Rgb8,
+        correct_hdr_color_algorithm,
+        hdr_tone_mapping: hdr_tone_mapping.unwrap_or_default(),
+        correct_color_filter,
+        color_profile_mode,
+    };
+    let encode_options = encode_options.unwrap_or_default();
+
+    // 需要保留 HDR 数据时先捕获激活的显示器，拼接所有显示器时直接复用
+    #[allow(unused_mut)]
+    let mut hdr_float_image: Option<HdrImage> = None;
+    #[cfg(target_os = "windows")]
+    if correct_hdr_color_algorithm != CorrectHdrColorAlgorithm::None
+        && encode_options.hdr_transfer_function.is_some()
+        && let Some(monitor_info) = active_monitor
+            .iter()
+            .find(|monitor_info| monitor_info.monitor_hdr_info.hdr_enabled)
+    {
+        hdr_float_image =
+            match snow_shot_app_utils::windows_capture_image::capture_monitor_hdr_image(
+                monitor_info,
+                None,
+                None,
+            ) {
+                Ok(image) => Some(image),
+                Err(e) => {
+                    log::error!(
+                        "[capture_full_screen] Failed to capture HDR monitor image: {}",
+                        e
+                    );
+                    None
+                }
+            };
+    }
+
+    let all_monitors_image = match (&hdr_float_image, active_monitor.iter().next()) {
+        (Some(hdr_float_image), Some(monitor_info)) => {
+            monitor_list
+                .capture_with_hdr_image(None, capture_option, monitor_info.rect, hdr_float_image)
+                .await?
+        }
+        _ => monitor_list.capture(None, capture_option).await?,
+    };
     // 所有显示器的最小矩形
     let all_monitors_bounding_box = monitor_list.get_monitors_bounding_box();
     // 获取激活的显示器相对所有显示器的位置
@@ -776,13 +1086,51 @@ where
         }
     };
 
+    let resolved_file_path = file_name_template::resolve_file_path_template(
+        &app_handle,
+        &file_path,
+        &FileNameTemplateContext {
+            monitor_index: active_monitor
+                .iter()
+                .next()
+                .and_then(|monitor_info| get_monitor_index(&monitor_info.monitor)),
+            capture_mode: Some(CaptureMode::FullScreen),
+            ..file_name_template_context
+        },
+    )?;
+
+    let capture_metadata = capture_metadata_options
+        .unwrap_or_default()
+        .apply(CaptureMetadata {
+            monitor_name: active_monitor
+                .iter()
+                .next()
+                .and_then(|monitor_info| monitor_info.monitor.name().ok()),
+            ..CaptureMetadata::new(&app_handle, CaptureMode::FullScreen, capture_time)
+        });
+
     save_and_copy_image(
+        &app_handle,
         write_image_to_clipboard,
         active_monitor_image,
-        PathBuf::from(file_path),
+        resolved_file_path.file_path.clone(),
         copy_to_clipboard,
+        encode_options,
+        hdr_float_image,
+        capture_option.correct_hdr_color_algorithm,
+        capture_option.hdr_tone_mapping,
+        // 保存的图像只包含激活的显示器，嵌入该显示器的配置文件
+        match color_profile_mode {
+            ColorProfileMode::Embed => active_monitor
+                .iter()
+                .next()
+                .and_then(|monitor_info| monitor_info.get_color_profile(&capture_option)),
+            _ => None,
+        },
+        capture_metadata,
     )
     .await?;
+    resolved_file_path.commit(&app_handle);
 
     // 写入到截图历史
     let capture_history_file_path = PathBuf::from(capture_history_file_path);
@@ -798,5 +1146,6 @@ where
 
     Ok(CaptureFullScreenResult {
         monitor_rect: active_monitor_crop_region,
+        file_path: resolved_file_path.file_path,
     })
 }
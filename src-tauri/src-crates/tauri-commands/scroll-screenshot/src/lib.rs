@@ -3,17 +3,52 @@ use image::imageops::FilterType;
 use serde::Serialize;
 use snow_shot_app_scroll_screenshot_service::scroll_screenshot_capture_service::ScrollScreenshotCaptureService;
 use snow_shot_app_shared::ElementRect;
+use snow_shot_app_utils::capture_metadata::{
+    CaptureMetadata, CaptureMetadataOptions, CaptureMode, current_timestamp_millis,
+};
+use snow_shot_app_utils::color_profile::ColorProfileMode;
+use snow_shot_app_utils::hdr_tone_mapping::HdrToneMappingOptions;
 use snow_shot_app_utils::monitor_info::{CaptureOption, ColorFormat, CorrectHdrColorAlgorithm};
 use snow_shot_global_state::WebViewSharedBufferState;
 use std::path::PathBuf;
 use tauri::ipc::Response;
 use tokio::sync::Mutex;
 
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_diagnostics::ScrollStitchReport;
 use snow_shot_app_scroll_screenshot_service::scroll_screenshot_image_service::ScrollScreenshotImageService;
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_offline_service::{
+    self, ScrollScreenshotOfflineParams, ScrollScreenshotOfflineResult,
+};
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_pdf_service::ScrollScreenshotPdfOptions;
 use snow_shot_app_scroll_screenshot_service::scroll_screenshot_service::{
-    ScrollDirection, ScrollImageList, ScrollScreenshotService,
+    CropRegion, ScrollDirection, ScrollImageList, ScrollScreenshotService,
+};
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_session::{
+    self, ScrollScreenshotSessionInfo,
 };
-use snow_shot_app_utils::{self, save_image_to_file};
+use snow_shot_app_utils::{self, EncodeOptions, ImageEncoder, save_image_to_file};
+use tauri::{Emitter, Manager};
+
+const SCROLL_SCREENSHOT_SESSION_DIR_NAME: &str = "scroll_screenshot_sessions";
+
+pub const SCROLL_SCREENSHOT_STITCH_REPORT_EVENT: &str = "scroll-screenshot-stitch-report";
+
+/**
+ * 导出完成后检查接缝，存在可疑接缝时发送 SCROLL_SCREENSHOT_STITCH_REPORT_EVENT 事件
+ */
+fn emit_stitch_report<R: tauri::Runtime>(
+    emitter: &impl Emitter<R>,
+    scroll_screenshot_service: &mut ScrollScreenshotService,
+) {
+    let report = match scroll_screenshot_service.check_exported_seams() {
+        Some(report) => report,
+        None => return,
+    };
+
+    if let Err(e) = emitter.emit(SCROLL_SCREENSHOT_STITCH_REPORT_EVENT, report) {
+        log::error!("[emit_stitch_report] Failed to emit stitch report: {}", e);
+    }
+}
 
 pub async fn scroll_screenshot_init(
     scroll_screenshot_service: tauri::State<'_, Mutex<ScrollScreenshotService>>,
@@ -25,6 +60,7 @@ pub async fn scroll_screenshot_init(
     descriptor_patch_size: usize,
     min_size_delta: i32,
     try_rollback: bool,
+    mask_rects: Option<Vec<ElementRect>>,
 ) -> Result<(), String> {
     let mut scroll_screenshot_service = scroll_screenshot_service.lock().await;
 
@@ -39,6 +75,11 @@ pub async fn scroll_screenshot_init(
         try_rollback,
     );
 
+    // 屏蔽区域相对于滚动区域的左上角
+    if let Some(mask_rects) = mask_rects {
+        scroll_screenshot_service.set_mask_rects(mask_rects);
+    }
+
     Ok(())
 }
 
@@ -52,7 +93,9 @@ pub async fn scroll_screenshot_capture(
     max_x: i32,
     max_y: i32,
     correct_hdr_color_algorithm: CorrectHdrColorAlgorithm,
+    hdr_tone_mapping: Option<HdrToneMappingOptions>,
     correct_color_filter: bool,
+    color_profile_mode: Option<ColorProfileMode>,
 ) -> Result<(), String> {
     // 区域截图
     let image = {
@@ -94,7 +137,9 @@ pub async fn scroll_screenshot_capture(
                 CaptureOption {
                     color_format: ColorFormat::Rgba8,
                     correct_hdr_color_algorithm,
+                    hdr_tone_mapping: hdr_tone_mapping.unwrap_or_default(),
                     correct_color_filter,
+                    color_profile_mode: color_profile_mode.unwrap_or_default().without_embed(),
                 },
             )
             .await?
@@ -160,17 +205,22 @@ pub async fn scroll_screenshot_handle_image(
         }
     };
 
+    let crop_image_data = match crop_image.image() {
+        Some(image) => image,
+        None => return Ok(Response::new(vec![1])),
+    };
+
     let mut buf = Vec::new();
 
-    let image_width = crop_image.image.width();
-    let image_height = crop_image.image.height();
+    let image_width = crop_image.width;
+    let image_height = crop_image.height;
     let scale = if scroll_screenshot_service.current_direction == ScrollDirection::Vertical {
         thumbnail_size as f32 / image_width as f32
     } else {
         thumbnail_size as f32 / image_height as f32
     };
 
-    let thumbnail = crop_image.image.resize(
+    let thumbnail = crop_image_data.resize(
         ((image_width as f32 * scale) as u32).max(1), // 防止图片某一边为 0
         ((image_height as f32 * scale) as u32).max(1),
         FilterType::Triangle,
@@ -211,28 +261,219 @@ pub async fn scroll_screenshot_get_size(
     })
 }
 
+#[derive(Serialize)]
+pub struct ScrollScreenshotUndoResult {
+    pub removed_count: usize,
+    pub top_image_size: i32,
+    pub bottom_image_size: i32,
+}
+
+/**
+ * 撤销指定方向最近拼接的图片
+ */
+pub async fn scroll_screenshot_undo(
+    scroll_screenshot_service: tauri::State<'_, Mutex<ScrollScreenshotService>>,
+    scroll_image_list: ScrollImageList,
+    count: usize,
+) -> Result<ScrollScreenshotUndoResult, String> {
+    let mut scroll_screenshot_service = scroll_screenshot_service.lock().await;
+
+    let removed_count = scroll_screenshot_service.undo(scroll_image_list, count);
+
+    Ok(ScrollScreenshotUndoResult {
+        removed_count,
+        top_image_size: scroll_screenshot_service.top_image_size,
+        bottom_image_size: scroll_screenshot_service.bottom_image_size,
+    })
+}
+
+/**
+ * 保存滚动截图，图片超过 max_page_size 时按页保存为多个文件
+ * 保存为 PDF 时按 pdf_options 分页，为空时使用 A4 纸张
+ * capture_time 为开始滚动截图的时间，为空时使用当前时间
+ *
+ * 返回保存的文件路径
+ */
 pub async fn scroll_screenshot_save_to_file(
+    app: tauri::AppHandle,
     scroll_screenshot_service: tauri::State<'_, Mutex<ScrollScreenshotService>>,
     file_path: String,
-) -> Result<(), String> {
+    max_page_size: Option<u32>,
+    pdf_options: Option<ScrollScreenshotPdfOptions>,
+    capture_metadata_options: Option<CaptureMetadataOptions>,
+    encode_options: Option<EncodeOptions>,
+    capture_time: Option<u64>,
+) -> Result<Vec<String>, String> {
     let mut scroll_screenshot_service = scroll_screenshot_service.lock().await;
 
-    let image = scroll_screenshot_service.export();
-    let image = match image {
-        Some(image) => image,
+    let capture_metadata =
+        capture_metadata_options
+            .unwrap_or_default()
+            .apply(CaptureMetadata::new(
+                &app,
+                CaptureMode::Scroll,
+                capture_time.unwrap_or_else(current_timestamp_millis),
+            ));
+
+    let file_path_list = save_scroll_screenshot_to_file(
+        &app,
+        &mut scroll_screenshot_service,
+        file_path,
+        max_page_size,
+        pdf_options,
+        encode_options.unwrap_or_default(),
+        capture_metadata.as_ref(),
+    )
+    .await?;
+
+    emit_stitch_report(&app, &mut scroll_screenshot_service);
+
+    Ok(file_path_list)
+}
+
+fn create_export_file(file_path: &PathBuf) -> Result<std::fs::File, String> {
+    if let Some(parent_dir) = file_path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent_dir) {
+            return Err(format!(
+                "[save_scroll_screenshot_to_file] Failed to create directory {}: {}",
+                parent_dir.display(),
+                e
+            ));
+        }
+    }
+
+    match std::fs::File::create(file_path) {
+        Ok(file) => Ok(file),
+        Err(e) => Err(format!(
+            "[save_scroll_screenshot_to_file] Failed to create file {}: {}",
+            file_path.display(),
+            e
+        )),
+    }
+}
+
+async fn save_scroll_screenshot_to_file(
+    app: &tauri::AppHandle,
+    scroll_screenshot_service: &mut ScrollScreenshotService,
+    file_path: String,
+    max_page_size: Option<u32>,
+    pdf_options: Option<ScrollScreenshotPdfOptions>,
+    encode_options: EncodeOptions,
+    capture_metadata: Option<&CaptureMetadata>,
+) -> Result<Vec<String>, String> {
+    let (total_width, total_height) = match scroll_screenshot_service.get_export_size() {
+        Some(size) => size,
         None => {
             return Err(format!(
-                "[scroll_screenshot_save_to_file] Failed to export image"
+                "[save_scroll_screenshot_to_file] Failed to export image"
             ));
         }
     };
 
-    save_image_to_file(&image, PathBuf::from(file_path)).await?;
+    let file_path = PathBuf::from(file_path);
+
+    // PDF 在单个文件中分页，不按 max_page_size 拆分文件
+    let is_pdf = file_path
+        .extension()
+        .map(|extension| extension.eq_ignore_ascii_case("pdf"))
+        .unwrap_or(false);
+    if is_pdf {
+        let file = create_export_file(&file_path)?;
+        scroll_screenshot_service.export_pdf(
+            std::io::BufWriter::new(file),
+            &pdf_options.unwrap_or_default(),
+        )?;
+
+        return Ok(vec![file_path.to_string_lossy().to_string()]);
+    }
 
-    Ok(())
+    let page_list = match max_page_size {
+        Some(max_page_size) => scroll_screenshot_service.get_export_pages(max_page_size),
+        None => vec![CropRegion::new(0, 0, total_width, total_height)],
+    };
+
+    let is_png = file_path
+        .extension()
+        .map(|extension| extension.eq_ignore_ascii_case("png"))
+        .unwrap_or(false);
+
+    let mut file_path_list = Vec::with_capacity(page_list.len());
+    for (page_index, page_region) in page_list.iter().enumerate() {
+        // 多页时在文件名后追加页码
+        let page_file_path = if page_list.len() > 1 {
+            let file_stem = file_path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            let page_file_name = match file_path.extension() {
+                Some(extension) => format!(
+                    "{}_{}.{}",
+                    file_stem,
+                    page_index + 1,
+                    extension.to_string_lossy()
+                ),
+                None => format!("{}_{}", file_stem, page_index + 1),
+            };
+            file_path.with_file_name(page_file_name)
+        } else {
+            file_path.clone()
+        };
+
+        if is_png {
+            // PNG 逐段编码写入文件，不在内存中合成整张图片
+            let file = create_export_file(&page_file_path)?;
+            scroll_screenshot_service.export_png(
+                std::io::BufWriter::new(file),
+                *page_region,
+                &encode_options,
+                capture_metadata,
+            )?;
+        } else {
+            let page_pixels = scroll_screenshot_service.export_region(*page_region)?;
+            let page_image = match image::RgbaImage::from_raw(
+                page_region.width,
+                page_region.height,
+                page_pixels,
+            ) {
+                Some(page_image) => image::DynamicImage::ImageRgba8(page_image),
+                None => {
+                    return Err(String::from(
+                        "[save_scroll_screenshot_to_file] Failed to create image",
+                    ));
+                }
+            };
+
+            save_image_to_file(
+                &page_image,
+                page_file_path.clone(),
+                &encode_options,
+                None,
+                capture_metadata,
+            )
+            .await?;
+        }
+
+        snow_shot_app_utils::png_optimizer::spawn_png_optimization(
+            app,
+            page_file_path.clone(),
+            &encode_options,
+        );
+
+        file_path_list.push(page_file_path.to_string_lossy().to_string());
+    }
+
+    Ok(file_path_list)
 }
 
+/**
+ * 将滚动截图写入剪贴板
+ *
+ * 剪贴板只接受完整的 RGBA 像素，系统也会在写入时复制一份，无法像保存到文件一样分段编码，
+ * 所以这里仍然通过 export 在内存中合成整张图片
+ */
 pub async fn scroll_screenshot_save_to_clipboard<F>(
+    app: &tauri::AppHandle,
     write_image_to_clipboard: F,
     scroll_screenshot_service: tauri::State<'_, Mutex<ScrollScreenshotService>>,
 ) -> Result<(), String>
@@ -256,6 +497,8 @@ where
         }
     }
 
+    emit_stitch_report(app, &mut scroll_screenshot_service);
+
     Ok(())
 }
 
@@ -283,6 +526,7 @@ pub async fn scroll_screenshot_get_image_data(
     >,
     #[allow(unused_variables)] webview: tauri::Webview,
     force_to_png: bool,
+    encode_options: Option<EncodeOptions>,
 ) -> Result<Response, String> {
     let mut scroll_screenshot_service = scroll_screenshot_service.lock().await;
 
@@ -296,6 +540,8 @@ pub async fn scroll_screenshot_get_image_data(
         }
     };
 
+    emit_stitch_report(&webview, &mut scroll_screenshot_service);
+
     #[cfg(target_os = "windows")]
     {
         // 判断是否支持通过 SharedBuffer 传递
@@ -328,25 +574,312 @@ pub async fn scroll_screenshot_get_image_data(
         }
     }
 
-    let mut buf = Vec::new();
+    // 未指定编码参数时，强制 PNG 使用默认压缩等级，否则优先速度
+    let encode_options = encode_options.unwrap_or_else(|| EncodeOptions {
+        png_compression_level: if force_to_png { 6 } else { 1 },
+        ..EncodeOptions::default()
+    });
+    let buf = snow_shot_app_utils::encode_image(&image_data, ImageEncoder::Png, &encode_options)?;
 
-    match image_data.write_with_encoder(PngEncoder::new_with_quality(
-        &mut buf,
-        if force_to_png {
-            CompressionType::Default
-        } else {
-            CompressionType::Fast
-        },
-        png::FilterType::Paeth,
-    )) {
-        Ok(_) => (),
+    Ok(Response::new(buf))
+}
+
+/**
+ * 估算滚动截图按编码参数保存后的文件大小（字节）
+ *
+ * image_format 为图片的 MIME 类型
+ */
+pub async fn scroll_screenshot_estimate_file_size(
+    scroll_screenshot_service: tauri::State<'_, Mutex<ScrollScreenshotService>>,
+    image_format: String,
+    encode_options: Option<EncodeOptions>,
+) -> Result<u64, String> {
+    let encoder = match ImageEncoder::from_mime_type(&image_format) {
+        Some(encoder) => encoder,
+        None => {
+            return Err(format!(
+                "[scroll_screenshot_estimate_file_size] Unsupported image format: {}",
+                image_format
+            ));
+        }
+    };
+
+    // 只合成抽样的条带，不合成整张图片
+    let (strips, total_height) = {
+        let mut scroll_screenshot_service = scroll_screenshot_service.lock().await;
+        let (total_width, total_height) = match scroll_screenshot_service.get_export_size() {
+            Some(size) => size,
+            None => {
+                return Err(String::from(
+                    "[scroll_screenshot_estimate_file_size] Failed to export image",
+                ));
+            }
+        };
+
+        let mut strips = Vec::new();
+        for (strip_y, strip_height) in
+            snow_shot_app_utils::estimate_sample_strips(total_width, total_height)
+        {
+            let strip_pixels = scroll_screenshot_service.export_region(CropRegion::new(
+                0,
+                strip_y,
+                total_width,
+                strip_height,
+            ))?;
+            match image::RgbaImage::from_raw(total_width, strip_height, strip_pixels) {
+                Some(strip_image) => strips.push(image::DynamicImage::ImageRgba8(strip_image)),
+                None => {
+                    return Err(String::from(
+                        "[scroll_screenshot_estimate_file_size] Invalid strip image",
+                    ));
+                }
+            }
+        }
+
+        (strips, total_height)
+    };
+
+    let encode_options = encode_options.unwrap_or_default();
+    match tokio::task::spawn_blocking(move || {
+        snow_shot_app_utils::estimate_encoded_size_from_strips(
+            &strips,
+            total_height,
+            encoder,
+            &encode_options,
+        )
+    })
+    .await
+    {
+        Ok(estimate_result) => estimate_result,
+        Err(e) => Err(format!(
+            "[scroll_screenshot_estimate_file_size] Failed to estimate file size: {}",
+            e
+        )),
+    }
+}
+
+fn get_scroll_screenshot_session_dir(
+    app: &tauri::AppHandle,
+    session_id: Option<&str>,
+) -> Result<PathBuf, String> {
+    let sessions_dir = match app.path().app_data_dir() {
+        Ok(app_data_dir) => app_data_dir.join(SCROLL_SCREENSHOT_SESSION_DIR_NAME),
         Err(e) => {
             return Err(format!(
-                "[scroll_screenshot_get_image_data] Failed to write image: {}",
+                "[get_scroll_screenshot_session_dir] Failed to get app data dir: {}",
                 e
             ));
         }
+    };
+
+    let session_id = match session_id {
+        Some(session_id) => session_id,
+        None => return Ok(sessions_dir),
+    };
+
+    // 会话 ID 会拼接到路径中，只允许简单字符
+    if session_id.is_empty()
+        || !session_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!(
+            "[get_scroll_screenshot_session_dir] Invalid session id: {}",
+            session_id
+        ));
     }
 
-    Ok(Response::new(buf))
+    Ok(sessions_dir.join(session_id))
+}
+
+/**
+ * 保存当前的滚动截图会话，之后可以恢复继续截图或重新导出
+ */
+pub async fn scroll_screenshot_save_session(
+    app: tauri::AppHandle,
+    scroll_screenshot_service: tauri::State<'_, Mutex<ScrollScreenshotService>>,
+) -> Result<ScrollScreenshotSessionInfo, String> {
+    let session_id = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0)
+        .to_string();
+    let session_dir = get_scroll_screenshot_session_dir(&app, Some(&session_id))?;
+
+    let mut scroll_screenshot_service = scroll_screenshot_service.lock().await;
+
+    scroll_screenshot_service.save_session(&session_dir, session_id)
+}
+
+pub async fn scroll_screenshot_list_sessions(
+    app: tauri::AppHandle,
+) -> Result<Vec<ScrollScreenshotSessionInfo>, String> {
+    let sessions_dir = get_scroll_screenshot_session_dir(&app, None)?;
+
+    Ok(scroll_screenshot_session::list_sessions(&sessions_dir))
+}
+
+/**
+ * 恢复滚动截图会话，会丢弃当前的截图和待处理的图片
+ */
+pub async fn scroll_screenshot_restore_session(
+    app: tauri::AppHandle,
+    scroll_screenshot_service: tauri::State<'_, Mutex<ScrollScreenshotService>>,
+    scroll_screenshot_image_service: tauri::State<'_, Mutex<ScrollScreenshotImageService>>,
+    session_id: String,
+) -> Result<ScrollScreenshotSessionInfo, String> {
+    let session_dir = get_scroll_screenshot_session_dir(&app, Some(&session_id))?;
+
+    let mut scroll_screenshot_service = scroll_screenshot_service.lock().await;
+    let mut scroll_screenshot_image_service = scroll_screenshot_image_service.lock().await;
+
+    scroll_screenshot_image_service.clear();
+
+    scroll_screenshot_service.restore_session(&session_dir)
+}
+
+pub async fn scroll_screenshot_remove_session(
+    app: tauri::AppHandle,
+    session_id: String,
+) -> Result<(), String> {
+    let session_dir = get_scroll_screenshot_session_dir(&app, Some(&session_id))?;
+
+    match tokio::fs::remove_dir_all(&session_dir).await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!(
+            "[scroll_screenshot_remove_session] Failed to remove session {}: {}",
+            session_dir.display(),
+            e
+        )),
+    }
+}
+
+#[derive(Serialize)]
+pub struct ScrollScreenshotStitchResult {
+    pub stitch_result: ScrollScreenshotOfflineResult,
+    pub file_path_list: Vec<String>,
+}
+
+/**
+ * 离线拼接图片文件并保存，路径为目录时读取目录下的所有图片
+ *
+ * 使用独立的拼接服务，不影响正在进行的滚动截图
+ */
+pub async fn scroll_screenshot_stitch_images(
+    app: tauri::AppHandle,
+    params: ScrollScreenshotOfflineParams,
+    image_paths: Vec<String>,
+    file_path: String,
+    max_page_size: Option<u32>,
+    encode_options: Option<EncodeOptions>,
+) -> Result<ScrollScreenshotStitchResult, String> {
+    let stitch_task = tokio::task::spawn_blocking(move || {
+        let mut file_list = Vec::new();
+        for image_path in image_paths {
+            let image_path = PathBuf::from(image_path);
+            if image_path.is_dir() {
+                file_list.extend(scroll_screenshot_offline_service::list_image_files(
+                    &image_path,
+                )?);
+            } else {
+                file_list.push(image_path);
+            }
+        }
+
+        let mut scroll_screenshot_service = ScrollScreenshotService::new();
+        let stitch_result = scroll_screenshot_service.stitch_image_files(&params, &file_list)?;
+
+        Ok::<_, String>((scroll_screenshot_service, stitch_result))
+    });
+
+    let (mut scroll_screenshot_service, stitch_result) = match stitch_task.await {
+        Ok(result) => result?,
+        Err(e) => {
+            return Err(format!(
+                "[scroll_screenshot_stitch_images] Failed to stitch images: {}",
+                e
+            ));
+        }
+    };
+
+    let file_path_list = save_scroll_screenshot_to_file(
+        &app,
+        &mut scroll_screenshot_service,
+        file_path,
+        max_page_size,
+        None,
+        encode_options.unwrap_or_default(),
+        None,
+    )
+    .await?;
+
+    Ok(ScrollScreenshotStitchResult {
+        stitch_result,
+        file_path_list,
+    })
+}
+
+/**
+ * 通过 ffmpeg 插件解码录屏视频，离线拼接后保存
+ */
+pub async fn scroll_screenshot_stitch_video(
+    app: tauri::AppHandle,
+    params: ScrollScreenshotOfflineParams,
+    ffmpeg_plugin_dir: PathBuf,
+    video_path: String,
+    frame_rate: Option<f32>,
+    file_path: String,
+    max_page_size: Option<u32>,
+    encode_options: Option<EncodeOptions>,
+) -> Result<ScrollScreenshotStitchResult, String> {
+    let stitch_task = tokio::task::spawn_blocking(move || {
+        let ffmpeg_path = snow_shot_app_utils::get_ffmpeg_path(&ffmpeg_plugin_dir);
+
+        let mut scroll_screenshot_service = ScrollScreenshotService::new();
+        let stitch_result = scroll_screenshot_service.stitch_video(
+            &params,
+            &ffmpeg_path,
+            &PathBuf::from(video_path),
+            frame_rate,
+        )?;
+
+        Ok::<_, String>((scroll_screenshot_service, stitch_result))
+    });
+
+    let (mut scroll_screenshot_service, stitch_result) = match stitch_task.await {
+        Ok(result) => result?,
+        Err(e) => {
+            return Err(format!(
+                "[scroll_screenshot_stitch_video] Failed to stitch video: {}",
+                e
+            ));
+        }
+    };
+
+    let file_path_list = save_scroll_screenshot_to_file(
+        &app,
+        &mut scroll_screenshot_service,
+        file_path,
+        max_page_size,
+        None,
+        encode_options.unwrap_or_default(),
+        None,
+    )
+    .await?;
+
+    Ok(ScrollScreenshotStitchResult {
+        stitch_result,
+        file_path_list,
+    })
+}
+
+/**
+ * 获取拼接质量报告，包含每张图片的匹配统计和可疑接缝的位置
+ */
+pub async fn scroll_screenshot_get_stitch_report(
+    scroll_screenshot_service: tauri::State<'_, Mutex<ScrollScreenshotService>>,
+) -> Result<ScrollStitchReport, String> {
+    let mut scroll_screenshot_service = scroll_screenshot_service.lock().await;
+
+    scroll_screenshot_service.get_stitch_report()
 }
@@ -1,10 +1,21 @@
 use snow_shot_app_services::file_cache_service::FileCacheService;
+use snow_shot_app_utils::file_name_template::{
+    DailyCounter, FileNameTemplateContext, ResolvedFilePath,
+};
 use std::{path::PathBuf, sync::Arc};
 use tauri::command;
 
 #[command]
-pub async fn save_file(request: tauri::ipc::Request<'_>) -> Result<(), String> {
-    snow_shot_tauri_commands_file::save_file(request).await
+pub async fn save_file(
+    app: tauri::AppHandle,
+    request: tauri::ipc::Request<'_>,
+) -> Result<(), String> {
+    snow_shot_tauri_commands_file::save_file(app, request).await
+}
+
+#[command]
+pub async fn estimate_image_file_size(request: tauri::ipc::Request<'_>) -> Result<u64, String> {
+    snow_shot_tauri_commands_file::estimate_image_file_size(request).await
 }
 
 #[command]
@@ -22,6 +33,32 @@ pub async fn copy_file(from: PathBuf, to: PathBuf) -> Result<(), String> {
     snow_shot_tauri_commands_file::copy_file(from, to).await
 }
 
+#[command]
+pub async fn resolve_file_path_template(
+    app: tauri::AppHandle,
+    path_template: String,
+    context: FileNameTemplateContext,
+) -> Result<ResolvedFilePath, String> {
+    snow_shot_tauri_commands_file::resolve_file_path_template(app, path_template, context).await
+}
+
+#[command]
+pub async fn commit_daily_counter(
+    app: tauri::AppHandle,
+    daily_counter: DailyCounter,
+) -> Result<(), String> {
+    snow_shot_tauri_commands_file::commit_daily_counter(app, daily_counter).await
+}
+
+#[command]
+pub async fn preview_file_name_template(
+    app: tauri::AppHandle,
+    template: String,
+    context: FileNameTemplateContext,
+) -> Result<String, String> {
+    snow_shot_tauri_commands_file::preview_file_name_template(app, template, context).await
+}
+
 #[command]
 pub async fn text_file_read(
     text_file_cache_service: tauri::State<'_, Arc<FileCacheService>>,
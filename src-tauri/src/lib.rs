@@ -33,6 +33,7 @@ use snow_shot_app_services::video_record_service;
 use snow_shot_app_shared::EnigoManager;
 use snow_shot_global_state::{CaptureState, ReadClipboardState, WebViewSharedBufferState};
 use snow_shot_plugin_service::plugin_service;
+use snow_shot_tauri_commands_ocr::search::CaptureSearchIndex;
 
 #[cfg(feature = "dhat-heap")]
 pub static PROFILER: std::sync::LazyLock<Mutex<Option<dhat::Profiler>>> =
@@ -40,7 +41,8 @@ pub static PROFILER: std::sync::LazyLock<Mutex<Option<dhat::Profiler>>> =
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let ocr_instance = Mutex::new(OcrService::new());
+    let ocr_instance = OcrService::new();
+    let capture_search_index = Mutex::new(CaptureSearchIndex::default());
     let video_record_service = Mutex::new(video_record_service::VideoRecordService::new());
     let hot_load_page_service = Arc::new(hot_load_page_service::HotLoadPageService::new());
     let enigo_instance = Mutex::new(EnigoManager::new());
@@ -210,6 +212,7 @@ pub fn run() {
         })
         .manage(ui_elements)
         .manage(ocr_instance)
+        .manage(capture_search_index)
         .manage(enigo_instance)
         .manage(scroll_screenshot_service)
         .manage(scroll_screenshot_image_service)
@@ -232,6 +235,7 @@ pub fn run() {
             screenshot::capture_current_monitor,
             screenshot::capture_all_monitors,
             screenshot::capture_focused_window,
+            screenshot::get_focused_window_info,
             screenshot::get_window_elements,
             screenshot::init_ui_elements,
             screenshot::get_element_from_position,
@@ -242,9 +246,13 @@ pub fn run() {
             screenshot::set_draw_window_style,
             screenshot::capture_full_screen,
             file::save_file,
+            file::estimate_image_file_size,
             file::write_file,
             file::copy_file,
             file::remove_file,
+            file::resolve_file_path_template,
+            file::commit_daily_counter,
+            file::preview_file_name_template,
             file::create_dir,
             file::remove_dir,
             file::get_app_config_dir,
@@ -255,6 +263,19 @@ pub fn run() {
             ocr::ocr_detect_with_shared_buffer,
             ocr::ocr_init,
             ocr::ocr_release,
+            ocr::ocr_get_model_plugins,
+            ocr::ocr_cancel,
+            ocr::ocr_export_table,
+            ocr::ocr_export_document,
+            ocr::ocr_batch_detect,
+            ocr::ocr_batch_cancel,
+            ocr::capture_search_sync,
+            ocr::capture_search_query,
+            ocr::barcode_detect,
+            ocr::barcode_generate_qr_code,
+            ocr::region_watch_start,
+            ocr::region_watch_stop,
+            ocr::region_watch_list,
             core::exit_app,
             core::start_free_drag,
             core::start_resize_window,
@@ -289,13 +310,22 @@ pub fn run() {
             core::show_main_window,
             core::set_window_rect,
             scroll_screenshot::scroll_screenshot_get_image_data,
+            scroll_screenshot::scroll_screenshot_estimate_file_size,
             scroll_screenshot::scroll_screenshot_init,
             scroll_screenshot::scroll_screenshot_capture,
             scroll_screenshot::scroll_screenshot_handle_image,
             scroll_screenshot::scroll_screenshot_save_to_file,
             scroll_screenshot::scroll_screenshot_save_to_clipboard,
             scroll_screenshot::scroll_screenshot_get_size,
+            scroll_screenshot::scroll_screenshot_undo,
             scroll_screenshot::scroll_screenshot_clear,
+            scroll_screenshot::scroll_screenshot_save_session,
+            scroll_screenshot::scroll_screenshot_list_sessions,
+            scroll_screenshot::scroll_screenshot_restore_session,
+            scroll_screenshot::scroll_screenshot_remove_session,
+            scroll_screenshot::scroll_screenshot_stitch_images,
+            scroll_screenshot::scroll_screenshot_stitch_video,
+            scroll_screenshot::scroll_screenshot_get_stitch_report,
             video_record::video_record_start,
             video_record::video_record_stop,
             video_record::video_record_pause,
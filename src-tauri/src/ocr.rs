@@ -1,18 +1,34 @@
 use std::path::PathBuf;
 
+use std::sync::Arc;
 use tauri::command;
 use tokio::sync::Mutex;
 
-use snow_shot_app_services::ocr_service::{OcrModel, OcrService};
+use snow_shot_app_services::ocr_service::{
+    OcrModel, OcrModelPlugin, OcrService, OcrSessionPoolOptions,
+};
+use snow_shot_app_utils::EncodeOptions;
+use snow_shot_app_utils::capture_metadata::CaptureMetadataOptions;
+use snow_shot_plugin_service::plugin_service::PluginService;
 use snow_shot_tauri_commands_ocr::OcrDetectResult;
+use snow_shot_tauri_commands_ocr::barcode::Barcode;
+use snow_shot_tauri_commands_ocr::batch::{OcrBatchOptions, OcrBatchResult};
+use snow_shot_tauri_commands_ocr::export::OcrDocumentFormat;
+use snow_shot_tauri_commands_ocr::options::OcrOptions;
+use snow_shot_tauri_commands_ocr::search::{
+    CaptureSearchDocument, CaptureSearchHit, CaptureSearchIndex, CaptureSearchSyncResult,
+};
+use snow_shot_tauri_commands_ocr::table::{OcrTable, OcrTableExportFormat};
+use snow_shot_tauri_commands_ocr::watch::RegionWatchOptions;
 
 #[command]
 pub async fn ocr_init(
-    ocr_instance: tauri::State<'_, Mutex<OcrService>>,
+    ocr_instance: tauri::State<'_, OcrService>,
     orc_plugin_path: PathBuf,
     model: OcrModel,
     hot_start: bool,
     model_write_to_memory: bool,
+    pool_options: Option<OcrSessionPoolOptions>,
 ) -> Result<(), String> {
     snow_shot_tauri_commands_ocr::ocr_init(
         orc_plugin_path,
@@ -20,38 +36,226 @@ pub async fn ocr_init(
         model,
         hot_start,
         model_write_to_memory,
+        pool_options,
     )
     .await
 }
 
 #[command]
 pub async fn ocr_detect(
-    ocr_instance: tauri::State<'_, Mutex<OcrService>>,
+    ocr_instance: tauri::State<'_, OcrService>,
+    plugin_service: tauri::State<'_, Arc<PluginService>>,
     request: tauri::ipc::Request<'_>,
 ) -> Result<OcrDetectResult, String> {
-    snow_shot_tauri_commands_ocr::ocr_detect(ocr_instance, request).await
+    snow_shot_tauri_commands_ocr::ocr_detect(ocr_instance, plugin_service, request).await
 }
 
 #[cfg(target_os = "windows")]
 #[command]
 pub async fn ocr_detect_with_shared_buffer(
-    ocr_instance: tauri::State<'_, Mutex<OcrService>>,
+    ocr_instance: tauri::State<'_, OcrService>,
+    plugin_service: tauri::State<'_, Arc<PluginService>>,
     shared_buffer_service: tauri::State<'_, std::sync::Arc<snow_shot_webview::SharedBufferService>>,
     channel_id: String,
     scale_factor: f32,
     detect_angle: bool,
+    model: Option<OcrModel>,
+    options: Option<OcrOptions>,
+    request_id: Option<String>,
 ) -> Result<OcrDetectResult, String> {
     snow_shot_tauri_commands_ocr::ocr_detect_with_shared_buffer(
         ocr_instance,
+        plugin_service,
         shared_buffer_service,
         channel_id,
         scale_factor,
         detect_angle,
+        model,
+        options,
+        request_id,
     )
     .await
 }
 
 #[command]
-pub async fn ocr_release(ocr_instance: tauri::State<'_, Mutex<OcrService>>) -> Result<(), String> {
+pub async fn ocr_export_table(
+    table: OcrTable,
+    format: OcrTableExportFormat,
+) -> Result<String, String> {
+    snow_shot_tauri_commands_ocr::ocr_export_table(table, format).await
+}
+
+#[command]
+pub async fn ocr_export_document(
+    ocr_instance: tauri::State<'_, OcrService>,
+    plugin_service: tauri::State<'_, Arc<PluginService>>,
+    image_path: PathBuf,
+    output_path: PathBuf,
+    format: OcrDocumentFormat,
+    scale_factor: f32,
+    detect_angle: bool,
+    model: Option<OcrModel>,
+    options: Option<OcrOptions>,
+) -> Result<(), String> {
+    snow_shot_tauri_commands_ocr::ocr_export_document(
+        ocr_instance,
+        plugin_service,
+        image_path,
+        output_path,
+        format,
+        scale_factor,
+        detect_angle,
+        model,
+        options,
+    )
+    .await
+}
+
+#[command]
+pub async fn ocr_batch_detect(
+    app_handle: tauri::AppHandle,
+    ocr_instance: tauri::State<'_, OcrService>,
+    plugin_service: tauri::State<'_, Arc<PluginService>>,
+    batch_id: String,
+    paths: Vec<PathBuf>,
+    scale_factor: f32,
+    detect_angle: bool,
+    model: Option<OcrModel>,
+    options: Option<OcrOptions>,
+    batch_options: Option<OcrBatchOptions>,
+) -> Result<OcrBatchResult, String> {
+    snow_shot_tauri_commands_ocr::ocr_batch_detect(
+        app_handle,
+        ocr_instance,
+        plugin_service,
+        batch_id,
+        paths,
+        scale_factor,
+        detect_angle,
+        model,
+        options,
+        batch_options,
+    )
+    .await
+}
+
+#[command]
+pub async fn ocr_batch_cancel(
+    ocr_instance: tauri::State<'_, OcrService>,
+    batch_id: String,
+) -> Result<(), String> {
+    snow_shot_tauri_commands_ocr::ocr_batch_cancel(ocr_instance, batch_id).await
+}
+
+#[command]
+pub async fn capture_search_sync(
+    app_handle: tauri::AppHandle,
+    ocr_instance: tauri::State<'_, OcrService>,
+    plugin_service: tauri::State<'_, Arc<PluginService>>,
+    search_index: tauri::State<'_, Mutex<CaptureSearchIndex>>,
+    batch_id: String,
+    documents: Vec<CaptureSearchDocument>,
+    only_if_indexed: bool,
+    scale_factor: f32,
+    detect_angle: bool,
+    model: Option<OcrModel>,
+    options: Option<OcrOptions>,
+) -> Result<CaptureSearchSyncResult, String> {
+    snow_shot_tauri_commands_ocr::capture_search_sync(
+        app_handle,
+        ocr_instance,
+        plugin_service,
+        search_index,
+        batch_id,
+        documents,
+        only_if_indexed,
+        scale_factor,
+        detect_angle,
+        model,
+        options,
+    )
+    .await
+}
+
+#[command]
+pub async fn capture_search_query(
+    app_handle: tauri::AppHandle,
+    search_index: tauri::State<'_, Mutex<CaptureSearchIndex>>,
+    query: String,
+    limit: usize,
+) -> Result<Vec<CaptureSearchHit>, String> {
+    snow_shot_tauri_commands_ocr::capture_search_query(app_handle, search_index, query, limit).await
+}
+
+#[command]
+pub async fn barcode_detect(request: tauri::ipc::Request<'_>) -> Result<Vec<Barcode>, String> {
+    snow_shot_tauri_commands_ocr::barcode_detect(request).await
+}
+
+#[command]
+pub async fn barcode_generate_qr_code(
+    text: String,
+    size: u32,
+) -> Result<tauri::ipc::Response, String> {
+    snow_shot_tauri_commands_ocr::barcode_generate_qr_code(text, size).await
+}
+
+#[command]
+pub async fn region_watch_start(
+    app_handle: tauri::AppHandle,
+    window: tauri::Window,
+    ocr_instance: tauri::State<'_, OcrService>,
+    watch_id: String,
+    min_x: i32,
+    min_y: i32,
+    max_x: i32,
+    max_y: i32,
+    scale_factor: f32,
+    options: Option<RegionWatchOptions>,
+    encode_options: Option<EncodeOptions>,
+    capture_metadata_options: Option<CaptureMetadataOptions>,
+) -> Result<(), String> {
+    snow_shot_tauri_commands_ocr::region_watch_start(
+        app_handle,
+        window,
+        ocr_instance,
+        watch_id,
+        min_x,
+        min_y,
+        max_x,
+        max_y,
+        scale_factor,
+        options,
+        encode_options,
+        capture_metadata_options,
+    )
+    .await
+}
+
+#[command]
+pub async fn region_watch_stop(watch_id: Option<String>) -> Result<(), String> {
+    snow_shot_tauri_commands_ocr::region_watch_stop(watch_id).await
+}
+
+#[command]
+pub async fn region_watch_list() -> Result<Vec<String>, String> {
+    snow_shot_tauri_commands_ocr::region_watch_list().await
+}
+
+#[command]
+pub async fn ocr_cancel(
+    ocr_instance: tauri::State<'_, OcrService>,
+    request_id: String,
+) -> Result<(), String> {
+    snow_shot_tauri_commands_ocr::ocr_cancel(ocr_instance, request_id).await
+}
+
+#[command]
+pub async fn ocr_release(ocr_instance: tauri::State<'_, OcrService>) -> Result<(), String> {
     snow_shot_tauri_commands_ocr::ocr_release(ocr_instance).await
 }
+
+#[command]
+pub async fn ocr_get_model_plugins() -> Result<Vec<OcrModelPlugin>, String> {
+    snow_shot_tauri_commands_ocr::ocr_get_model_plugins().await
+}
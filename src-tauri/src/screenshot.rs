@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use tauri::command;
 use tauri::ipc::Response;
 use tauri_plugin_clipboard_manager::ClipboardExt;
@@ -5,9 +6,16 @@ use tokio::sync::Mutex;
 
 use snow_shot_app_os::ui_automation::UIElements;
 use snow_shot_app_shared::ElementRect;
+use snow_shot_app_utils::EncodeOptions;
+use snow_shot_app_utils::capture_metadata::CaptureMetadataOptions;
+use snow_shot_app_utils::color_profile::ColorProfileMode;
+use snow_shot_app_utils::file_name_template::FileNameTemplateContext;
+use snow_shot_app_utils::hdr_tone_mapping::HdrToneMappingOptions;
 use snow_shot_app_utils::monitor_info::CorrectHdrColorAlgorithm;
 use snow_shot_global_state::WebViewSharedBufferState;
-use snow_shot_tauri_commands_screenshot::{CaptureFullScreenResult, WindowElement};
+use snow_shot_tauri_commands_screenshot::{
+    CaptureFullScreenResult, FocusedWindowInfo, WindowElement,
+};
 
 #[command]
 pub async fn capture_current_monitor(
@@ -25,7 +33,9 @@ pub async fn capture_all_monitors(
     webview_shared_buffer_state: tauri::State<'_, WebViewSharedBufferState>,
     enable_multiple_monitor: bool,
     correct_hdr_color_algorithm: CorrectHdrColorAlgorithm,
+    hdr_tone_mapping: Option<HdrToneMappingOptions>,
     correct_color_filter: bool,
+    color_profile_mode: Option<ColorProfileMode>,
 ) -> Result<Response, String> {
     snow_shot_tauri_commands_screenshot::capture_all_monitors(
         app,
@@ -34,7 +44,9 @@ pub async fn capture_all_monitors(
         webview_shared_buffer_state,
         enable_multiple_monitor,
         correct_hdr_color_algorithm,
+        hdr_tone_mapping,
         correct_color_filter,
+        color_profile_mode,
     )
     .await
 }
@@ -47,10 +59,15 @@ pub async fn capture_focused_window(
     app: tauri::AppHandle,
     file_path: String,
     copy_to_clipboard: bool,
-    focus_window_app_name_variable_name: String,
     correct_hdr_color_algorithm: CorrectHdrColorAlgorithm,
-) -> Result<(), String> {
+    hdr_tone_mapping: Option<HdrToneMappingOptions>,
+    color_profile_mode: Option<ColorProfileMode>,
+    capture_metadata_options: Option<CaptureMetadataOptions>,
+    file_name_template_context: Option<FileNameTemplateContext>,
+    encode_options: Option<EncodeOptions>,
+) -> Result<PathBuf, String> {
     snow_shot_tauri_commands_screenshot::capture_focused_window(
+        app.clone(),
         move |image| match app.clipboard().write_image(&tauri::image::Image::new(
             image.as_bytes(),
             image.width(),
@@ -64,12 +81,24 @@ pub async fn capture_focused_window(
         },
         file_path,
         copy_to_clipboard,
-        focus_window_app_name_variable_name,
         correct_hdr_color_algorithm,
+        hdr_tone_mapping,
+        color_profile_mode,
+        capture_metadata_options,
+        file_name_template_context,
+        encode_options,
     )
     .await
 }
 
+/**
+ * 获取焦点窗口的标题和进程名称
+ */
+#[command]
+pub async fn get_focused_window_info() -> Result<FocusedWindowInfo, String> {
+    snow_shot_tauri_commands_screenshot::get_focused_window_info().await
+}
+
 #[command]
 pub async fn init_ui_elements(ui_elements: tauri::State<'_, Mutex<UIElements>>) -> Result<(), ()> {
     snow_shot_tauri_commands_screenshot::init_ui_elements(ui_elements).await
@@ -125,7 +154,12 @@ pub async fn capture_full_screen(
     copy_to_clipboard: bool,
     capture_history_file_path: String,
     correct_hdr_color_algorithm: CorrectHdrColorAlgorithm,
+    hdr_tone_mapping: Option<HdrToneMappingOptions>,
     correct_color_filter: bool,
+    color_profile_mode: Option<ColorProfileMode>,
+    capture_metadata_options: Option<CaptureMetadataOptions>,
+    file_name_template_context: Option<FileNameTemplateContext>,
+    encode_options: Option<EncodeOptions>,
 ) -> Result<CaptureFullScreenResult, String> {
     snow_shot_tauri_commands_screenshot::capture_full_screen(
         app.clone(),
@@ -145,7 +179,12 @@ pub async fn capture_full_screen(
         copy_to_clipboard,
         capture_history_file_path,
         correct_hdr_color_algorithm,
+        hdr_tone_mapping,
         correct_color_filter,
+        color_profile_mode,
+        capture_metadata_options,
+        file_name_template_context,
+        encode_options,
     )
     .await
 }
@@ -1,14 +1,24 @@
+use snow_shot_app_shared::ElementRect;
+use snow_shot_app_utils::EncodeOptions;
+use snow_shot_app_utils::capture_metadata::CaptureMetadataOptions;
+use snow_shot_app_utils::color_profile::ColorProfileMode;
+use snow_shot_app_utils::hdr_tone_mapping::HdrToneMappingOptions;
 use snow_shot_app_utils::monitor_info::CorrectHdrColorAlgorithm;
+use std::path::PathBuf;
 use tauri::command;
 use tauri::ipc::Response;
 use tauri_plugin_clipboard_manager::ClipboardExt;
 use tokio::sync::Mutex;
 
 use snow_shot_app_scroll_screenshot_service::scroll_screenshot_capture_service::ScrollScreenshotCaptureService;
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_diagnostics::ScrollStitchReport;
 use snow_shot_app_scroll_screenshot_service::scroll_screenshot_image_service::ScrollScreenshotImageService;
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_offline_service::ScrollScreenshotOfflineParams;
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_pdf_service::ScrollScreenshotPdfOptions;
 use snow_shot_app_scroll_screenshot_service::scroll_screenshot_service::{
     ScrollDirection, ScrollImageList, ScrollScreenshotService,
 };
+use snow_shot_app_scroll_screenshot_service::scroll_screenshot_session::ScrollScreenshotSessionInfo;
 use snow_shot_global_state::WebViewSharedBufferState;
 
 #[command]
@@ -22,6 +32,7 @@ pub async fn scroll_screenshot_init(
     descriptor_patch_size: usize,
     min_size_delta: i32,
     try_rollback: bool,
+    mask_rects: Option<Vec<ElementRect>>,
 ) -> Result<(), String> {
     snow_shot_tauri_commands_scroll_screenshot::scroll_screenshot_init(
         scroll_screenshot_service,
@@ -33,6 +44,7 @@ pub async fn scroll_screenshot_init(
         descriptor_patch_size,
         min_size_delta,
         try_rollback,
+        mask_rects,
     )
     .await
 }
@@ -48,7 +60,9 @@ pub async fn scroll_screenshot_capture(
     max_x: i32,
     max_y: i32,
     correct_hdr_color_algorithm: CorrectHdrColorAlgorithm,
+    hdr_tone_mapping: Option<HdrToneMappingOptions>,
     correct_color_filter: bool,
+    color_profile_mode: Option<ColorProfileMode>,
 ) -> Result<(), String> {
     snow_shot_tauri_commands_scroll_screenshot::scroll_screenshot_capture(
         window,
@@ -60,7 +74,9 @@ pub async fn scroll_screenshot_capture(
         max_x,
         max_y,
         correct_hdr_color_algorithm,
+        hdr_tone_mapping,
         correct_color_filter,
+        color_profile_mode,
     )
     .await
 }
@@ -92,14 +108,40 @@ pub async fn scroll_screenshot_get_size(
     .await
 }
 
+#[command]
+pub async fn scroll_screenshot_undo(
+    scroll_screenshot_service: tauri::State<'_, Mutex<ScrollScreenshotService>>,
+    scroll_image_list: ScrollImageList,
+    count: usize,
+) -> Result<snow_shot_tauri_commands_scroll_screenshot::ScrollScreenshotUndoResult, String> {
+    snow_shot_tauri_commands_scroll_screenshot::scroll_screenshot_undo(
+        scroll_screenshot_service,
+        scroll_image_list,
+        count,
+    )
+    .await
+}
+
 #[command]
 pub async fn scroll_screenshot_save_to_file(
+    app: tauri::AppHandle,
     scroll_screenshot_service: tauri::State<'_, Mutex<ScrollScreenshotService>>,
     file_path: String,
-) -> Result<(), String> {
+    max_page_size: Option<u32>,
+    pdf_options: Option<ScrollScreenshotPdfOptions>,
+    capture_metadata_options: Option<CaptureMetadataOptions>,
+    encode_options: Option<EncodeOptions>,
+    capture_time: Option<u64>,
+) -> Result<Vec<String>, String> {
     snow_shot_tauri_commands_scroll_screenshot::scroll_screenshot_save_to_file(
+        app,
         scroll_screenshot_service,
         file_path,
+        max_page_size,
+        pdf_options,
+        capture_metadata_options,
+        encode_options,
+        capture_time,
     )
     .await
 }
@@ -110,6 +152,7 @@ pub async fn scroll_screenshot_save_to_clipboard(
     scroll_screenshot_service: tauri::State<'_, Mutex<ScrollScreenshotService>>,
 ) -> Result<(), String> {
     snow_shot_tauri_commands_scroll_screenshot::scroll_screenshot_save_to_clipboard(
+        &app,
         |image| match app.clipboard().write_image(&tauri::image::Image::new(
             image.as_bytes(),
             image.width(),
@@ -146,12 +189,126 @@ pub async fn scroll_screenshot_get_image_data(
     webview_shared_buffer_state: tauri::State<'_, WebViewSharedBufferState>,
     webview: tauri::Webview,
     force_to_png: Option<bool>,
+    encode_options: Option<EncodeOptions>,
 ) -> Result<Response, String> {
     snow_shot_tauri_commands_scroll_screenshot::scroll_screenshot_get_image_data(
         scroll_screenshot_service,
         webview_shared_buffer_state,
         webview,
         force_to_png.unwrap_or(false),
+        encode_options,
+    )
+    .await
+}
+
+#[command]
+pub async fn scroll_screenshot_estimate_file_size(
+    scroll_screenshot_service: tauri::State<'_, Mutex<ScrollScreenshotService>>,
+    image_format: String,
+    encode_options: Option<EncodeOptions>,
+) -> Result<u64, String> {
+    snow_shot_tauri_commands_scroll_screenshot::scroll_screenshot_estimate_file_size(
+        scroll_screenshot_service,
+        image_format,
+        encode_options,
+    )
+    .await
+}
+
+#[command]
+pub async fn scroll_screenshot_save_session(
+    app: tauri::AppHandle,
+    scroll_screenshot_service: tauri::State<'_, Mutex<ScrollScreenshotService>>,
+) -> Result<ScrollScreenshotSessionInfo, String> {
+    snow_shot_tauri_commands_scroll_screenshot::scroll_screenshot_save_session(
+        app,
+        scroll_screenshot_service,
+    )
+    .await
+}
+
+#[command]
+pub async fn scroll_screenshot_list_sessions(
+    app: tauri::AppHandle,
+) -> Result<Vec<ScrollScreenshotSessionInfo>, String> {
+    snow_shot_tauri_commands_scroll_screenshot::scroll_screenshot_list_sessions(app).await
+}
+
+#[command]
+pub async fn scroll_screenshot_restore_session(
+    app: tauri::AppHandle,
+    scroll_screenshot_service: tauri::State<'_, Mutex<ScrollScreenshotService>>,
+    scroll_screenshot_image_service: tauri::State<'_, Mutex<ScrollScreenshotImageService>>,
+    session_id: String,
+) -> Result<ScrollScreenshotSessionInfo, String> {
+    snow_shot_tauri_commands_scroll_screenshot::scroll_screenshot_restore_session(
+        app,
+        scroll_screenshot_service,
+        scroll_screenshot_image_service,
+        session_id,
+    )
+    .await
+}
+
+#[command]
+pub async fn scroll_screenshot_remove_session(
+    app: tauri::AppHandle,
+    session_id: String,
+) -> Result<(), String> {
+    snow_shot_tauri_commands_scroll_screenshot::scroll_screenshot_remove_session(app, session_id)
+        .await
+}
+
+#[command]
+pub async fn scroll_screenshot_stitch_images(
+    app: tauri::AppHandle,
+    params: ScrollScreenshotOfflineParams,
+    image_paths: Vec<String>,
+    file_path: String,
+    max_page_size: Option<u32>,
+    encode_options: Option<EncodeOptions>,
+) -> Result<snow_shot_tauri_commands_scroll_screenshot::ScrollScreenshotStitchResult, String> {
+    snow_shot_tauri_commands_scroll_screenshot::scroll_screenshot_stitch_images(
+        app,
+        params,
+        image_paths,
+        file_path,
+        max_page_size,
+        encode_options,
+    )
+    .await
+}
+
+#[command]
+pub async fn scroll_screenshot_stitch_video(
+    app: tauri::AppHandle,
+    params: ScrollScreenshotOfflineParams,
+    ffmpeg_plugin_dir: PathBuf,
+    video_path: String,
+    frame_rate: Option<f32>,
+    file_path: String,
+    max_page_size: Option<u32>,
+    encode_options: Option<EncodeOptions>,
+) -> Result<snow_shot_tauri_commands_scroll_screenshot::ScrollScreenshotStitchResult, String> {
+    snow_shot_tauri_commands_scroll_screenshot::scroll_screenshot_stitch_video(
+        app,
+        params,
+        ffmpeg_plugin_dir,
+        video_path,
+        frame_rate,
+        file_path,
+        max_page_size,
+        encode_options,
+    )
+    .await
+}
+
+#[command]
+pub async fn scroll_screenshot_get_stitch_report(
+    scroll_screenshot_service: tauri::State<'_, Mutex<ScrollScreenshotService>>,
+) -> Result<ScrollStitchReport, String> {
+    snow_shot_tauri_commands_scroll_screenshot::scroll_screenshot_get_stitch_report(
+        scroll_screenshot_service,
     )
     .await
 }
@@ -1,5 +1,10 @@
 import { invoke } from "@tauri-apps/api/core";
 import { Base64 } from "js-base64";
+import type {
+	DailyCounter,
+	FileNameTemplateContext,
+	ResolvedFilePath,
+} from "@/types/utils/file";
 
 export const writeFile = async (
 	filePath: string,
@@ -23,6 +28,45 @@ export const removeFile = async (filePath: string) => {
 	return result;
 };
 
+/**
+ * 渲染保存路径中的文件名模板并避免覆盖已有文件
+ * 不更新每日计数，写入文件后调用 commitDailyCounter 提交
+ */
+export const resolveFilePathTemplate = async (
+	pathTemplate: string,
+	context: FileNameTemplateContext,
+) => {
+	const result = await invoke<ResolvedFilePath>("resolve_file_path_template", {
+		pathTemplate,
+		context,
+	});
+	return result;
+};
+
+/**
+ * 文件写入成功后提交渲染时使用的每日计数
+ */
+export const commitDailyCounter = async (dailyCounter: DailyCounter) => {
+	const result = await invoke<void>("commit_daily_counter", {
+		dailyCounter,
+	});
+	return result;
+};
+
+/**
+ * 预览文件名模板的渲染结果，不更新每日计数
+ */
+export const previewFileNameTemplate = async (
+	template: string,
+	context: FileNameTemplateContext,
+) => {
+	const result = await invoke<string>("preview_file_name_template", {
+		template,
+		context,
+	});
+	return result;
+};
+
 export const createDir = async (dirPath: string) => {
 	const result = await invoke<void>("create_dir", {
 		dirPath,
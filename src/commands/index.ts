@@ -1,13 +1,14 @@
 import { invoke } from "@tauri-apps/api/core";
 import { Base64 } from "js-base64";
 import {
+	type CaptureMetadata,
 	type ElementRect,
 	type ImageBuffer,
 	ImageBufferType,
 	type ImageEncoder,
 	type WindowElement,
 } from "@/types/commands/screenshot";
-import type { ImageFormat } from "@/types/utils/file";
+import type { EncodeOptions, ImageFormat } from "@/types/utils/file";
 
 /**
  * 捕获鼠标所在位置的屏幕图像
@@ -67,15 +68,64 @@ export const getMousePosition = async () => {
 	return result;
 };
 
+const getEncodeOptionsHeaders = (
+	encodeOptions?: EncodeOptions,
+): Record<string, string> => {
+	if (!encodeOptions) {
+		return {};
+	}
+
+	return {
+		"x-encode-options": Base64.encode(JSON.stringify(encodeOptions)),
+	};
+};
+
+const getCaptureMetadataHeaders = (
+	captureMetadata?: CaptureMetadata,
+): Record<string, string> => {
+	if (!captureMetadata) {
+		return {};
+	}
+
+	return {
+		"x-capture-metadata": Base64.encode(JSON.stringify(captureMetadata)),
+	};
+};
+
+/**
+ * 保存文件
+ * @param captureMetadata 写入图片文件的截图元数据，只支持 PNG、JPEG、WebP
+ */
 export const saveFile = async (
 	filePath: string,
 	data: ArrayBuffer | Uint8Array,
 	fileType: ImageFormat,
+	encodeOptions?: EncodeOptions,
+	captureMetadata?: CaptureMetadata,
 ) => {
 	const result = await invoke<void>("save_file", data, {
 		headers: {
 			"x-file-path": Base64.encode(filePath),
 			"x-file-type": Base64.encode(fileType),
+			...getEncodeOptionsHeaders(encodeOptions),
+			...getCaptureMetadataHeaders(captureMetadata),
+		},
+	});
+	return result;
+};
+
+/**
+ * 估算图片按指定格式和编码参数保存后的文件大小（字节）
+ */
+export const estimateImageFileSize = async (
+	data: ArrayBuffer | Uint8Array,
+	fileType: ImageFormat,
+	encodeOptions?: EncodeOptions,
+) => {
+	const result = await invoke<number>("estimate_image_file_size", data, {
+		headers: {
+			"x-file-type": Base64.encode(fileType),
+			...getEncodeOptionsHeaders(encodeOptions),
 		},
 	});
 	return result;
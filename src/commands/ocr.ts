@@ -1,16 +1,45 @@
 import { invoke } from "@tauri-apps/api/core";
 import type { OcrModel } from "@/types/appSettings";
-import type { OcrDetectResult } from "@/types/commands/ocr";
+import type {
+	Barcode,
+	CaptureSearchDocument,
+	CaptureSearchHit,
+	CaptureSearchSyncResult,
+	OcrBatchOptions,
+	OcrBatchResult,
+	OcrDetectResult,
+	OcrDocumentFormat,
+	OcrModelPlugin,
+	OcrOptions,
+	OcrSessionPoolOptions,
+	OcrTable,
+	OcrTableExportFormat,
+	RegionWatchOptions,
+} from "@/types/commands/ocr";
+import type { CaptureMetadataOptions } from "@/types/commands/screenshot";
+import type { EncodeOptions } from "@/types/utils/file";
 
+/**
+ * 识别图片中的文本
+ * @param model 识别使用的语言包，为空时使用初始化时的模型
+ * @param options 文本识别参数，为空时使用屏幕文字的预设
+ * @param requestId 请求 ID，可以通过 ocrCancel 取消排队中的请求
+ */
 export const ocrDetect = async (
 	data: ArrayBuffer | Uint8Array,
 	scaleFactor: number,
 	detectAngle: boolean,
+	model?: OcrModel,
+	options?: OcrOptions,
+	requestId?: string,
 ): Promise<OcrDetectResult> => {
 	return await invoke<OcrDetectResult>("ocr_detect", data, {
 		headers: {
 			"x-scale-factor": scaleFactor.toFixed(3),
 			"x-detect-angle": detectAngle ? "true" : "false",
+			...(model ? { "x-ocr-model": model } : {}),
+			...(options ? { "x-ocr-options": JSON.stringify(options) } : {}),
+			...(requestId ? { "x-request-id": requestId } : {}),
 		},
 	});
 };
@@ -19,11 +48,26 @@ export const ocrDetectWithSharedBuffer = async (
 	channelId: string,
 	scaleFactor: number,
 	detectAngle: boolean,
+	model?: OcrModel,
+	options?: OcrOptions,
+	requestId?: string,
 ): Promise<OcrDetectResult> => {
 	return await invoke<OcrDetectResult>("ocr_detect_with_shared_buffer", {
 		channelId,
 		scaleFactor,
 		detectAngle,
+		model,
+		options,
+		requestId,
+	});
+};
+
+/**
+ * 取消排队中的识别请求，正在识别的请求不受影响
+ */
+export const ocrCancel = async (requestId: string): Promise<void> => {
+	await invoke<void>("ocr_cancel", {
+		requestId,
 	});
 };
 
@@ -32,15 +76,206 @@ export const ocrInit = async (
 	model: OcrModel,
 	hotStart: boolean,
 	modelWriteToMemory: boolean,
+	poolOptions?: OcrSessionPoolOptions,
 ): Promise<void> => {
 	await invoke<void>("ocr_init", {
 		orcPluginPath,
 		model,
 		hotStart,
 		modelWriteToMemory,
+		poolOptions,
+	});
+};
+
+/**
+ * 导出表格为 CSV、TSV、Markdown 或 HTML
+ */
+export const ocrExportTable = async (
+	table: OcrTable,
+	format: OcrTableExportFormat,
+): Promise<string> => {
+	return await invoke<string>("ocr_export_table", {
+		table,
+		format,
+	});
+};
+
+/**
+ * 识别图片文件并导出为可搜索的 PDF、hOCR 或 ALTO 文档
+ * @param model 识别使用的语言包，为空时使用初始化时的模型
+ * @param options 文本识别参数，为空时使用屏幕文字的预设
+ */
+export const ocrExportDocument = async (
+	imagePath: string,
+	outputPath: string,
+	format: OcrDocumentFormat,
+	scaleFactor: number,
+	detectAngle: boolean,
+	model?: OcrModel,
+	options?: OcrOptions,
+): Promise<void> => {
+	await invoke<void>("ocr_export_document", {
+		imagePath,
+		outputPath,
+		format,
+		scaleFactor,
+		detectAngle,
+		model,
+		options,
+	});
+};
+
+/**
+ * 批量识别图片文件和文件夹中的图片，识别结果按图片内容缓存
+ * 进度通过 OCR_BATCH_PROGRESS_EMIT_KEY 事件发送
+ * @param batchId 任务的 ID，用于区分进度事件和取消任务
+ */
+export const ocrBatchDetect = async (
+	batchId: string,
+	paths: string[],
+	scaleFactor: number,
+	detectAngle: boolean,
+	model?: OcrModel,
+	options?: OcrOptions,
+	batchOptions?: OcrBatchOptions,
+): Promise<OcrBatchResult> => {
+	return await invoke<OcrBatchResult>("ocr_batch_detect", {
+		batchId,
+		paths,
+		scaleFactor,
+		detectAngle,
+		model,
+		options,
+		batchOptions,
+	});
+};
+
+/**
+ * 取消批量识别，正在识别的图片完成后生效
+ */
+export const ocrBatchCancel = async (batchId: string): Promise<void> => {
+	await invoke<void>("ocr_batch_cancel", {
+		batchId,
+	});
+};
+
+/**
+ * 使用截图历史的列表同步搜索索引，识别新增和变化的截图
+ * 进度通过 OCR_BATCH_PROGRESS_EMIT_KEY 事件发送，可以通过 ocrBatchCancel 取消
+ * @param onlyIfIndexed 还没有建立过索引时不做处理
+ */
+export const captureSearchSync = async (
+	batchId: string,
+	documents: CaptureSearchDocument[],
+	onlyIfIndexed: boolean,
+	scaleFactor: number,
+	detectAngle: boolean,
+	model?: OcrModel,
+	options?: OcrOptions,
+): Promise<CaptureSearchSyncResult> => {
+	return await invoke<CaptureSearchSyncResult>("capture_search_sync", {
+		batchId,
+		documents,
+		onlyIfIndexed,
+		scaleFactor,
+		detectAngle,
+		model,
+		options,
+	});
+};
+
+/**
+ * 搜索截图历史，结果按相关度排列
+ */
+export const captureSearchQuery = async (
+	query: string,
+	limit: number,
+): Promise<CaptureSearchHit[]> => {
+	return await invoke<CaptureSearchHit[]>("capture_search_query", {
+		query,
+		limit,
 	});
 };
 
 export const ocrRelease = async (): Promise<void> => {
 	await invoke<void>("ocr_release");
 };
+
+/**
+ * 获取提供文本识别模型的插件和插件包含的模型文件
+ */
+export const ocrGetModelPlugins = async (): Promise<OcrModelPlugin[]> => {
+	return await invoke<OcrModelPlugin[]>("ocr_get_model_plugins");
+};
+
+/**
+ * 识别图片中的二维码和条码
+ * @param data PNG 图片
+ */
+export const barcodeDetect = async (
+	data: ArrayBuffer | Uint8Array,
+): Promise<Barcode[]> => {
+	return await invoke<Barcode[]>("barcode_detect", data);
+};
+
+/**
+ * 将文本编码为二维码
+ * @param size 二维码的最小边长（像素）
+ * @returns PNG 图片
+ */
+export const barcodeGenerateQrCode = async (
+	text: string,
+	size: number,
+): Promise<ArrayBuffer> => {
+	return await invoke<ArrayBuffer>("barcode_generate_qr_code", {
+		text,
+		size,
+	});
+};
+
+/**
+ * 在后台监控屏幕区域，画面或文字变化时发送 REGION_WATCH_HIT_EMIT_KEY 事件
+ * @param scaleFactor 识别文字时使用的缩放比例
+ * @param encodeOptions 保存截图时的图片编码参数
+ * @param captureMetadataOptions 写入截图元数据的隐私设置
+ */
+export const regionWatchStart = async (
+	watchId: string,
+	minX: number,
+	minY: number,
+	maxX: number,
+	maxY: number,
+	scaleFactor: number,
+	options?: RegionWatchOptions,
+	encodeOptions?: EncodeOptions,
+	captureMetadataOptions?: CaptureMetadataOptions,
+): Promise<void> => {
+	await invoke<void>("region_watch_start", {
+		watchId,
+		minX,
+		minY,
+		maxX,
+		maxY,
+		scaleFactor,
+		options,
+		encodeOptions,
+		captureMetadataOptions,
+	});
+};
+
+/**
+ * 停止区域监控
+ * @param watchId 为空时停止所有监控
+ */
+export const regionWatchStop = async (watchId?: string): Promise<void> => {
+	await invoke<void>("region_watch_stop", {
+		watchId,
+	});
+};
+
+/**
+ * 正在运行的区域监控的 ID
+ */
+export const regionWatchList = async (): Promise<string[]> => {
+	return await invoke<string[]>("region_watch_list");
+};
@@ -1,11 +1,21 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { HdrColorAlgorithm } from "@/types/appSettings";
+import type {
+	ColorProfileMode,
+	HdrColorAlgorithm,
+	HdrToneMappingOptions,
+} from "@/types/appSettings";
 import {
 	type CaptureFullScreenResult,
+	type CaptureMetadataOptions,
+	type FocusedWindowInfo,
 	type ImageBuffer,
 	ImageBufferType,
 	ImageEncoder,
 } from "@/types/commands/screenshot";
+import type {
+	EncodeOptions,
+	FileNameTemplateContext,
+} from "@/types/utils/file";
 
 export const switchAlwaysOnTop = async (windowId: number) => {
 	const result = await invoke<string>("switch_always_on_top", {
@@ -23,31 +33,57 @@ export const setDrawWindowStyle = async () => {
  * 捕获焦点窗口
  * @param filePath 文件路径
  * @param copyToClipboard 是否复制到剪贴板
+ * @param encodeOptions 图片编码参数
+ * @param hdrToneMapping HDR 色调映射参数
+ * @param colorProfileMode 显示器色彩配置文件的处理方式
+ * @param captureMetadataOptions 写入截图元数据的隐私设置
+ * @param fileNameTemplateContext 渲染文件路径模板时使用的截图信息
+ * @returns 截图实际保存的路径，重名时会追加序号
  */
 export const captureFocusedWindow = async (
 	filePath: string,
 	copyToClipboard: boolean,
-	focusWindowAppNameVariableName: string,
 	correctHdrColorAlgorithm: HdrColorAlgorithm,
+	encodeOptions?: EncodeOptions,
+	hdrToneMapping?: HdrToneMappingOptions,
+	colorProfileMode?: ColorProfileMode,
+	captureMetadataOptions?: CaptureMetadataOptions,
+	fileNameTemplateContext?: FileNameTemplateContext,
 ) => {
-	const result = await invoke("capture_focused_window", {
+	const result = await invoke<string>("capture_focused_window", {
 		filePath,
 		copyToClipboard,
-		focusWindowAppNameVariableName,
 		correctHdrColorAlgorithm,
+		hdrToneMapping,
+		colorProfileMode,
+		captureMetadataOptions,
+		fileNameTemplateContext,
+		encodeOptions,
 	});
 	return result;
 };
 
+/**
+ * 获取焦点窗口的标题和进程名称，需要在截图窗口显示前调用
+ */
+export const getFocusedWindowInfo = async () => {
+	const result = await invoke<FocusedWindowInfo>("get_focused_window_info");
+	return result;
+};
+
 export const captureAllMonitors = async (
 	enableMultipleMonitor: boolean,
 	correctHdrColorAlgorithm: HdrColorAlgorithm,
 	correctColorFilter: boolean,
+	hdrToneMapping?: HdrToneMappingOptions,
+	colorProfileMode?: ColorProfileMode,
 ): Promise<ImageBuffer | undefined> => {
 	const result = await invoke<ArrayBuffer>("capture_all_monitors", {
 		enableMultipleMonitor,
 		correctHdrColorAlgorithm,
+		hdrToneMapping,
 		correctColorFilter,
+		colorProfileMode,
 	});
 
 	if (result.byteLength === 0) {
@@ -76,6 +112,11 @@ export const captureFullScreen = async (
 	captureHistoryFilePath: string,
 	correctHdrColorAlgorithm: HdrColorAlgorithm,
 	correctColorFilter: boolean,
+	encodeOptions?: EncodeOptions,
+	hdrToneMapping?: HdrToneMappingOptions,
+	colorProfileMode?: ColorProfileMode,
+	captureMetadataOptions?: CaptureMetadataOptions,
+	fileNameTemplateContext?: FileNameTemplateContext,
 ): Promise<CaptureFullScreenResult> => {
 	const result = await invoke<CaptureFullScreenResult>("capture_full_screen", {
 		enableMultipleMonitor,
@@ -83,7 +124,12 @@ export const captureFullScreen = async (
 		copyToClipboard,
 		captureHistoryFilePath,
 		correctHdrColorAlgorithm,
+		hdrToneMapping,
 		correctColorFilter,
+		colorProfileMode,
+		captureMetadataOptions,
+		fileNameTemplateContext,
+		encodeOptions,
 	});
 	return result;
 };
@@ -1,5 +1,14 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { HdrColorAlgorithm } from "@/types/appSettings";
+import type {
+	ColorProfileMode,
+	HdrColorAlgorithm,
+	HdrToneMappingOptions,
+} from "@/types/appSettings";
+import type {
+	CaptureMetadataOptions,
+	ElementRect,
+} from "@/types/commands/screenshot";
+import type { EncodeOptions, ImageFormat } from "@/types/utils/file";
 import { appError, formatErrorDetails } from "@/utils/log";
 
 export enum ScrollDirection {
@@ -27,6 +36,8 @@ export const scrollScreenshotInit = async (
 	descriptorPatchSize: number,
 	minSizeDelta: number,
 	tryRollback: boolean,
+	/** 屏蔽区域，相对于滚动区域的左上角 */
+	maskRects?: ElementRect[],
 ) => {
 	const result = await invoke("scroll_screenshot_init", {
 		direction,
@@ -39,6 +50,7 @@ export const scrollScreenshotInit = async (
 		descriptorPatchSize,
 		minSizeDelta,
 		tryRollback,
+		maskRects,
 	});
 	return result;
 };
@@ -64,6 +76,8 @@ export const scrollScreenshotCapture = async (
 	maxY: number,
 	correctHdrColorAlgorithm: HdrColorAlgorithm,
 	correctColorFilter: boolean,
+	hdrToneMapping?: HdrToneMappingOptions,
+	colorProfileMode?: ColorProfileMode,
 ) => {
 	const result = await invoke<ArrayBuffer>("scroll_screenshot_capture", {
 		scrollImageList,
@@ -72,7 +86,9 @@ export const scrollScreenshotCapture = async (
 		maxX,
 		maxY,
 		correctHdrColorAlgorithm,
+		hdrToneMapping,
 		correctColorFilter,
+		colorProfileMode,
 	});
 
 	return result;
@@ -171,9 +187,60 @@ export const scrollScreenshotGetSize = async () => {
 	return result;
 };
 
-export const scrollScreenshotSaveToFile = async (filePath: string) => {
-	const result = await invoke("scroll_screenshot_save_to_file", {
+export type ScrollScreenshotUndoResult = {
+	removed_count: number;
+	top_image_size: number;
+	bottom_image_size: number;
+};
+
+export const scrollScreenshotUndo = async (
+	scrollImageList: ScrollImageList,
+	count: number,
+) => {
+	const result = await invoke<ScrollScreenshotUndoResult>(
+		"scroll_screenshot_undo",
+		{
+			scrollImageList,
+			count,
+		},
+	);
+	return result;
+};
+
+export type ScrollScreenshotPdfOptions = {
+	/** 页面宽度（毫米） */
+	page_width: number;
+	/** 页面高度（毫米） */
+	page_height: number;
+	/** 页边距（毫米） */
+	margin: number;
+	/** 图片分辨率，图片超出页面宽度时会缩小显示 */
+	dpi: number;
+};
+
+/**
+ * @param maxPageSize 图片超过该尺寸时按页保存为多个文件
+ * @param pdfOptions 保存为 PDF 时的页面设置，默认为 A4 纸张
+ * @param encodeOptions 图片编码参数
+ * @param captureMetadataOptions 写入截图元数据的隐私设置
+ * @param captureTime 开始滚动截图的时间，为空时使用当前时间
+ * @returns 保存的文件路径
+ */
+export const scrollScreenshotSaveToFile = async (
+	filePath: string,
+	maxPageSize?: number,
+	pdfOptions?: ScrollScreenshotPdfOptions,
+	encodeOptions?: EncodeOptions,
+	captureMetadataOptions?: CaptureMetadataOptions,
+	captureTime?: number,
+) => {
+	const result = await invoke<string[]>("scroll_screenshot_save_to_file", {
 		filePath,
+		maxPageSize,
+		pdfOptions,
+		captureMetadataOptions,
+		encodeOptions,
+		captureTime,
 	});
 	return result;
 };
@@ -190,11 +257,13 @@ export const scrollScreenshotClear = async () => {
 
 export const scrollScreenshotGetImageData = async (
 	forceToPng: boolean = false,
+	encodeOptions?: EncodeOptions,
 ): Promise<ArrayBuffer | undefined> => {
 	let result: ArrayBuffer | undefined;
 	try {
 		result = await invoke<ArrayBuffer>("scroll_screenshot_get_image_data", {
 			forceToPng,
+			encodeOptions,
 		});
 	} catch (error) {
 		appError("[scrollScreenshotGetImageData] error", formatErrorDetails(error));
@@ -207,3 +276,187 @@ export const scrollScreenshotGetImageData = async (
 
 	return result;
 };
+
+/**
+ * 估算滚动截图按编码参数保存后的文件大小（字节）
+ */
+export const scrollScreenshotEstimateFileSize = async (
+	imageFormat: ImageFormat,
+	encodeOptions?: EncodeOptions,
+) => {
+	const result = await invoke<number>(
+		"scroll_screenshot_estimate_file_size",
+		{
+			imageFormat,
+			encodeOptions,
+		},
+	);
+	return result;
+};
+
+export type ScrollScreenshotSessionInfo = {
+	id: string;
+	/** 创建时间（毫秒时间戳） */
+	created_at: number;
+	direction: ScrollDirection;
+	image_count: number;
+	width: number;
+	height: number;
+};
+
+export const scrollScreenshotSaveSession = async () => {
+	const result = await invoke<ScrollScreenshotSessionInfo>(
+		"scroll_screenshot_save_session",
+	);
+	return result;
+};
+
+export const scrollScreenshotListSessions = async () => {
+	const result = await invoke<ScrollScreenshotSessionInfo[]>(
+		"scroll_screenshot_list_sessions",
+	);
+	return result;
+};
+
+export const scrollScreenshotRestoreSession = async (sessionId: string) => {
+	const result = await invoke<ScrollScreenshotSessionInfo>(
+		"scroll_screenshot_restore_session",
+		{
+			sessionId,
+		},
+	);
+	return result;
+};
+
+export const scrollScreenshotRemoveSession = async (sessionId: string) => {
+	const result = await invoke("scroll_screenshot_remove_session", {
+		sessionId,
+	});
+	return result;
+};
+
+export type ScrollScreenshotOfflineParams = {
+	direction: ScrollDirection;
+	sample_rate: number;
+	min_sample_size: number;
+	max_sample_size: number;
+	corner_threshold: number;
+	descriptor_patch_size: number;
+	/** 为空时根据首帧的尺寸计算 */
+	min_size_delta: number | undefined;
+	try_rollback: boolean;
+};
+
+export type ScrollScreenshotStitchResult = {
+	stitch_result: {
+		frame_count: number;
+		stitched_count: number;
+		unchanged_count: number;
+		unmatched_count: number;
+		width: number;
+		height: number;
+	};
+	file_path_list: string[];
+};
+
+/**
+ * 离线拼接图片文件，路径为目录时读取目录下的所有图片
+ */
+export const scrollScreenshotStitchImages = async (
+	params: ScrollScreenshotOfflineParams,
+	imagePaths: string[],
+	filePath: string,
+	maxPageSize?: number,
+	encodeOptions?: EncodeOptions,
+) => {
+	const result = await invoke<ScrollScreenshotStitchResult>(
+		"scroll_screenshot_stitch_images",
+		{
+			params,
+			imagePaths,
+			filePath,
+			maxPageSize,
+			encodeOptions,
+		},
+	);
+	return result;
+};
+
+/**
+ * 通过 ffmpeg 插件解码录屏视频并离线拼接
+ */
+export const scrollScreenshotStitchVideo = async (
+	params: ScrollScreenshotOfflineParams,
+	ffmpegPluginDir: string,
+	videoPath: string,
+	filePath: string,
+	frameRate?: number,
+	maxPageSize?: number,
+	encodeOptions?: EncodeOptions,
+) => {
+	const result = await invoke<ScrollScreenshotStitchResult>(
+		"scroll_screenshot_stitch_video",
+		{
+			params,
+			ffmpegPluginDir,
+			videoPath,
+			frameRate,
+			filePath,
+			maxPageSize,
+			encodeOptions,
+		},
+	);
+	return result;
+};
+
+export type ScrollMatchStats = {
+	corner_count: number;
+	match_count: number;
+	/** 主偏移的票数，即内点数量 */
+	inlier_count: number;
+	dominant_offset: number;
+	second_offset: number | null;
+	second_count: number;
+	/** 内点的平均描述符距离 */
+	mean_distance: number;
+};
+
+export type ScrollFrameStats = {
+	frame_index: number;
+	status: "Stitched" | "Unchanged" | "Unmatched";
+	scroll_image_list: ScrollImageList;
+	edge_position: number | null;
+	match_stats: ScrollMatchStats | null;
+};
+
+export type ScrollSeamIssue = "PixelMismatch" | "FewInliers" | "AmbiguousOffset";
+
+export type ScrollSeam = {
+	scroll_image_list: ScrollImageList;
+	index: number;
+	/** 接缝在导出图片中沿滚动方向的位置 */
+	position: number;
+	overlap_size: number;
+	/** 重叠区域的平均像素差异（0 - 255） */
+	mean_difference: number | null;
+	max_line_difference: number | null;
+	match_stats: ScrollMatchStats | null;
+	issues: ScrollSeamIssue[];
+};
+
+export type ScrollStitchReport = {
+	direction: ScrollDirection;
+	width: number;
+	height: number;
+	frame_stats: ScrollFrameStats[];
+	seams: ScrollSeam[];
+	/** 可疑接缝的位置 */
+	suspicious_positions: number[];
+};
+
+export const scrollScreenshotGetStitchReport = async () => {
+	const result = await invoke<ScrollStitchReport>(
+		"scroll_screenshot_get_stitch_report",
+	);
+	return result;
+};
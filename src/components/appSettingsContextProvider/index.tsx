@@ -43,6 +43,7 @@ import {
 	AppSettingsTheme,
 	type CloudSaveUrlFormat,
 	CloudSaveUrlType,
+	type ColorProfileMode,
 	type DoubleClickAction,
 	ExtraToolList,
 	type HdrColorAlgorithm,
@@ -772,6 +773,21 @@ const AppSettingsContextProviderCore: React.FC<{
 							? newSettings.ocrModel
 							: (prevSettings?.ocrModel ??
 								defaultAppSettingsData[group].ocrModel),
+					ocrPreset:
+						typeof newSettings?.ocrPreset === "string"
+							? newSettings.ocrPreset
+							: (prevSettings?.ocrPreset ??
+								defaultAppSettingsData[group].ocrPreset),
+					ocrDetectBarcode:
+						typeof newSettings?.ocrDetectBarcode === "boolean"
+							? newSettings.ocrDetectBarcode
+							: (prevSettings?.ocrDetectBarcode ??
+								defaultAppSettingsData[group].ocrDetectBarcode),
+					ocrRecognizeTable:
+						typeof newSettings?.ocrRecognizeTable === "boolean"
+							? newSettings.ocrRecognizeTable
+							: (prevSettings?.ocrRecognizeTable ??
+								defaultAppSettingsData[group].ocrRecognizeTable),
 					htmlVisionModel:
 						typeof newSettings?.htmlVisionModel === "string"
 							? newSettings.htmlVisionModel
@@ -1018,6 +1034,42 @@ const AppSettingsContextProviderCore: React.FC<{
 						typeof newSettings?.selectRectPresetList === "object"
 							? newSettings.selectRectPresetList
 							: (prevSettings?.selectRectPresetList ?? []),
+					regionWatchIntervalMs:
+						typeof newSettings?.regionWatchIntervalMs === "number"
+							? Math.max(
+									Math.min(60000, newSettings.regionWatchIntervalMs),
+									200,
+								)
+							: (prevSettings?.regionWatchIntervalMs ??
+								defaultAppSettingsData[group].regionWatchIntervalMs),
+					regionWatchCpuBudget:
+						typeof newSettings?.regionWatchCpuBudget === "number"
+							? Math.max(Math.min(100, newSettings.regionWatchCpuBudget), 1)
+							: (prevSettings?.regionWatchCpuBudget ??
+								defaultAppSettingsData[group].regionWatchCpuBudget),
+					regionWatchChangeThreshold:
+						typeof newSettings?.regionWatchChangeThreshold === "number"
+							? Math.max(
+									Math.min(100, newSettings.regionWatchChangeThreshold),
+									1,
+								)
+							: (prevSettings?.regionWatchChangeThreshold ??
+								defaultAppSettingsData[group].regionWatchChangeThreshold),
+					regionWatchTextPattern:
+						typeof newSettings?.regionWatchTextPattern === "string"
+							? newSettings.regionWatchTextPattern
+							: (prevSettings?.regionWatchTextPattern ??
+								defaultAppSettingsData[group].regionWatchTextPattern),
+					regionWatchNotify:
+						typeof newSettings?.regionWatchNotify === "boolean"
+							? newSettings.regionWatchNotify
+							: (prevSettings?.regionWatchNotify ??
+								defaultAppSettingsData[group].regionWatchNotify),
+					regionWatchSaveCapture:
+						typeof newSettings?.regionWatchSaveCapture === "boolean"
+							? newSettings.regionWatchSaveCapture
+							: (prevSettings?.regionWatchSaveCapture ??
+								defaultAppSettingsData[group].regionWatchSaveCapture),
 				};
 			} else if (group === AppSettingsGroup.FunctionOutput) {
 				newSettings = newSettings as AppSettingsData[typeof group];
@@ -1299,6 +1351,16 @@ const AppSettingsContextProviderCore: React.FC<{
 							? newSettings.ocrModelWriteToMemory
 							: (prevSettings?.ocrModelWriteToMemory ??
 								defaultAppSettingsData[group].ocrModelWriteToMemory),
+					ocrSessionPoolSize:
+						typeof newSettings?.ocrSessionPoolSize === "number"
+							? Math.max(Math.min(8, newSettings.ocrSessionPoolSize), 1)
+							: (prevSettings?.ocrSessionPoolSize ??
+								defaultAppSettingsData[group].ocrSessionPoolSize),
+					ocrThreadsPerSession:
+						typeof newSettings?.ocrThreadsPerSession === "number"
+							? Math.max(Math.min(64, newSettings.ocrThreadsPerSession), 0)
+							: (prevSettings?.ocrThreadsPerSession ??
+								defaultAppSettingsData[group].ocrThreadsPerSession),
 					ocrDetectAngle:
 						typeof newSettings?.ocrDetectAngle === "boolean"
 							? newSettings.ocrDetectAngle
@@ -1344,6 +1406,37 @@ const AppSettingsContextProviderCore: React.FC<{
 							? (newSettings.correctHdrColorAlgorithm as HdrColorAlgorithm)
 							: (prevSettings?.correctHdrColorAlgorithm ??
 								defaultAppSettingsData[group].correctHdrColorAlgorithm),
+					hdrPeakNits:
+						typeof newSettings?.hdrPeakNits === "number"
+							? Math.max(Math.min(10000, newSettings.hdrPeakNits), 80)
+							: (prevSettings?.hdrPeakNits ??
+								defaultAppSettingsData[group].hdrPeakNits),
+					hdrPaperWhiteNits:
+						typeof newSettings?.hdrPaperWhiteNits === "number"
+							? Math.max(Math.min(1000, newSettings.hdrPaperWhiteNits), 0)
+							: (prevSettings?.hdrPaperWhiteNits ??
+								defaultAppSettingsData[group].hdrPaperWhiteNits),
+					colorProfileMode:
+						typeof newSettings?.colorProfileMode === "string"
+							? (newSettings.colorProfileMode as ColorProfileMode)
+							: (prevSettings?.colorProfileMode ??
+								defaultAppSettingsData[group].colorProfileMode),
+					embedCaptureMetadata:
+						typeof newSettings?.embedCaptureMetadata === "boolean"
+							? newSettings.embedCaptureMetadata
+							: (prevSettings?.embedCaptureMetadata ??
+								defaultAppSettingsData[group].embedCaptureMetadata),
+					captureMetadataIncludeWindowInfo:
+						typeof newSettings?.captureMetadataIncludeWindowInfo === "boolean"
+							? newSettings.captureMetadataIncludeWindowInfo
+							: (prevSettings?.captureMetadataIncludeWindowInfo ??
+								defaultAppSettingsData[group]
+									.captureMetadataIncludeWindowInfo),
+					pngOptimizeLevel:
+						typeof newSettings?.pngOptimizeLevel === "number"
+							? Math.max(Math.min(6, newSettings.pngOptimizeLevel), 0)
+							: (prevSettings?.pngOptimizeLevel ??
+								defaultAppSettingsData[group].pngOptimizeLevel),
 				};
 			} else if (group === AppSettingsGroup.SystemCore) {
 				newSettings = newSettings as AppSettingsData[typeof group];
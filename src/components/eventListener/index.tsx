@@ -26,6 +26,11 @@ import {
 	LISTEN_KEY_SERVICE_MOUSE_STOP_EMIT_KEY,
 	LISTEN_KEY_SERVICE_MOUSE_UP_EMIT_KEY,
 	LISTEN_KEY_SERVICE_STOP_EMIT_KEY,
+	OCR_BATCH_PROGRESS_EMIT_KEY,
+	PNG_OPTIMIZE_FINISHED_EMIT_KEY,
+	REGION_WATCH_HIT_EMIT_KEY,
+	REGION_WATCH_STATE_EMIT_KEY,
+	SCROLL_SCREENSHOT_STITCH_REPORT_EMIT_KEY,
 } from "@/constants/eventListener";
 import { PLUGIN_EVENT_PLUGIN_STATUS_CHANGE } from "@/constants/pluginService";
 import { AntdContext } from "@/contexts/antdContext";
@@ -326,6 +331,26 @@ const EventListenerCore: React.FC<{ children: React.ReactNode }> = ({
 				event: "on-capture-history-change",
 				callback: async () => {},
 			});
+			defaultListener.push({
+				event: OCR_BATCH_PROGRESS_EMIT_KEY,
+				callback: async () => {},
+			});
+			defaultListener.push({
+				event: PNG_OPTIMIZE_FINISHED_EMIT_KEY,
+				callback: async () => {},
+			});
+			defaultListener.push({
+				event: REGION_WATCH_HIT_EMIT_KEY,
+				callback: async () => {},
+			});
+			defaultListener.push({
+				event: REGION_WATCH_STATE_EMIT_KEY,
+				callback: async () => {},
+			});
+			defaultListener.push({
+				event: SCROLL_SCREENSHOT_STITCH_REPORT_EMIT_KEY,
+				callback: async () => {},
+			});
 		} else {
 			defaultListener.push({
 				event: FIXED_CONTENT_FOCUS_MODE_SHOW_ALL_WINDOW,
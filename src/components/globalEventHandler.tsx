@@ -1,16 +1,48 @@
 import { useRouter } from "@tanstack/react-router";
+import {
+	isPermissionGranted,
+	requestPermission,
+	sendNotification,
+} from "@tauri-apps/plugin-notification";
 import { openPath } from "@tauri-apps/plugin-opener";
 import React, { useContext, useEffect } from "react";
+import { useIntl } from "react-intl";
 import { getSelectedText } from "@/commands/core";
+import type { ScrollStitchReport } from "@/commands/scrollScreenshot";
 import { showMainWindow } from "@/commands/videoRecord";
 import { EventListenerContext } from "@/components/eventListener";
+import {
+	PNG_OPTIMIZE_FINISHED_EMIT_KEY,
+	REGION_WATCH_HIT_EMIT_KEY,
+	SCROLL_SCREENSHOT_STITCH_REPORT_EMIT_KEY,
+} from "@/constants/eventListener";
 import { AppSettingsPublisher } from "@/contexts/appSettingsActionContext";
+import { onCaptureHistoryChange } from "@/functions/screenshot";
 import { useStateSubscriber } from "@/hooks/useStateSubscriber";
+import type { RegionWatchHit } from "@/types/commands/ocr";
+import type { PngOptimizeResult } from "@/types/utils/file";
+import { CaptureHistorySource } from "@/utils/appStore";
 import { encodeParamsValue } from "@/utils/base64";
+import { CaptureHistory } from "@/utils/captureHistory";
 import { getImageSaveDirectory } from "@/utils/file";
+import { appInfo, appWarn } from "@/utils/log";
 import { showWindow } from "@/utils/window";
 
+const sendSystemNotification = async (title: string, body: string) => {
+	let permissionGranted = await isPermissionGranted();
+	if (!permissionGranted) {
+		const permission = await requestPermission();
+		permissionGranted = permission === "granted";
+	}
+	if (!permissionGranted) {
+		return;
+	}
+
+	sendNotification({ title, body });
+};
+
 const GlobalEventHandlerCore: React.FC = () => {
+	const intl = useIntl();
 	const router = useRouter();
 
 	const { addListener, removeListener } = useContext(EventListenerContext);
@@ -54,6 +86,75 @@ const GlobalEventHandlerCore: React.FC = () => {
 					to: `/tools/captureHistory`,
 				});
 			}),
+			addListener(PNG_OPTIMIZE_FINISHED_EMIT_KEY, (args) => {
+				const payload = (args as { payload: PngOptimizeResult }).payload;
+				appInfo("[GlobalEventHandler] png optimize finished", payload);
+			}),
+			addListener(REGION_WATCH_HIT_EMIT_KEY, async (args) => {
+				const payload = (args as { payload: RegionWatchHit }).payload;
+				if (payload.capture_history_file_name) {
+					const selectedRect = {
+						min_x: 0,
+						min_y: 0,
+						max_x: payload.region.max_x - payload.region.min_x,
+						max_y: payload.region.max_y - payload.region.min_y,
+					};
+					const captureHistory = new CaptureHistory();
+					await captureHistory.init();
+					await captureHistory.save(
+						{
+							type: "full-screen",
+							captureHistoryItem: {
+								id: payload.capture_time.toString(),
+								selected_rect: selectedRect,
+								file_name: payload.capture_history_file_name,
+								create_ts: payload.capture_time,
+								excalidraw_elements: undefined,
+								excalidraw_app_state: undefined,
+								source: CaptureHistorySource.RegionWatch,
+							},
+						},
+						undefined,
+						undefined,
+						selectedRect,
+						undefined,
+						CaptureHistorySource.RegionWatch,
+					);
+					onCaptureHistoryChange();
+				}
+
+				if (!payload.notify) {
+					return;
+				}
+
+				await sendSystemNotification(
+					intl.formatMessage({ id: "draw.extraTool.regionWatch.hit" }),
+					payload.matched_text
+						? intl.formatMessage(
+								{ id: "draw.extraTool.regionWatch.hit.textMatched" },
+								{ text: payload.matched_text },
+							)
+						: intl.formatMessage(
+								{ id: "draw.extraTool.regionWatch.hit.changed" },
+								{ ratio: Math.round(payload.change_ratio * 100) },
+							),
+				);
+			}),
+			addListener(SCROLL_SCREENSHOT_STITCH_REPORT_EMIT_KEY, async (args) => {
+				const payload = (args as { payload: ScrollStitchReport }).payload;
+				appWarn(
+					"[GlobalEventHandler] scroll screenshot suspicious seams",
+					payload.suspicious_positions,
+				);
+
+				await sendSystemNotification(
+					intl.formatMessage({ id: "draw.scrollScreenshot.suspiciousSeam" }),
+					intl.formatMessage(
+						{ id: "draw.scrollScreenshot.suspiciousSeam.positions" },
+						{ positions: payload.suspicious_positions.join(", ") },
+					),
+				);
+			}),
 		);
 
 		return () => {
@@ -61,7 +162,7 @@ const GlobalEventHandlerCore: React.FC = () => {
 				removeListener(id);
 			});
 		};
-	}, [addListener, removeListener, router, getAppSettings]);
+	}, [addListener, removeListener, router, getAppSettings, intl]);
 
 	return undefined;
 };
@@ -10,8 +10,8 @@ import { hotLoadPageInit } from "@/commands/hotLoadPage";
 import { ocrInit } from "@/commands/ocr";
 import { videoRecordInit } from "@/commands/videoRecord";
 import {
+	getOcrModelPluginId,
 	PLUGIN_ID_FFMPEG,
-	PLUGIN_ID_RAPID_OCR,
 } from "@/constants/pluginService";
 import { usePluginServiceContext } from "@/contexts/pluginServiceContext";
 import { useAppSettingsLoad } from "@/hooks/useAppSettingsLoad";
@@ -51,6 +51,9 @@ export const InitService = () => {
 			return;
 		}
 
+		const ocrPluginId = await getOcrModelPluginId(
+			appSettings[AppSettingsGroup.FunctionOcr].ocrModel,
+		);
 		if (
 			(!hasInitOcr.current ||
 				(prevAppSettings &&
@@ -61,17 +64,32 @@ export const InitService = () => {
 						appSettings[AppSettingsGroup.SystemScreenshot]
 							.ocrModelWriteToMemory !==
 							prevAppSettings[AppSettingsGroup.SystemScreenshot]
-								.ocrModelWriteToMemory))) &&
-			isReadyStatus(PLUGIN_ID_RAPID_OCR)
+								.ocrModelWriteToMemory ||
+						appSettings[AppSettingsGroup.SystemScreenshot]
+							.ocrSessionPoolSize !==
+							prevAppSettings[AppSettingsGroup.SystemScreenshot]
+								.ocrSessionPoolSize ||
+						appSettings[AppSettingsGroup.SystemScreenshot]
+							.ocrThreadsPerSession !==
+							prevAppSettings[AppSettingsGroup.SystemScreenshot]
+								.ocrThreadsPerSession))) &&
+			isReadyStatus(ocrPluginId)
 		) {
 			hasInitOcr.current = true;
 
 			if (pluginConfigRef.current) {
 				ocrInit(
-					await pluginConfigRef.current.getPluginDirPath(PLUGIN_ID_RAPID_OCR),
+					await pluginConfigRef.current.getPluginDirPath(ocrPluginId),
 					appSettings[AppSettingsGroup.FunctionOcr].ocrModel,
 					appSettings[AppSettingsGroup.SystemScreenshot].ocrHotStart,
 					appSettings[AppSettingsGroup.SystemScreenshot].ocrModelWriteToMemory,
+					{
+						pool_size:
+							appSettings[AppSettingsGroup.SystemScreenshot].ocrSessionPoolSize,
+						threads_per_session:
+							appSettings[AppSettingsGroup.SystemScreenshot]
+								.ocrThreadsPerSession,
+					},
 				);
 			} else {
 				appWarn("[InitService] pluginConfigRef.current is not set");
@@ -8,9 +8,9 @@ import {
 	pluginRegisterPlugin,
 } from "@/commands/plugin";
 import {
+	getOcrModelPluginsWithCache,
 	PLUGIN_ID_AI_CHAT,
 	PLUGIN_ID_FFMPEG,
-	PLUGIN_ID_RAPID_OCR,
 	PLUGIN_ID_TRANSLATE,
 } from "@/constants/pluginService";
 import { PluginServiceContext } from "@/contexts/pluginServiceContext";
@@ -28,17 +28,9 @@ export const PluginServiceContextProvider: React.FC<{
 	children: React.ReactNode;
 	autoInit: boolean;
 }> = ({ children, autoInit }) => {
-	const pluginList = useMemo<PluginItem[]>(() => {
+	// 文本识别的插件由后端的模型注册表提供
+	const otherPluginList = useMemo<PluginItem[]>(() => {
 		return [
-			{
-				id: PLUGIN_ID_RAPID_OCR,
-				file_list: [
-					"ch_ppocr_mobile_v2.0_cls_infer.onnx",
-					"ch_PP-OCRv4_det_infer.onnx",
-					"ch_PP-OCRv4_rec_infer.onnx",
-					"ch_PP-OCRv5_rec_mobile_infer.onnx",
-				],
-			},
 			{
 				id: PLUGIN_ID_FFMPEG,
 				file_list: getPlatform() === "windows" ? ["ffmpeg.exe"] : ["ffmpeg"],
@@ -71,6 +63,15 @@ export const PluginServiceContextProvider: React.FC<{
 	const initPluginConfig = useCallback(async () => {
 		const configDirPath = await getAppConfigBaseDirWithCache();
 
+		const ocrModelPlugins = await getOcrModelPluginsWithCache();
+		const pluginList: PluginItem[] = [
+			...ocrModelPlugins.map((plugin) => ({
+				id: plugin.id,
+				file_list: plugin.file_list,
+			})),
+			...otherPluginList,
+		];
+
 		const pluginConfig = new PluginConfig(
 			pluginList,
 			"20251005",
@@ -99,7 +100,7 @@ export const PluginServiceContextProvider: React.FC<{
 
 			initServiceReadyRef.current = true;
 		}
-	}, [setPluginConfig, pluginList, autoInit]);
+	}, [setPluginConfig, otherPluginList, autoInit]);
 
 	const refreshPluginStatus = useCallback(async () => {
 		const pluginStatus = await pluginGetPluginsStatus();
@@ -13,6 +13,9 @@ import {
 	createFixedContentWindow,
 	createFullScreenDrawWindow,
 } from "@/commands/core";
+import { regionWatchList, regionWatchStop } from "@/commands/ocr";
+import { EventListenerContext } from "@/components/eventListener";
+import { REGION_WATCH_STATE_EMIT_KEY } from "@/constants/eventListener";
 import {
 	PLUGIN_ID_AI_CHAT,
 	PLUGIN_ID_FFMPEG,
@@ -143,6 +146,23 @@ const TrayIconLoaderComponent = () => {
 		true,
 	);
 
+	// 正在运行的区域监控数量
+	const [regionWatchCount, setRegionWatchCount] = useState(0);
+	const { addListener, removeListener } = useContext(EventListenerContext);
+	useEffect(() => {
+		regionWatchList().then((watchIds) => {
+			setRegionWatchCount(watchIds.length);
+		});
+
+		const listenerId = addListener(REGION_WATCH_STATE_EMIT_KEY, (args) => {
+			const watchIds = (args as { payload: string[] }).payload;
+			setRegionWatchCount(watchIds.length);
+		});
+		return () => {
+			removeListener(listenerId);
+		};
+	}, [addListener, removeListener]);
+
 	const { isReadyStatus } = usePluginServiceContext();
 	const initTrayIcon = useCallback(async (): Promise<
 		| {
@@ -453,6 +473,20 @@ const TrayIconLoaderComponent = () => {
 						createFullScreenDrawWindow();
 					},
 				},
+				...(regionWatchCount > 0
+					? [
+							{
+								id: `${appWindow.label}-stop-region-watch`,
+								text: intl.formatMessage(
+									{ id: "draw.extraTool.regionWatch.stopAll" },
+									{ count: regionWatchCount },
+								),
+								action: async () => {
+									regionWatchStop();
+								},
+							},
+						]
+					: []),
 				{
 					id: `${appWindow.label}-open-image-save-folder`,
 					text: intl.formatMessage({ id: "home.openImageSaveFolder" }),
@@ -560,6 +594,7 @@ const TrayIconLoaderComponent = () => {
 		currentTheme,
 		defaultIconDark,
 		iconPathDark,
+		regionWatchCount,
 	]);
 
 	useEffect(() => {
@@ -8,6 +8,7 @@ import {
 	CloudSaveUrlFormat,
 	CloudSaveUrlType,
 	ColorPickerShowMode,
+	ColorProfileMode,
 	DoubleClickAction,
 	ExtraToolList,
 	GifFormat,
@@ -20,6 +21,7 @@ import {
 	TrayIconDefaultIcon,
 	VideoMaxSize,
 } from "@/types/appSettings";
+import { OcrPreset } from "@/types/commands/ocr";
 import { DrawState } from "@/types/draw";
 import {
 	TranslationDomain,
@@ -154,6 +156,9 @@ export const defaultAppSettingsData: AppSettingsData = {
 	[AppSettingsGroup.FunctionOcr]: {
 		htmlVisionModel: "",
 		ocrModel: OcrModel.RapidOcrV4,
+		ocrPreset: OcrPreset.ScreenText,
+		ocrDetectBarcode: false,
+		ocrRecognizeTable: false,
 		htmlVisionModelSystemPrompt: `You are a professional image-to-HTML conversion engine. Your sole objective is to accurately convert images into clean, semantic HTML code.
 
 ## Conversion Rules (must follow)
@@ -255,6 +260,12 @@ Priority order (highest to lowest):
 		ocrAfterAction: OcrDetectAfterAction.None,
 		ocrCopyText: true,
 		selectRectPresetList: [],
+		regionWatchIntervalMs: 1000,
+		regionWatchCpuBudget: 10,
+		regionWatchChangeThreshold: 2,
+		regionWatchTextPattern: "",
+		regionWatchNotify: true,
+		regionWatchSaveCapture: false,
 	},
 	[AppSettingsGroup.SystemScrollScreenshot]: {
 		tryRollback: true,
@@ -305,6 +316,8 @@ Priority order (highest to lowest):
 	[AppSettingsGroup.SystemScreenshot]: {
 		ocrHotStart: true,
 		ocrModelWriteToMemory: false,
+		ocrSessionPoolSize: 2,
+		ocrThreadsPerSession: 0,
 		ocrDetectAngle: false,
 		historyValidDuration: HistoryValidDuration.Week,
 		recordCaptureHistory: true,
@@ -319,6 +332,18 @@ Priority order (highest to lowest):
 		correctHdrColor: true,
 		/** HDR 颜色转换算法 */
 		correctHdrColorAlgorithm: HdrColorAlgorithm.Linear,
+		/** HDR 色调映射的峰值亮度（尼特） */
+		hdrPeakNits: 1000,
+		/** HDR 色调映射的纸白亮度（尼特），为 0 时使用显示器的 SDR 白色亮度 */
+		hdrPaperWhiteNits: 0,
+		/** 显示器色彩配置文件的处理方式 */
+		colorProfileMode: ColorProfileMode.Ignore,
+		/** 在图片文件中写入截图时间、来源等元数据 */
+		embedCaptureMetadata: true,
+		/** 元数据中包含窗口标题和进程名称 */
+		captureMetadataIncludeWindowInfo: false,
+		/** 保存 PNG 后在后台进行无损优化的等级（1 - 6），为 0 时不优化 */
+		pngOptimizeLevel: 0,
 	},
 	[AppSettingsGroup.FunctionTrayIcon]: {
 		iconClickAction: TrayIconClickAction.Screenshot,
@@ -9,3 +9,13 @@ export const LISTEN_KEY_SERVICE_MOUSE_UP_EMIT_KEY =
 	"listen-mouse-service:mouse-up";
 export const LISTEN_KEY_SERVICE_MOUSE_STOP_EMIT_KEY =
 	"listen-mouse-service:mouse-stop"; // 停止监听鼠标
+
+export const PNG_OPTIMIZE_FINISHED_EMIT_KEY = "png-optimize-finished"; // PNG 后台优化完成
+
+export const OCR_BATCH_PROGRESS_EMIT_KEY = "ocr-batch-progress"; // 批量识别进度
+
+export const REGION_WATCH_HIT_EMIT_KEY = "region-watch-hit"; // 区域监控触发
+export const REGION_WATCH_STATE_EMIT_KEY = "region-watch-state"; // 区域监控开始或停止
+
+export const SCROLL_SCREENSHOT_STITCH_REPORT_EMIT_KEY =
+	"scroll-screenshot-stitch-report"; // 滚动截图导出后发现可疑接缝
@@ -1,6 +1,35 @@
+import { ocrGetModelPlugins } from "@/commands/ocr";
+import type { OcrModel } from "@/types/appSettings";
+import type { OcrModelPlugin } from "@/types/commands/ocr";
+
 export const PLUGIN_EVENT_PLUGIN_STATUS_CHANGE = "plugin-status-change";
 
 export const PLUGIN_ID_RAPID_OCR = "rapid_ocr";
 export const PLUGIN_ID_FFMPEG = "ffmpeg";
 export const PLUGIN_ID_AI_CHAT = "ai_chat";
 export const PLUGIN_ID_TRANSLATE = "translate";
+
+let ocrModelPluginsCache: OcrModelPlugin[] | undefined;
+/**
+ * 获取提供文本识别模型的插件，插件和模型文件由后端的 OcrModel::plugins 定义
+ */
+export const getOcrModelPluginsWithCache = async () => {
+	if (ocrModelPluginsCache !== undefined) {
+		return ocrModelPluginsCache;
+	}
+
+	const result = await ocrGetModelPlugins();
+	ocrModelPluginsCache = result;
+	return result;
+};
+
+/**
+ * 获取文本识别模型所在的插件
+ */
+export const getOcrModelPluginId = async (model: OcrModel) => {
+	const ocrModelPlugins = await getOcrModelPluginsWithCache();
+	return (
+		ocrModelPlugins.find((plugin) => plugin.models.includes(model))?.id ??
+		PLUGIN_ID_RAPID_OCR
+	);
+};
@@ -1,12 +1,20 @@
 import { emit } from "@tauri-apps/api/event";
 import * as tauriLog from "@tauri-apps/plugin-log";
 import { captureFocusedWindow } from "@/commands/screenshot";
-import { FOCUS_WINDOW_APP_NAME_ENV_VARIABLE } from "@/constants/components/chat";
 import { type AppSettingsData, AppSettingsGroup } from "@/types/appSettings";
-import { getCorrectHdrColorAlgorithm } from "@/utils/appSettings";
+import { CaptureMode } from "@/types/commands/screenshot";
+import {
+	getCaptureMetadataOptions,
+	getCorrectHdrColorAlgorithm,
+	getEncodeOptions,
+	getHdrToneMappingOptions,
+} from "@/utils/appSettings";
 import { playCameraShutterSound } from "@/utils/audio";
-import { getImagePathFromSettings } from "@/utils/file";
-import { appError } from "@/utils/log";
+import {
+	createFileNameTemplateContext,
+	getImagePathFromSettings,
+} from "@/utils/file";
+import { appError, appInfo } from "@/utils/log";
 import { ScreenshotType } from "@/utils/types";
 
 export const executeScreenshot = async (
@@ -41,11 +49,19 @@ export const executeScreenshotFocusedWindow = async (
 			imagePath.filePath,
 			appSettings[AppSettingsGroup.FunctionScreenshot]
 				.focusedWindowCopyToClipboard,
-			FOCUS_WINDOW_APP_NAME_ENV_VARIABLE,
 			getCorrectHdrColorAlgorithm(appSettings),
+			getEncodeOptions(appSettings),
+			getHdrToneMappingOptions(appSettings),
+			appSettings[AppSettingsGroup.SystemScreenshot].colorProfileMode,
+			getCaptureMetadataOptions(appSettings),
+			createFileNameTemplateContext(CaptureMode.Window),
 		);
 		playCameraShutterSound();
-		await captureFocusedWindowPromise;
+		// 重名时后端会在文件名后追加序号，以返回的路径为准
+		const filePath = await captureFocusedWindowPromise;
+		appInfo("[executeScreenshotFocusedWindow] Saved focused window", {
+			filePath,
+		});
 	} catch (error) {
 		appError(
 			"[executeScreenshotFocusedWindow] Failed to capture focused window",
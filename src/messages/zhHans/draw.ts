@@ -95,6 +95,13 @@ export const draw = {
 	"draw.resetCanvasTool": "重置画布",
 	"draw.ocrLoading": "文本识别中",
 	"draw.copySelectedText": "复制选中文本",
+	"draw.copyTable": "复制表格",
+	"draw.copyTable.csv": "CSV",
+	"draw.copyTable.tsv": "TSV（粘贴到电子表格）",
+	"draw.copyTable.markdown": "Markdown",
+	"draw.copyTable.html": "HTML",
+	"draw.copyBarcode": "复制二维码、条码内容",
+	"draw.generateQrCode": "复制为二维码",
 	"draw.scrollScreenshot.limitTip":
 		"所选区域过小，无法进行滚动截图（最小边需 > 200px）",
 	"draw.scrollScreenshot.limitTip.warning":
@@ -104,6 +111,9 @@ export const draw = {
 	"draw.scrollScreenshot.captureMiss": "滚动过快，请滚动到已截取的边缘再次尝试",
 	"draw.scrollScreenshot.changeDirection": "切换滚动方向",
 	"draw.scrollScreenshot.scrollError": "部分滚动失效，请检查应用权限",
+	"draw.scrollScreenshot.suspiciousSeam": "滚动截图可能存在拼接错误",
+	"draw.scrollScreenshot.suspiciousSeam.positions":
+		"请检查图片中以下位置附近的内容：{positions} px",
 	"draw.ocrDetect.translate": "翻译",
 	"draw.ocrDetect.translate.showProcess": "显示翻译进度",
 	"draw.ocrDetect.translate.showProcess.loading": "翻译中",
@@ -117,6 +127,12 @@ export const draw = {
 	"draw.extraTool.videoRecord": "视频录制",
 	"draw.extraTool.scanQrcode.error": "未识别到有效二维码",
 	"draw.extraTool.videoRecord.multiMonitor": "MacOS 暂不支持跨屏录制",
+	"draw.extraTool.regionWatch": "区域监控",
+	"draw.extraTool.regionWatch.started": "已开始监控选区，可在托盘菜单中停止",
+	"draw.extraTool.regionWatch.hit": "监控区域发生变化",
+	"draw.extraTool.regionWatch.hit.changed": "画面变化了 {ratio}%",
+	"draw.extraTool.regionWatch.hit.textMatched": "识别到：{text}",
+	"draw.extraTool.regionWatch.stopAll": "停止区域监控（{count}）",
 	"draw.scrollScreenshot.tip": "单击截图区域进行自动滚动",
 	"draw.scrollScreenshot.tip2": "如果无法滚动窗口，请双击截图区域以聚焦窗口",
 	"draw.selectText": "选取文本",
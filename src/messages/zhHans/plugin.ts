@@ -8,6 +8,26 @@ export const plugin = {
 	"plugin.rapid_ocr.description":
 		"使用基于 ONNXRuntime 和 Paddle OCR 的 Rapid OCR 实现高性能跨平台文本识别。支持结合 AI 对话插件获得更高阶的文本识别能力",
 	"plugin.rapid_ocr.functionDescription": "文本识别",
+	"plugin.rapid_ocr_japanese.name": "文本识别语言包（日语）",
+	"plugin.rapid_ocr_japanese.description":
+		"Rapid OCR 的日语识别模型，在文本识别模型设置中选择后使用",
+	"plugin.rapid_ocr_japanese.functionDescription": "日语文本识别",
+	"plugin.rapid_ocr_korean.name": "文本识别语言包（韩语）",
+	"plugin.rapid_ocr_korean.description":
+		"Rapid OCR 的韩语识别模型，在文本识别模型设置中选择后使用",
+	"plugin.rapid_ocr_korean.functionDescription": "韩语文本识别",
+	"plugin.rapid_ocr_latin.name": "文本识别语言包（拉丁字母）",
+	"plugin.rapid_ocr_latin.description":
+		"Rapid OCR 的拉丁字母识别模型，在文本识别模型设置中选择后使用",
+	"plugin.rapid_ocr_latin.functionDescription": "拉丁字母文本识别",
+	"plugin.rapid_ocr_cyrillic.name": "文本识别语言包（西里尔字母）",
+	"plugin.rapid_ocr_cyrillic.description":
+		"Rapid OCR 的西里尔字母识别模型，在文本识别模型设置中选择后使用",
+	"plugin.rapid_ocr_cyrillic.functionDescription": "西里尔字母文本识别",
+	"plugin.rapid_ocr_arabic.name": "文本识别语言包（阿拉伯语）",
+	"plugin.rapid_ocr_arabic.description":
+		"Rapid OCR 的阿拉伯语识别模型，在文本识别模型设置中选择后使用",
+	"plugin.rapid_ocr_arabic.functionDescription": "阿拉伯语文本识别",
 	"plugin.ai_chat.name": "AI 对话",
 	"plugin.ai_chat.description":
 		"使用最前沿的 AI 大模型简化工作流程，支持自定义配置兼容 Open AI SDK 的 API。",
@@ -222,6 +222,27 @@ export const settings = {
 		"截取当前具有焦点的窗口时复制到剪贴板",
 	"settings.functionSettings.screenshotSettings.fullScreenCopyToClipboard":
 		"截取全屏时复制到剪贴板",
+	"settings.functionSettings.screenshotSettings.regionWatch": "区域监控",
+	"settings.functionSettings.screenshotSettings.regionWatchIntervalMs":
+		"截图间隔",
+	"settings.functionSettings.screenshotSettings.regionWatchCpuBudget":
+		"CPU 占用上限",
+	"settings.functionSettings.screenshotSettings.regionWatchCpuBudget.tip":
+		"截图和识别耗时占比超过该值时自动延长截图间隔",
+	"settings.functionSettings.screenshotSettings.regionWatchChangeThreshold":
+		"变化阈值",
+	"settings.functionSettings.screenshotSettings.regionWatchChangeThreshold.tip":
+		"画面变化的面积超过该比例时触发",
+	"settings.functionSettings.screenshotSettings.regionWatchTextPattern":
+		"匹配文本",
+	"settings.functionSettings.screenshotSettings.regionWatchTextPattern.tip":
+		"正则表达式，画面变化后识别文字，匹配时才触发，为空时画面变化即触发",
+	"settings.functionSettings.screenshotSettings.regionWatchNotify":
+		"触发时发送通知",
+	"settings.functionSettings.screenshotSettings.regionWatchSaveCapture":
+		"触发时保存截图",
+	"settings.functionSettings.screenshotSettings.regionWatchSaveCapture.tip":
+		"截图保存到截图的保存路径",
 	"settings.functionSettings.screenshotSettings.saveToCloud": "保存到云端",
 	"settings.functionSettings.screenshotSettings.saveToCloud.tip":
 		"工具栏新增保存到云端按钮，快速保存到云端并复制结果到剪贴板",
@@ -265,6 +286,18 @@ export const settings = {
 	"settings.functionSettings.outputSettings.variables.focusedWindowAppName":
 		"截取当前具有焦点的窗口的程序名",
 	"settings.functionSettings.outputSettings.variables.date": "日期",
+	"settings.functionSettings.outputSettings.variables.windowTitle":
+		"截取窗口的标题",
+	"settings.functionSettings.outputSettings.variables.processName":
+		"截取窗口的程序名",
+	"settings.functionSettings.outputSettings.variables.monitorIndex":
+		"显示器序号",
+	"settings.functionSettings.outputSettings.variables.captureMode":
+		"截图方式",
+	"settings.functionSettings.outputSettings.variables.counter":
+		"每日序号（补零到 3 位）",
+	"settings.functionSettings.outputSettings.variables.random":
+		"随机字符（8 位）",
 	"settings.functionSettings.outputSettings.fileNameFormat": "文件名格式",
 	"settings.functionSettings.outputSettings.manualSaveFileNameFormat":
 		"手动保存文件名格式",
@@ -511,6 +544,16 @@ export const settings = {
 		"Rapid OCR V4",
 	"settings.systemSettings.screenshotSettings.ocrModel.rapidOcrV5":
 		"Rapid OCR V5",
+	"settings.systemSettings.screenshotSettings.ocrModel.rapidOcrJapanese":
+		"Rapid OCR 日语（需要安装语言包插件）",
+	"settings.systemSettings.screenshotSettings.ocrModel.rapidOcrKorean":
+		"Rapid OCR 韩语（需要安装语言包插件）",
+	"settings.systemSettings.screenshotSettings.ocrModel.rapidOcrLatin":
+		"Rapid OCR 拉丁字母（需要安装语言包插件）",
+	"settings.systemSettings.screenshotSettings.ocrModel.rapidOcrCyrillic":
+		"Rapid OCR 西里尔字母（需要安装语言包插件）",
+	"settings.systemSettings.screenshotSettings.ocrModel.rapidOcrArabic":
+		"Rapid OCR 阿拉伯语（需要安装语言包插件）",
 	"settings.systemSettings.screenshotSettings.ocrHotStart": "文本识别热启动",
 	"settings.systemSettings.screenshotSettings.ocrHotStart.tip":
 		"预加载文本识别模型，提高文本识别的识别速度，但会提高内存占用",
@@ -518,6 +561,14 @@ export const settings = {
 		"文本识别模型写入内存",
 	"settings.systemSettings.screenshotSettings.ocrModelWriteToMemory.tip":
 		"将文本识别模型写入内存，避免重复读写硬盘数据，提高文本识别的加载速度，但会提高内存占用",
+	"settings.systemSettings.screenshotSettings.ocrSessionPoolSize":
+		"文本识别并发数",
+	"settings.systemSettings.screenshotSettings.ocrSessionPoolSize.tip":
+		"同时进行的文本识别数量，批量识别时会保留一个给截图等交互识别，并发数越大内存占用越高",
+	"settings.systemSettings.screenshotSettings.ocrThreadsPerSession":
+		"文本识别线程数",
+	"settings.systemSettings.screenshotSettings.ocrThreadsPerSession.tip":
+		"每个文本识别使用的线程数，为 0 时按 CPU 核心数平分",
 	"settings.systemSettings.screenshotSettings.ocrDetectAngle":
 		"文本识别检测角度",
 	"settings.systemSettings.screenshotSettings.recordCaptureHistory":
@@ -552,6 +603,51 @@ export const settings = {
 		"线性转换",
 	"settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.linear.tip":
 		"适合将 HDR 中的 SDR 内容还原为 SDR，但当 Windows 的 HDR 设置中的 SDR 内容亮度不等于 50% 时，HDR 内容可能严重失真",
+	"settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.reinhard":
+		"Reinhard",
+	"settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.reinhard.tip":
+		"按亮度平滑压缩高光并保持色相，SDR 内容会略微变暗",
+	"settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.acesFilmic":
+		"ACES 电影",
+	"settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.acesFilmic.tip":
+		"电影风格的色调曲线，对比度较高，高光颜色会趋向白色",
+	"settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.hable":
+		"Hable",
+	"settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.hable.tip":
+		"游戏中常用的色调曲线，暗部和高光过渡柔和",
+	"settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.bt2390":
+		"BT.2390",
+	"settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.bt2390.tip":
+		"ITU-R BT.2390 标准的高光压缩，SDR 亮度以内的内容保持不变，适合同时包含 SDR 和 HDR 的画面",
+	"settings.systemSettings.screenshotSettings.colorProfileMode":
+		"显示器色彩配置文件",
+	"settings.systemSettings.screenshotSettings.colorProfileMode.tip":
+		"广色域显示器的截图在其他设备上可能颜色失真。转换为 sRGB 兼容性最好；嵌入配置文件可保留完整色域，但只有 PNG、JPEG、WebP 支持，其他格式仍会转换为 sRGB",
+	"settings.systemSettings.screenshotSettings.colorProfileMode.ignore":
+		"不处理",
+	"settings.systemSettings.screenshotSettings.colorProfileMode.convertToSrgb":
+		"转换为 sRGB",
+	"settings.systemSettings.screenshotSettings.colorProfileMode.embed":
+		"嵌入配置文件",
+	"settings.systemSettings.screenshotSettings.embedCaptureMetadata":
+		"写入截图元数据",
+	"settings.systemSettings.screenshotSettings.embedCaptureMetadata.tip":
+		"在保存的 PNG、JPEG、WebP 图片中写入截图时间、截图方式、显示器和软件版本，AVIF 和 JPEG XL 图片暂不支持写入元数据",
+	"settings.systemSettings.screenshotSettings.captureMetadataIncludeWindowInfo":
+		"元数据包含窗口信息",
+	"settings.systemSettings.screenshotSettings.captureMetadataIncludeWindowInfo.tip":
+		"在元数据中写入截取窗口的标题和进程名称，分享图片时可能泄露隐私",
+	"settings.systemSettings.screenshotSettings.pngOptimizeLevel":
+		"PNG 优化等级",
+	"settings.systemSettings.screenshotSettings.pngOptimizeLevel.tip":
+		"保存 PNG 后在后台进行无损压缩以减小文件体积，等级越高越慢，为 0 时不优化",
+	"settings.systemSettings.screenshotSettings.hdrPeakNits": "HDR 峰值亮度",
+	"settings.systemSettings.screenshotSettings.hdrPeakNits.tip":
+		"高于该亮度（尼特）的 HDR 内容将被映射为白色，线性转换不使用该设置",
+	"settings.systemSettings.screenshotSettings.hdrPaperWhiteNits":
+		"HDR 纸白亮度",
+	"settings.systemSettings.screenshotSettings.hdrPaperWhiteNits.tip":
+		"该亮度（尼特）的内容将被映射为 SDR 白色，为 0 时使用 Windows 的 HDR 设置中的 SDR 内容亮度",
 	"settings.systemSettings.screenshotSettings.enableBrowserClipboard.tip":
 		"尝试使用浏览器接口快速写入剪贴板内容，如果出现无法复制到剪贴板的问题请禁用该功能",
 	"settings.systemSettings.screenshotSettings.historyValidDuration.day": "1 天",
@@ -588,6 +684,18 @@ export const settings = {
 	"settings.systemSettings.coreSettings.hotLoadPageCount.tip":
 		"通过热加载页面，实现固定剪贴板内容到屏幕、视频录制、全屏画布等功能的快速加载，但同时提高了内存的占用",
 	"settings.functionSettings.ocrSettings": "文本识别",
+	"settings.functionSettings.ocrSettings.ocrPreset": "识别参数",
+	"settings.functionSettings.ocrSettings.ocrPreset.tip":
+		"调整文本检测的阈值和图片放大倍数，识别效果不理想时可以尝试其他预设",
+	"settings.functionSettings.ocrSettings.ocrPreset.screenText": "屏幕文字",
+	"settings.functionSettings.ocrSettings.ocrPreset.document": "文档",
+	"settings.functionSettings.ocrSettings.ocrPreset.denseTable": "密集表格",
+	"settings.functionSettings.ocrSettings.ocrDetectBarcode": "识别二维码和条码",
+	"settings.functionSettings.ocrSettings.ocrDetectBarcode.tip":
+		"识别文本时同时识别图片中的二维码和条码，会增加识别耗时",
+	"settings.functionSettings.ocrSettings.ocrRecognizeTable": "识别表格",
+	"settings.functionSettings.ocrSettings.ocrRecognizeTable.tip":
+		"识别文本时同时识别表格结构，识别到表格后可以复制为 CSV、Markdown 等格式，会增加识别耗时",
 	"settings.functionSettings.ocrSettings.htmlVisionModel": "视觉理解模型",
 	"settings.functionSettings.ocrSettings.htmlVisionModel.tip":
 		"将图片转为 HTML 的视觉理解模型",
@@ -87,6 +87,12 @@ export const tools = {
 	"tools.captureHistory.switch": "编辑",
 	"tools.captureHistory.copy": "复制",
 	"tools.captureHistory.copyMonitor": "复制显示器画面",
+	"tools.captureHistory.exportDocument": "导出文本",
+	"tools.captureHistory.exportDocument.searchablePdf": "可搜索的 PDF",
+	"tools.captureHistory.exportDocument.hocr": "hOCR",
+	"tools.captureHistory.exportDocument.alto": "ALTO XML",
+	"tools.captureHistory.exportDocument.success": "导出成功",
+	"tools.captureHistory.exportDocument.failed": "导出失败",
 	"tools.captureHistory.delete": "删除",
 	"tools.captureHistory.delete.confirm": "确定要删除该截图吗？",
 	"tools.captureHistory.switchImage.tip": "右键切换“显示器画面”/“截图结果”预览",
@@ -99,6 +105,7 @@ export const tools = {
 	"tools.captureHistory.source.fixed": "固定到屏幕",
 	"tools.captureHistory.source.detectText": "文本识别",
 	"tools.captureHistory.source.fullScreen": "截取全屏",
+	"tools.captureHistory.source.regionWatch": "区域监控",
 	"tools.captureHistory.source.unknown": "未知",
 	"tools.chat.noSelectedModel": "请先选择模型",
 	"tools.translation.type.custom": "自定义",
@@ -109,4 +116,10 @@ export const tools = {
 	"tools.captureHistory.deleteSelected.confirm": "确定要删除选中的所有记录吗？",
 	"tools.captureHistory.clearAll": "清空所有记录",
 	"tools.captureHistory.clearAll.confirm": "确定要清空所有记录吗？",
+	"tools.captureHistory.batchOcr": "识别所有截图的文本并建立搜索索引",
+	"tools.captureHistory.batchOcr.cancel": "取消识别",
+	"tools.captureHistory.batchOcr.finished":
+		"索引已更新，新增 {indexed} 张，删除 {removed} 张，失败 {failed} 张",
+	"tools.captureHistory.searchText": "截图中的文字",
+	"tools.captureHistory.batchOcr.failed": "识别失败",
 };
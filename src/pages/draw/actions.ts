@@ -17,9 +17,15 @@ import {
 	AppSettingsGroup,
 	CloudSaveUrlFormat,
 } from "@/types/appSettings";
+import { CaptureMode } from "@/types/commands/screenshot";
 import { ImageFormat, type ImagePath } from "@/types/utils/file";
+import { createCaptureMetadata, getEncodeOptions } from "@/utils/appSettings";
 import { writeImageToClipboard } from "@/utils/clipboard";
-import { generateImageFileName, showImageDialog } from "@/utils/file";
+import {
+	commitImagePath,
+	generateImageFileName,
+	showImageDialog,
+} from "@/utils/file";
 import { appError } from "@/utils/log";
 import { getPlatform } from "@/utils/platform";
 import { randomString } from "@/utils/random";
@@ -182,6 +188,7 @@ export const getCanvas = async (
 
 /**
  * 保存截图到指定文件
+ * @param captureTime 截图时间，写入截图元数据，为空时使用当前时间
  */
 export const saveToFile = async (
 	appSettings: AppSettingsData,
@@ -189,6 +196,7 @@ export const saveToFile = async (
 	beforeSaveFile?: (filePath: string) => Promise<void>,
 	prevImageFormat?: ImageFormat,
 	fastSavePath?: ImagePath,
+	captureTime?: number,
 ) => {
 	const imagePath =
 		fastSavePath ?? (await showImageDialog(appSettings, prevImageFormat));
@@ -242,7 +250,14 @@ export const saveToFile = async (
 		return;
 	}
 
-	await saveFile(imagePath.filePath, imageData, imagePath.imageFormat);
+	await saveFile(
+		imagePath.filePath,
+		imageData,
+		imagePath.imageFormat,
+		getEncodeOptions(appSettings),
+		await createCaptureMetadata(appSettings, CaptureMode.Region, captureTime),
+	);
+	await commitImagePath(imagePath);
 };
 
 export const fixedToScreen = async (
@@ -19,14 +19,21 @@ import { useAppSettingsLoad } from "@/hooks/useAppSettingsLoad";
 import { withStatePublisher } from "@/hooks/useStatePublisher";
 import { useStateSubscriber } from "@/hooks/useStateSubscriber";
 import { type AppSettingsData, AppSettingsGroup } from "@/types/appSettings";
-import type {
-	CaptureFullScreenResult,
-	ElementRect,
-	ImageBuffer,
+import {
+	type CaptureFullScreenResult,
+	CaptureMode,
+	type ElementRect,
+	type FocusedWindowInfo,
+	type ImageBuffer,
 } from "@/types/commands/screenshot";
 import { DrawToolbarKeyEventKey } from "@/types/components/drawToolbar";
 import { DrawState } from "@/types/draw";
-import { getCorrectHdrColorAlgorithm } from "@/utils/appSettings";
+import {
+	getCaptureMetadataOptions,
+	getCorrectHdrColorAlgorithm,
+	getEncodeOptions,
+	getHdrToneMappingOptions,
+} from "@/utils/appSettings";
 import {
 	type CaptureHistoryItem,
 	CaptureHistorySource,
@@ -36,8 +43,11 @@ import {
 	CaptureHistory,
 	getCaptureHistoryImageAbsPath,
 } from "@/utils/captureHistory";
-import { getImagePathFromSettings } from "@/utils/file";
-import { appError } from "@/utils/log";
+import {
+	createFileNameTemplateContext,
+	getImagePathFromSettings,
+} from "@/utils/file";
+import { appError, appInfo } from "@/utils/log";
 import { ScreenshotType } from "@/utils/types";
 import {
 	CaptureEvent,
@@ -62,6 +72,7 @@ export type CaptureHistoryActionType = {
 		appState: Readonly<AppState> | undefined,
 		captureResult?: ArrayBuffer,
 		source?: CaptureHistorySource,
+		focusedWindowInfo?: FocusedWindowInfo,
 	) => Promise<void>;
 	switch: (captureHistoryId: string) => Promise<void>;
 	captureFullScreen: () => Promise<void>;
@@ -298,6 +309,7 @@ const CaptureHistoryControllerCore: React.FC<{
 			appState: Readonly<AppState> | undefined,
 			captureResult?: ArrayBuffer,
 			source?: CaptureHistorySource,
+			focusedWindowInfo?: FocusedWindowInfo,
 		) => {
 			if (!captureHistoryRef.current) {
 				appError(
@@ -326,6 +338,7 @@ const CaptureHistoryControllerCore: React.FC<{
 				selectRect,
 				captureResult,
 				source,
+				focusedWindowInfo,
 			);
 			captureHistoryListRef.current.push(captureHistoryItem);
 			resetCurrentIndex();
@@ -373,6 +386,11 @@ const CaptureHistoryControllerCore: React.FC<{
 				await getCaptureHistoryImageAbsPath(captureHistoryParams.file_name),
 				getCorrectHdrColorAlgorithm(appSettings),
 				appSettings[AppSettingsGroup.SystemScreenshot].correctColorFilter,
+				getEncodeOptions(appSettings),
+				getHdrToneMappingOptions(appSettings),
+				appSettings[AppSettingsGroup.SystemScreenshot].colorProfileMode,
+				getCaptureMetadataOptions(appSettings),
+				createFileNameTemplateContext(CaptureMode.FullScreen),
 			);
 			playCameraShutterSound();
 			captureFullScreenResult = await captureFullScreenResultPromise;
@@ -384,6 +402,11 @@ const CaptureHistoryControllerCore: React.FC<{
 			return;
 		}
 
+		// 重名时后端会在文件名后追加序号，以返回的路径为准
+		appInfo("[CaptureHistoryController] Saved full screen capture", {
+			filePath: captureFullScreenResult.file_path,
+		});
+
 		captureHistoryParams.selected_rect = captureFullScreenResult.monitor_rect;
 		const captureHistoryItemPromise = captureHistoryRef.current.save(
 			{
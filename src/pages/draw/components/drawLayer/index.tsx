@@ -253,6 +253,7 @@ const DrawLayerCore: React.FC<{
 					drawState === DrawState.ScanQrcode ||
 					drawState === DrawState.ExtraTools ||
 					drawState === DrawState.VideoRecord ||
+					drawState === DrawState.RegionWatch ||
 					drawState === DrawState.ScrollScreenshot
 				) {
 					drawCoreElement.style.pointerEvents = "none";
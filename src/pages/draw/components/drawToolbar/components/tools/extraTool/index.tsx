@@ -1,11 +1,15 @@
-import { ScanOutlined } from "@ant-design/icons";
+import { EyeOutlined, ScanOutlined } from "@ant-design/icons";
 import { Button, Flex, message, theme } from "antd";
 import { useCallback, useContext, useState } from "react";
 import { useIntl } from "react-intl";
 import { createVideoRecordWindow } from "@/commands/core";
+import { regionWatchStart } from "@/commands/ocr";
 import { DrawStatePublisher } from "@/components/drawCore/extra";
 import { VideoRecordIcon } from "@/components/icons";
-import { PLUGIN_ID_FFMPEG } from "@/constants/pluginService";
+import {
+	PLUGIN_ID_FFMPEG,
+	PLUGIN_ID_RAPID_OCR,
+} from "@/constants/pluginService";
 import {
 	AppSettingsActionContext,
 	AppSettingsPublisher,
@@ -19,6 +23,12 @@ import {
 	ExtraToolList,
 } from "@/types/appSettings";
 import { DrawState } from "@/types/draw";
+import {
+	getCaptureMetadataOptions,
+	getEncodeOptions,
+} from "@/utils/appSettings";
+import { getCaptureHistoryImageAbsPath } from "@/utils/captureHistory";
+import { getImagePathFromSettings } from "@/utils/file";
 import { getPlatform } from "@/utils/platform";
 import { getButtonTypeByState } from "../../../extra";
 import { ToolbarPopover } from "../../toolbarPopover";
@@ -37,7 +47,7 @@ export const ExtraTool: React.FC<{
 	const [lastActiveTool, setLastActiveTool] = useState<ExtraToolList>(
 		ExtraToolList.None,
 	);
-	useStateSubscriber(
+	const [getAppSettings] = useStateSubscriber(
 		AppSettingsPublisher,
 		useCallback((settings: AppSettingsData) => {
 			setLastActiveTool(settings[AppSettingsGroup.Cache].lastExtraTool);
@@ -87,6 +97,66 @@ export const ExtraTool: React.FC<{
 		}, 0);
 	}, [captureBoundingBoxInfoRef, finishCapture, intl, selectLayerActionRef]);
 
+	const { isReadyStatus } = usePluginServiceContext();
+
+	const executeRegionWatch = useCallback(async () => {
+		const captureBoundingBoxInfo = captureBoundingBoxInfoRef.current;
+		const selectRect = selectLayerActionRef.current?.getSelectRect();
+		if (!captureBoundingBoxInfo || !selectRect) {
+			return;
+		}
+
+		const monitorRect = captureBoundingBoxInfo.transformWindowRect(selectRect);
+		const appSettings = getAppSettings();
+		const screenshotSettings = appSettings[AppSettingsGroup.FunctionScreenshot];
+
+		try {
+			await regionWatchStart(
+				Date.now().toString(),
+				monitorRect.min_x,
+				monitorRect.min_y,
+				monitorRect.max_x,
+				monitorRect.max_y,
+				window.devicePixelRatio,
+				{
+					interval_ms: screenshotSettings.regionWatchIntervalMs,
+					cpu_budget: screenshotSettings.regionWatchCpuBudget / 100,
+					change_threshold: screenshotSettings.regionWatchChangeThreshold / 100,
+					// 未加载文本识别插件时只监控画面变化
+					text_pattern: isReadyStatus?.(PLUGIN_ID_RAPID_OCR)
+						? screenshotSettings.regionWatchTextPattern
+						: undefined,
+					notify: screenshotSettings.regionWatchNotify,
+					file_path: screenshotSettings.regionWatchSaveCapture
+						? (await getImagePathFromSettings(appSettings, "region-watch"))
+								?.filePath
+						: undefined,
+					capture_history_dir: await getCaptureHistoryImageAbsPath(""),
+				},
+				getEncodeOptions(appSettings),
+				getCaptureMetadataOptions(appSettings),
+			);
+		} catch (error) {
+			message.error(`${error}`);
+			return;
+		}
+
+		message.success(
+			intl.formatMessage({ id: "draw.extraTool.regionWatch.started" }),
+		);
+
+		setTimeout(() => {
+			finishCapture();
+		}, 0);
+	}, [
+		captureBoundingBoxInfoRef,
+		finishCapture,
+		getAppSettings,
+		intl,
+		isReadyStatus,
+		selectLayerActionRef,
+	]);
+
 	const updateLastActiveTool = useCallback(
 		(value: ExtraToolList) => {
 			updateAppSettings(
@@ -109,7 +179,8 @@ export const ExtraTool: React.FC<{
 				if (
 					drawState === DrawState.ExtraTools ||
 					drawState === DrawState.ScanQrcode ||
-					drawState === DrawState.VideoRecord
+					drawState === DrawState.VideoRecord ||
+					drawState === DrawState.RegionWatch
 				) {
 					if (drawState === DrawState.ScanQrcode) {
 						executeScanQrcode();
@@ -117,6 +188,9 @@ export const ExtraTool: React.FC<{
 					} else if (drawState === DrawState.VideoRecord) {
 						executeVideoRecord();
 						updateLastActiveTool(ExtraToolList.VideoRecord);
+					} else if (drawState === DrawState.RegionWatch) {
+						executeRegionWatch();
+						updateLastActiveTool(ExtraToolList.RegionWatch);
 					}
 
 					setEnabled(true);
@@ -125,7 +199,12 @@ export const ExtraTool: React.FC<{
 					setEnabled(false);
 				}
 			},
-			[executeScanQrcode, executeVideoRecord, updateLastActiveTool],
+			[
+				executeScanQrcode,
+				executeVideoRecord,
+				executeRegionWatch,
+				updateLastActiveTool,
+			],
 		),
 	);
 
@@ -155,7 +234,18 @@ export const ExtraTool: React.FC<{
 		/>
 	);
 
-	const { isReadyStatus } = usePluginServiceContext();
+	const regionWatchButton = (
+		<Button
+			icon={<EyeOutlined />}
+			title={intl.formatMessage({ id: "draw.extraTool.regionWatch" })}
+			type={getButtonTypeByState(activeTool === ExtraToolList.RegionWatch)}
+			key="regionWatch"
+			onClick={() => {
+				onToolClickAction(DrawState.RegionWatch);
+			}}
+			disabled={disable}
+		/>
+	);
 
 	let mainToolbarButton = isReadyStatus?.(PLUGIN_ID_FFMPEG)
 		? videoRecordButton
@@ -168,16 +258,20 @@ export const ExtraTool: React.FC<{
 		isReadyStatus?.(PLUGIN_ID_FFMPEG)
 	) {
 		mainToolbarButton = videoRecordButton;
+	} else if (lastActiveTool === ExtraToolList.RegionWatch) {
+		mainToolbarButton = regionWatchButton;
 	}
 
 	return (
 		<ToolbarPopover
-			trigger={isReadyStatus?.(PLUGIN_ID_FFMPEG) ? "hover" : []}
+			trigger="hover"
 			content={
 				<Flex align="center" gap={token.paddingXS} className="popover-toolbar">
 					{scanQrcodeButton}
 
 					{isReadyStatus?.(PLUGIN_ID_FFMPEG) && videoRecordButton}
+
+					{regionWatchButton}
 				</Flex>
 			}
 		>
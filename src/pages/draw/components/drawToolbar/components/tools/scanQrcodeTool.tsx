@@ -9,16 +9,40 @@ import {
 	useState,
 } from "react";
 import { useIntl } from "react-intl";
+import { barcodeDetect } from "@/commands/ocr";
 import { DrawStatePublisher } from "@/components/drawCore/extra";
 import { AntdContext } from "@/contexts/antdContext";
 import { useHotkeysApp } from "@/hooks/useHotkeysApp";
 import { useStateSubscriber } from "@/hooks/useStateSubscriber";
 import { DrawContext } from "@/pages/draw/types";
 import { DrawState } from "@/types/draw";
+import { appError } from "@/utils/log";
 import { getPlatformValue } from "@/utils/platform";
 import { zIndexs } from "@/utils/zIndex";
 import { useMonitorRect } from "../../../statusBar";
 
+const scanQrcodeByWechat = async (
+	canvas: HTMLCanvasElement,
+): Promise<string | undefined> => {
+	// biome-ignore lint/suspicious/noExplicitAny: 方便 CDN 加载
+	let QrCodeScanner: any;
+	if (import.meta.env.PUBLIC_ONLINE_STATUS === "true") {
+		QrCodeScanner = await import(
+			// @ts-expect-error
+			"https://snowshot.top/npm/qr-scanner-wechat/dist/index.mjs"
+		);
+	} else {
+		QrCodeScanner = await import(
+			// @ts-expect-error
+			"qr-scanner-wechat"
+		);
+	}
+
+	await QrCodeScanner.ready();
+	const result = await QrCodeScanner.scan(canvas);
+	return result.text;
+};
+
 const ScanQrcodeToolCore: React.FC = () => {
 	const intl = useIntl();
 
@@ -65,32 +89,31 @@ const ScanQrcodeToolCore: React.FC = () => {
 
 		tempCtx.drawImage(imageBitmap, 0, 0);
 
-		// biome-ignore lint/suspicious/noExplicitAny: 方便 CDN 加载
-		let QrCodeScanner: any;
-		if (import.meta.env.PUBLIC_ONLINE_STATUS === "true") {
-			QrCodeScanner = await import(
-				// @ts-expect-error
-				"https://snowshot.top/npm/qr-scanner-wechat/dist/index.mjs"
-			);
-		} else {
-			QrCodeScanner = await import(
-				// @ts-expect-error
-				"qr-scanner-wechat"
-			);
-		}
+		let scanResult = "";
 		try {
-			await QrCodeScanner.ready();
-			const result = await QrCodeScanner.scan(tempCanvas);
-			setQrCode(result.text ?? "");
-			if (!result.text) {
-				message.warning(
-					intl.formatMessage({
-						id: "draw.extraTool.scanQrcode.error",
-					}),
-				);
+			// 优先使用后端识别，支持 DataMatrix、EAN/UPC 和 Code128 等条码
+			const imageBlob = await new Promise<Blob | null>((resolve) => {
+				tempCanvas.toBlob(resolve, "image/png", 1);
+			});
+			if (imageBlob) {
+				const barcodes = await barcodeDetect(await imageBlob.arrayBuffer());
+				scanResult = barcodes.map((barcode) => barcode.text).join("\n");
 			}
 		} catch (error) {
-			console.error(error);
+			appError("[ScanQrcodeTool] barcodeDetect error", error);
+		}
+
+		// 后端未识别到时使用微信的二维码识别，对模糊和变形的二维码效果更好
+		if (!scanResult) {
+			try {
+				scanResult = (await scanQrcodeByWechat(tempCanvas)) ?? "";
+			} catch (error) {
+				console.error(error);
+			}
+		}
+
+		setQrCode(scanResult);
+		if (!scanResult) {
 			message.warning(
 				intl.formatMessage({
 					id: "draw.extraTool.scanQrcode.error",
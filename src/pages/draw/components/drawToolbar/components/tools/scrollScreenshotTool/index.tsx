@@ -43,7 +43,10 @@ import { DrawContext } from "@/pages/draw/types";
 import { AppSettingsGroup } from "@/types/appSettings";
 import type { ElementRect } from "@/types/commands/screenshot";
 import { DrawState } from "@/types/draw";
-import { getCorrectHdrColorAlgorithm } from "@/utils/appSettings";
+import {
+	getCorrectHdrColorAlgorithm,
+	getHdrToneMappingOptions,
+} from "@/utils/appSettings";
 import { appError, appWarn } from "@/utils/log";
 import { getPlatform } from "@/utils/platform";
 import { zIndexs } from "@/utils/zIndex";
@@ -405,6 +408,8 @@ export const ScrollScreenshot: React.FC<{
 				rect.max_y,
 				getCorrectHdrColorAlgorithm(getAppSettings()),
 				getAppSettings()[AppSettingsGroup.SystemScreenshot].correctColorFilter,
+				getHdrToneMappingOptions(getAppSettings()),
+				getAppSettings()[AppSettingsGroup.SystemScreenshot].colorProfileMode,
 			);
 
 			handleCaptureImageListDebounce();
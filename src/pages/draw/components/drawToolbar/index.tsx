@@ -486,6 +486,7 @@ const DrawToolbarCore: React.FC<DrawToolbarProps> = ({
 					}
 					break;
 				case DrawState.VideoRecord:
+				case DrawState.RegionWatch:
 				case DrawState.ScanQrcode:
 				case DrawState.ExtraTools:
 					break;
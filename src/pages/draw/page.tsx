@@ -25,7 +25,11 @@ import {
 } from "@/commands/core";
 import { setCaptureState } from "@/commands/globalSate";
 import { listenKeyStart, listenKeyStop } from "@/commands/listenKey";
-import { captureAllMonitors, switchAlwaysOnTop } from "@/commands/screenshot";
+import {
+	captureAllMonitors,
+	getFocusedWindowInfo,
+	switchAlwaysOnTop,
+} from "@/commands/screenshot";
 import {
 	scrollScreenshotClear,
 	scrollScreenshotGetImageData,
@@ -59,13 +63,20 @@ import { withStatePublisher } from "@/hooks/useStatePublisher";
 import { useStateSubscriber } from "@/hooks/useStateSubscriber";
 import { AppSettingsGroup, DoubleClickAction } from "@/types/appSettings";
 import {
+	CaptureMode,
 	type ElementRect,
+	type FocusedWindowInfo,
 	type ImageBuffer,
 	ImageBufferType,
 	ImageEncoder,
 } from "@/types/commands/screenshot";
 import { DrawState } from "@/types/draw";
-import { getCorrectHdrColorAlgorithm } from "@/utils/appSettings";
+import {
+	getCaptureMetadataOptions,
+	getCorrectHdrColorAlgorithm,
+	getEncodeOptions,
+	getHdrToneMappingOptions,
+} from "@/utils/appSettings";
 import {
 	type CaptureHistoryItem,
 	CaptureHistorySource,
@@ -76,6 +87,7 @@ import {
 	writeTextToClipboard,
 } from "@/utils/clipboard";
 import {
+	commitImagePath,
 	getImageFormat,
 	getImagePathFromSettings,
 	showImageDialog,
@@ -213,6 +225,12 @@ const DrawPageCore: React.FC<{
 
 	// 状态
 	const drawPageStateRef = useRef<DrawPageState>(DrawPageState.Init);
+	/** 开始截图的时间，保存时写入截图元数据 */
+	const captureTimeRef = useRef<number | undefined>(undefined);
+	/** 开始截图时的焦点窗口，保存到截图历史 */
+	const focusedWindowInfoRef = useRef<FocusedWindowInfo | undefined>(
+		undefined,
+	);
 	const mousePositionRef = useRef<MousePosition>(new MousePosition(0, 0));
 	const [getAppSettings] = useStateSubscriber(AppSettingsPublisher, undefined);
 	const { updateAppSettings } = useContext(AppSettingsActionContext);
@@ -602,6 +620,9 @@ const DrawPageCore: React.FC<{
 					getCorrectHdrColorAlgorithm(getAppSettings(), true),
 					getAppSettings()[AppSettingsGroup.SystemScreenshot]
 						.correctColorFilter,
+					getHdrToneMappingOptions(getAppSettings()),
+					getAppSettings()[AppSettingsGroup.SystemScreenshot]
+						.colorProfileMode,
 				).catch((error) => {
 					appError("[DrawPageCore] captureAllMonitors error", error);
 					return undefined;
@@ -634,6 +655,18 @@ const DrawPageCore: React.FC<{
 			params: { windowId?: string; captureHistoryId?: string },
 		) => {
 			capturingRef.current = true;
+			captureTimeRef.current = Date.now();
+			// 需要在截图窗口显示前获取焦点窗口
+			focusedWindowInfoRef.current = undefined;
+			if (excuteScreenshotType !== ScreenshotType.SwitchCaptureHistory) {
+				getFocusedWindowInfo()
+					.then((focusedWindowInfo) => {
+						focusedWindowInfoRef.current = focusedWindowInfo;
+					})
+					.catch((error) => {
+						appWarn("[DrawPageCore] getFocusedWindowInfo error", error);
+					});
+			}
 			setCaptureStateAction(true);
 			drawToolbarActionRef.current?.setEnable(false);
 
@@ -812,6 +845,7 @@ const DrawPageCore: React.FC<{
 				appState,
 				captureResultImageBuffer,
 				source,
+				focusedWindowInfoRef.current,
 			);
 		},
 		[getAppSettings, updateAppSettings, getScreenshotType],
@@ -840,7 +874,13 @@ const DrawPageCore: React.FC<{
 					(await getImagePathFromSettings(
 						fastSave ? getAppSettings() : undefined,
 						"fast",
-					)) ?? (await showImageDialog(getAppSettings()));
+						CaptureMode.Scroll,
+					)) ??
+					(await showImageDialog(
+						getAppSettings(),
+						undefined,
+						CaptureMode.Scroll,
+					));
 
 				if (!imagePath) {
 					return;
@@ -860,7 +900,15 @@ const DrawPageCore: React.FC<{
 					);
 				}
 
-				scrollScreenshotSaveToFile(imagePath.filePath)
+				scrollScreenshotSaveToFile(
+					imagePath.filePath,
+					undefined,
+					undefined,
+					getEncodeOptions(getAppSettings()),
+					getCaptureMetadataOptions(getAppSettings()),
+					captureTimeRef.current,
+				)
+					.then(() => commitImagePath(imagePath))
 					.catch((error) => {
 						appError("[DrawPageCore] scrollScreenshotSaveToFile error", error);
 					})
@@ -917,6 +965,7 @@ const DrawPageCore: React.FC<{
 				fastSave
 					? await getImagePathFromSettings(getAppSettings(), "fast")
 					: undefined,
+				captureTimeRef.current,
 			);
 		},
 		[
@@ -1111,9 +1160,11 @@ const DrawPageCore: React.FC<{
 
 			saveCaptureHistory(undefined, CaptureHistorySource.ScrollScreenshotCopy);
 
-			const filePath = (
-				await getImagePathFromSettings(getAppSettings(), "auto")
-			)?.filePath;
+			const imagePath = await getImagePathFromSettings(
+				getAppSettings(),
+				"auto",
+				CaptureMode.Scroll,
+			);
 			Promise.all([
 				scrollScreenshotSaveToClipboard().catch((error) => {
 					appError(
@@ -1121,13 +1172,22 @@ const DrawPageCore: React.FC<{
 						error,
 					);
 				}),
-				enableAutoSave && filePath
-					? scrollScreenshotSaveToFile(filePath).catch((error) => {
-							appError(
-								"[DrawPageCore] scrollScreenshotSaveToFile error",
-								error,
-							);
-						})
+				enableAutoSave && imagePath
+					? scrollScreenshotSaveToFile(
+							imagePath.filePath,
+							undefined,
+							undefined,
+							getEncodeOptions(getAppSettings()),
+							getCaptureMetadataOptions(getAppSettings()),
+							captureTimeRef.current,
+						)
+							.then(() => commitImagePath(imagePath))
+							.catch((error) => {
+								appError(
+									"[DrawPageCore] scrollScreenshotSaveToFile error",
+									error,
+								);
+							})
 					: Promise.resolve(),
 			]).finally(() => {
 				scrollScreenshotClear();
@@ -1260,6 +1320,7 @@ const DrawPageCore: React.FC<{
 						undefined,
 						undefined,
 						imagePath,
+						captureTimeRef.current,
 					);
 					await writeFilePathToClipboard(imagePath.filePath);
 				}
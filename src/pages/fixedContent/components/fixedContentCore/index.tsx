@@ -72,8 +72,9 @@ import {
 	type CommonKeyEventValue,
 } from "@/types/core/commonKeyEvent";
 import { ImageFormat } from "@/types/utils/file";
+import { getEncodeOptions } from "@/utils/appSettings";
 import { writeHtmlToClipboard, writeTextToClipboard } from "@/utils/clipboard";
-import { generateImageFileName } from "@/utils/file";
+import { previewImageFileName } from "@/utils/file";
 import { formatKey } from "@/utils/format";
 import { appError } from "@/utils/log";
 import { MousePosition } from "@/utils/mousePosition";
@@ -1203,7 +1204,7 @@ const FixedContentCoreInner: React.FC<{
 					extensions: ["png"],
 				},
 			],
-			defaultPath: generateImageFileName(
+			defaultPath: await previewImageFileName(
 				getAppSettings()[AppSettingsGroup.FunctionOutput]
 					.manualSaveFileNameFormat,
 			),
@@ -1220,7 +1221,12 @@ const FixedContentCoreInner: React.FC<{
 			return;
 		}
 
-		await saveFile(filePath, await canvasBlob.arrayBuffer(), ImageFormat.PNG);
+		await saveFile(
+			filePath,
+			await canvasBlob.arrayBuffer(),
+			ImageFormat.PNG,
+			getEncodeOptions(getAppSettings()),
+		);
 	}, [getAppSettings, renderToBlob, isThumbnailRef]);
 
 	const switchSelectTextCore = useCallback(async () => {
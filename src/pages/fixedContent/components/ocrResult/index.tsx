@@ -13,7 +13,13 @@ import {
 	useState,
 } from "react";
 import { FormattedMessage, useIntl } from "react-intl";
-import { ocrDetect, ocrDetectWithSharedBuffer } from "@/commands/ocr";
+import {
+	barcodeGenerateQrCode,
+	ocrCancel,
+	ocrDetect,
+	ocrDetectWithSharedBuffer,
+	ocrExportTable,
+} from "@/commands/ocr";
 import { createWebViewSharedBufferChannel } from "@/commands/webview";
 import { PLUGIN_ID_RAPID_OCR } from "@/constants/pluginService";
 import { AntdContext } from "@/contexts/antdContext";
@@ -34,9 +40,17 @@ import { CUSTOM_MODEL_PREFIX, MarkdownContent } from "@/pages/tools/chat/page";
 import { appFetch, getUrl } from "@/services/tools";
 import { getChatModelsWithCache } from "@/services/tools/chat";
 import { AppSettingsGroup, type ChatApiConfig } from "@/types/appSettings";
-import type { OcrDetectResult } from "@/types/commands/ocr";
+import {
+	type OcrDetectResult,
+	type OcrOptions,
+	OcrTableExportFormat,
+} from "@/types/commands/ocr";
 import type { ElementRect } from "@/types/commands/screenshot";
-import { writeHtmlToClipboard, writeTextToClipboard } from "@/utils/clipboard";
+import {
+	writeHtmlToClipboard,
+	writeImageToClipboard,
+	writeTextToClipboard,
+} from "@/utils/clipboard";
 import { appError } from "@/utils/log";
 import { getPlatformValue } from "@/utils/platform";
 import { randomString } from "@/utils/random";
@@ -48,6 +62,10 @@ import {
 
 // 定义角度阈值常量（以度为单位）
 const ROTATION_THRESHOLD = 3; // 小于3度的旋转被视为误差，不进行旋转
+/** 复制为二维码时的图片边长 */
+const QR_CODE_SIZE = 512;
+/** 右键菜单中显示的条码内容的最大长度 */
+const BARCODE_MENU_TEXT_MAX_LENGTH = 32;
 
 export type AppOcrResult = {
 	result: OcrDetectResult;
@@ -93,8 +111,88 @@ export type OcrResultActionType = {
 	convertImageToMarkdown: (canvas: HTMLCanvasElement) => Promise<void>;
 };
 
+const isCjkChar = (char: string | undefined) =>
+	char !== undefined &&
+	/[\u3000-\u30ff\u3400-\u4dbf\u4e00-\u9fff\uf900-\ufaff\uff00-\uffef]/.test(
+		char,
+	);
+
+/** 拼接同一行的文本，中文、日文之间不添加空格 */
+const joinOcrLineText = (textList: string[]) => {
+	return textList.reduce((result, text) => {
+		const trimmedText = text.trim();
+		if (!trimmedText) {
+			return result;
+		}
+
+		if (!result) {
+			return trimmedText;
+		}
+
+		return isCjkChar(result.at(-1)) && isCjkChar(trimmedText[0])
+			? result + trimmedText
+			: `${result} ${trimmedText}`;
+	}, "");
+};
+
+/**
+ * 按阅读顺序排列文本块，未包含在版面结构中的文本块排在最后
+ */
+const getOcrBlocksInReadingOrder = (ocrResult: OcrDetectResult) => {
+	if (!ocrResult.layout) {
+		return ocrResult.text_blocks;
+	}
+
+	const indices = ocrResult.layout.columns.flatMap((column) =>
+		column.paragraphs.flatMap((paragraph) =>
+			paragraph.lines.flatMap((line) => line.block_indices),
+		),
+	);
+	const indexSet = new Set(indices);
+	ocrResult.text_blocks.forEach((_, index) => {
+		if (!indexSet.has(index)) {
+			indices.push(index);
+		}
+	});
+
+	return indices
+		.map((index) => ocrResult.text_blocks[index])
+		.filter((block) => block !== undefined);
+};
+
+const convertOcrBlocksToText = (ocrResult: OcrDetectResult) => {
+	if (!ocrResult.layout) {
+		return ocrResult.text_blocks.map((block) => block.text).join("\n");
+	}
+
+	return ocrResult.layout.columns
+		.flatMap((column) => column.paragraphs)
+		.map((paragraph) =>
+			paragraph.lines
+				.map((line) =>
+					joinOcrLineText(
+						line.block_indices.map(
+							(index) => ocrResult.text_blocks[index]?.text ?? "",
+						),
+					),
+				)
+				.join("\n"),
+		)
+		.join("\n\n");
+};
+
+/**
+ * 按版面结构转换为文本，段落之间以空行分隔
+ * 从 text_blocks 中读取文本，翻译后的结果也能保持原有的结构
+ * 识别到的二维码、条码内容作为单独的段落排在最后
+ */
 export const covertOcrResultToText = (ocrResult: OcrDetectResult) => {
-	return ocrResult.text_blocks.map((block) => block.text).join("\n");
+	return [
+		convertOcrBlocksToText(ocrResult),
+		...(ocrResult.barcodes ?? []).map((barcode) => barcode.text),
+	]
+		.filter((text) => text)
+		.join("\n\n");
 };
 
 export enum OcrResultType {
@@ -310,8 +408,9 @@ export const OcrResult: React.FC<{
 				return;
 			}
 
+			// 按阅读顺序添加文本元素，选中复制时保持文本的顺序
 			await Promise.all(
-				ocrResult.text_blocks.map(async (block) => {
+				getOcrBlocksInReadingOrder(ocrResult).map(async (block) => {
 					if (Number.isNaN(block.text_score) || block.text_score < 0.3) {
 						return null;
 					}
@@ -469,6 +568,8 @@ export const OcrResult: React.FC<{
 			canvas: HTMLCanvasElement,
 			scaleFactor: number,
 			detectAngle: boolean,
+			options: OcrOptions,
+			requestId: string,
 		): Promise<OcrDetectResult | undefined> => {
 			const sharedBufferChannelId = `ocrDetectByCanvas:${Date.now()}:${randomString(8)}`;
 			const getWebViewSharedBufferPromise = getWebViewSharedBuffer(
@@ -516,43 +617,79 @@ export const OcrResult: React.FC<{
 				sharedBufferChannelId,
 				scaleFactor,
 				detectAngle,
+				undefined,
+				options,
+				requestId,
 			);
 		},
 		[],
 	);
 
+	/** 排队中的识别请求，开始新的识别或清除结果时取消 */
+	const pendingOcrRequestIdRef = useRef<string | undefined>(undefined);
+	const cancelPendingOcrRequest = useCallback(() => {
+		if (!pendingOcrRequestIdRef.current) {
+			return;
+		}
+
+		ocrCancel(pendingOcrRequestIdRef.current);
+		pendingOcrRequestIdRef.current = undefined;
+	}, []);
+
 	const ocrDetectByCanvas = useCallback(
 		async (
 			canvas: HTMLCanvasElement,
 			scaleFactor: number,
 			detectAngle: boolean,
 		): Promise<OcrDetectResult | undefined> => {
-			const ocrResultWithSharedBuffer = await ocrDetectWithSharedBufferAction(
-				canvas,
-				scaleFactor,
-				detectAngle,
-			);
+			const ocrSettings = getAppSettings()[AppSettingsGroup.FunctionOcr];
+			const options: OcrOptions = {
+				preset: ocrSettings.ocrPreset,
+				recognize_table: ocrSettings.ocrRecognizeTable,
+				detect_barcode: ocrSettings.ocrDetectBarcode,
+			};
 
-			if (ocrResultWithSharedBuffer) {
-				return ocrResultWithSharedBuffer;
-			}
+			cancelPendingOcrRequest();
+			const requestId = `ocrResult:${Date.now()}:${randomString(8)}`;
+			pendingOcrRequestIdRef.current = requestId;
 
-			const imageBlob = await new Promise<Blob | null>((resolve) => {
-				canvas.toBlob(resolve, "image/png", 1);
-			});
+			try {
+				const ocrResultWithSharedBuffer = await ocrDetectWithSharedBufferAction(
+					canvas,
+					scaleFactor,
+					detectAngle,
+					options,
+					requestId,
+				);
 
-			if (!imageBlob) {
-				return undefined;
-			}
+				if (ocrResultWithSharedBuffer) {
+					return ocrResultWithSharedBuffer;
+				}
 
-			const ocrResult = await ocrDetect(
-				await imageBlob.arrayBuffer(),
-				scaleFactor,
-				detectAngle,
-			);
-			return ocrResult;
+				const imageBlob = await new Promise<Blob | null>((resolve) => {
+					canvas.toBlob(resolve, "image/png", 1);
+				});
+
+				if (!imageBlob) {
+					return undefined;
+				}
+
+				const ocrResult = await ocrDetect(
+					await imageBlob.arrayBuffer(),
+					scaleFactor,
+					detectAngle,
+					undefined,
+					options,
+					requestId,
+				);
+				return ocrResult;
+			} finally {
+				if (pendingOcrRequestIdRef.current === requestId) {
+					pendingOcrRequestIdRef.current = undefined;
+				}
+			}
 		},
-		[ocrDetectWithSharedBufferAction],
+		[ocrDetectWithSharedBufferAction, getAppSettings, cancelPendingOcrRequest],
 	);
 
 	/** 请求 ID，避免 OCR 检测中切换工具后仍然触发 OCR 结果 */
@@ -793,6 +930,8 @@ export const OcrResult: React.FC<{
 		}
 
 		const appWindow = getCurrentWindow();
+		const table = ocrResultRef.current?.result.table;
+		const barcodes = ocrResultRef.current?.result.barcodes ?? [];
 		const result = await Menu.new({
 			items: [
 				{
@@ -810,12 +949,78 @@ export const OcrResult: React.FC<{
 						}
 					},
 				},
+				{
+					id: `${appWindow.label}-generateQrCode`,
+					text: intl.formatMessage({ id: "draw.generateQrCode" }),
+					action: async () => {
+						if (!selectedTextRef.current) {
+							return;
+						}
+
+						try {
+							const image = await barcodeGenerateQrCode(
+								selectedTextRef.current.text,
+								QR_CODE_SIZE,
+							);
+							await writeImageToClipboard(image);
+						} catch (error) {
+							appError("[OcrResult] barcodeGenerateQrCode error", error);
+						}
+					},
+				},
+				// 识别到二维码、条码时，可以直接复制其中的内容
+				...(barcodes.length > 0
+					? [
+							{
+								id: `${appWindow.label}-copyBarcode`,
+								text: intl.formatMessage({ id: "draw.copyBarcode" }),
+								items: barcodes.map((barcode, index) => ({
+									id: `${appWindow.label}-copyBarcode-${index}`,
+									text:
+										barcode.text.length > BARCODE_MENU_TEXT_MAX_LENGTH
+											? `${barcode.text.slice(0, BARCODE_MENU_TEXT_MAX_LENGTH)}…`
+											: barcode.text,
+									action: async () => {
+										writeTextToClipboard(barcode.text);
+									},
+								})),
+							},
+						]
+					: []),
+				// 识别到表格时，可以按表格的结构复制
+				...(table
+					? [
+							{
+								id: `${appWindow.label}-copyTable`,
+								text: intl.formatMessage({ id: "draw.copyTable" }),
+								items: [
+									OcrTableExportFormat.Csv,
+									OcrTableExportFormat.Tsv,
+									OcrTableExportFormat.Markdown,
+									OcrTableExportFormat.Html,
+								].map((format) => ({
+									id: `${appWindow.label}-copyTable-${format}`,
+									text: intl.formatMessage({
+										id: `draw.copyTable.${format.toLowerCase()}`,
+									}),
+									action: async () => {
+										const text = await ocrExportTable(table, format);
+										if (format === OcrTableExportFormat.Html) {
+											writeHtmlToClipboard(text);
+										} else {
+											writeTextToClipboard(text);
+										}
+									},
+								})),
+							},
+						]
+					: []),
 			],
 		});
 		menuRef.current = result;
 
 		return result;
-	}, [disabled, intl]);
+	}, [disabled, intl, ocrResultRef]);
 
 	useEffect(() => {
 		const appWindow = getCurrentWindow();
@@ -1243,6 +1448,7 @@ export const OcrResult: React.FC<{
 			setEnable,
 			setScale,
 			clear: () => {
+				cancelPendingOcrRequest();
 				setTextContainerContent("");
 				if (textContainerElementRef.current) {
 					textContainerElementRef.current.innerHTML = "";
@@ -1372,6 +1578,7 @@ export const OcrResult: React.FC<{
 			convertImageToVisionModelFormat,
 			visionModelHtmlResultRef,
 			visionModelMarkdownResultRef,
+			cancelPendingOcrRequest,
 		],
 	);
 
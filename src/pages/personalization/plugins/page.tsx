@@ -3,12 +3,12 @@
 import { DeleteOutlined, PlusOutlined, SyncOutlined } from "@ant-design/icons";
 import { openUrl } from "@tauri-apps/plugin-opener";
 import { Badge, Button, List } from "antd";
-import { useMemo } from "react";
+import { useEffect, useMemo, useState } from "react";
 import { FormattedMessage, useIntl } from "react-intl";
 import { pluginInstallPlugin, pluginUninstallPlugin } from "@/commands/plugin";
 import {
+	getOcrModelPluginsWithCache,
 	PLUGIN_ID_FFMPEG,
-	PLUGIN_ID_RAPID_OCR,
 } from "@/constants/pluginService";
 import { usePluginServiceContext } from "@/contexts/pluginServiceContext";
 import { PluginStatus } from "@/types/commands/plugin";
@@ -18,6 +18,13 @@ export const PluginsPage = () => {
 	const intl = useIntl();
 	const { pluginConfig, pluginStatus } = usePluginServiceContext();
 
+	const [ocrPluginIds, setOcrPluginIds] = useState<string[]>([]);
+	useEffect(() => {
+		getOcrModelPluginsWithCache().then((ocrModelPlugins) => {
+			setOcrPluginIds(ocrModelPlugins.map((plugin) => plugin.id));
+		});
+	}, []);
+
 	const pluginList = useMemo(() => {
 		return Array.from(pluginConfig?.plugins.values() || []).map((plugin) => {
 			let link: string | undefined;
@@ -25,8 +32,10 @@ export const PluginsPage = () => {
 				case PLUGIN_ID_FFMPEG:
 					link = "https://ffmpeg.org/";
 					break;
-				case PLUGIN_ID_RAPID_OCR:
-					link = "https://github.com/RapidAI/RapidOCR";
+				default:
+					if (ocrPluginIds.includes(plugin.id)) {
+						link = "https://github.com/RapidAI/RapidOCR";
+					}
 					break;
 			}
 
@@ -43,7 +52,7 @@ export const PluginsPage = () => {
 				status: pluginStatus?.[plugin.id]?.status || PluginStatus.NotInstalled,
 			};
 		});
-	}, [intl, pluginConfig?.plugins, pluginStatus]);
+	}, [intl, ocrPluginIds, pluginConfig?.plugins, pluginStatus]);
 
 	const convertPluginStatusToBadgeStatus = (status: PluginStatus) => {
 		switch (status) {
@@ -0,0 +1,46 @@
+import { ProFormText } from "@ant-design/pro-form";
+import { Col } from "antd";
+import type React from "react";
+import { useEffect, useState } from "react";
+import type { CaptureMode } from "@/types/commands/screenshot";
+import { previewImageFileName } from "@/utils/file";
+
+/**
+ * 预览文件名模板，窗口和显示器相关的变量使用占位值
+ */
+export const FileNameTemplatePreview: React.FC<{
+	template: string;
+	label: React.ReactNode;
+	captureMode?: CaptureMode;
+}> = ({ template, label, captureMode }) => {
+	const [text, setText] = useState("");
+
+	useEffect(() => {
+		let canceled = false;
+
+		previewImageFileName(template, captureMode).then((result) => {
+			if (canceled) {
+				return;
+			}
+
+			setText(result);
+		});
+
+		return () => {
+			canceled = true;
+		};
+	}, [template, captureMode]);
+
+	return (
+		<Col span={24}>
+			<ProFormText
+				layout="horizontal"
+				readonly
+				label={label}
+				fieldProps={{
+					value: text,
+				}}
+			/>
+		</Col>
+	);
+};
@@ -73,6 +73,8 @@ import {
 	TrayIconClickAction,
 	VideoMaxSize,
 } from "@/types/appSettings";
+import { OcrPreset } from "@/types/commands/ocr";
+import { CaptureMode } from "@/types/commands/screenshot";
 import { DrawState } from "@/types/draw";
 import { ImageFormat } from "@/types/utils/file";
 import {
@@ -80,6 +82,7 @@ import {
 	getImageSaveDirectory,
 	getVideoRecordSaveDirectory,
 } from "@/utils/file";
+import { FileNameTemplatePreview } from "./components/fileNameTemplatePreview";
 import { TestChat } from "./components/testChat";
 import { TranslationConfig } from "./components/translationConfig";
 
@@ -632,6 +635,59 @@ export const FunctionSettingsPage = () => {
 				}),
 				value: OcrModel.RapidOcrV5,
 			},
+			{
+				label: intl.formatMessage({
+					id: "settings.systemSettings.screenshotSettings.ocrModel.rapidOcrJapanese",
+				}),
+				value: OcrModel.RapidOcrJapanese,
+			},
+			{
+				label: intl.formatMessage({
+					id: "settings.systemSettings.screenshotSettings.ocrModel.rapidOcrKorean",
+				}),
+				value: OcrModel.RapidOcrKorean,
+			},
+			{
+				label: intl.formatMessage({
+					id: "settings.systemSettings.screenshotSettings.ocrModel.rapidOcrLatin",
+				}),
+				value: OcrModel.RapidOcrLatin,
+			},
+			{
+				label: intl.formatMessage({
+					id: "settings.systemSettings.screenshotSettings.ocrModel.rapidOcrCyrillic",
+				}),
+				value: OcrModel.RapidOcrCyrillic,
+			},
+			{
+				label: intl.formatMessage({
+					id: "settings.systemSettings.screenshotSettings.ocrModel.rapidOcrArabic",
+				}),
+				value: OcrModel.RapidOcrArabic,
+			},
+		];
+	}, [intl]);
+
+	const ocrPresetOptions = useMemo(() => {
+		return [
+			{
+				label: intl.formatMessage({
+					id: "settings.functionSettings.ocrSettings.ocrPreset.screenText",
+				}),
+				value: OcrPreset.ScreenText,
+			},
+			{
+				label: intl.formatMessage({
+					id: "settings.functionSettings.ocrSettings.ocrPreset.document",
+				}),
+				value: OcrPreset.Document,
+			},
+			{
+				label: intl.formatMessage({
+					id: "settings.functionSettings.ocrSettings.ocrPreset.denseTable",
+				}),
+				value: OcrPreset.DenseTable,
+			},
 		];
 	}, [intl]);
 
@@ -1064,6 +1120,120 @@ export const FunctionSettingsPage = () => {
 							);
 						}}
 					</ProFormDependency>
+
+					<SubGroupTitle>
+						<FormattedMessage id="settings.functionSettings.screenshotSettings.regionWatch" />
+					</SubGroupTitle>
+
+					<Row gutter={token.marginLG}>
+						<Col span={12}>
+							<ProFormDigit
+								name="regionWatchIntervalMs"
+								layout="horizontal"
+								label={
+									<FormattedMessage id="settings.functionSettings.screenshotSettings.regionWatchIntervalMs" />
+								}
+								min={200}
+								max={60000}
+								fieldProps={{
+									precision: 0,
+									addonAfter: "ms",
+								}}
+							/>
+						</Col>
+
+						<Col span={12}>
+							<ProFormDigit
+								name="regionWatchCpuBudget"
+								layout="horizontal"
+								label={
+									<IconLabel
+										label={
+											<FormattedMessage id="settings.functionSettings.screenshotSettings.regionWatchCpuBudget" />
+										}
+										tooltipTitle={
+											<FormattedMessage id="settings.functionSettings.screenshotSettings.regionWatchCpuBudget.tip" />
+										}
+									/>
+								}
+								min={1}
+								max={100}
+								fieldProps={{
+									precision: 0,
+									addonAfter: "%",
+								}}
+							/>
+						</Col>
+
+						<Col span={12}>
+							<ProFormDigit
+								name="regionWatchChangeThreshold"
+								layout="horizontal"
+								label={
+									<IconLabel
+										label={
+											<FormattedMessage id="settings.functionSettings.screenshotSettings.regionWatchChangeThreshold" />
+										}
+										tooltipTitle={
+											<FormattedMessage id="settings.functionSettings.screenshotSettings.regionWatchChangeThreshold.tip" />
+										}
+									/>
+								}
+								min={1}
+								max={100}
+								fieldProps={{
+									precision: 0,
+									addonAfter: "%",
+								}}
+							/>
+						</Col>
+
+						{isReadyStatus?.(PLUGIN_ID_RAPID_OCR) && (
+							<Col span={12}>
+								<ProFormText
+									name="regionWatchTextPattern"
+									layout="horizontal"
+									label={
+										<IconLabel
+											label={
+												<FormattedMessage id="settings.functionSettings.screenshotSettings.regionWatchTextPattern" />
+											}
+											tooltipTitle={
+												<FormattedMessage id="settings.functionSettings.screenshotSettings.regionWatchTextPattern.tip" />
+											}
+										/>
+									}
+								/>
+							</Col>
+						)}
+
+						<Col span={12}>
+							<ProFormSwitch
+								name="regionWatchNotify"
+								layout="horizontal"
+								label={
+									<FormattedMessage id="settings.functionSettings.screenshotSettings.regionWatchNotify" />
+								}
+							/>
+						</Col>
+
+						<Col span={12}>
+							<ProFormSwitch
+								name="regionWatchSaveCapture"
+								layout="horizontal"
+								label={
+									<IconLabel
+										label={
+											<FormattedMessage id="settings.functionSettings.screenshotSettings.regionWatchSaveCapture" />
+										}
+										tooltipTitle={
+											<FormattedMessage id="settings.functionSettings.screenshotSettings.regionWatchSaveCapture.tip" />
+										}
+									/>
+								}
+							/>
+						</Col>
+					</Row>
 				</ProForm>
 			</Spin>
 
@@ -1314,6 +1484,55 @@ export const FunctionSettingsPage = () => {
 										options={ocrModelOptions}
 									/>
 								</Col>
+								<Col span={12}>
+									<ProFormSelect
+										label={
+											<IconLabel
+												label={
+													<FormattedMessage id="settings.functionSettings.ocrSettings.ocrPreset" />
+												}
+												tooltipTitle={
+													<FormattedMessage id="settings.functionSettings.ocrSettings.ocrPreset.tip" />
+												}
+											/>
+										}
+										name="ocrPreset"
+										options={ocrPresetOptions}
+										allowClear={false}
+									/>
+								</Col>
+								<Col span={12}>
+									<ProFormSwitch
+										label={
+											<IconLabel
+												label={
+													<FormattedMessage id="settings.functionSettings.ocrSettings.ocrDetectBarcode" />
+												}
+												tooltipTitle={
+													<FormattedMessage id="settings.functionSettings.ocrSettings.ocrDetectBarcode.tip" />
+												}
+											/>
+										}
+										name="ocrDetectBarcode"
+										layout="horizontal"
+									/>
+								</Col>
+								<Col span={12}>
+									<ProFormSwitch
+										label={
+											<IconLabel
+												label={
+													<FormattedMessage id="settings.functionSettings.ocrSettings.ocrRecognizeTable" />
+												}
+												tooltipTitle={
+													<FormattedMessage id="settings.functionSettings.ocrSettings.ocrRecognizeTable.tip" />
+												}
+											/>
+										}
+										name="ocrRecognizeTable"
+										layout="horizontal"
+									/>
+								</Col>
 
 								{isReadyStatus?.(PLUGIN_ID_AI_CHAT) && (
 									<>
@@ -2416,6 +2635,30 @@ export const FunctionSettingsPage = () => {
 								<FormattedMessage id="settings.functionSettings.outputSettings.variables.focusedWindowAppName" />
 								<code>{FOCUS_WINDOW_APP_NAME_ENV_VARIABLE}</code>
 							</Col>
+							<Col span={12}>
+								<FormattedMessage id="settings.functionSettings.outputSettings.variables.windowTitle" />
+								<code>{"{{WINDOW_TITLE}}"}</code>
+							</Col>
+							<Col span={12}>
+								<FormattedMessage id="settings.functionSettings.outputSettings.variables.processName" />
+								<code>{"{{PROCESS_NAME}}"}</code>
+							</Col>
+							<Col span={12}>
+								<FormattedMessage id="settings.functionSettings.outputSettings.variables.monitorIndex" />
+								<code>{"{{MONITOR_INDEX}}"}</code>
+							</Col>
+							<Col span={12}>
+								<FormattedMessage id="settings.functionSettings.outputSettings.variables.captureMode" />
+								<code>{"{{CAPTURE_MODE}}"}</code>
+							</Col>
+							<Col span={12}>
+								<FormattedMessage id="settings.functionSettings.outputSettings.variables.counter" />
+								<code>{"{{COUNTER:3}}"}</code>
+							</Col>
+							<Col span={12}>
+								<FormattedMessage id="settings.functionSettings.outputSettings.variables.random" />
+								<code>{"{{RANDOM:8}}"}</code>
+							</Col>
 						</Row>
 					</Typography>
 				}
@@ -2454,23 +2697,15 @@ export const FunctionSettingsPage = () => {
 						<ProFormDependency<{ manualSaveFileNameFormat: string }>
 							name={["manualSaveFileNameFormat"]}
 						>
-							{({ manualSaveFileNameFormat }) => {
-								const text = generateImageFileName(manualSaveFileNameFormat);
-								return (
-									<Col span={24}>
-										<ProFormText
-											layout="horizontal"
-											readonly
-											label={
-												<FormattedMessage id="settings.functionSettings.outputSettings.manualSaveFileNameFormatPreview" />
-											}
-											fieldProps={{
-												value: text,
-											}}
-										/>
-									</Col>
-								);
-							}}
+							{({ manualSaveFileNameFormat }) => (
+								<FileNameTemplatePreview
+									template={manualSaveFileNameFormat}
+									captureMode={CaptureMode.Region}
+									label={
+										<FormattedMessage id="settings.functionSettings.outputSettings.manualSaveFileNameFormatPreview" />
+									}
+								/>
+							)}
 						</ProFormDependency>
 
 						<Col span={24}>
@@ -2486,23 +2721,15 @@ export const FunctionSettingsPage = () => {
 						<ProFormDependency<{ autoSaveFileNameFormat: string }>
 							name={["autoSaveFileNameFormat"]}
 						>
-							{({ autoSaveFileNameFormat }) => {
-								const text = generateImageFileName(autoSaveFileNameFormat);
-								return (
-									<Col span={24}>
-										<ProFormText
-											layout="horizontal"
-											readonly
-											label={
-												<FormattedMessage id="settings.functionSettings.outputSettings.autoSaveFileNameFormatPreview" />
-											}
-											fieldProps={{
-												value: text,
-											}}
-										/>
-									</Col>
-								);
-							}}
+							{({ autoSaveFileNameFormat }) => (
+								<FileNameTemplatePreview
+									template={autoSaveFileNameFormat}
+									captureMode={CaptureMode.Region}
+									label={
+										<FormattedMessage id="settings.functionSettings.outputSettings.autoSaveFileNameFormatPreview" />
+									}
+								/>
+							)}
 						</ProFormDependency>
 
 						<Col span={24}>
@@ -2518,23 +2745,15 @@ export const FunctionSettingsPage = () => {
 						<ProFormDependency<{ fastSaveFileNameFormat: string }>
 							name={["fastSaveFileNameFormat"]}
 						>
-							{({ fastSaveFileNameFormat }) => {
-								const text = generateImageFileName(fastSaveFileNameFormat);
-								return (
-									<Col span={24}>
-										<ProFormText
-											layout="horizontal"
-											readonly
-											label={
-												<FormattedMessage id="settings.functionSettings.outputSettings.fastSaveFileNameFormatPreview" />
-											}
-											fieldProps={{
-												value: text,
-											}}
-										/>
-									</Col>
-								);
-							}}
+							{({ fastSaveFileNameFormat }) => (
+								<FileNameTemplatePreview
+									template={fastSaveFileNameFormat}
+									captureMode={CaptureMode.Region}
+									label={
+										<FormattedMessage id="settings.functionSettings.outputSettings.fastSaveFileNameFormatPreview" />
+									}
+								/>
+							)}
 						</ProFormDependency>
 
 						<Col span={24}>
@@ -2550,23 +2769,15 @@ export const FunctionSettingsPage = () => {
 						<ProFormDependency<{ focusedWindowFileNameFormat: string }>
 							name={["focusedWindowFileNameFormat"]}
 						>
-							{({ focusedWindowFileNameFormat }) => {
-								const text = generateImageFileName(focusedWindowFileNameFormat);
-								return (
-									<Col span={24}>
-										<ProFormText
-											layout="horizontal"
-											readonly
-											label={
-												<FormattedMessage id="settings.functionSettings.outputSettings.focusedWindowFileNameFormatPreview" />
-											}
-											fieldProps={{
-												value: text,
-											}}
-										/>
-									</Col>
-								);
-							}}
+							{({ focusedWindowFileNameFormat }) => (
+								<FileNameTemplatePreview
+									template={focusedWindowFileNameFormat}
+									captureMode={CaptureMode.Window}
+									label={
+										<FormattedMessage id="settings.functionSettings.outputSettings.focusedWindowFileNameFormatPreview" />
+									}
+								/>
+							)}
 						</ProFormDependency>
 
 						<Col span={24}>
@@ -2582,23 +2793,15 @@ export const FunctionSettingsPage = () => {
 						<ProFormDependency<{ fullScreenFileNameFormat: string }>
 							name={["fullScreenFileNameFormat"]}
 						>
-							{({ fullScreenFileNameFormat }) => {
-								const text = generateImageFileName(fullScreenFileNameFormat);
-								return (
-									<Col span={24}>
-										<ProFormText
-											layout="horizontal"
-											readonly
-											label={
-												<FormattedMessage id="settings.functionSettings.outputSettings.fullScreenFileNameFormatPreview" />
-											}
-											fieldProps={{
-												value: text,
-											}}
-										/>
-									</Col>
-								);
-							}}
+							{({ fullScreenFileNameFormat }) => (
+								<FileNameTemplatePreview
+									template={fullScreenFileNameFormat}
+									captureMode={CaptureMode.FullScreen}
+									label={
+										<FormattedMessage id="settings.functionSettings.outputSettings.fullScreenFileNameFormatPreview" />
+									}
+								/>
+							)}
 						</ProFormDependency>
 
 						<Col span={24}>
@@ -2646,23 +2849,15 @@ export const FunctionSettingsPage = () => {
 						<ProFormDependency<{ videoRecordFileNameFormat: string }>
 							name={["videoRecordFileNameFormat"]}
 						>
-							{({ videoRecordFileNameFormat }) => {
-								const text = generateImageFileName(videoRecordFileNameFormat);
-								return (
-									<Col span={24}>
-										<ProFormText
-											layout="horizontal"
-											readonly
-											label={
-												<FormattedMessage id="settings.functionSettings.outputSettings.videoRecordFileNameFormatPreview" />
-											}
-											fieldProps={{
-												value: text,
-											}}
-										/>
-									</Col>
-								);
-							}}
+							{({ videoRecordFileNameFormat }) => (
+								<FileNameTemplatePreview
+									template={videoRecordFileNameFormat}
+									captureMode={CaptureMode.VideoRecord}
+									label={
+										<FormattedMessage id="settings.functionSettings.outputSettings.videoRecordFileNameFormatPreview" />
+									}
+								/>
+							)}
 						</ProFormDependency>
 					</Row>
 				</ProForm>
@@ -42,6 +42,7 @@ import { useStateRef } from "@/hooks/useStateRef";
 import {
 	type AppSettingsData,
 	AppSettingsGroup,
+	ColorProfileMode,
 	HdrColorAlgorithm,
 	HistoryValidDuration,
 } from "@/types/appSettings";
@@ -210,6 +211,29 @@ export const SystemSettingsPage = () => {
 		return options;
 	}, [intl]);
 
+	const colorProfileModeOptions = useMemo((): SelectProps["options"] => {
+		return [
+			{
+				label: intl.formatMessage({
+					id: "settings.systemSettings.screenshotSettings.colorProfileMode.ignore",
+				}),
+				value: ColorProfileMode.Ignore,
+			},
+			{
+				label: intl.formatMessage({
+					id: "settings.systemSettings.screenshotSettings.colorProfileMode.convertToSrgb",
+				}),
+				value: ColorProfileMode.ConvertToSrgb,
+			},
+			{
+				label: intl.formatMessage({
+					id: "settings.systemSettings.screenshotSettings.colorProfileMode.embed",
+				}),
+				value: ColorProfileMode.Embed,
+			},
+		];
+	}, [intl]);
+
 	const hdrColorAlgorithmOptions = useMemo((): SelectProps["options"] => {
 		return [
 			{
@@ -228,6 +252,70 @@ export const SystemSettingsPage = () => {
 				),
 				value: HdrColorAlgorithm.Linear,
 			},
+			{
+				label: (
+					<IconLabel
+						title={intl.formatMessage({
+							id: "settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.reinhard",
+						})}
+						label={
+							<FormattedMessage id="settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.reinhard" />
+						}
+						tooltipTitle={
+							<FormattedMessage id="settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.reinhard.tip" />
+						}
+					/>
+				),
+				value: HdrColorAlgorithm.Reinhard,
+			},
+			{
+				label: (
+					<IconLabel
+						title={intl.formatMessage({
+							id: "settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.acesFilmic",
+						})}
+						label={
+							<FormattedMessage id="settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.acesFilmic" />
+						}
+						tooltipTitle={
+							<FormattedMessage id="settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.acesFilmic.tip" />
+						}
+					/>
+				),
+				value: HdrColorAlgorithm.AcesFilmic,
+			},
+			{
+				label: (
+					<IconLabel
+						title={intl.formatMessage({
+							id: "settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.hable",
+						})}
+						label={
+							<FormattedMessage id="settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.hable" />
+						}
+						tooltipTitle={
+							<FormattedMessage id="settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.hable.tip" />
+						}
+					/>
+				),
+				value: HdrColorAlgorithm.Hable,
+			},
+			{
+				label: (
+					<IconLabel
+						title={intl.formatMessage({
+							id: "settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.bt2390",
+						})}
+						label={
+							<FormattedMessage id="settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.bt2390" />
+						}
+						tooltipTitle={
+							<FormattedMessage id="settings.systemSettings.screenshotSettings.enableCorrectHdrColor.algorithm.bt2390.tip" />
+						}
+					/>
+				),
+				value: HdrColorAlgorithm.Bt2390,
+			},
 		];
 	}, [intl]);
 
@@ -413,6 +501,80 @@ export const SystemSettingsPage = () => {
 								valuePropName="checked"
 							/>
 						</Col>
+						<Col span={12}>
+							<ProFormSelect
+								label={
+									<IconLabel
+										label={
+											<FormattedMessage id="settings.systemSettings.screenshotSettings.colorProfileMode" />
+										}
+										tooltipTitle={
+											<FormattedMessage id="settings.systemSettings.screenshotSettings.colorProfileMode.tip" />
+										}
+									/>
+								}
+								name="colorProfileMode"
+								options={colorProfileModeOptions}
+							/>
+						</Col>
+					</Row>
+
+					<Row gutter={token.marginLG}>
+						<Col span={12}>
+							<ProFormSwitch
+								label={
+									<IconLabel
+										label={
+											<FormattedMessage id="settings.systemSettings.screenshotSettings.embedCaptureMetadata" />
+										}
+										tooltipTitle={
+											<FormattedMessage id="settings.systemSettings.screenshotSettings.embedCaptureMetadata.tip" />
+										}
+									/>
+								}
+								name="embedCaptureMetadata"
+								valuePropName="checked"
+							/>
+						</Col>
+						<Col span={12}>
+							<ProFormSwitch
+								label={
+									<IconLabel
+										label={
+											<FormattedMessage id="settings.systemSettings.screenshotSettings.captureMetadataIncludeWindowInfo" />
+										}
+										tooltipTitle={
+											<FormattedMessage id="settings.systemSettings.screenshotSettings.captureMetadataIncludeWindowInfo.tip" />
+										}
+									/>
+								}
+								name="captureMetadataIncludeWindowInfo"
+								valuePropName="checked"
+							/>
+						</Col>
+					</Row>
+
+					<Row gutter={token.marginLG}>
+						<Col span={12}>
+							<ProFormDigit
+								label={
+									<IconLabel
+										label={
+											<FormattedMessage id="settings.systemSettings.screenshotSettings.pngOptimizeLevel" />
+										}
+										tooltipTitle={
+											<FormattedMessage id="settings.systemSettings.screenshotSettings.pngOptimizeLevel.tip" />
+										}
+									/>
+								}
+								name="pngOptimizeLevel"
+								min={0}
+								max={6}
+								fieldProps={{
+									precision: 0,
+								}}
+							/>
+						</Col>
 					</Row>
 
 					{currentPlatform === "windows" && (
@@ -464,6 +626,48 @@ export const SystemSettingsPage = () => {
 									options={hdrColorAlgorithmOptions}
 								/>
 							</Col>
+
+							<Col span={12}>
+								<ProFormDigit
+									label={
+										<IconLabel
+											label={
+												<FormattedMessage id="settings.systemSettings.screenshotSettings.hdrPeakNits" />
+											}
+											tooltipTitle={
+												<FormattedMessage id="settings.systemSettings.screenshotSettings.hdrPeakNits.tip" />
+											}
+										/>
+									}
+									name="hdrPeakNits"
+									min={80}
+									max={10000}
+									fieldProps={{
+										precision: 0,
+									}}
+								/>
+							</Col>
+
+							<Col span={12}>
+								<ProFormDigit
+									label={
+										<IconLabel
+											label={
+												<FormattedMessage id="settings.systemSettings.screenshotSettings.hdrPaperWhiteNits" />
+											}
+											tooltipTitle={
+												<FormattedMessage id="settings.systemSettings.screenshotSettings.hdrPaperWhiteNits.tip" />
+											}
+										/>
+									}
+									name="hdrPaperWhiteNits"
+									min={0}
+									max={1000}
+									fieldProps={{
+										precision: 0,
+									}}
+								/>
+							</Col>
 						</Row>
 					)}
 
@@ -548,6 +752,48 @@ export const SystemSettingsPage = () => {
 								/>
 							</Col>
 
+							<Col span={12}>
+								<ProFormDigit
+									label={
+										<IconLabel
+											label={
+												<FormattedMessage id="settings.systemSettings.screenshotSettings.ocrSessionPoolSize" />
+											}
+											tooltipTitle={
+												<FormattedMessage id="settings.systemSettings.screenshotSettings.ocrSessionPoolSize.tip" />
+											}
+										/>
+									}
+									name="ocrSessionPoolSize"
+									min={1}
+									max={8}
+									fieldProps={{
+										precision: 0,
+									}}
+								/>
+							</Col>
+
+							<Col span={12}>
+								<ProFormDigit
+									label={
+										<IconLabel
+											label={
+												<FormattedMessage id="settings.systemSettings.screenshotSettings.ocrThreadsPerSession" />
+											}
+											tooltipTitle={
+												<FormattedMessage id="settings.systemSettings.screenshotSettings.ocrThreadsPerSession.tip" />
+											}
+										/>
+									}
+									name="ocrThreadsPerSession"
+									min={0}
+									max={64}
+									fieldProps={{
+										precision: 0,
+									}}
+								/>
+							</Col>
+
 							<Col span={12}>
 								<ProFormSwitch
 									label={
@@ -0,0 +1,116 @@
+import { FileSearchOutlined, StopOutlined } from "@ant-design/icons";
+import { Button, Progress, Space } from "antd";
+import { useCallback, useContext, useEffect, useRef, useState } from "react";
+import { useIntl } from "react-intl";
+import { ocrBatchCancel } from "@/commands/ocr";
+import { EventListenerContext } from "@/components/eventListener";
+import { OCR_BATCH_PROGRESS_EMIT_KEY } from "@/constants/eventListener";
+import { AntdContext } from "@/contexts/antdContext";
+import type {
+	CaptureSearchSyncResult,
+	OcrBatchProgress,
+} from "@/types/commands/ocr";
+import { appError } from "@/utils/log";
+
+/**
+ * 识别截图历史中的所有图片并建立搜索索引
+ */
+export const CaptureHistoryBatchOcr: React.FC<{
+	syncSearchIndex: (batchId: string) => Promise<CaptureSearchSyncResult>;
+}> = ({ syncSearchIndex }) => {
+	const intl = useIntl();
+	const { message } = useContext(AntdContext);
+	const { addListener, removeListener } = useContext(EventListenerContext);
+
+	const batchIdRef = useRef<string | undefined>(undefined);
+	const [progress, setProgress] = useState<
+		{ finished: number; total: number } | undefined
+	>(undefined);
+
+	useEffect(() => {
+		const listenerId = addListener(OCR_BATCH_PROGRESS_EMIT_KEY, (args) => {
+			const payload = (args as { payload: OcrBatchProgress }).payload;
+			if (payload.batch_id !== batchIdRef.current) {
+				return;
+			}
+
+			setProgress({ finished: payload.finished, total: payload.total });
+		});
+		return () => {
+			removeListener(listenerId);
+		};
+	}, [addListener, removeListener]);
+
+	const startBatchOcr = useCallback(async () => {
+		const batchId = Date.now().toString();
+		batchIdRef.current = batchId;
+		setProgress({ finished: 0, total: 0 });
+
+		try {
+			const result = await syncSearchIndex(batchId);
+
+			if (!result.cancelled) {
+				message.success(
+					intl.formatMessage(
+						{ id: "tools.captureHistory.batchOcr.finished" },
+						{
+							indexed: result.indexed,
+							removed: result.removed,
+							failed: result.failed.length,
+						},
+					),
+				);
+			}
+		} catch (error) {
+			appError("[CaptureHistoryBatchOcr] syncSearchIndex error", error);
+			message.error(
+				intl.formatMessage({ id: "tools.captureHistory.batchOcr.failed" }),
+			);
+		}
+
+		batchIdRef.current = undefined;
+		setProgress(undefined);
+	}, [intl, message, syncSearchIndex]);
+
+	const cancelBatchOcr = useCallback(async () => {
+		if (!batchIdRef.current) {
+			return;
+		}
+
+		await ocrBatchCancel(batchIdRef.current);
+	}, []);
+
+	if (!progress) {
+		return (
+			<Button
+				type="text"
+				icon={<FileSearchOutlined />}
+				onClick={startBatchOcr}
+				title={intl.formatMessage({ id: "tools.captureHistory.batchOcr" })}
+			/>
+		);
+	}
+
+	return (
+		<Space>
+			<Progress
+				size="small"
+				style={{ width: 160, marginBottom: 0 }}
+				percent={
+					progress.total > 0
+						? Math.floor((progress.finished / progress.total) * 100)
+						: 0
+				}
+				format={() => `${progress.finished}/${progress.total}`}
+			/>
+			<Button
+				type="text"
+				icon={<StopOutlined />}
+				onClick={cancelBatchOcr}
+				title={intl.formatMessage({
+					id: "tools.captureHistory.batchOcr.cancel",
+				})}
+			/>
+		</Space>
+	);
+};
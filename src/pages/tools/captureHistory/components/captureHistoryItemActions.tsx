@@ -1,13 +1,44 @@
-import { CopyOutlined, DeleteOutlined, EditOutlined } from "@ant-design/icons";
-import { Button, Popconfirm, Space } from "antd";
-import { useCallback, useState } from "react";
-import { FormattedMessage } from "react-intl";
+import {
+	CopyOutlined,
+	DeleteOutlined,
+	EditOutlined,
+	FileTextOutlined,
+} from "@ant-design/icons";
+import * as dialog from "@tauri-apps/plugin-dialog";
+import { Button, Dropdown, Popconfirm, Space } from "antd";
+import { useCallback, useContext, useState } from "react";
+import { FormattedMessage, useIntl } from "react-intl";
+import { ocrExportDocument } from "@/commands/ocr";
+import { AntdContext } from "@/contexts/antdContext";
+import { AppSettingsPublisher } from "@/contexts/appSettingsActionContext";
 import { executeScreenshot } from "@/functions/screenshot";
+import { useStateSubscriber } from "@/hooks/useStateSubscriber";
+import { AppSettingsGroup } from "@/types/appSettings";
+import { OcrDocumentFormat } from "@/types/commands/ocr";
 import type { CaptureHistory } from "@/utils/captureHistory";
 import { writeFilePathToClipboard } from "@/utils/clipboard";
+import { appError } from "@/utils/log";
 import { ScreenshotType } from "@/utils/types";
 import type { CaptureHistoryRecordItem } from "../extra";
 
+const exportDocumentFilters: Record<
+	OcrDocumentFormat,
+	{ name: string; extensions: string[] }
+> = {
+	[OcrDocumentFormat.SearchablePdf]: {
+		name: "PDF(*.pdf)",
+		extensions: ["pdf"],
+	},
+	[OcrDocumentFormat.Hocr]: {
+		name: "hOCR(*.hocr)",
+		extensions: ["hocr"],
+	},
+	[OcrDocumentFormat.Alto]: {
+		name: "ALTO XML(*.xml)",
+		extensions: ["xml"],
+	},
+};
+
 export const CaptureHistoryItemActions: React.FC<{
 	item: CaptureHistoryRecordItem;
 	reloadList: () => Promise<void>;
@@ -16,6 +47,10 @@ export const CaptureHistoryItemActions: React.FC<{
 	const [editLoading, setEditLoading] = useState(false);
 	const [copyLoading, setCopyLoading] = useState(false);
 	const [deleteLoading, setDeleteLoading] = useState(false);
+	const [exportLoading, setExportLoading] = useState(false);
+	const intl = useIntl();
+	const { message } = useContext(AntdContext);
+	const [getAppSettings] = useStateSubscriber(AppSettingsPublisher, undefined);
 	const deleteAction = useCallback(async () => {
 		if (!(await captureHistoryRef.current?.inited())) {
 			return;
@@ -24,6 +59,64 @@ export const CaptureHistoryItemActions: React.FC<{
 		await reloadList();
 	}, [captureHistoryRef, item.id, reloadList]);
 
+	const exportDocumentAction = useCallback(
+		async (format: OcrDocumentFormat) => {
+			const imagePath = item.capture_result_file_path ?? item.file_path;
+			const filter = exportDocumentFilters[format];
+			const fileName = imagePath
+				.split(/[\\/]/)
+				.pop()
+				?.replace(/\.[^.]+$/, "");
+
+			const outputPath = await dialog.save({
+				filters: [filter],
+				defaultPath: fileName
+					? `${fileName}.${filter.extensions[0]}`
+					: undefined,
+				canCreateDirectories: true,
+			});
+			if (!outputPath) {
+				return;
+			}
+
+			const appSettings = getAppSettings();
+			setExportLoading(true);
+			try {
+				await ocrExportDocument(
+					imagePath,
+					outputPath,
+					format,
+					window.devicePixelRatio,
+					appSettings[AppSettingsGroup.SystemScreenshot].ocrDetectAngle,
+					undefined,
+					{
+						preset: appSettings[AppSettingsGroup.FunctionOcr].ocrPreset,
+					},
+				);
+				message.success(
+					intl.formatMessage({
+						id: "tools.captureHistory.exportDocument.success",
+					}),
+				);
+			} catch (error) {
+				appError("[CaptureHistoryItemActions] ocrExportDocument error", error);
+				message.error(
+					intl.formatMessage({
+						id: "tools.captureHistory.exportDocument.failed",
+					}),
+				);
+			}
+			setExportLoading(false);
+		},
+		[
+			getAppSettings,
+			intl,
+			item.capture_result_file_path,
+			item.file_path,
+			message,
+		],
+	);
+
 	return (
 		<Space wrap style={{ width: "100%" }}>
 			<Button
@@ -64,6 +157,45 @@ export const CaptureHistoryItemActions: React.FC<{
 			>
 				<FormattedMessage id="tools.captureHistory.copy" />
 			</Button>
+			<Dropdown
+				key="exportDocument"
+				menu={{
+					items: [
+						{
+							key: OcrDocumentFormat.SearchablePdf,
+							label: (
+								<FormattedMessage id="tools.captureHistory.exportDocument.searchablePdf" />
+							),
+						},
+						{
+							key: OcrDocumentFormat.Hocr,
+							label: (
+								<FormattedMessage id="tools.captureHistory.exportDocument.hocr" />
+							),
+						},
+						{
+							key: OcrDocumentFormat.Alto,
+							label: (
+								<FormattedMessage id="tools.captureHistory.exportDocument.alto" />
+							),
+						},
+					],
+					onClick: ({ key }) => {
+						exportDocumentAction(key as OcrDocumentFormat);
+					},
+				}}
+				disabled={exportLoading}
+			>
+				<Button
+					size="small"
+					color="primary"
+					variant="link"
+					icon={<FileTextOutlined />}
+					loading={exportLoading}
+				>
+					<FormattedMessage id="tools.captureHistory.exportDocument" />
+				</Button>
+			</Dropdown>
 			<Popconfirm
 				key="delete"
 				title={<FormattedMessage id="tools.captureHistory.delete.confirm" />}
@@ -1,12 +1,37 @@
-import { Image, Tooltip } from "antd";
+import { Image, Tooltip, theme } from "antd";
 import { useState } from "react";
 import { FormattedMessage } from "react-intl";
 import type { CaptureHistoryRecordItem } from "../extra";
 
+const PREVIEW_WIDTH = 350;
+const PREVIEW_HEIGHT = 128;
+
 export const CaptureHistoryItemPreview: React.FC<{
 	item: CaptureHistoryRecordItem;
 }> = ({ item }) => {
+	const { token } = theme.useToken();
 	const [showCaptureResult, setShowCaptureResult] = useState(true);
+	const [imageSize, setImageSize] = useState<
+		{ width: number; height: number } | undefined
+	>(undefined);
+
+	// 搜索结果的文本块位于识别文字使用的图片中，只在预览该图片时显示
+	const showHighlights =
+		imageSize &&
+		item.search_highlights &&
+		(showCaptureResult || !item.capture_result_file_url);
+	const previewScale = imageSize
+		? Math.min(
+				PREVIEW_WIDTH / imageSize.width,
+				PREVIEW_HEIGHT / imageSize.height,
+			)
+		: 1;
+	const previewOffsetX = imageSize
+		? (PREVIEW_WIDTH - imageSize.width * previewScale) / 2
+		: 0;
+	const previewOffsetY = imageSize
+		? (PREVIEW_HEIGHT - imageSize.height * previewScale) / 2
+		: 0;
 
 	return (
 		<Tooltip
@@ -16,23 +41,50 @@ export const CaptureHistoryItemPreview: React.FC<{
 				) : undefined
 			}
 		>
-			<Image
-				alt="preview"
-				loading="lazy"
-				key={item.id}
-				src={
-					showCaptureResult
-						? (item.capture_result_file_url ?? item.file_url)
-						: item.file_url
-				}
-				width={350}
-				height={128}
-				style={{ objectFit: "contain" }}
-				onContextMenu={(e) => {
-					e.preventDefault();
-					setShowCaptureResult(!showCaptureResult);
-				}}
-			/>
+			<div style={{ position: "relative" }}>
+				<Image
+					alt="preview"
+					loading="lazy"
+					key={item.id}
+					src={
+						showCaptureResult
+							? (item.capture_result_file_url ?? item.file_url)
+							: item.file_url
+					}
+					width={PREVIEW_WIDTH}
+					height={PREVIEW_HEIGHT}
+					style={{ objectFit: "contain" }}
+					onLoad={(e) => {
+						setImageSize({
+							width: e.currentTarget.naturalWidth,
+							height: e.currentTarget.naturalHeight,
+						});
+					}}
+					onContextMenu={(e) => {
+						e.preventDefault();
+						setShowCaptureResult(!showCaptureResult);
+					}}
+				/>
+				{showHighlights &&
+					item.search_highlights?.map((highlight) => (
+						<div
+							key={`${highlight.rect.min_x},${highlight.rect.min_y},${highlight.text}`}
+							style={{
+								position: "absolute",
+								left: previewOffsetX + highlight.rect.min_x * previewScale,
+								top: previewOffsetY + highlight.rect.min_y * previewScale,
+								width:
+									(highlight.rect.max_x - highlight.rect.min_x) * previewScale,
+								height:
+									(highlight.rect.max_y - highlight.rect.min_y) * previewScale,
+								border: `1px solid ${token.colorWarning}`,
+								backgroundColor: token.colorWarningBg,
+								opacity: 0.6,
+								pointerEvents: "none",
+							}}
+						/>
+					))}
+			</div>
 		</Tooltip>
 	);
 };
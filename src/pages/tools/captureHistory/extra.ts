@@ -1,3 +1,4 @@
+import type { CaptureSearchHighlight } from "@/types/commands/ocr";
 import type { CaptureHistoryItem } from "@/utils/appStore";
 
 export type CaptureHistoryRecordItem = CaptureHistoryItem & {
@@ -6,4 +7,6 @@ export type CaptureHistoryRecordItem = CaptureHistoryItem & {
 	file_url: string;
 	capture_result_file_path?: string;
 	capture_result_file_url?: string;
+	/** 搜索时包含关键词的文本块，位于识别文字使用的图片中 */
+	search_highlights?: CaptureSearchHighlight[];
 };
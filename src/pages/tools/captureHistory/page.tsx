@@ -3,18 +3,23 @@
 import { DeleteOutlined, ReloadOutlined } from "@ant-design/icons";
 import { type ActionType, ProList } from "@ant-design/pro-components";
 import { convertFileSrc } from "@tauri-apps/api/core";
-import { Button, Popconfirm, Space, Tag, theme } from "antd";
+import { Button, Popconfirm, Space, Tag, Typography, theme } from "antd";
 import dayjs from "dayjs";
 import type { Key } from "react";
 import { useCallback, useContext, useEffect, useRef, useState } from "react";
 import { FormattedMessage, useIntl } from "react-intl";
+import { captureSearchQuery, captureSearchSync } from "@/commands/ocr";
 import { EventListenerContext } from "@/components/eventListener";
 import { AppSettingsPublisher } from "@/contexts/appSettingsActionContext";
 import { executeScreenshot } from "@/functions/screenshot";
 import { useAppSettingsLoad } from "@/hooks/useAppSettingsLoad";
 import { useStateRef } from "@/hooks/useStateRef";
 import { useStateSubscriber } from "@/hooks/useStateSubscriber";
-import type { AppSettingsData } from "@/types/appSettings";
+import { type AppSettingsData, AppSettingsGroup } from "@/types/appSettings";
+import type {
+	CaptureSearchDocument,
+	CaptureSearchHighlight,
+} from "@/types/commands/ocr";
 import {
 	type CaptureHistoryItem,
 	CaptureHistorySource,
@@ -25,10 +30,37 @@ import {
 } from "@/utils/captureHistory";
 import { appWarn } from "@/utils/log";
 import { ScreenshotType } from "@/utils/types";
+import { CaptureHistoryBatchOcr } from "./components/captureHistoryBatchOcr";
 import { CaptureHistoryItemActions } from "./components/captureHistoryItemActions";
 import { CaptureHistoryItemPreview } from "./components/captureHistoryItemPreview";
 import type { CaptureHistoryRecordItem } from "./extra";
 
+/** 搜索结果的最大数量 */
+const CAPTURE_SEARCH_LIMIT = 1000;
+
+const getSourceMessageId = (source: CaptureHistorySource | undefined) => {
+	switch (source) {
+		case CaptureHistorySource.ScrollScreenshotCopy:
+			return "tools.captureHistory.source.scrollScreenshotCopy";
+		case CaptureHistorySource.ScrollScreenshotSave:
+			return "tools.captureHistory.source.scrollScreenshotSave";
+		case CaptureHistorySource.ScrollScreenshotFixed:
+			return "tools.captureHistory.source.scrollScreenshotFixed";
+		case CaptureHistorySource.Copy:
+			return "tools.captureHistory.source.copy";
+		case CaptureHistorySource.Save:
+			return "tools.captureHistory.source.save";
+		case CaptureHistorySource.Fixed:
+			return "tools.captureHistory.source.fixed";
+		case CaptureHistorySource.FullScreen:
+			return "tools.captureHistory.source.fullScreen";
+		case CaptureHistorySource.RegionWatch:
+			return "tools.captureHistory.source.regionWatch";
+	}
+
+	return "tools.captureHistory.source.unknown";
+};
+
 export const CaptureHistoryPage = () => {
 	const intl = useIntl();
 	const [loading, setLoading] = useState(true);
@@ -41,6 +73,47 @@ export const CaptureHistoryPage = () => {
 
 	const initedRef = useRef(false);
 	const [getAppSettings] = useStateSubscriber(AppSettingsPublisher, undefined);
+
+	/**
+	 * 同步截图历史的搜索索引，识别截图使用编辑后的结果
+	 */
+	const syncSearchIndex = useCallback(
+		async (
+			batchId: string,
+			list: CaptureHistoryItem[],
+			onlyIfIndexed: boolean,
+		) => {
+			const documents: CaptureSearchDocument[] = await Promise.all(
+				list.map(async (item) => ({
+					id: item.id,
+					file_path: await getCaptureHistoryImageAbsPath(
+						item.capture_result_file_name ?? item.file_name,
+					),
+					create_ts: item.create_ts,
+					window_title: item.window_title,
+					process_name: item.process_name,
+					tags: [
+						intl.formatMessage({ id: getSourceMessageId(item.source) }),
+						dayjs(item.create_ts).format("YYYY-MM-DD"),
+					],
+				})),
+			);
+
+			const appSettings = getAppSettings();
+			return await captureSearchSync(
+				batchId,
+				documents,
+				onlyIfIndexed,
+				window.devicePixelRatio,
+				appSettings[AppSettingsGroup.SystemScreenshot].ocrDetectAngle,
+				undefined,
+				{
+					preset: appSettings[AppSettingsGroup.FunctionOcr].ocrPreset,
+				},
+			);
+		},
+		[getAppSettings, intl],
+	);
 	const initDataSource = useCallback(
 		async (appSettings: AppSettingsData) => {
 			if (initedRef.current) {
@@ -58,8 +131,13 @@ export const CaptureHistoryPage = () => {
 			setDataSource(list);
 
 			actionRef.current?.reload();
+
+			// 已经建立过索引时，在后台识别新增的截图并删除已经不存在的截图
+			syncSearchIndex(`auto-${Date.now()}`, list, true).catch((error) => {
+				appWarn("[CaptureHistoryPage] sync search index failed", error);
+			});
 		},
-		[setDataSource],
+		[setDataSource, syncSearchIndex],
 	);
 	const reloadList = useCallback(async () => {
 		initedRef.current = false;
@@ -80,32 +158,7 @@ export const CaptureHistoryPage = () => {
 
 	const getSourceDesc = useCallback(
 		(source: CaptureHistorySource | undefined) => {
-			switch (source) {
-				case CaptureHistorySource.ScrollScreenshotCopy:
-					return (
-						<FormattedMessage id="tools.captureHistory.source.scrollScreenshotCopy" />
-					);
-				case CaptureHistorySource.ScrollScreenshotSave:
-					return (
-						<FormattedMessage id="tools.captureHistory.source.scrollScreenshotSave" />
-					);
-				case CaptureHistorySource.ScrollScreenshotFixed:
-					return (
-						<FormattedMessage id="tools.captureHistory.source.scrollScreenshotFixed" />
-					);
-				case CaptureHistorySource.Copy:
-					return <FormattedMessage id="tools.captureHistory.source.copy" />;
-				case CaptureHistorySource.Save:
-					return <FormattedMessage id="tools.captureHistory.source.save" />;
-				case CaptureHistorySource.Fixed:
-					return <FormattedMessage id="tools.captureHistory.source.fixed" />;
-				case CaptureHistorySource.FullScreen:
-					return (
-						<FormattedMessage id="tools.captureHistory.source.fullScreen" />
-					);
-			}
-
-			return <FormattedMessage id="tools.captureHistory.source.unknown" />;
+			return <FormattedMessage id={getSourceMessageId(source)} />;
 		},
 		[],
 	);
@@ -207,6 +260,12 @@ export const CaptureHistoryPage = () => {
 							/>
 						</Popconfirm>
 					</div>,
+					<CaptureHistoryBatchOcr
+						key="batchOcr"
+						syncSearchIndex={(batchId) =>
+							syncSearchIndex(batchId, dataSourceRef.current ?? [], false)
+						}
+					/>,
 					<Button
 						key="reload"
 						type="text"
@@ -248,7 +307,33 @@ export const CaptureHistoryPage = () => {
 						endTs = dayjs(params.create_ts[1], "YYYY-MM-DD HH:mm:ss").valueOf();
 					}
 
-					const filterData = dataSourceRef.current.filter((item) => {
+					// 搜索文本时按相关度排列搜索结果
+					let sourceList = dataSourceRef.current;
+					let highlightsMap: Map<string, CaptureSearchHighlight[]> | undefined;
+					const searchText =
+						"search_text" in params && typeof params.search_text === "string"
+							? params.search_text.trim()
+							: "";
+					if (searchText) {
+						const itemMap = new Map(sourceList.map((item) => [item.id, item]));
+						try {
+							const hits = await captureSearchQuery(
+								searchText,
+								CAPTURE_SEARCH_LIMIT,
+							);
+							highlightsMap = new Map(
+								hits.map((hit) => [hit.id, hit.highlights]),
+							);
+							sourceList = hits
+								.map((hit) => itemMap.get(hit.id))
+								.filter((item) => item !== undefined);
+						} catch (error) {
+							appWarn("[CaptureHistoryPage] captureSearchQuery failed", error);
+							sourceList = [];
+						}
+					}
+
+					const filterData = sourceList.filter((item) => {
 						let isMatch = true;
 						if (startTs && endTs) {
 							isMatch &&= item.create_ts >= startTs && item.create_ts <= endTs;
@@ -279,6 +364,7 @@ export const CaptureHistoryPage = () => {
 									capture_result_file_url: capture_result_file_path
 										? convertFileSrc(capture_result_file_path)
 										: undefined,
+									search_highlights: highlightsMap?.get(item.id),
 								};
 							}),
 					);
@@ -319,6 +405,9 @@ export const CaptureHistoryPage = () => {
 							[CaptureHistorySource.FullScreen]: (
 								<FormattedMessage id="tools.captureHistory.source.fullScreen" />
 							),
+							[CaptureHistorySource.RegionWatch]: (
+								<FormattedMessage id="tools.captureHistory.source.regionWatch" />
+							),
 							[CaptureHistorySource.ScrollScreenshotCopy]: (
 								<FormattedMessage id="tools.captureHistory.source.scrollScreenshotCopy" />
 							),
@@ -381,6 +470,25 @@ export const CaptureHistoryPage = () => {
 							);
 						},
 					},
+					content: {
+						title: <FormattedMessage id="tools.captureHistory.searchText" />,
+						search: true,
+						dataIndex: "search_text",
+						valueType: "text",
+						render: (_, item) => {
+							if (!item.search_highlights?.length) {
+								return null;
+							}
+
+							return (
+								<Typography.Text type="secondary" ellipsis>
+									{item.search_highlights
+										.map((highlight) => highlight.text)
+										.join(" … ")}
+								</Typography.Text>
+							);
+						},
+					},
 					actions: {
 						search: false,
 						cardActionProps: "extra",
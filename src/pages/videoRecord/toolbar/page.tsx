@@ -31,7 +31,11 @@ import {
 	getMonitorsBoundingBox,
 	type MonitorBoundingBox,
 } from "@/commands/core";
-import { createDir } from "@/commands/file";
+import {
+	commitDailyCounter,
+	createDir,
+	resolveFilePathTemplate,
+} from "@/commands/file";
 import {
 	setExcludeFromCapture,
 	videoRecordKill,
@@ -65,12 +69,12 @@ import {
 	VideoFormat,
 	VideoMaxSize,
 } from "@/types/appSettings";
-import type { ElementRect } from "@/types/commands/screenshot";
+import { CaptureMode, type ElementRect } from "@/types/commands/screenshot";
 import {
-	generateImageFileName,
+	createFileNameTemplateContext,
 	getVideoRecordSaveDirectory,
 } from "@/utils/file";
-import { appError } from "@/utils/log";
+import { appError, appWarn } from "@/utils/log";
 import { getPlatformValue } from "@/utils/platform";
 import type { VideoRecordWindowInfo } from "@/utils/types";
 import { setWindowRect } from "@/utils/window";
@@ -337,18 +341,20 @@ export const VideoRecordToolbarPage: React.FC = () => {
 				appSettings[AppSettingsGroup.FunctionVideoRecord].videoMaxSize,
 			);
 
+		const resolvedFilePath = await resolveFilePathTemplate(
+			await joinPath(
+				await getVideoRecordSaveDirectory(appSettings),
+				appSettings[AppSettingsGroup.FunctionOutput].videoRecordFileNameFormat,
+			),
+			createFileNameTemplateContext(CaptureMode.VideoRecord),
+		);
+
 		videoRecordStart(
 			selectRectRef.current?.min_x ?? 0,
 			selectRectRef.current?.min_y ?? 0,
 			selectRectRef.current?.max_x ?? 0,
 			selectRectRef.current?.max_y ?? 0,
-			await joinPath(
-				await getVideoRecordSaveDirectory(appSettings),
-				generateImageFileName(
-					appSettings[AppSettingsGroup.FunctionOutput]
-						.videoRecordFileNameFormat,
-				),
-			),
+			resolvedFilePath.file_path,
 			VideoFormat.Mp4,
 			appSettings[AppSettingsGroup.FunctionVideoRecord].frameRate,
 			enableMicrophone,
@@ -361,6 +367,12 @@ export const VideoRecordToolbarPage: React.FC = () => {
 			videoMaxHeight,
 		)
 			.then(() => {
+				if (resolvedFilePath.daily_counter) {
+					commitDailyCounter(resolvedFilePath.daily_counter).catch((error) => {
+						appWarn("[startRecord] Failed to commit daily counter", error);
+					});
+				}
+
 				setVideoRecordState(VideoRecordState.Recording);
 
 				stopDurationTimer();
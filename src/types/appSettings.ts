@@ -1,3 +1,4 @@
+import type { OcrPreset } from "./commands/ocr";
 import type { ElementRect } from "./commands/screenshot";
 import type { AppFunction, AppFunctionConfig } from "./components/appFunction";
 import type {
@@ -56,9 +57,29 @@ export enum OcrDetectAfterAction {
 
 export enum HdrColorAlgorithm {
 	Linear = "Linear",
+	Reinhard = "Reinhard",
+	AcesFilmic = "AcesFilmic",
+	Hable = "Hable",
+	Bt2390 = "Bt2390",
 	None = "None",
 }
 
+export enum ColorProfileMode {
+	/** 不处理显示器的色彩配置文件 */
+	Ignore = "Ignore",
+	/** 按显示器的色彩配置文件转换为 sRGB */
+	ConvertToSrgb = "ConvertToSrgb",
+	/** 在图片文件中嵌入显示器的 ICC 配置文件，格式不支持时转换为 sRGB */
+	Embed = "Embed",
+}
+
+export type HdrToneMappingOptions = {
+	/** 映射到白色的内容峰值亮度（尼特），为空时为 1000 尼特 */
+	peak_nits?: number;
+	/** 映射到 SDR 白色的亮度（尼特），为空时使用显示器的 SDR 白色亮度 */
+	paper_white_nits?: number;
+};
+
 export type ChatApiConfig = {
 	api_uri: string;
 	api_key: string;
@@ -168,6 +189,7 @@ export enum ExtraToolList {
 	None = 0,
 	ScanQrcode = 1,
 	VideoRecord = 2,
+	RegionWatch = 3,
 }
 
 export type SelectRectPreset = {
@@ -200,6 +222,13 @@ export enum ColorPickerShowMode {
 export enum OcrModel {
 	RapidOcrV4 = "RapidOcrV4",
 	RapidOcrV5 = "RapidOcrV5",
+	RapidOcrJapanese = "RapidOcrJapanese",
+	RapidOcrKorean = "RapidOcrKorean",
+	/** 拉丁字母，包括英语、法语、德语等欧洲语言 */
+	RapidOcrLatin = "RapidOcrLatin",
+	/** 西里尔字母，包括俄语、乌克兰语等 */
+	RapidOcrCyrillic = "RapidOcrCyrillic",
+	RapidOcrArabic = "RapidOcrArabic",
 }
 
 export enum KeyDisplayDirection {
@@ -379,6 +408,12 @@ export type AppSettingsData = {
 	[AppSettingsGroup.FunctionOcr]: {
 		/** 文本识别模型 */
 		ocrModel: OcrModel;
+		/** 文本识别参数的预设 */
+		ocrPreset: OcrPreset;
+		/** 识别文本时同时识别二维码和条码 */
+		ocrDetectBarcode: boolean;
+		/** 识别文本时同时识别表格结构，用于复制为表格 */
+		ocrRecognizeTable: boolean;
 		/** 将图片转为 HTML 的视觉理解模型 */
 		htmlVisionModel: string;
 		/** 图片转为 HTML 的 System 提示词 */
@@ -451,6 +486,18 @@ export type AppSettingsData = {
 		ocrCopyText: boolean;
 		/** 选区预设 */
 		selectRectPresetList: SelectRectPreset[];
+		/** 区域监控的截图间隔（毫秒） */
+		regionWatchIntervalMs: number;
+		/** 区域监控占用的 CPU 时间比例上限（百分比） */
+		regionWatchCpuBudget: number;
+		/** 画面变化超过该比例时触发区域监控（百分比） */
+		regionWatchChangeThreshold: number;
+		/** 画面变化后识别的文字匹配该正则表达式时才触发区域监控 */
+		regionWatchTextPattern: string;
+		/** 区域监控触发时发送系统通知 */
+		regionWatchNotify: boolean;
+		/** 区域监控触发时保存截图 */
+		regionWatchSaveCapture: boolean;
 	};
 	[AppSettingsGroup.FunctionOutput]: {
 		/** 手动保存文件名格式 */
@@ -530,6 +577,10 @@ export type AppSettingsData = {
 		ocrHotStart: boolean;
 		/** OCR 模型写入内存 */
 		ocrModelWriteToMemory: boolean;
+		/** 同时存在的文本识别 session 数量 */
+		ocrSessionPoolSize: number;
+		/** 每个文本识别 session 的线程数，为 0 时自动分配 */
+		ocrThreadsPerSession: number;
 		ocrDetectAngle: boolean;
 		/** 尝试使用 Bitmap 格式写入到剪贴板 */
 		tryWriteBitmapImageToClipboard: boolean;
@@ -541,6 +592,18 @@ export type AppSettingsData = {
 		correctHdrColor: boolean;
 		/** HDR 颜色转换算法 */
 		correctHdrColorAlgorithm: HdrColorAlgorithm;
+		/** HDR 色调映射的峰值亮度（尼特） */
+		hdrPeakNits: number;
+		/** HDR 色调映射的纸白亮度（尼特），为 0 时使用显示器的 SDR 白色亮度 */
+		hdrPaperWhiteNits: number;
+		/** 显示器色彩配置文件的处理方式 */
+		colorProfileMode: ColorProfileMode;
+		/** 在图片文件中写入截图时间、来源等元数据，AVIF 和 JPEG XL 不支持 */
+		embedCaptureMetadata: boolean;
+		/** 元数据中包含窗口标题和进程名称 */
+		captureMetadataIncludeWindowInfo: boolean;
+		/** 保存 PNG 后在后台进行无损优化的等级（1 - 6），为 0 时不优化 */
+		pngOptimizeLevel: number;
 	};
 	[AppSettingsGroup.SystemScrollScreenshot]: {
 		tryRollback: boolean;
@@ -1,3 +1,6 @@
+import type { OcrModel } from "../appSettings";
+import type { ElementRect } from "./screenshot";
+
 export interface OcrDetectResultTextPoint {
 	x: number;
 	y: number;
@@ -9,7 +12,279 @@ export interface OcrDetectResultTextBlock {
 	text_score: number;
 }
 
+/** 文本的书写方向 */
+export enum OcrWritingMode {
+	HorizontalLtr = "HorizontalLtr",
+	HorizontalRtl = "HorizontalRtl",
+	VerticalRl = "VerticalRl",
+}
+
+export interface OcrLayoutLine {
+	rect: ElementRect;
+	/** 行内文本块在 text_blocks 中的索引，按阅读顺序排列 */
+	block_indices: number[];
+	text: string;
+}
+
+export interface OcrLayoutParagraph {
+	rect: ElementRect;
+	heading_level: number | null;
+	lines: OcrLayoutLine[];
+}
+
+export interface OcrLayoutColumn {
+	rect: ElementRect;
+	paragraphs: OcrLayoutParagraph[];
+}
+
+/** 按阅读顺序排列的版面结构 */
+export interface OcrLayout {
+	writing_mode: OcrWritingMode;
+	columns: OcrLayoutColumn[];
+	text: string;
+	markdown: string;
+}
+
+export interface OcrTableCell {
+	row: number;
+	column: number;
+	row_span: number;
+	column_span: number;
+	rect: ElementRect;
+	text: string;
+	block_indices: number[];
+}
+
+/** 从 OCR 结果重建的表格 */
+export interface OcrTable {
+	rect: ElementRect;
+	row_count: number;
+	column_count: number;
+	/** 是否根据图片中的表格线划分单元格 */
+	has_ruling_lines: boolean;
+	/** 按行、列排列，被合并的单元格不包含在内 */
+	cells: OcrTableCell[];
+}
+
+export enum OcrTableExportFormat {
+	Csv = "Csv",
+	Tsv = "Tsv",
+	Markdown = "Markdown",
+	Html = "Html",
+}
+
+/** 识别结果导出的文档格式 */
+export enum OcrDocumentFormat {
+	/** 带隐藏文本层的 PDF */
+	SearchablePdf = "SearchablePdf",
+	Hocr = "Hocr",
+	/** ALTO XML v4 */
+	Alto = "Alto",
+}
+
+/** 支持识别的条码格式 */
+export enum BarcodeFormat {
+	QrCode = "QrCode",
+	DataMatrix = "DataMatrix",
+	Ean13 = "Ean13",
+	Ean8 = "Ean8",
+	UpcA = "UpcA",
+	UpcE = "UpcE",
+	Code128 = "Code128",
+}
+
+export interface BarcodePoint {
+	x: number;
+	y: number;
+}
+
+export interface Barcode {
+	format: BarcodeFormat;
+	text: string;
+	/**
+	 * 识别时使用的定位点，不是条码的轮廓
+	 *
+	 * 二维码为三个定位图案（和校正图案）的中心，DataMatrix 为 L 形边框的角点，
+	 * 一维条码为扫描线的两个端点
+	 */
+	points: BarcodePoint[];
+	/** 定位点的外接矩形，比条码实际区域小，一维条码的高度可能为 0 */
+	rect: ElementRect;
+}
+
 export interface OcrDetectResult {
 	text_blocks: OcrDetectResultTextBlock[];
 	scale_factor: number;
+	layout?: OcrLayout | null;
+	/** 识别的表格结构，未开启或未识别到表格时为空 */
+	table?: OcrTable | null;
+	/** 识别的二维码和条码，未开启识别时为空 */
+	barcodes?: Barcode[] | null;
+}
+
+/** 文本识别参数的预设 */
+export enum OcrPreset {
+	/** 屏幕截取的文字 */
+	ScreenText = "ScreenText",
+	/** 扫描或拍摄的文档 */
+	Document = "Document",
+	/** 行距较小的表格 */
+	DenseTable = "DenseTable",
+}
+
+/** 文本识别 session 池的配置 */
+export type OcrSessionPoolOptions = {
+	/** 最多同时存在的 session 数量 */
+	pool_size?: number;
+	/** 每个 session 的线程数，为 0 时按物理核心数平分 */
+	threads_per_session?: number;
+};
+
+/** 提供文本识别模型的插件 */
+export type OcrModelPlugin = {
+	id: string;
+	/** 插件包含的所有模型文件 */
+	file_list: string[];
+	/** 插件提供的模型 */
+	models: OcrModel[];
+};
+
+/** 文本识别参数，未设置的参数使用预设的值 */
+export type OcrOptions = {
+	preset?: OcrPreset;
+	/** 图片四周的留白（像素） */
+	padding?: number;
+	/** 文本框的最低得分 */
+	box_score_thresh?: number;
+	/** 文本区域概率图的二值化阈值 */
+	box_thresh?: number;
+	/** 文本框的扩张比例 */
+	unclip_ratio?: number;
+	/** 文字方向分类的置信度低于该值时不旋转文本 */
+	angle_rollback_threshold?: number;
+	/** 有效缩放低于该值时放大图片，为 0 时不放大 */
+	min_scale_factor?: number;
+	/** 识别表格结构 */
+	recognize_table?: boolean;
+	/** 识别二维码和条码 */
+	detect_barcode?: boolean;
+};
+
+/** 批量识别的参数 */
+export type OcrBatchOptions = {
+	/** 递归识别子文件夹中的图片 */
+	recursive?: boolean;
+	/** 在图片旁写入 .ocr.txt 和 .ocr.json 文件，如 a.png.ocr.txt */
+	write_sidecar?: boolean;
+};
+
+/** 批量识别时每处理完一张图片发送的进度 */
+export interface OcrBatchProgress {
+	batch_id: string;
+	file_path: string;
+	/** 已处理的图片数量，包含识别失败的图片 */
+	finished: number;
+	total: number;
+	/** 识别结果是否来自缓存 */
+	from_cache: boolean;
+	error?: string | null;
+}
+
+export interface OcrBatchFailure {
+	file_path: string;
+	error: string;
+}
+
+export interface OcrBatchResult {
+	total: number;
+	/** 重新识别的图片数量 */
+	detected: number;
+	/** 使用缓存结果的图片数量 */
+	from_cache: number;
+	failed: OcrBatchFailure[];
+	/** 任务是否被取消 */
+	cancelled: boolean;
+}
+
+/** 需要建立搜索索引的截图 */
+export type CaptureSearchDocument = {
+	/** 截图历史的 ID */
+	id: string;
+	/** 识别文字使用的图片 */
+	file_path: string;
+	/** 截图时间，Unix 时间戳（毫秒） */
+	create_ts: number;
+	window_title?: string;
+	process_name?: string;
+	/** 其他可以搜索的描述，例如截图来源 */
+	tags?: string[];
+};
+
+export interface CaptureSearchHighlight {
+	/** 文本块在图片中的位置 */
+	rect: ElementRect;
+	text: string;
+}
+
+export interface CaptureSearchHit {
+	id: string;
+	file_path: string;
+	create_ts: number;
+	window_title?: string | null;
+	process_name?: string | null;
+	score: number;
+	/** 包含关键词的文本块 */
+	highlights: CaptureSearchHighlight[];
+}
+
+export interface CaptureSearchSyncResult {
+	/** 截图历史中的截图数量 */
+	total: number;
+	/** 新建或更新索引的截图数量 */
+	indexed: number;
+	/** 从索引中删除的截图数量 */
+	removed: number;
+	failed: OcrBatchFailure[];
+	/** 任务是否被取消 */
+	cancelled: boolean;
+}
+
+/** 区域监控参数，未设置的参数使用默认值 */
+export type RegionWatchOptions = {
+	/** 两次截图的间隔（毫秒） */
+	interval_ms?: number;
+	/** 监控占用的 CPU 时间比例上限，0 到 1 */
+	cpu_budget?: number;
+	/** 画面变化的比例超过该值时触发，0 到 1 */
+	change_threshold?: number;
+	/** 画面变化后识别文字，匹配该正则表达式时才触发 */
+	text_pattern?: string;
+	/** 两次触发的最短间隔（毫秒） */
+	cooldown_ms?: number;
+	/** 触发时发送系统通知 */
+	notify?: boolean;
+	/** 触发时将截图保存到该路径，支持文件名模板 */
+	file_path?: string;
+	/** 触发时将截图写入该截图历史文件夹 */
+	capture_history_dir?: string;
+	/** 触发一次后停止监控 */
+	stop_on_hit?: boolean;
+};
+
+export interface RegionWatchHit {
+	watch_id: string;
+	region: ElementRect;
+	/** 画面变化的比例 */
+	change_ratio: number;
+	/** 识别到的文字，未设置 text_pattern 时为空 */
+	text?: string | null;
+	/** 匹配正则表达式的文字 */
+	matched_text?: string | null;
+	/** 触发时的截图时间（毫秒时间戳） */
+	capture_time: number;
+	/** 保存的截图路径 */
+	capture_path?: string | null;
+	/** 写入截图历史的文件名 */
+	capture_history_file_name?: string | null;
+	notify: boolean;
 }
@@ -29,6 +29,43 @@ export type WindowElement = {
 	window_id: number;
 };
 
+export type FocusedWindowInfo = {
+	window_title?: string | null;
+	process_name?: string | null;
+};
+
+export enum CaptureMode {
+	Region = "Region",
+	Window = "Window",
+	Scroll = "Scroll",
+	FullScreen = "FullScreen",
+	VideoRecord = "VideoRecord",
+}
+
+/** 截图的来源信息，保存时写入图片文件 */
+export type CaptureMetadata = {
+	/** 截图时间，Unix 时间戳（毫秒） */
+	capture_time?: number;
+	/** 截取窗口的标题 */
+	window_title?: string;
+	/** 截取窗口的进程名称 */
+	process_name?: string;
+	/** 截取的显示器名称 */
+	monitor_name?: string;
+	capture_mode?: CaptureMode;
+	app_version?: string;
+};
+
+/** 写入截图元数据的隐私设置 */
+export type CaptureMetadataOptions = {
+	/** 是否写入截图元数据，AVIF 和 JPEG XL 不支持 */
+	enable: boolean;
+	/** 是否写入窗口标题和进程名称 */
+	include_window_info: boolean;
+};
+
 export type CaptureFullScreenResult = {
 	monitor_rect: ElementRect;
+	/** 截图实际保存的路径，重名时会追加序号 */
+	file_path: string;
 };
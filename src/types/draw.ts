@@ -70,4 +70,6 @@ export enum DrawState {
 	SaveToCloud = 116,
 	// 重置画布
 	ResetCanvas = 117,
+	// 区域监控
+	RegionWatch = 118,
 }
@@ -1,3 +1,5 @@
+import type { CaptureMode } from "@/types/commands/screenshot";
+
 export enum ImageFormat {
 	PNG = "image/png",
 	JPEG = "image/jpeg",
@@ -6,7 +8,89 @@ export enum ImageFormat {
 	JPEG_XL = "image/jpeg-xl",
 }
 
+export enum ChromaSubsampling {
+	/** 不进行色度抽样，文字边缘最清晰 */
+	Yuv444 = "Yuv444",
+	Yuv422 = "Yuv422",
+	Yuv420 = "Yuv420",
+}
+
+export enum HdrTransferFunction {
+	/** 保存屏幕上显示的绝对亮度 */
+	Pq = "Pq",
+	/** SDR 白色映射到 203 尼特 */
+	Hlg = "Hlg",
+}
+
+/**
+ * 图片编码参数，未设置的字段使用默认值
+ */
+export type EncodeOptions = {
+	/** 有损编码的质量（1 - 100），JPEG、WebP、AVIF 有效 */
+	quality?: number;
+	/** 是否无损编码，为空时使用各格式的默认值，JPEG XL 只支持无损编码 */
+	lossless?: boolean | null;
+	/** 编码耗时和压缩率的权衡（0 - 10），越大越慢、文件越小 */
+	effort?: number | null;
+	/** 色度抽样，JPEG 有效 */
+	chroma_subsampling?: ChromaSubsampling;
+	/** PNG 压缩等级（0 - 9） */
+	png_compression_level?: number;
+	/** 保存 PNG 后在后台进行无损优化的等级（0 - 6），为空时不优化 */
+	png_optimize_level?: number | null;
+	/**
+	 * 捕获到 HDR 图像时保留浮点数据，保存为 16 位 PNG（使用该传递函数）、JPEG XL 或 EXR
+	 * 为空时转换为 8 位 SDR 图像
+	 */
+	hdr_transfer_function?: HdrTransferFunction | null;
+};
+
+/**
+ * PNG 后台优化完成后的结果，单位为字节
+ */
+export type PngOptimizeResult = {
+	file_path: string;
+	original_size: number;
+	optimized_size: number;
+	saved_size: number;
+};
+
+/**
+ * 渲染文件名模板时使用的截图信息，未设置的字段由后端补充
+ */
+export type FileNameTemplateContext = {
+	/** 截图时间，Unix 时间戳（毫秒） */
+	capture_time?: number;
+	/** 本地时间相对 UTC 的偏移（分钟） */
+	utc_offset_minutes?: number;
+	window_title?: string;
+	process_name?: string;
+	/** 显示器序号，从 1 开始 */
+	monitor_index?: number;
+	capture_mode?: CaptureMode;
+};
+
+/**
+ * 渲染文件名模板时使用的每日计数
+ */
+export type DailyCounter = {
+	/** 计数对应的日期，如 2024-01-02 */
+	date_key: string;
+	counter: number;
+};
+
+/**
+ * 渲染后的保存路径
+ */
+export type ResolvedFilePath = {
+	file_path: string;
+	/** 模板使用了每日计数时，写入文件后需要提交的计数 */
+	daily_counter: DailyCounter | null;
+};
+
 export type ImagePath = {
 	filePath: string;
 	imageFormat: ImageFormat;
+	/** 写入文件后需要提交的每日计数 */
+	dailyCounter?: DailyCounter;
 };
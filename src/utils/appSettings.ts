@@ -1,9 +1,17 @@
+import { getVersion } from "@tauri-apps/api/app";
 import { compare } from "compare-versions";
 import {
 	type AppSettingsData,
 	AppSettingsGroup,
 	HdrColorAlgorithm,
+	type HdrToneMappingOptions,
 } from "@/types/appSettings";
+import type {
+	CaptureMetadata,
+	CaptureMetadataOptions,
+	CaptureMode,
+} from "@/types/commands/screenshot";
+import type { EncodeOptions } from "@/types/utils/file";
 import { appInfo } from "./log";
 import { getPlatform, getPlatformVersion } from "./platform";
 
@@ -38,3 +46,64 @@ export const getCorrectHdrColorAlgorithm = (
 		? appSettings[AppSettingsGroup.SystemScreenshot].correctHdrColorAlgorithm
 		: HdrColorAlgorithm.None;
 };
+
+export const getHdrToneMappingOptions = (
+	appSettings: AppSettingsData,
+): HdrToneMappingOptions => {
+	const { hdrPeakNits, hdrPaperWhiteNits } =
+		appSettings[AppSettingsGroup.SystemScreenshot];
+
+	return {
+		peak_nits: hdrPeakNits,
+		// 为 0 时使用显示器的 SDR 白色亮度
+		paper_white_nits: hdrPaperWhiteNits > 0 ? hdrPaperWhiteNits : undefined,
+	};
+};
+
+export const getCaptureMetadataOptions = (
+	appSettings: AppSettingsData,
+): CaptureMetadataOptions => {
+	const { embedCaptureMetadata, captureMetadataIncludeWindowInfo } =
+		appSettings[AppSettingsGroup.SystemScreenshot];
+
+	return {
+		enable: embedCaptureMetadata,
+		include_window_info: captureMetadataIncludeWindowInfo,
+	};
+};
+
+/**
+ * 获取保存截图时使用的编码参数，没有需要设置的参数时返回 undefined
+ */
+export const getEncodeOptions = (
+	appSettings: AppSettingsData,
+): EncodeOptions | undefined => {
+	const { pngOptimizeLevel } = appSettings[AppSettingsGroup.SystemScreenshot];
+
+	if (pngOptimizeLevel <= 0) {
+		return undefined;
+	}
+
+	return {
+		png_optimize_level: pngOptimizeLevel,
+	};
+};
+
+/**
+ * 创建前端保存的截图的元数据，未开启时返回 undefined
+ */
+export const createCaptureMetadata = async (
+	appSettings: AppSettingsData,
+	captureMode: CaptureMode,
+	captureTime?: number,
+): Promise<CaptureMetadata | undefined> => {
+	if (!getCaptureMetadataOptions(appSettings).enable) {
+		return undefined;
+	}
+
+	return {
+		capture_time: captureTime ?? Date.now(),
+		capture_mode: captureMode,
+		app_version: await getVersion(),
+	};
+};
@@ -127,6 +127,7 @@ export enum CaptureHistorySource {
 	Save = "save",
 	Fixed = "fixed",
 	FullScreen = "full-screen",
+	RegionWatch = "region-watch",
 }
 
 export type CaptureHistoryItem = {
@@ -140,6 +141,10 @@ export type CaptureHistoryItem = {
 	excalidraw_app_state: Pick<AppState, keyof AppState> | undefined;
 	source?: CaptureHistorySource;
 	create_ts: number;
+	/** 截图时焦点窗口的标题 */
+	window_title?: string;
+	/** 截图时焦点窗口的进程名称 */
+	process_name?: string;
 };
 
 export class CaptureHistoryStore extends BaseStore<CaptureHistoryItem> {
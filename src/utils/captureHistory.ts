@@ -21,6 +21,7 @@ import {
 } from "@/types/appSettings";
 import {
 	type ElementRect,
+	type FocusedWindowInfo,
 	type ImageBuffer,
 	ImageEncoder,
 } from "@/types/commands/screenshot";
@@ -82,6 +83,7 @@ export class CaptureHistory {
 		selectedRect: ElementRect | undefined,
 		captureResult?: ArrayBuffer,
 		source?: CaptureHistorySource,
+		focusedWindowInfo?: FocusedWindowInfo,
 	): CaptureHistoryItem {
 		let fileExtension = ".webp";
 		if (imageBuffer === "full-screen") {
@@ -104,6 +106,12 @@ export class CaptureHistory {
 		const timestamp = Date.now();
 		const fileName = `${timestamp}${fileExtension}`;
 
+		// 从截图历史再次截图时沿用原截图的窗口信息
+		const sourceItem =
+			typeof imageBuffer === "object" && "id" in imageBuffer
+				? imageBuffer
+				: undefined;
+
 		return {
 			id: timestamp.toString(),
 			selected_rect: selectedRect ?? {
@@ -126,6 +134,10 @@ export class CaptureHistory {
 				? `${timestamp}_capture_result.png`
 				: undefined,
 			source,
+			window_title:
+				focusedWindowInfo?.window_title ?? sourceItem?.window_title,
+			process_name:
+				focusedWindowInfo?.process_name ?? sourceItem?.process_name,
 		};
 	}
 
@@ -145,6 +157,7 @@ export class CaptureHistory {
 		selectedRect: ElementRect,
 		captureResult?: ArrayBuffer,
 		source?: CaptureHistorySource,
+		focusedWindowInfo?: FocusedWindowInfo,
 	): Promise<CaptureHistoryItem> {
 		const captureHistoryItem =
 			"type" in imageData
@@ -156,6 +169,7 @@ export class CaptureHistory {
 						selectedRect,
 						captureResult,
 						source,
+						focusedWindowInfo,
 					);
 
 		try {
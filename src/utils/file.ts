@@ -1,9 +1,20 @@
 import { join as joinPath, pictureDir, videoDir } from "@tauri-apps/api/path";
 import * as dialog from "@tauri-apps/plugin-dialog";
 import dayjs from "dayjs";
-import { createDir } from "@/commands/file";
+import {
+	commitDailyCounter,
+	createDir,
+	previewFileNameTemplate,
+	resolveFilePathTemplate,
+} from "@/commands/file";
 import { type AppSettingsData, AppSettingsGroup } from "@/types/appSettings";
-import { ImageFormat, type ImagePath } from "@/types/utils/file";
+import { CaptureMode } from "@/types/commands/screenshot";
+import {
+	type FileNameTemplateContext,
+	ImageFormat,
+	type ImagePath,
+} from "@/types/utils/file";
+import { appWarn } from "./log";
 
 const parseTemplate = (template: string): string => {
 	const regex = /\{\{([^}]+)\}\}/g;
@@ -29,6 +40,46 @@ export const generateImageFileName = (format: string) => {
 	return parseTemplate(format);
 };
 
+/**
+ * 创建渲染文件名模板使用的截图信息
+ * @param captureMode 截图方式
+ * @param captureTime 截图时间，为空时使用当前时间
+ */
+export const createFileNameTemplateContext = (
+	captureMode?: CaptureMode,
+	captureTime?: number,
+): FileNameTemplateContext => {
+	return {
+		capture_time: captureTime ?? Date.now(),
+		utc_offset_minutes: -new Date().getTimezoneOffset(),
+		capture_mode: captureMode,
+	};
+};
+
+/**
+ * 预览文件名模板的渲染结果，不更新每日计数
+ * @param format 格式模板，例如 "SnowShot_{{YYYY-MM-DD_HH-mm-ss}}_{{COUNTER:3}}"
+ * @param captureMode 截图方式
+ */
+export const previewImageFileName = async (
+	format: string,
+	captureMode?: CaptureMode,
+) => {
+	if (!format) {
+		return "";
+	}
+
+	try {
+		return await previewFileNameTemplate(
+			format,
+			createFileNameTemplateContext(captureMode),
+		);
+	} catch (error) {
+		appWarn("[previewImageFileName] Failed to preview file name", error);
+		return generateImageFileName(format);
+	}
+};
+
 export const joinImagePath = (filePath: string, imageFormat: ImageFormat) => {
 	let fileExtension = "png";
 	switch (imageFormat) {
@@ -80,7 +131,8 @@ export const getImageSaveDirectory = async (appSettings: AppSettingsData) => {
 
 export const getImagePathFromSettings = async (
 	appSettings: AppSettingsData | undefined,
-	method: "auto" | "fast" | "focused-window" | "full-screen",
+	method: "auto" | "fast" | "focused-window" | "full-screen" | "region-watch",
+	captureMode: CaptureMode = CaptureMode.Region,
 ): Promise<ImagePath | undefined> => {
 	if (!appSettings) {
 		return undefined;
@@ -93,39 +145,70 @@ export const getImagePathFromSettings = async (
 		return undefined;
 	}
 
-	let fileName = "";
+	let fileNameFormat = "";
 	switch (method) {
 		case "auto":
-			fileName = generateImageFileName(outputSettings.autoSaveFileNameFormat);
+		case "region-watch":
+			fileNameFormat = outputSettings.autoSaveFileNameFormat;
 			break;
 		case "fast":
-			fileName = generateImageFileName(outputSettings.fastSaveFileNameFormat);
+			fileNameFormat = outputSettings.fastSaveFileNameFormat;
 			break;
 		case "focused-window":
-			fileName = generateImageFileName(
-				outputSettings.focusedWindowFileNameFormat,
-			);
+			fileNameFormat = outputSettings.focusedWindowFileNameFormat;
 			break;
 		case "full-screen":
-			fileName = generateImageFileName(outputSettings.fullScreenFileNameFormat);
+			fileNameFormat = outputSettings.fullScreenFileNameFormat;
 			break;
 	}
 
 	const saveDirectory = await getImageSaveDirectory(appSettings);
 	await createDir(saveDirectory);
 
+	const filePath = joinImagePath(
+		await joinPath(saveDirectory, fileNameFormat),
+		screenshotSettings.saveFileFormat,
+	);
+	// 焦点窗口和全屏截图需要窗口和显示器的信息，由后端在截图时渲染模板
+	// 区域监控每次触发的时间不同，同样交给后端渲染
+	if (method === "auto" || method === "fast") {
+		const resolvedFilePath = await resolveFilePathTemplate(
+			filePath,
+			createFileNameTemplateContext(captureMode),
+		);
+
+		return {
+			filePath: resolvedFilePath.file_path,
+			imageFormat: screenshotSettings.saveFileFormat,
+			dailyCounter: resolvedFilePath.daily_counter ?? undefined,
+		};
+	}
+
 	return {
-		filePath: joinImagePath(
-			await joinPath(saveDirectory, fileName),
-			screenshotSettings.saveFileFormat,
-		),
+		filePath,
 		imageFormat: screenshotSettings.saveFileFormat,
 	};
 };
 
+/**
+ * 文件写入成功后提交路径使用的每日计数
+ */
+export const commitImagePath = async (imagePath: ImagePath) => {
+	if (!imagePath.dailyCounter) {
+		return;
+	}
+
+	try {
+		await commitDailyCounter(imagePath.dailyCounter);
+	} catch (error) {
+		appWarn("[commitImagePath] Failed to commit daily counter", error);
+	}
+};
+
 export const showImageDialog = async (
 	appSettings: AppSettingsData,
 	prevFormat?: ImageFormat,
+	captureMode: CaptureMode = CaptureMode.Region,
 ): Promise<ImagePath | undefined> => {
 	let firstFilter: { name: string; extensions: string[] };
 	switch (prevFormat) {
@@ -185,8 +268,9 @@ export const showImageDialog = async (
 				extensions: ["jxl"],
 			},
 		],
-		defaultPath: generateImageFileName(
+		defaultPath: await previewImageFileName(
 			appSettings[AppSettingsGroup.FunctionOutput].manualSaveFileNameFormat,
+			captureMode,
 		),
 		canCreateDirectories: true,
 	});